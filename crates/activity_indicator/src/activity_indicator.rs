@@ -12,6 +12,7 @@ use language::{
 };
 use project::{
     LanguageServerProgress, LspStoreEvent, ProgressToken, Project, ProjectEnvironmentEvent,
+    TaskActivity,
     git_store::{GitStoreEvent, Repository},
 };
 use smallvec::SmallVec;
@@ -205,6 +206,13 @@ impl ActivityIndicator {
             )
             .detach();
 
+            cx.subscribe(&project, |_, _, event: &project::Event, cx| {
+                if let project::Event::BackgroundTaskActivityChanged = event {
+                    cx.notify()
+                }
+            })
+            .detach();
+
             cx.subscribe(
                 &project.read(cx).git_store().clone(),
                 |_, _, event: &GitStoreEvent, cx| {
@@ -432,6 +440,26 @@ impl ActivityIndicator {
             });
         }
 
+        // Show any task running in the background that is busy, e.g. rebuilding after a change.
+        if let Some((task, _)) = self
+            .project
+            .read(cx)
+            .background_tasks(cx)
+            .find(|(_, activity)| *activity == TaskActivity::Busy)
+        {
+            return Some(Content {
+                icon: Some(
+                    Icon::new(IconName::ArrowCircle)
+                        .size(IconSize::Small)
+                        .with_rotate_animation(2)
+                        .into_any_element(),
+                ),
+                message: format!("Task: {}", task.label),
+                tooltip_message: Some(task.command_label.clone()),
+                on_click: None,
+            });
+        }
+
         let current_job = self
             .project
             .read(cx)
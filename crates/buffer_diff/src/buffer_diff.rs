@@ -1047,6 +1047,170 @@ fn process_patch_hunk(
     }
 }
 
+/// A run of changed lines, as half-open row ranges in the old and new text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LineChange {
+    old_rows: Range<u32>,
+    new_rows: Range<u32>,
+}
+
+fn line_changes(old_text: &Rope, new_text: &Rope) -> Vec<LineChange> {
+    let old_text = old_text.to_string();
+    let new_text = new_text.to_string();
+    let mut options = GitOptions::default();
+    options.context_lines(0);
+    let Some(patch) = GitPatch::from_buffers(
+        old_text.as_bytes(),
+        None,
+        new_text.as_bytes(),
+        None,
+        Some(&mut options),
+    )
+    .log_err() else {
+        return Vec::new();
+    };
+
+    (0..patch.num_hunks())
+        .filter_map(|hunk_index| {
+            let (hunk, _) = patch.hunk(hunk_index).log_err()?;
+            Some(LineChange {
+                old_rows: patch_hunk_rows(hunk.old_start(), hunk.old_lines()),
+                new_rows: patch_hunk_rows(hunk.new_start(), hunk.new_lines()),
+            })
+        })
+        .collect()
+}
+
+fn patch_hunk_rows(start: u32, lines: u32) -> Range<u32> {
+    // Line numbers are one-based, except that an empty range names the line
+    // it follows.
+    if lines == 0 {
+        start..start
+    } else {
+        start - 1..start - 1 + lines
+    }
+}
+
+fn coalesce_row_ranges(rows: &[Range<u32>]) -> Vec<Range<u32>> {
+    let mut rows = rows.to_vec();
+    rows.sort_by_key(|rows| rows.start);
+    let mut result: Vec<Range<u32>> = Vec::with_capacity(rows.len());
+    for range in rows {
+        match result.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => result.push(range),
+        }
+    }
+    result
+}
+
+/// Narrows a change down to the part of it that touches the given rows of the
+/// new text. When the change replaces as many old lines as it adds, the old
+/// lines are paired with the new ones by position and clipped along with them.
+/// Otherwise there's no pairing, so all of the old lines are kept. A pure
+/// deletion is kept whole when the rows include the row that follows it.
+fn clip_line_change(change: &LineChange, rows: &Range<u32>) -> Option<LineChange> {
+    let new_rows = &change.new_rows;
+    if new_rows.is_empty() {
+        // A deletion belongs to the row that follows it.
+        let touches = rows.start <= new_rows.start && new_rows.start < rows.end.max(rows.start + 1);
+        return touches.then(|| change.clone());
+    }
+
+    let start = new_rows.start.max(rows.start);
+    let end = new_rows.end.min(rows.end);
+    if start >= end {
+        return None;
+    }
+
+    let old_rows = if change.old_rows.len() == new_rows.len() {
+        let old_start = change.old_rows.start + (start - new_rows.start);
+        old_start..old_start + (end - start)
+    } else {
+        change.old_rows.clone()
+    };
+    Some(LineChange {
+        old_rows,
+        new_rows: start..end,
+    })
+}
+
+fn select_line_changes(changes: &[LineChange], rows: &[Range<u32>]) -> Vec<LineChange> {
+    changes
+        .iter()
+        .flat_map(|change| {
+            let mut clipped = rows
+                .iter()
+                .filter_map(|rows| clip_line_change(change, rows))
+                .collect::<Vec<_>>();
+            // The old lines of an uneven change can't be split between several
+            // selections, so select the span that covers all of them instead.
+            if clipped.len() > 1
+                && !change.old_rows.is_empty()
+                && change.old_rows.len() != change.new_rows.len()
+            {
+                let start = clipped[0].new_rows.start;
+                let end = clipped[clipped.len() - 1].new_rows.end;
+                clipped = vec![LineChange {
+                    old_rows: change.old_rows.clone(),
+                    new_rows: start..end,
+                }];
+            }
+            clipped
+        })
+        .collect()
+}
+
+/// Maps a row in the new text of the given changes back to the old text. Rows
+/// inside a change map to the start of its old lines when `is_start` is true,
+/// or to their end otherwise.
+fn map_row_to_old(changes: &[LineChange], row: u32, is_start: bool) -> u32 {
+    let mut old_row = row as i64;
+    for change in changes {
+        let new_rows = &change.new_rows;
+        if is_start && new_rows.start <= row && row < new_rows.end {
+            return change.old_rows.start;
+        } else if !is_start && new_rows.start < row && row < new_rows.end {
+            return change.old_rows.end;
+        } else if new_rows.end <= row {
+            old_row += change.old_rows.len() as i64 - new_rows.len() as i64;
+        } else {
+            break;
+        }
+    }
+    old_row.max(0) as u32
+}
+
+fn row_offset(text: &Rope, row: u32) -> usize {
+    if row > text.max_point().row {
+        text.len()
+    } else {
+        text.point_to_offset(Point::new(row, 0))
+    }
+}
+
+/// Replaces rows of `target` with rows of `source`. The replacements must be
+/// sorted and must not overlap.
+fn replace_rows(
+    target: &Rope,
+    source: &Rope,
+    replacements: impl IntoIterator<Item = (Range<u32>, Range<u32>)>,
+) -> Rope {
+    let mut result = Rope::new();
+    let mut offset = 0;
+    for (target_rows, source_rows) in replacements {
+        let target_start = row_offset(target, target_rows.start);
+        result.append(target.slice(offset..target_start));
+        result.append(
+            source
+                .slice(row_offset(source, source_rows.start)..row_offset(source, source_rows.end)),
+        );
+        offset = row_offset(target, target_rows.end);
+    }
+    result.append(target.slice(offset..target.len()));
+    result
+}
+
 impl std::fmt::Debug for BufferDiff {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BufferChangeSet")
@@ -1159,6 +1323,88 @@ impl BufferDiff {
         new_index_text
     }
 
+    /// Stages or unstages only the changed lines that fall within the given
+    /// buffer rows, leaving the rest of their hunks untouched.
+    ///
+    /// Returns the new index text, or `None` if the file should be removed
+    /// from the index.
+    pub fn stage_or_unstage_lines(
+        &mut self,
+        stage: bool,
+        rows: &[Range<u32>],
+        buffer: &text::BufferSnapshot,
+        cx: &mut Context<Self>,
+    ) -> Option<Rope> {
+        let unstaged_diff = &self.secondary_diff.as_ref()?.read(cx).inner;
+        let head_text = self
+            .inner
+            .base_text_exists
+            .then(|| self.inner.base_text.as_rope().clone());
+        let index_text = unstaged_diff
+            .base_text_exists
+            .then(|| unstaged_diff.base_text.as_rope().clone());
+        let rows = coalesce_row_ranges(rows);
+
+        let new_index_text = if stage {
+            let old_index_text = index_text.clone().unwrap_or_default();
+            let changes =
+                select_line_changes(&line_changes(&old_index_text, buffer.as_rope()), &rows);
+            if changes.is_empty() {
+                return index_text;
+            }
+            Some(replace_rows(
+                &old_index_text,
+                buffer.as_rope(),
+                changes
+                    .iter()
+                    .map(|change| (change.old_rows.clone(), change.new_rows.clone())),
+            ))
+        } else {
+            // Nothing is staged, so there is nothing to unstage.
+            let old_index_text = index_text.clone()?;
+            let unstaged_changes = line_changes(&old_index_text, buffer.as_rope());
+            let index_rows = rows
+                .iter()
+                .map(|rows| {
+                    map_row_to_old(&unstaged_changes, rows.start, true)
+                        ..map_row_to_old(&unstaged_changes, rows.end, false)
+                })
+                .collect::<Vec<_>>();
+            let changes = select_line_changes(
+                &line_changes(&head_text.clone().unwrap_or_default(), &old_index_text),
+                &index_rows,
+            );
+            if changes.is_empty() {
+                return index_text;
+            }
+            let new_index_text = replace_rows(
+                &old_index_text,
+                &head_text.clone().unwrap_or_default(),
+                changes
+                    .iter()
+                    .map(|change| (change.new_rows.clone(), change.old_rows.clone())),
+            );
+            // Unstaging every line of a newly added file removes it from the index.
+            if head_text.is_none() && new_index_text.is_empty() {
+                None
+            } else {
+                Some(new_index_text)
+            }
+        };
+
+        cx.emit(BufferDiffEvent::HunksStagedOrUnstaged(
+            new_index_text.clone(),
+        ));
+        if let Some((first, last)) = rows.first().zip(rows.last()) {
+            let start = buffer.clip_point(Point::new(first.start, 0), Bias::Left);
+            let end = buffer.clip_point(Point::new(last.end, 0), Bias::Left);
+            cx.emit(BufferDiffEvent::DiffChanged {
+                changed_range: Some(buffer.anchor_before(start)..buffer.anchor_after(end)),
+            });
+        }
+        new_index_text
+    }
+
     pub fn range_to_hunk_range(
         &self,
         range: Range<Anchor>,
@@ -2244,6 +2490,96 @@ mod tests {
         assert_eq!(range.to_point(&buffer), Point::new(7, 0)..Point::new(8, 0));
     }
 
+    #[gpui::test]
+    async fn test_stage_and_unstage_lines(cx: &mut TestAppContext) {
+        let head_text = "
+            one
+            two
+            three
+            four
+            five
+        "
+        .unindent();
+        let buffer_text = "
+            one
+            TWO
+            three
+            FOUR
+            FOUR_AND_A_HALF
+            five
+            six
+        "
+        .unindent();
+        let buffer = Buffer::new(ReplicaId::LOCAL, BufferId::new(1).unwrap(), buffer_text);
+
+        let stage_or_unstage_lines =
+            |stage: bool, index_text: &str, rows: &[Range<u32>], cx: &mut TestAppContext| {
+                let unstaged = BufferDiffSnapshot::new_sync(buffer.clone(), index_text.into(), cx);
+                let uncommitted =
+                    BufferDiffSnapshot::new_sync(buffer.clone(), head_text.clone(), cx);
+                let unstaged_diff = cx.new(|cx| {
+                    let mut diff = BufferDiff::new(&buffer, cx);
+                    diff.set_snapshot(unstaged, &buffer, cx);
+                    diff
+                });
+                let uncommitted_diff = cx.new(|cx| {
+                    let mut diff = BufferDiff::new(&buffer, cx);
+                    diff.set_snapshot(uncommitted, &buffer, cx);
+                    diff.set_secondary_diff(unstaged_diff);
+                    diff
+                });
+                uncommitted_diff.update(cx, |diff, cx| {
+                    diff.stage_or_unstage_lines(stage, rows, &buffer, cx)
+                        .unwrap()
+                        .to_string()
+                })
+            };
+
+        // Lines of a modification are paired up with the lines they replace.
+        let index_text = stage_or_unstage_lines(true, &head_text, &[1..2], cx);
+        pretty_assertions::assert_eq!(
+            index_text,
+            "
+            one
+            TWO
+            three
+            four
+            five
+            "
+            .unindent()
+        );
+
+        // Selecting some of the added lines of an uneven modification keeps
+        // all of the lines it removes.
+        let index_text = stage_or_unstage_lines(true, &index_text, &[4..5, 6..7], cx);
+        pretty_assertions::assert_eq!(
+            index_text,
+            "
+            one
+            TWO
+            three
+            FOUR_AND_A_HALF
+            five
+            six
+            "
+            .unindent()
+        );
+
+        // Unstaging maps the selected buffer rows back to the index.
+        let index_text = stage_or_unstage_lines(false, &index_text, &[1..2, 6..7], cx);
+        pretty_assertions::assert_eq!(
+            index_text,
+            "
+            one
+            two
+            three
+            FOUR_AND_A_HALF
+            five
+            "
+            .unindent()
+        );
+    }
+
     #[gpui::test(iterations = 100)]
     async fn test_staging_and_unstaging_hunks(cx: &mut TestAppContext, mut rng: StdRng) {
         fn gen_line(rng: &mut StdRng) -> String {
@@ -0,0 +1,775 @@
+//! Shows the callers or callees of a function as a tree, using the call
+//! hierarchy requests of the language server.
+//!
+//! Calls are only requested when an entry is expanded, and selecting an entry
+//! opens the places where the calls are made in a multibuffer.
+
+use std::{collections::HashMap, ops::Range};
+
+use editor::{Editor, MultibufferSelectionMode};
+use gpui::{
+    App, Context, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, SharedString, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, uniform_list,
+};
+use language::{Buffer, Point, ToPoint as _};
+use project::{CallHierarchyCall, CallHierarchyItem, Location, Project};
+use settings::Settings as _;
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use workspace::{
+    SplitDirection, Toast, Workspace,
+    item::{Item, ItemEvent, PreviewTabsSettings},
+    notifications::NotificationId,
+};
+
+actions!(
+    call_hierarchy,
+    [
+        /// Shows the functions that call the function under the cursor.
+        ShowIncomingCalls,
+        /// Shows the functions that the function under the cursor calls.
+        ShowOutgoingCalls,
+        /// Switches the call hierarchy between callers and callees.
+        ToggleDirection,
+        /// Expands the selected entry, loading its calls.
+        ExpandSelectedEntry,
+        /// Collapses the selected entry.
+        CollapseSelectedEntry,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(register).detach();
+}
+
+fn register(editor: &mut Editor, _: Option<&mut Window>, cx: &mut Context<Editor>) {
+    if !editor.mode().is_full() {
+        return;
+    }
+
+    let handle = cx.entity().downgrade();
+    editor
+        .register_action({
+            let handle = handle.clone();
+            move |_: &ShowIncomingCalls, window, cx| {
+                if let Some(editor) = handle.upgrade() {
+                    show_call_hierarchy(editor, CallDirection::Incoming, window, cx);
+                }
+            }
+        })
+        .detach();
+    editor
+        .register_action(move |_: &ShowOutgoingCalls, window, cx| {
+            if let Some(editor) = handle.upgrade() {
+                show_call_hierarchy(editor, CallDirection::Outgoing, window, cx);
+            }
+        })
+        .detach();
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallDirection {
+    /// The callers of the functions.
+    Incoming,
+    /// The callees of the functions.
+    Outgoing,
+}
+
+/// Opens a call hierarchy for the function under the newest cursor of `editor`,
+/// in a split next to it.
+pub fn show_call_hierarchy(
+    editor: Entity<Editor>,
+    direction: CallDirection,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let editor = editor.read(cx);
+    let Some(workspace) = editor.workspace() else {
+        return;
+    };
+    let head = editor.selections.newest_anchor().head();
+    let Some((buffer, position)) = editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    else {
+        return;
+    };
+
+    let project = workspace.read(cx).project().clone();
+    let items = project.update(cx, |project, cx| {
+        project.prepare_call_hierarchy(&buffer, position, cx)
+    });
+    window
+        .spawn(cx, async move |cx| {
+            let items = items.await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                if items.is_empty() {
+                    struct NoCallHierarchy;
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<NoCallHierarchy>(),
+                            "No call hierarchy found at the cursor",
+                        )
+                        .autohide(),
+                        cx,
+                    );
+                    return;
+                }
+
+                let view = cx.new(|cx| {
+                    CallHierarchyView::new(
+                        workspace.weak_handle(),
+                        project,
+                        buffer,
+                        items,
+                        direction,
+                        cx,
+                    )
+                });
+                workspace.split_item(SplitDirection::Right, Box::new(view), window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+}
+
+enum Children {
+    Unloaded,
+    Loading { _task: Task<()> },
+    Loaded(Vec<usize>),
+    Failed(SharedString),
+}
+
+struct CallEntry {
+    item: CallHierarchyItem,
+    parent: Option<usize>,
+    /// Where the calls between the entry and its parent are made, empty for roots.
+    call_sites: Vec<Location>,
+    depth: usize,
+    expanded: bool,
+    children: Children,
+}
+
+/// A tree of the callers or callees of some functions.
+pub struct CallHierarchyView {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    /// The buffer the hierarchy was requested in, to route the requests for calls
+    /// to the language server that produced the items.
+    buffer: Entity<Buffer>,
+    roots: Vec<CallHierarchyItem>,
+    direction: CallDirection,
+    entries: Vec<CallEntry>,
+    visible_entries: Vec<usize>,
+    selected_entry: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+impl CallHierarchyView {
+    pub fn new(
+        workspace: WeakEntity<Workspace>,
+        project: Entity<Project>,
+        buffer: Entity<Buffer>,
+        roots: Vec<CallHierarchyItem>,
+        direction: CallDirection,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            workspace,
+            project,
+            buffer,
+            roots,
+            direction,
+            entries: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_entry: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        };
+        this.reset_entries(cx);
+        this
+    }
+
+    pub fn direction(&self) -> CallDirection {
+        self.direction
+    }
+
+    fn set_direction(&mut self, direction: CallDirection, cx: &mut Context<Self>) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.reset_entries(cx);
+            cx.emit(ItemEvent::UpdateTab);
+        }
+    }
+
+    fn toggle_direction(&mut self, _: &ToggleDirection, _: &mut Window, cx: &mut Context<Self>) {
+        let direction = match self.direction {
+            CallDirection::Incoming => CallDirection::Outgoing,
+            CallDirection::Outgoing => CallDirection::Incoming,
+        };
+        self.set_direction(direction, cx);
+    }
+
+    /// Rebuilds the tree from its roots, loading their calls right away.
+    fn reset_entries(&mut self, cx: &mut Context<Self>) {
+        self.entries = self
+            .roots
+            .iter()
+            .map(|item| CallEntry {
+                item: item.clone(),
+                parent: None,
+                call_sites: Vec::new(),
+                depth: 0,
+                expanded: false,
+                children: Children::Unloaded,
+            })
+            .collect();
+        self.selected_entry = (!self.entries.is_empty()).then_some(0);
+        for ix in 0..self.entries.len() {
+            self.expand_entry(ix, cx);
+        }
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn update_visible_entries(&mut self) {
+        fn push_visible(entries: &[CallEntry], ix: usize, visible: &mut Vec<usize>) {
+            visible.push(ix);
+            let entry = &entries[ix];
+            if let (true, Children::Loaded(children)) = (entry.expanded, &entry.children) {
+                for &child in children {
+                    push_visible(entries, child, visible);
+                }
+            }
+        }
+
+        self.visible_entries.clear();
+        for ix in 0..self.entries.len() {
+            if self.entries[ix].parent.is_none() {
+                push_visible(&self.entries, ix, &mut self.visible_entries);
+            }
+        }
+    }
+
+    fn expand_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let entry = &mut self.entries[ix];
+        entry.expanded = true;
+        if let Children::Unloaded | Children::Failed(_) = entry.children {
+            let item = entry.item.clone();
+            let calls = self.project.update(cx, |project, cx| match self.direction {
+                CallDirection::Incoming => project.incoming_calls(&self.buffer, &item, cx),
+                CallDirection::Outgoing => project.outgoing_calls(&self.buffer, &item, cx),
+            });
+            let task = cx.spawn(async move |this, cx| {
+                let calls = calls.await;
+                this.update(cx, |this, cx| this.set_calls(ix, calls, cx))
+                    .ok();
+            });
+            self.entries[ix].children = Children::Loading { _task: task };
+        }
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn set_calls(
+        &mut self,
+        ix: usize,
+        calls: anyhow::Result<Vec<CallHierarchyCall>>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(parent) = self.entries.get(ix) else {
+            return;
+        };
+        let depth = parent.depth + 1;
+        let children = match calls {
+            Ok(calls) => {
+                let first_child = self.entries.len();
+                self.entries.extend(calls.into_iter().map(|call| CallEntry {
+                    item: call.item,
+                    parent: Some(ix),
+                    call_sites: call.ranges,
+                    depth,
+                    expanded: false,
+                    children: Children::Unloaded,
+                }));
+                Children::Loaded((first_child..self.entries.len()).collect())
+            }
+            Err(error) => {
+                log::error!("failed to load calls: {error:#}");
+                Children::Failed(format!("{error:#}").into())
+            }
+        };
+        self.entries[ix].children = children;
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn collapse_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.entries[ix].expanded = false;
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn toggle_expanded(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.entries[ix].expanded {
+            self.collapse_entry(ix, cx);
+        } else {
+            self.expand_entry(ix, cx);
+        }
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.selected_entry else {
+            return;
+        };
+        if !self.entries[ix].expanded {
+            self.expand_entry(ix, cx);
+        } else if let Children::Loaded(children) = &self.entries[ix].children
+            && let Some(&child) = children.first()
+        {
+            self.select_entry(child, cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.selected_entry else {
+            return;
+        };
+        if self.entries[ix].expanded {
+            self.collapse_entry(ix, cx);
+        } else if let Some(parent) = self.entries[ix].parent {
+            self.select_entry(parent, cx);
+        }
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        if let Some(visible_ix) = self.visible_entries.iter().position(|&entry| entry == ix) {
+            self.scroll_handle
+                .scroll_to_item(visible_ix, ScrollStrategy::Center);
+        }
+        cx.notify();
+    }
+
+    fn selected_visible_ix(&self) -> Option<usize> {
+        let selected = self.selected_entry?;
+        self.visible_entries.iter().position(|&ix| ix == selected)
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next = match self.selected_visible_ix() {
+            Some(visible_ix) => visible_ix + 1,
+            None => 0,
+        };
+        if let Some(&ix) = self.visible_entries.get(next) {
+            self.select_entry(ix, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous = match self.selected_visible_ix() {
+            Some(visible_ix) => visible_ix.saturating_sub(1),
+            None => 0,
+        };
+        if let Some(&ix) = self.visible_entries.get(previous) {
+            self.select_entry(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.open_entry(ix, window, cx);
+        }
+    }
+
+    /// Opens the places where the calls of an entry are made in a multibuffer,
+    /// or the definition of a root entry.
+    fn open_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let entry = &self.entries[ix];
+        let name = &entry.item.name;
+        let (locations, title) = match entry.parent.map(|parent| &self.entries[parent]) {
+            Some(parent) => {
+                let title = match self.direction {
+                    CallDirection::Incoming => format!("Calls to {} in {name}", parent.item.name),
+                    CallDirection::Outgoing => format!("Calls to {name} in {}", parent.item.name),
+                };
+                (entry.call_sites.clone(), title)
+            }
+            None => (vec![entry.item.location.clone()], name.clone()),
+        };
+
+        let mut ranges = HashMap::<Entity<Buffer>, Vec<Range<Point>>>::default();
+        for location in locations {
+            let buffer = location.buffer.read(cx);
+            let range = location.range.start.to_point(buffer)..location.range.end.to_point(buffer);
+            ranges.entry(location.buffer).or_default().push(range);
+        }
+        if ranges.is_empty() {
+            return;
+        }
+
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let allow_preview = PreviewTabsSettings::get_global(cx)
+                    .enable_preview_multibuffer_from_code_navigation;
+                Editor::open_locations_in_multibuffer(
+                    workspace,
+                    ranges,
+                    title,
+                    true,
+                    allow_preview,
+                    MultibufferSelectionMode::First,
+                    window,
+                    cx,
+                );
+            })
+            .ok();
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let buffer = entry.item.location.buffer.read(cx);
+        let file_name = buffer
+            .file()
+            .map(|file| file.file_name(cx).to_string())
+            .unwrap_or_default();
+        let row = entry.item.location.range.start.to_point(buffer).row + 1;
+        let is_leaf = matches!(&entry.children, Children::Loaded(children) if children.is_empty());
+
+        ListItem::new(("call-hierarchy-entry", ix))
+            .spacing(ListItemSpacing::Sparse)
+            .indent_level(entry.depth)
+            .indent_step_size(px(12.))
+            .toggle((!is_leaf).then_some(entry.expanded))
+            .on_toggle(cx.listener(move |this, _, _, cx| this.toggle_expanded(ix, cx)))
+            .toggle_state(self.selected_entry == Some(ix))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .overflow_hidden()
+                    .child(Label::new(entry.item.name.clone()))
+                    .children(entry.item.detail.clone().map(|detail| {
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate()
+                    }))
+                    .child(
+                        Label::new(format!("{file_name}:{row}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .end_slot::<AnyElement>(match &entry.children {
+                Children::Loading { .. } => Some(
+                    Label::new("Loading…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .into_any_element(),
+                ),
+                Children::Failed(error) => Some(
+                    div()
+                        .id(("call-hierarchy-error", ix))
+                        .tooltip(Tooltip::text(error.clone()))
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Error),
+                        )
+                        .into_any_element(),
+                ),
+                Children::Unloaded | Children::Loaded(_) if entry.call_sites.len() > 1 => Some(
+                    Label::new(format!("{} calls", entry.call_sites.len()))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .into_any_element(),
+                ),
+                Children::Unloaded | Children::Loaded(_) => None,
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select_entry(ix, cx);
+                this.open_entry(ix, window, cx);
+            }))
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (title, toggle_tooltip) = match self.direction {
+            CallDirection::Incoming => ("Callers", "Show Callees"),
+            CallDirection::Outgoing => ("Callees", "Show Callers"),
+        };
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(title).color(Color::Muted))
+            .child(
+                IconButton::new("toggle-call-direction", IconName::ArrowRightLeft)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::for_action_title(toggle_tooltip, &ToggleDirection))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_direction(&ToggleDirection, window, cx)
+                    })),
+            )
+    }
+
+    #[cfg(test)]
+    fn visible_entries(&self) -> Vec<(usize, String)> {
+        self.visible_entries
+            .iter()
+            .map(|&ix| (self.entries[ix].depth, self.entries[ix].item.name.clone()))
+            .collect()
+    }
+}
+
+impl EventEmitter<ItemEvent> for CallHierarchyView {}
+
+impl Focusable for CallHierarchyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for CallHierarchyView {
+    type Event = ItemEvent;
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let name = self
+            .roots
+            .first()
+            .map(|item| item.name.as_str())
+            .unwrap_or_default();
+        match self.direction {
+            CallDirection::Incoming => format!("Callers of {name}").into(),
+            CallDirection::Outgoing => format!("Callees of {name}").into(),
+        }
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::ListTree))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for CallHierarchyView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("CallHierarchy")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::toggle_direction))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_header(cx))
+            .child(
+                uniform_list(
+                    "call-hierarchy",
+                    self.visible_entries.len(),
+                    cx.processor(|this, range: Range<usize>, _window, cx| {
+                        range
+                            .map(|visible_ix| {
+                                let ix = this.visible_entries[visible_ix];
+                                this.render_entry(ix, cx).into_any_element()
+                            })
+                            .collect()
+                    }),
+                )
+                .track_scroll(&self.scroll_handle)
+                .p_1()
+                .flex_1(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt as _;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::FakeLspAdapter;
+    use project::FakeFs;
+    use serde_json::json;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+    use util::{path, rel_path::rel_path};
+    use workspace::AppState;
+
+    #[gpui::test]
+    async fn test_call_hierarchy_view(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "a.rs": "fn a() {}\nfn b() { a(); }\nfn c() { b(); }\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(language::rust_lang());
+        let mut fake_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("a.rs")), None, true, window, cx)
+            })
+            .await
+            .unwrap();
+        let fake_server = fake_servers.next().await.unwrap();
+
+        // Each function is on its own row, and calls the one on the previous row.
+        fn lsp_item(row: u32) -> lsp::CallHierarchyItem {
+            let range = lsp::Range::new(lsp::Position::new(row, 3), lsp::Position::new(row, 4));
+            lsp::CallHierarchyItem {
+                name: ["a", "b", "c"][row as usize].to_string(),
+                kind: lsp::SymbolKind::FUNCTION,
+                tags: None,
+                detail: None,
+                uri: lsp::Uri::from_file_path(path!("/dir/a.rs")).unwrap(),
+                range,
+                selection_range: range,
+                data: None,
+            }
+        }
+
+        fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+            |_, _| async move { Ok(Some(vec![lsp_item(0)])) },
+        );
+        let incoming_requests = Arc::new(AtomicUsize::new(0));
+        fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+            let incoming_requests = incoming_requests.clone();
+            move |params, _| {
+                incoming_requests.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    let callee_row = params.item.range.start.line;
+                    Ok(Some(
+                        (callee_row < 2)
+                            .then(|| lsp::CallHierarchyIncomingCall {
+                                from: lsp_item(callee_row + 1),
+                                from_ranges: vec![lsp::Range::new(
+                                    lsp::Position::new(callee_row + 1, 9),
+                                    lsp::Position::new(callee_row + 1, 10),
+                                )],
+                            })
+                            .into_iter()
+                            .collect(),
+                    ))
+                }
+            }
+        });
+        fake_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+            |_, _| async move { Ok(Some(Vec::new())) },
+        );
+
+        cx.dispatch_action(ShowIncomingCalls);
+        cx.run_until_parked();
+        let view = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_item_as::<CallHierarchyView>(cx)
+                .expect("call hierarchy should be opened")
+        });
+
+        // The callers of the root are loaded right away, but not theirs.
+        assert_eq!(
+            visible_entries(&view, cx),
+            [(0, "a".to_string()), (1, "b".to_string())]
+        );
+        assert_eq!(incoming_requests.load(Ordering::SeqCst), 1);
+
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(ExpandSelectedEntry);
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries(&view, cx),
+            [
+                (0, "a".to_string()),
+                (1, "b".to_string()),
+                (2, "c".to_string())
+            ]
+        );
+        assert_eq!(incoming_requests.load(Ordering::SeqCst), 2);
+
+        cx.dispatch_action(CollapseSelectedEntry);
+        assert_eq!(
+            visible_entries(&view, cx),
+            [(0, "a".to_string()), (1, "b".to_string())]
+        );
+
+        // Opening a caller shows where it makes the calls.
+        cx.dispatch_action(menu::Confirm);
+        cx.run_until_parked();
+        let title = workspace.update(cx, |workspace, cx| {
+            let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+            editor.read(cx).buffer().read(cx).title(cx).to_string()
+        });
+        assert_eq!(title, "Calls to a in b");
+
+        view.update_in(cx, |view, window, cx| {
+            view.toggle_direction(&ToggleDirection, window, cx)
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.direction(), CallDirection::Outgoing);
+        });
+        assert_eq!(visible_entries(&view, cx), [(0, "a".to_string())]);
+    }
+
+    fn visible_entries(
+        view: &Entity<CallHierarchyView>,
+        cx: &mut VisualTestContext,
+    ) -> Vec<(usize, String)> {
+        view.read_with(cx, |view, _| view.visible_entries())
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            crate::init(cx);
+            editor::init(cx);
+            state
+        })
+    }
+}
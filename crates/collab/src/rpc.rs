@@ -339,16 +339,31 @@ impl Server {
             .add_request_handler(forward_read_only_project_request::<proto::FindSearchCandidates>)
             .add_request_handler(forward_read_only_project_request::<proto::GetDocumentHighlights>)
             .add_request_handler(forward_read_only_project_request::<proto::GetDocumentSymbols>)
+            .add_request_handler(forward_read_only_project_request::<proto::PrepareCallHierarchy>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetIncomingCalls>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetOutgoingCalls>)
+            .add_request_handler(forward_read_only_project_request::<proto::PrepareTypeHierarchy>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSupertypes>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSubtypes>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSemanticTokens>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSemanticTokensDelta>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetFoldingRanges>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetSelectionRanges>)
+            .add_request_handler(forward_read_only_project_request::<proto::GetDocumentLinks>)
             .add_request_handler(forward_read_only_project_request::<proto::GetProjectSymbols>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenBufferForSymbol>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenBufferById>)
             .add_request_handler(forward_read_only_project_request::<proto::SynchronizeBuffers>)
             .add_request_handler(forward_read_only_project_request::<proto::ResolveInlayHint>)
+            .add_request_handler(forward_read_only_project_request::<proto::ResolveDocumentLink>)
             .add_request_handler(forward_read_only_project_request::<proto::GetColorPresentation>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenBufferByPath>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenImageByPath>)
             .add_request_handler(forward_read_only_project_request::<proto::GitGetBranches>)
             .add_request_handler(forward_read_only_project_request::<proto::GetDefaultBranch>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitGetRebaseEntries>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitGetRebaseStatus>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitGetBisectStatus>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenUnstagedDiff>)
             .add_request_handler(forward_read_only_project_request::<proto::OpenUncommittedDiff>)
             .add_request_handler(forward_read_only_project_request::<proto::LspExtExpandMacro>)
@@ -400,6 +415,9 @@ impl Server {
             .add_request_handler(update_buffer)
             .add_message_handler(broadcast_project_message_from_host::<proto::RefreshInlayHints>)
             .add_message_handler(broadcast_project_message_from_host::<proto::RefreshCodeLens>)
+            .add_message_handler(
+                broadcast_project_message_from_host::<proto::RefreshSemanticTokens>,
+            )
             .add_message_handler(broadcast_project_message_from_host::<proto::UpdateBufferFile>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BufferReloaded>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BufferSaved>)
@@ -471,6 +489,19 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::GitChangeBranch>)
             .add_request_handler(forward_mutating_project_request::<proto::GitCreateRemote>)
             .add_request_handler(forward_mutating_project_request::<proto::GitRemoveRemote>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebase>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRebaseCommand>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitBisectStart>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitBisectMark>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitBisectReset>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCherryPick>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitRevert>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitGetTags>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitCreateTag>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitDeleteTag>)
+            .add_request_handler(forward_mutating_project_request::<proto::GitPushTag>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitCommitLog>)
+            .add_request_handler(forward_read_only_project_request::<proto::GitLoadConflictStages>)
             .add_request_handler(forward_mutating_project_request::<proto::CheckForPushedCommits>)
             .add_message_handler(broadcast_project_message_from_host::<proto::AdvertiseContexts>)
             .add_message_handler(update_context)
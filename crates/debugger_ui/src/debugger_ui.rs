@@ -52,6 +52,10 @@ actions!(
         StepOut,
         /// Steps back to the previous statement.
         StepBack,
+        /// Steps over the next machine instruction.
+        StepOverInstruction,
+        /// Steps into the next machine instruction.
+        StepIntoInstruction,
         /// Stops the debugging session.
         Stop,
         /// Toggles whether to ignore all breakpoints.
@@ -163,6 +167,8 @@ pub fn init(cx: &mut App) {
 
                 let caps = running_state.capabilities(cx);
                 let supports_step_back = caps.supports_step_back.unwrap_or_default();
+                let supports_stepping_granularity =
+                    caps.supports_stepping_granularity.unwrap_or_default();
                 let supports_detach = running_state.session().read(cx).is_attached();
                 let status = running_state.thread_status(cx);
 
@@ -194,6 +200,24 @@ pub fn init(cx: &mut App) {
                             active_item.update(cx, |item, cx| item.step_out(cx)).ok();
                         }
                     })
+                    .when(supports_stepping_granularity, |div| {
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOverInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_over_instruction(cx))
+                                    .ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepIntoInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_in_instruction(cx))
+                                    .ok();
+                            }
+                        })
+                    })
                     .when(supports_step_back, |div| {
                         let active_item = active_item.clone();
                         div.on_action(move |_: &StepBack, _, cx| {
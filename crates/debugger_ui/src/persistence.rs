@@ -7,12 +7,13 @@ use project::Project;
 use serde::{Deserialize, Serialize};
 use ui::{App, SharedString};
 use util::ResultExt;
-use workspace::{Member, Pane, PaneAxis, Workspace};
+use workspace::{Member, Pane, PaneAxis, Workspace, WorkspaceId};
 
 use crate::session::running::{
     self, DebugTerminal, RunningState, SubView, breakpoint_list::BreakpointList, console::Console,
-    loaded_source_list::LoadedSourceList, memory_view::MemoryView, module_list::ModuleList,
-    stack_frame_list::StackFrameList, variable_list::VariableList,
+    disassembly_view::DisassemblyView, loaded_source_list::LoadedSourceList,
+    memory_view::MemoryView, module_list::ModuleList, stack_frame_list::StackFrameList,
+    variable_list::VariableList,
 };
 
 #[derive(Clone, Hash, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -25,6 +26,7 @@ pub(crate) enum DebuggerPaneItem {
     LoadedSources,
     Terminal,
     MemoryView,
+    Disassembly,
 }
 
 impl DebuggerPaneItem {
@@ -38,6 +40,7 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::LoadedSources,
             DebuggerPaneItem::Terminal,
             DebuggerPaneItem::MemoryView,
+            DebuggerPaneItem::Disassembly,
         ];
         VARIANTS
     }
@@ -48,6 +51,9 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::MemoryView => capabilities
                 .supports_read_memory_request
                 .unwrap_or_default(),
+            DebuggerPaneItem::Disassembly => capabilities
+                .supports_disassemble_request
+                .unwrap_or_default(),
             DebuggerPaneItem::LoadedSources => capabilities
                 .supports_loaded_sources_request
                 .unwrap_or_default(),
@@ -65,6 +71,7 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::LoadedSources => SharedString::new_static("Sources"),
             DebuggerPaneItem::Terminal => SharedString::new_static("Terminal"),
             DebuggerPaneItem::MemoryView => SharedString::new_static("Memory View"),
+            DebuggerPaneItem::Disassembly => SharedString::new_static("Disassembly"),
         }
     }
     pub(crate) fn tab_tooltip(self) -> SharedString {
@@ -87,6 +94,9 @@ impl DebuggerPaneItem {
                 "Provides an interactive terminal session within the debugging environment."
             }
             DebuggerPaneItem::MemoryView => "Allows inspection of memory contents.",
+            DebuggerPaneItem::Disassembly => {
+                "Shows the machine instructions around the current stack frame."
+            }
         };
         SharedString::new_static(tooltip)
     }
@@ -199,6 +209,34 @@ pub(crate) async fn get_serialized_layout(
         .and_then(|value| serde_json::from_str::<SerializedLayout>(&value).ok())
 }
 
+const DEBUGGER_WATCHES_PREFIX: &str = "debugger_watches_";
+
+/// Stores the watch expressions of the project open in the workspace with the given ID.
+pub(crate) async fn serialize_watches(
+    workspace_id: WorkspaceId,
+    expressions: Vec<SharedString>,
+) -> anyhow::Result<()> {
+    let serialized_watches =
+        serde_json::to_string(&expressions).context("Serializing watch expressions")?;
+    KEY_VALUE_STORE
+        .write_kvp(
+            format!("{DEBUGGER_WATCHES_PREFIX}{}", i64::from(workspace_id)),
+            serialized_watches,
+        )
+        .await
+}
+
+pub(crate) fn get_serialized_watches(workspace_id: WorkspaceId) -> Vec<SharedString> {
+    let key = format!("{DEBUGGER_WATCHES_PREFIX}{}", i64::from(workspace_id));
+
+    KEY_VALUE_STORE
+        .read_kvp(&key)
+        .log_err()
+        .flatten()
+        .and_then(|value| serde_json::from_str::<Vec<SharedString>>(&value).log_err())
+        .unwrap_or_default()
+}
+
 pub(crate) fn deserialize_pane_layout(
     serialized: SerializedPaneLayout,
     should_invert: bool,
@@ -212,6 +250,7 @@ pub(crate) fn deserialize_pane_layout(
     loaded_sources: &Entity<LoadedSourceList>,
     terminal: &Entity<DebugTerminal>,
     memory_view: &Entity<MemoryView>,
+    disassembly_view: &Entity<DisassemblyView>,
     subscriptions: &mut HashMap<EntityId, Subscription>,
     window: &mut Window,
     cx: &mut Context<RunningState>,
@@ -237,6 +276,7 @@ pub(crate) fn deserialize_pane_layout(
                     loaded_sources,
                     terminal,
                     memory_view,
+                    disassembly_view,
                     subscriptions,
                     window,
                     cx,
@@ -310,6 +350,12 @@ pub(crate) fn deserialize_pane_layout(
                         DebuggerPaneItem::MemoryView,
                         cx,
                     )),
+                    DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                        disassembly_view.focus_handle(cx),
+                        disassembly_view.clone().into(),
+                        DebuggerPaneItem::Disassembly,
+                        cx,
+                    )),
                 })
                 .collect();
 
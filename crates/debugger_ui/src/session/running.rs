@@ -1,5 +1,6 @@
 pub(crate) mod breakpoint_list;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
 pub(crate) mod loaded_source_list;
 pub(crate) mod memory_view;
 pub(crate) mod module_list;
@@ -18,7 +19,7 @@ use crate::{
     attach_modal::{AttachModal, ModalIntent},
     new_process_modal::resolve_path,
     persistence::{self, DebuggerPaneItem, SerializedLayout},
-    session::running::memory_view::MemoryView,
+    session::running::{disassembly_view::DisassemblyView, memory_view::MemoryView},
 };
 
 use anyhow::{Context as _, Result, anyhow, bail};
@@ -26,7 +27,7 @@ use breakpoint_list::BreakpointList;
 use collections::{HashMap, IndexMap};
 use console::Console;
 use dap::{
-    Capabilities, DapRegistry, RunInTerminalRequestArguments, Thread,
+    Capabilities, DapRegistry, RunInTerminalRequestArguments, SteppingGranularity, Thread,
     adapters::{DebugAdapterName, DebugTaskDefinition},
     client::SessionId,
     debugger_settings::DebuggerSettings,
@@ -86,9 +87,12 @@ pub struct RunningState {
     pane_close_subscriptions: HashMap<EntityId, Subscription>,
     dock_axis: Axis,
     _schedule_serialize: Option<Task<()>>,
+    serialized_watches: Option<Vec<SharedString>>,
+    _serialize_watches: Option<Task<()>>,
     pub(crate) scenario: Option<DebugScenario>,
     pub(crate) scenario_context: Option<DebugScenarioContext>,
     memory_view: Entity<MemoryView>,
+    disassembly_view: Entity<DisassemblyView>,
 }
 
 impl RunningState {
@@ -774,6 +778,16 @@ impl RunningState {
 
         let module_list = cx.new(|cx| ModuleList::new(session.clone(), workspace.clone(), cx));
 
+        let disassembly_view = cx.new(|cx| {
+            DisassemblyView::new(
+                session.clone(),
+                workspace.clone(),
+                stack_frame_list.clone(),
+                weak_state.clone(),
+                cx,
+            )
+        });
+
         let loaded_source_list = cx.new(|cx| LoadedSourceList::new(session.clone(), cx));
 
         let console = cx.new(|cx| {
@@ -845,10 +859,14 @@ impl RunningState {
                         {
                             this.remove_pane_item(DebuggerPaneItem::LoadedSources, window, cx);
                         }
+                        if !capabilities.supports_disassemble_request.unwrap_or(false) {
+                            this.remove_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+                        }
                     }
                     SessionEvent::RunInTerminal { request, sender } => this
                         .handle_run_in_terminal(request, sender.clone(), window, cx)
                         .detach_and_log_err(cx),
+                    SessionEvent::Watchers => this.serialize_watches(cx),
 
                     _ => {}
                 }
@@ -883,6 +901,7 @@ impl RunningState {
                 &loaded_source_list,
                 &debug_terminal,
                 &memory_view,
+                &disassembly_view,
                 &mut pane_close_subscriptions,
                 window,
                 cx,
@@ -910,8 +929,31 @@ impl RunningState {
         };
         let active_pane = panes.first_pane();
 
+        cx.spawn({
+            let workspace = workspace.clone();
+            async move |this, cx| {
+                let workspace_id = workspace
+                    .read_with(cx, |workspace, _| workspace.database_id())
+                    .ok()
+                    .flatten()?;
+                let expressions = persistence::get_serialized_watches(workspace_id);
+                if expressions.is_empty() {
+                    return None;
+                }
+                this.update(cx, |this, cx| {
+                    this.serialized_watches = Some(expressions.clone());
+                    this.session.update(cx, |session, cx| {
+                        session.restore_watchers(expressions, cx);
+                    });
+                })
+                .ok()
+            }
+        })
+        .detach();
+
         Self {
             memory_view,
+            disassembly_view,
             session,
             workspace,
             focus_handle,
@@ -931,6 +973,8 @@ impl RunningState {
             debug_terminal,
             dock_axis,
             _schedule_serialize: None,
+            serialized_watches: None,
+            _serialize_watches: None,
             scenario: None,
             scenario_context: None,
         }
@@ -1137,8 +1181,9 @@ impl RunningState {
                     });
                 })?;
 
+                // Build tasks running in the background, like watchers, only have to get ready.
                 let exit_status = terminal
-                    .read_with(cx, |terminal, cx| terminal.wait_for_completed_task(cx))?
+                    .update(cx, |terminal, cx| terminal.wait_for_ready_task(cx))?
                     .await
                     .context("Failed to wait for completed task")?;
 
@@ -1278,6 +1323,9 @@ impl RunningState {
             show_summary: false,
             show_command: false,
             show_rerun: false,
+            problem_matcher: Vec::new(),
+            background: None,
+            is_dependency: false,
         };
 
         let workspace = self.workspace.clone();
@@ -1358,6 +1406,12 @@ impl RunningState {
                 item_kind,
                 cx,
             )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                cx,
+            )),
         }
     }
 
@@ -1455,6 +1509,35 @@ impl RunningState {
         }
     }
 
+    /// Persists the watch expressions of the session for the project, if they changed.
+    fn serialize_watches(&mut self, cx: &mut Context<Self>) {
+        let expressions = self
+            .session
+            .read(cx)
+            .watchers()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        if self.serialized_watches.as_ref() == Some(&expressions) {
+            return;
+        }
+        self.serialized_watches = Some(expressions.clone());
+
+        let workspace = self.workspace.clone();
+        self._serialize_watches = Some(cx.spawn(async move |_, cx| {
+            let Some(workspace_id) = workspace
+                .read_with(cx, |workspace, _| workspace.database_id())
+                .ok()
+                .flatten()
+            else {
+                return;
+            };
+            persistence::serialize_watches(workspace_id, expressions)
+                .await
+                .log_err();
+        }));
+    }
+
     pub(crate) fn handle_pane_event(
         this: &mut RunningState,
         source_pane: &Entity<Pane>,
@@ -1542,6 +1625,11 @@ impl RunningState {
         &self.module_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
     pub(crate) fn activate_item(
         &mut self,
         item: DebuggerPaneItem,
@@ -1660,6 +1748,26 @@ impl RunningState {
         });
     }
 
+    pub(crate) fn step_over_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_over(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
+    pub(crate) fn step_in_instruction(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.step_in(thread_id, SteppingGranularity::Instruction, cx);
+        });
+    }
+
     pub(crate) fn step_out(&mut self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
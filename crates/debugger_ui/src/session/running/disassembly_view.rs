@@ -0,0 +1,467 @@
+use std::{ops::Range, path::Path, sync::Arc};
+
+use collections::HashMap;
+use dap::DisassembledInstruction;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, ListHorizontalSizingBehavior, ScrollStrategy,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use language::{Buffer, Point};
+use project::debugger::session::{Session, SessionEvent, ThreadStatus};
+use ui::{Tooltip, WithScrollbar, prelude::*};
+use util::ResultExt as _;
+use workspace::Workspace;
+
+use crate::session::running::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+
+/// How many instructions are disassembled before the instruction pointer.
+const INSTRUCTIONS_BEFORE: i64 = 32;
+/// How many instructions are disassembled in total.
+const INSTRUCTION_COUNT: u64 = 96;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum DisassemblyEntry {
+    /// The source line the following instructions were compiled from.
+    Source {
+        name: SharedString,
+        path: Option<Arc<Path>>,
+        line: u64,
+    },
+    Instruction(DisassembledInstruction),
+}
+
+/// Interleaves the instructions with the source lines they were compiled from.
+///
+/// Adapters only have to send an instruction's location when it differs from the one of the
+/// previous instruction, so the last seen location carries over.
+pub(crate) fn disassembly_entries(
+    instructions: &[DisassembledInstruction],
+) -> Vec<DisassemblyEntry> {
+    let mut entries = Vec::with_capacity(instructions.len());
+    let mut location: Option<&dap::Source> = None;
+    let mut last_line = None;
+
+    for instruction in instructions {
+        if instruction.location.is_some() {
+            location = instruction.location.as_ref();
+        }
+        if let Some(source) = location
+            && let Some(line) = instruction.line
+            && last_line != Some((source, line))
+        {
+            last_line = Some((source, line));
+            entries.push(DisassemblyEntry::Source {
+                name: source
+                    .name
+                    .clone()
+                    .or_else(|| source.path.clone())
+                    .unwrap_or_default()
+                    .into(),
+                path: source
+                    .path
+                    .as_deref()
+                    .map(|path| Arc::<Path>::from(Path::new(path))),
+                line,
+            });
+        }
+        entries.push(DisassemblyEntry::Instruction(instruction.clone()));
+    }
+
+    entries
+}
+
+fn parse_address(address: &str) -> Option<u64> {
+    parse_int::parse::<u64>(address).ok()
+}
+
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    workspace: WeakEntity<Workspace>,
+    stack_frame_list: WeakEntity<StackFrameList>,
+    running_state: WeakEntity<RunningState>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    entries: Vec<DisassemblyEntry>,
+    instruction_pointer: Option<String>,
+    scrolled_to: Option<String>,
+    source_buffers: HashMap<Arc<Path>, Option<Entity<Buffer>>>,
+    _rebuild_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        workspace: WeakEntity<Workspace>,
+        stack_frame_list: Entity<StackFrameList>,
+        running_state: WeakEntity<RunningState>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_)
+                | SessionEvent::StackTrace
+                | SessionEvent::HistoricSnapshotSelected
+                | SessionEvent::Disassembly => {
+                    if this._rebuild_task.is_some() {
+                        this.schedule_rebuild(cx);
+                    }
+                }
+                _ => {}
+            }),
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_)
+                | StackFrameListEvent::BuiltEntries => {
+                    if this._rebuild_task.is_some() {
+                        this.schedule_rebuild(cx);
+                    }
+                }
+            }),
+            // Instruction breakpoints are toggled without a session event.
+            cx.observe(&session, |_, _, cx| cx.notify()),
+        ];
+
+        Self {
+            session,
+            workspace,
+            stack_frame_list: stack_frame_list.downgrade(),
+            running_state,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            entries: Vec::new(),
+            instruction_pointer: None,
+            scrolled_to: None,
+            source_buffers: HashMap::default(),
+            _rebuild_task: None,
+            _subscriptions,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn entries(&self) -> &[DisassemblyEntry] {
+        &self.entries
+    }
+
+    fn schedule_rebuild(&mut self, cx: &mut Context<Self>) {
+        self._rebuild_task = Some(cx.spawn(async move |this, cx| {
+            this.update(cx, |this, cx| this.rebuild(cx)).ok();
+        }));
+    }
+
+    fn selected_stack_frame(&self, cx: &mut App) -> Option<dap::StackFrame> {
+        let stack_frame_list = self.stack_frame_list.upgrade()?;
+        let selected_id = stack_frame_list.read(cx).opened_stack_frame_id();
+        let stack_frames = stack_frame_list.update(cx, |list, cx| list.dap_stack_frames(cx));
+        match selected_id {
+            Some(id) => stack_frames.into_iter().find(|frame| frame.id == id),
+            None => stack_frames.into_iter().next(),
+        }
+    }
+
+    fn rebuild(&mut self, cx: &mut Context<Self>) {
+        self.instruction_pointer = self
+            .selected_stack_frame(cx)
+            .and_then(|frame| frame.instruction_pointer_reference);
+
+        let instructions = self.instruction_pointer.clone().and_then(|reference| {
+            self.session.update(cx, |session, cx| {
+                session.disassemble(reference, -INSTRUCTIONS_BEFORE, INSTRUCTION_COUNT, cx)
+            })
+        });
+        self.entries = instructions
+            .map(|instructions| disassembly_entries(&instructions))
+            .unwrap_or_default();
+
+        self.load_source_buffers(cx);
+        if self.scrolled_to != self.instruction_pointer
+            && let Some(ix) = self.instruction_pointer_ix()
+        {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+            self.scrolled_to = self.instruction_pointer.clone();
+        }
+        cx.notify();
+    }
+
+    fn instruction_pointer_ix(&self) -> Option<usize> {
+        let address = parse_address(self.instruction_pointer.as_deref()?)?;
+        self.entries.iter().position(|entry| {
+            matches!(entry, DisassemblyEntry::Instruction(instruction)
+                if parse_address(&instruction.address) == Some(address))
+        })
+    }
+
+    /// Opens the buffers of the interleaved source lines, so that their text can be shown.
+    fn load_source_buffers(&mut self, cx: &mut Context<Self>) {
+        let paths = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                DisassemblyEntry::Source {
+                    path: Some(path), ..
+                } => Some(path.clone()),
+                _ => None,
+            })
+            .filter(|path| !self.source_buffers.contains_key(path))
+            .collect::<Vec<_>>();
+
+        for path in paths {
+            self.source_buffers.insert(path.clone(), None);
+            let Some(task) = self
+                .workspace
+                .update(cx, |workspace, cx| {
+                    workspace
+                        .project()
+                        .update(cx, |project, cx| project.open_local_buffer(&path, cx))
+                })
+                .ok()
+            else {
+                continue;
+            };
+            cx.spawn(async move |this, cx| {
+                let buffer = task.await.log_err();
+                this.update(cx, |this, cx| {
+                    this.source_buffers.insert(path, buffer);
+                    cx.notify();
+                })
+                .ok();
+            })
+            .detach();
+        }
+    }
+
+    fn source_line_text(&self, path: &Path, line: u64, cx: &App) -> Option<String> {
+        let buffer = self.source_buffers.get(path)?.as_ref()?.read(cx);
+        let row = u32::try_from(line.checked_sub(1)?).ok()?;
+        if row > buffer.max_point().row {
+            return None;
+        }
+        let text = buffer
+            .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+            .collect::<String>();
+        Some(text.trim().to_string())
+    }
+
+    fn step(&mut self, step_in: bool, cx: &mut Context<Self>) {
+        self.running_state
+            .update(cx, |running_state, cx| {
+                if step_in {
+                    running_state.step_in_instruction(cx);
+                } else {
+                    running_state.step_over_instruction(cx);
+                }
+            })
+            .ok();
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.entries[ix] {
+            DisassemblyEntry::Source { name, path, line } => {
+                let text = path
+                    .as_deref()
+                    .and_then(|path| self.source_line_text(path, *line, cx));
+                h_flex()
+                    .id(("disassembly-source", ix))
+                    .w_full()
+                    .pl_6()
+                    .pt_1()
+                    .gap_2()
+                    .child(
+                        Label::new(format!("{name}:{line}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .children(text.map(|text| {
+                        Label::new(text)
+                            .size(LabelSize::Small)
+                            .buffer_font(cx)
+                            .color(Color::Muted)
+                    }))
+                    .into_any_element()
+            }
+            DisassemblyEntry::Instruction(instruction) => {
+                self.render_instruction(ix, instruction, cx)
+            }
+        }
+    }
+
+    fn render_instruction(
+        &self,
+        ix: usize,
+        instruction: &DisassembledInstruction,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let session = self.session.read(cx);
+        let supports_breakpoints = session
+            .capabilities()
+            .supports_instruction_breakpoints
+            .unwrap_or_default();
+        let has_breakpoint = session.has_instruction_breakpoint(&instruction.address);
+        let is_current = parse_address(&instruction.address).is_some_and(|address| {
+            self.instruction_pointer
+                .as_deref()
+                .and_then(parse_address)
+                .is_some_and(|pointer| pointer == address)
+        });
+        let address = instruction.address.clone();
+
+        h_flex()
+            .id(("disassembly-instruction", ix))
+            .w_full()
+            .gap_2()
+            .font_buffer(cx)
+            .text_buffer(cx)
+            .when(is_current, |this| {
+                this.bg(cx.theme().colors().editor_debugger_active_line_background)
+            })
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .child(
+                h_flex()
+                    .id(("disassembly-gutter", ix))
+                    .w_4()
+                    .flex_none()
+                    .justify_center()
+                    .map(|this| {
+                        if has_breakpoint {
+                            this.child(
+                                Icon::new(IconName::DebugBreakpoint)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Debugger),
+                            )
+                        } else if is_current {
+                            this.child(
+                                Icon::new(IconName::ArrowRight)
+                                    .size(IconSize::XSmall)
+                                    .color(Color::Debugger),
+                            )
+                        } else {
+                            this
+                        }
+                    })
+                    .when(supports_breakpoints, |this| {
+                        this.cursor_pointer()
+                            .tooltip(Tooltip::text("Toggle Instruction Breakpoint"))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.session.update(cx, |session, cx| {
+                                    session.toggle_instruction_breakpoint(address.clone(), cx);
+                                });
+                            }))
+                    }),
+            )
+            .child(
+                div()
+                    .flex_none()
+                    .child(Label::new(instruction.address.clone()).color(Color::Muted)),
+            )
+            .child(
+                div().w_40().flex_none().overflow_hidden().child(
+                    Label::new(instruction.instruction_bytes.clone().unwrap_or_default())
+                        .color(Color::Muted)
+                        .truncate(),
+                ),
+            )
+            .child(div().flex_none().child(instruction.instruction.clone()))
+            .children(instruction.symbol.clone().map(|symbol| {
+                Label::new(format!("<{symbol}>"))
+                    .color(Color::Muted)
+                    .truncate()
+            }))
+            .into_any_element()
+    }
+
+    fn render_controls(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let supports_instruction_stepping = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_stepping_granularity
+            .unwrap_or_default();
+        let is_stopped = self
+            .running_state
+            .upgrade()
+            .is_some_and(|state| state.read(cx).thread_status(cx) == Some(ThreadStatus::Stopped));
+        let disabled = !supports_instruction_stepping || !is_stopped;
+
+        h_flex()
+            .w_full()
+            .px_1()
+            .gap_1()
+            .child(
+                IconButton::new("disassembly-step-over", IconName::ArrowRight)
+                    .icon_size(IconSize::Small)
+                    .disabled(disabled)
+                    .tooltip(Tooltip::text("Step Over Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(false, cx))),
+            )
+            .child(
+                IconButton::new("disassembly-step-in", IconName::ArrowDownRight)
+                    .icon_size(IconSize::Small)
+                    .disabled(disabled)
+                    .tooltip(Tooltip::text("Step Into Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| this.step(true, cx))),
+            )
+            .when(!supports_instruction_stepping, |this| {
+                this.child(
+                    Label::new("The debug adapter doesn't support stepping by instruction")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+    }
+
+    fn render_list(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "disassembly-view",
+            self.entries.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.map(|ix| this.render_entry(ix, cx)).collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .with_horizontal_sizing_behavior(ListHorizontalSizingBehavior::Unconstrained)
+        .size_full()
+    }
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self._rebuild_task.is_none() {
+            self.schedule_rebuild(cx);
+        }
+
+        let content = if self.entries.is_empty() {
+            let message = if self.instruction_pointer.is_some() {
+                "Disassembling…"
+            } else {
+                "The selected stack frame has no instruction pointer"
+            };
+            div()
+                .p_2()
+                .child(Label::new(message).color(Color::Muted))
+                .into_any_element()
+        } else {
+            div()
+                .size_full()
+                .child(self.render_list(cx))
+                .vertical_scrollbar_for(&self.scroll_handle, window, cx)
+                .into_any_element()
+        };
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .gap_1()
+            .child(self.render_controls(cx))
+            .child(content)
+    }
+}
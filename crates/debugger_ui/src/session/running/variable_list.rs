@@ -40,6 +40,10 @@ actions!(
         AddWatch,
         /// Removes the selected variable from the watch list.
         RemoveWatch,
+        /// Adds a new expression to the watch list.
+        AddWatchExpression,
+        /// Edits the expression of the selected watch.
+        EditWatch,
         /// Jump to variable's memory location.
         GoToMemory,
     ]
@@ -197,6 +201,8 @@ pub struct VariableList {
     open_context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     focus_handle: FocusHandle,
     edited_path: Option<(EntryPath, Entity<Editor>)>,
+    /// The editor of a watch expression, along with the expression it replaces, if any.
+    watch_editor: Option<(Option<SharedString>, Entity<Editor>)>,
     disabled: bool,
     memory_view: Entity<MemoryView>,
     weak_running: WeakEntity<RunningState>,
@@ -235,6 +241,7 @@ impl VariableList {
             }),
             cx.on_focus_out(&focus_handle, window, |this, _, _, cx| {
                 this.edited_path.take();
+                this.watch_editor.take();
                 cx.notify();
             }),
         ];
@@ -251,6 +258,7 @@ impl VariableList {
             open_context_menu: None,
             disabled: false,
             edited_path: None,
+            watch_editor: None,
             entries: Default::default(),
             max_width_index: None,
             entry_states: Default::default(),
@@ -315,6 +323,7 @@ impl VariableList {
         stack.extend(
             watches
                 .into_values()
+                .rev()
                 .map(|watcher| {
                     (
                         watcher.variables_reference,
@@ -529,11 +538,19 @@ impl VariableList {
 
     fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
         self.edited_path.take();
+        self.watch_editor.take();
         self.focus_handle.focus(window);
         cx.notify();
     }
 
-    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some((old_expression, editor)) = self.watch_editor.take() {
+            let expression = editor.read(cx).text(cx).trim().to_string();
+            self.confirm_watch_expression(old_expression, expression.into(), cx);
+            self.focus_handle.focus(window);
+            return;
+        }
+
         if let Some((var_path, editor)) = self.edited_path.take() {
             let Some(state) = self.entry_states.get(&var_path) else {
                 return;
@@ -750,8 +767,11 @@ impl VariableList {
                             .when(supports_set_variable, |menu| {
                                 menu.action("Edit Value", EditVariable.boxed_clone())
                             })
+                            .action("Edit Watch Expression", EditWatch.boxed_clone())
                             .action("Remove Watch", RemoveWatch.boxed_clone())
                     })
+                    .when(entry.as_scope().is_none(), |menu| menu.separator())
+                    .action("Add Watch Expression", AddWatchExpression.boxed_clone())
                     .context(focus_handle.clone())
                 });
 
@@ -965,12 +985,86 @@ impl VariableList {
             return;
         };
 
-        self.session.update(cx, |session, _| {
-            session.remove_watcher(watcher.expression.clone());
+        self.session.update(cx, |session, cx| {
+            session.remove_watcher(watcher.expression.clone(), cx);
         });
         self.build_entries(cx);
     }
 
+    fn add_watch_expression(
+        &mut self,
+        _: &AddWatchExpression,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = Self::create_variable_editor("", window, cx);
+        self.watch_editor = Some((None, editor));
+        cx.notify();
+    }
+
+    fn edit_watch(&mut self, _: &EditWatch, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+
+        let Some(watcher) = self
+            .entries
+            .iter()
+            .find(|entry| &entry.path == selection)
+            .and_then(|entry| entry.as_watcher())
+        else {
+            return;
+        };
+
+        let expression = watcher.expression.clone();
+        let editor = Self::create_variable_editor(&expression, window, cx);
+        self.watch_editor = Some((Some(expression), editor));
+        cx.notify();
+    }
+
+    /// Adds the expression to the watch list, or replaces `old_expression` with it.
+    /// Clearing the expression of an existing watch removes it.
+    fn confirm_watch_expression(
+        &mut self,
+        old_expression: Option<SharedString>,
+        expression: SharedString,
+        cx: &mut Context<Self>,
+    ) {
+        if old_expression.as_ref() == Some(&expression) {
+            return;
+        }
+
+        if expression.is_empty() {
+            if let Some(old_expression) = old_expression {
+                self.session.update(cx, |session, cx| {
+                    session.remove_watcher(old_expression, cx);
+                });
+                self.build_entries(cx);
+            }
+            return;
+        }
+
+        let Some(stack_frame_id) = self.selected_stack_frame_id else {
+            return;
+        };
+
+        let task = self.session.update(cx, |session, cx| match old_expression {
+            Some(old_expression) => {
+                session.edit_watcher(old_expression, expression, stack_frame_id, cx)
+            }
+            None => session.add_watcher(expression, stack_frame_id, cx),
+        });
+
+        cx.spawn(async move |this, cx| {
+            task.await?;
+
+            this.update(cx, |this, cx| {
+                this.build_entries(cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     #[track_caller]
     #[cfg(test)]
     pub(crate) fn assert_visual_entries(&self, expected: Vec<&str>) {
@@ -1221,7 +1315,15 @@ impl VariableList {
             return div().into_any_element();
         };
 
-        let variable_color = self.variable_color(watcher.presentation_hint.as_ref(), cx);
+        let mut variable_color = self.variable_color(watcher.presentation_hint.as_ref(), cx);
+        if watcher.is_error && !self.disabled {
+            variable_color.value = Some(Color::Error.color(cx));
+        }
+        let expression_editor = self
+            .watch_editor
+            .as_ref()
+            .filter(|(expression, _)| expression.as_ref() == Some(&watcher.expression))
+            .map(|(_, editor)| editor.clone());
 
         let is_selected = self
             .selection
@@ -1302,27 +1404,44 @@ impl VariableList {
                         cx.stop_propagation();
                     }
                 }))
-                .child(
-                    h_flex()
-                        .gap_1()
-                        .text_ui_sm(cx)
-                        .w_full()
-                        .child(
-                            Label::new(&Self::center_truncate_string(
-                                watcher.expression.as_ref(),
-                                watcher_len,
-                            ))
-                            .when_some(variable_color.name, |this, color| {
-                                this.color(Color::from(color))
-                            }),
+                .child(h_flex().gap_1().text_ui_sm(cx).w_full().map(|this| {
+                    if let Some(editor) = expression_editor {
+                        this.child(div().w_full().px_2().child(editor))
+                    } else {
+                        this.child(
+                            div()
+                                .id(SharedString::from(format!(
+                                    "watcher-{}-expression",
+                                    watcher.expression
+                                )))
+                                .on_click(cx.listener({
+                                    let path = path.clone();
+                                    move |this, click: &ClickEvent, window, cx| {
+                                        if click.click_count() < 2 {
+                                            return;
+                                        }
+                                        this.selection = Some(path.clone());
+                                        this.edit_watch(&EditWatch, window, cx);
+                                    }
+                                }))
+                                .child(
+                                    Label::new(&Self::center_truncate_string(
+                                        watcher.expression.as_ref(),
+                                        watcher_len,
+                                    ))
+                                    .when_some(variable_color.name, |this, color| {
+                                        this.color(Color::from(color))
+                                    }),
+                                ),
                         )
                         .child(self.render_variable_value(
                             entry,
                             &variable_color,
                             watcher.value.to_string(),
                             cx,
-                        )),
-                )
+                        ))
+                    }
+                }))
                 .end_slot(
                     IconButton::new(
                         SharedString::from(format!("watcher-{}-remove-button", watcher.expression)),
@@ -1346,6 +1465,49 @@ impl VariableList {
             .into_any()
     }
 
+    fn render_watch_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let new_watch_editor = self
+            .watch_editor
+            .as_ref()
+            .filter(|(expression, _)| expression.is_none())
+            .map(|(_, editor)| editor.clone());
+        let focus_handle = self.focus_handle.clone();
+
+        h_flex()
+            .w_full()
+            .pl_2()
+            .pr_1()
+            .gap_1()
+            .justify_between()
+            .map(|this| {
+                if let Some(editor) = new_watch_editor {
+                    this.child(div().w_full().px_2().child(editor))
+                } else {
+                    this.child(
+                        Label::new("Watch")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                }
+            })
+            .child(
+                IconButton::new("add-watch-expression", IconName::Plus)
+                    .icon_size(IconSize::Small)
+                    .disabled(self.disabled)
+                    .tooltip(move |_window, cx| {
+                        Tooltip::for_action_in(
+                            "Add Watch Expression",
+                            &AddWatchExpression,
+                            &focus_handle,
+                            cx,
+                        )
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.add_watch_expression(&AddWatchExpression, window, cx);
+                    })),
+            )
+    }
+
     fn render_scope(
         &self,
         entry: &ListEntry,
@@ -1552,8 +1714,11 @@ impl Render for VariableList {
             .on_action(cx.listener(Self::edit_variable))
             .on_action(cx.listener(Self::add_watcher))
             .on_action(cx.listener(Self::remove_watcher))
+            .on_action(cx.listener(Self::add_watch_expression))
+            .on_action(cx.listener(Self::edit_watch))
             .on_action(cx.listener(Self::toggle_data_breakpoint))
             .on_action(cx.listener(Self::jump_to_variable_memory))
+            .child(self.render_watch_header(cx))
             .child(
                 uniform_list(
                     "variable-list",
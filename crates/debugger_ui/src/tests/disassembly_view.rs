@@ -0,0 +1,168 @@
+use crate::{
+    persistence::DebuggerPaneItem,
+    session::running::disassembly_view::DisassemblyEntry,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    DisassembledInstruction, StackFrame, StoppedEvent,
+    requests::{Disassemble, Initialize, Scopes, StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use util::path;
+
+fn instruction(
+    address: &str,
+    text: &str,
+    line: u64,
+    with_location: bool,
+) -> DisassembledInstruction {
+    DisassembledInstruction {
+        address: address.into(),
+        instruction_bytes: None,
+        instruction: text.into(),
+        symbol: None,
+        location: with_location.then(|| dap::Source {
+            name: Some("main.c".into()),
+            path: Some(path!("/project/main.c").into()),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: None,
+            adapter_data: None,
+            checksums: None,
+        }),
+        line: Some(line),
+        column: None,
+        end_line: None,
+        end_column: None,
+        presentation_hint: None,
+    }
+}
+
+#[gpui::test]
+async fn test_disassembly_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 3,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: Some("0x1004".into()),
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    let instructions = vec![
+        instruction("0x1000", "push rbp", 2, true),
+        instruction("0x1004", "mov rbp, rsp", 3, false),
+        instruction("0x1008", "xor eax, eax", 3, false),
+    ];
+    let called_disassemble = Arc::new(AtomicBool::new(false));
+    client.on_request::<Disassemble, _>({
+        let called_disassemble = called_disassemble.clone();
+        let instructions = instructions.clone();
+        move |_, args| {
+            assert_eq!("0x1004", args.memory_reference);
+            called_disassemble.store(true, Ordering::SeqCst);
+            Ok(dap::DisassembleResponse {
+                instructions: instructions.clone(),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |this, window, cx| {
+        let threads = this.session().update(cx, |session, cx| session.threads(cx));
+        this.select_current_thread(&threads, window, cx);
+    });
+    cx.run_until_parked();
+
+    running_state.update_in(cx, |this, window, cx| {
+        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+
+    assert!(
+        called_disassemble.load(Ordering::SeqCst),
+        "Disassemble should be requested once the disassembly view is shown"
+    );
+
+    let entries = running_state.update(cx, |state, cx| {
+        state.disassembly_view().read(cx).entries().to_vec()
+    });
+    let source = |line| DisassemblyEntry::Source {
+        name: "main.c".into(),
+        path: Some(Arc::from(std::path::Path::new(path!("/project/main.c")))),
+        line,
+    };
+    assert_eq!(
+        entries,
+        vec![
+            source(2),
+            DisassemblyEntry::Instruction(instructions[0].clone()),
+            source(3),
+            DisassemblyEntry::Instruction(instructions[1].clone()),
+            DisassemblyEntry::Instruction(instructions[2].clone()),
+        ]
+    );
+}
@@ -2,15 +2,16 @@ use std::iter::zip;
 
 use crate::{
     debugger_panel::DebugPanel,
-    persistence::SerializedPaneLayout,
+    persistence::{SerializedPaneLayout, get_serialized_watches, serialize_watches},
     tests::{init_test, init_test_workspace, start_debug_session},
 };
 use dap::{StoppedEvent, StoppedEventReason, messages::Events};
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use project::{FakeFs, Project};
 use serde_json::json;
+use ui::SharedString;
 use util::path;
-use workspace::{Panel, dock::DockPosition};
+use workspace::{Panel, WorkspaceId, dock::DockPosition};
 
 #[gpui::test]
 async fn test_invert_axis_on_panel_position_change(
@@ -129,3 +130,19 @@ async fn test_invert_axis_on_panel_position_change(
         }
     }
 }
+
+#[gpui::test]
+async fn test_serialize_watches() {
+    let workspace_id = WorkspaceId::default();
+
+    serialize_watches(workspace_id, vec!["foo".into(), "bar.baz".into()])
+        .await
+        .unwrap();
+    assert_eq!(
+        get_serialized_watches(workspace_id),
+        vec![SharedString::from("foo"), SharedString::from("bar.baz")]
+    );
+
+    serialize_watches(workspace_id, Vec::new()).await.unwrap();
+    assert!(get_serialized_watches(workspace_id).is_empty());
+}
@@ -7,17 +7,18 @@ use crate::{
     DebugPanel,
     persistence::DebuggerPaneItem,
     session::running::variable_list::{
-        AddWatch, CollapseSelectedEntry, ExpandSelectedEntry, RemoveWatch,
+        AddWatch, AddWatchExpression, CollapseSelectedEntry, EditWatch, ExpandSelectedEntry,
+        RemoveWatch,
     },
     tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
 };
 use collections::HashMap;
 use dap::{
-    Scope, StackFrame, Variable,
+    ErrorResponse, Scope, StackFrame, Variable,
     requests::{Evaluate, Initialize, Launch, Scopes, StackTrace, Variables},
 };
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
-use menu::{SelectFirst, SelectNext, SelectPrevious};
+use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
 use project::{FakeFs, Project};
 use serde_json::json;
 use ui::SharedString;
@@ -2340,3 +2341,213 @@ async fn test_refresh_watchers(executor: BackgroundExecutor, cx: &mut TestAppCon
         assert_eq!(3, watcher.variables_reference);
     });
 }
+
+#[gpui::test]
+async fn test_add_and_edit_watch_expression(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "const variable1 = 1;\nconst variable2 = 2;\n",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "Stack Frame 1".into(),
+                source: Some(dap::Source {
+                    name: Some("test.js".into()),
+                    path: Some(path!("/project/src/test.js").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    client.on_request::<Evaluate, _>(move |_, args| {
+        assert!(matches!(
+            args.context,
+            Some(dap::EvaluateArgumentsContext::Watch)
+        ));
+
+        let value = match args.expression.as_str() {
+            "variable1" => "1",
+            "variable2" => "2",
+            _ => {
+                return Err(ErrorResponse {
+                    error: Some(dap::Message {
+                        id: 1,
+                        format: "not defined".into(),
+                        variables: None,
+                        send_telemetry: None,
+                        show_user: None,
+                        url: None,
+                        url_label: None,
+                    }),
+                });
+            }
+        };
+
+        Ok(dap::EvaluateResponse {
+            result: value.to_owned(),
+            type_: None,
+            presentation_hint: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            let running = item.running_state().clone();
+
+            let variable_list = running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            });
+            variable_list.update(cx, |_, cx| cx.focus_self(window));
+            running
+        });
+    cx.run_until_parked();
+
+    let watchers = |cx: &mut VisualTestContext| {
+        session.update(cx, |session, _| {
+            session
+                .watchers()
+                .values()
+                .map(|watcher| {
+                    (
+                        watcher.expression.to_string(),
+                        watcher.is_error,
+                        (!watcher.is_error).then(|| watcher.value.to_string()),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    for expression in ["variable1", "missing"] {
+        running_state.update(cx, |running_state, cx| {
+            running_state.variable_list().update(cx, |_, cx| {
+                cx.dispatch_action(&AddWatchExpression);
+            });
+        });
+        cx.run_until_parked();
+        cx.simulate_input(expression);
+        cx.dispatch_action(menu::Confirm);
+        cx.run_until_parked();
+    }
+
+    // Watches that fail to evaluate stay in the list, in the order they were added
+    assert_eq!(
+        watchers(cx),
+        vec![
+            ("variable1".to_string(), false, Some("1".to_string())),
+            ("missing".to_string(), true, None),
+        ]
+    );
+    running_state.update(cx, |running_state, cx| {
+        running_state.variable_list().update(cx, |list, _| {
+            list.assert_visual_entries(vec!["> variable1", "> missing"]);
+        });
+    });
+
+    // Edit the expression of the first watch
+    running_state.update(cx, |running_state, cx| {
+        running_state.variable_list().update(cx, |_, cx| {
+            cx.dispatch_action(&SelectFirst);
+            cx.dispatch_action(&EditWatch);
+        });
+    });
+    cx.run_until_parked();
+    cx.simulate_input("variable2");
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+
+    assert_eq!(
+        watchers(cx),
+        vec![
+            ("variable2".to_string(), false, Some("2".to_string())),
+            ("missing".to_string(), true, None),
+        ]
+    );
+
+    // Clearing the expression of a watch removes it
+    running_state.update(cx, |running_state, cx| {
+        running_state.variable_list().update(cx, |_, cx| {
+            cx.dispatch_action(&SelectLast);
+            cx.dispatch_action(&EditWatch);
+        });
+    });
+    cx.run_until_parked();
+    cx.simulate_keystrokes("backspace");
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+
+    assert_eq!(
+        watchers(cx),
+        vec![("variable2".to_string(), false, Some("2".to_string()))]
+    );
+}
@@ -84,6 +84,10 @@ pub enum FoldStatus {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum HighlightKey {
+    /// Semantic token highlights from language servers, one key per distinct style.
+    /// Sorts before the other keys, so these are applied right over the tree-sitter
+    /// highlights and under all other text highlights.
+    SemanticToken(usize),
     Type(TypeId),
     TypePlus(TypeId, usize),
 }
@@ -94,6 +98,7 @@ pub trait ToDisplayPoint {
 
 type TextHighlights = TreeMap<HighlightKey, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>;
 type InlayHighlights = TreeMap<TypeId, TreeMap<InlayId, (HighlightStyle, InlayHighlight)>>;
+type LspFoldingRanges = TreeMap<BufferId, Arc<[Range<text::Anchor>]>>;
 
 /// Decides how text in a [`MultiBuffer`] should be displayed in a buffer, handling inlay hints,
 /// folding, hard tabs, soft wrapping, custom blocks (like diagnostics), and highlighting.
@@ -119,6 +124,9 @@ pub struct DisplayMap {
     inlay_highlights: InlayHighlights,
     /// A container for explicitly foldable ranges, which supersede indentation based fold range suggestions.
     crease_map: CreaseMap,
+    /// Foldable ranges reported by language servers, which supersede indentation based fold range suggestions
+    /// in their buffers.
+    lsp_folding_ranges: LspFoldingRanges,
     pub(crate) fold_placeholder: FoldPlaceholder,
     pub clip_at_line_ends: bool,
     pub(crate) masked: bool,
@@ -159,6 +167,7 @@ impl DisplayMap {
             wrap_map,
             block_map,
             crease_map,
+            lsp_folding_ranges: Default::default(),
             fold_placeholder,
             diagnostics_max_severity,
             text_highlights: Default::default(),
@@ -187,6 +196,7 @@ impl DisplayMap {
             block_snapshot,
             diagnostics_max_severity: self.diagnostics_max_severity,
             crease_snapshot: self.crease_map.snapshot(),
+            lsp_folding_ranges: self.lsp_folding_ranges.clone(),
             text_highlights: self.text_highlights.clone(),
             inlay_highlights: self.inlay_highlights.clone(),
             clip_at_line_ends: self.clip_at_line_ends,
@@ -411,6 +421,20 @@ impl DisplayMap {
         self.crease_map.remove(crease_ids, &snapshot)
     }
 
+    /// Sets the folding ranges reported by language servers for the buffer, sorted by their start,
+    /// larger ranges first. Each range spans from the end of its first line to the end of its last one.
+    pub(crate) fn set_lsp_folding_ranges(
+        &mut self,
+        buffer_id: BufferId,
+        ranges: Arc<[Range<text::Anchor>]>,
+    ) {
+        self.lsp_folding_ranges.insert(buffer_id, ranges);
+    }
+
+    pub(crate) fn remove_lsp_folding_ranges(&mut self, buffer_id: BufferId) -> bool {
+        self.lsp_folding_ranges.remove(&buffer_id).is_some()
+    }
+
     pub fn insert_blocks(
         &mut self,
         blocks: impl IntoIterator<Item = BlockProperties<Anchor>>,
@@ -507,6 +531,19 @@ impl DisplayMap {
         self.text_highlights.insert(key, to_insert);
     }
 
+    /// Replaces all semantic token highlights, each style with its ranges sorted by their start.
+    pub(crate) fn set_semantic_token_highlights(
+        &mut self,
+        highlights: Vec<(HighlightStyle, Vec<Range<Anchor>>)>,
+    ) {
+        self.text_highlights
+            .retain(|key, _| !matches!(key, HighlightKey::SemanticToken(_)));
+        for (ix, (style, ranges)) in highlights.into_iter().enumerate() {
+            self.text_highlights
+                .insert(HighlightKey::SemanticToken(ix), Arc::new((style, ranges)));
+        }
+    }
+
     pub(crate) fn highlight_inlays(
         &mut self,
         type_id: TypeId,
@@ -775,6 +812,7 @@ impl<'a> HighlightedChunk<'a> {
 #[derive(Clone)]
 pub struct DisplaySnapshot {
     pub crease_snapshot: CreaseSnapshot,
+    lsp_folding_ranges: LspFoldingRanges,
     block_snapshot: BlockSnapshot,
     text_highlights: TextHighlights,
     inlay_highlights: InlayHighlights,
@@ -1321,6 +1359,42 @@ impl DisplaySnapshot {
             .unwrap_or(false)
     }
 
+    /// Whether a fold range starts at the buffer row: one reported by a language server
+    /// if there are any for the row's buffer, or one based on indentation otherwise.
+    pub fn starts_fold(&self, buffer_row: MultiBufferRow) -> bool {
+        match self.lsp_fold_range_for_buffer_row(buffer_row) {
+            Some(fold_range) => fold_range.is_some(),
+            None => self.starts_indent(buffer_row),
+        }
+    }
+
+    /// Returns the largest language server fold range starting at the buffer row, or `None`
+    /// if there are no language server folding ranges for the row's buffer.
+    fn lsp_fold_range_for_buffer_row(
+        &self,
+        buffer_row: MultiBufferRow,
+    ) -> Option<Option<Range<Point>>> {
+        let buffer_snapshot = self.buffer_snapshot();
+        let (buffer, buffer_point, excerpt_id) =
+            buffer_snapshot.point_to_buffer_point(Point::new(buffer_row.0, 0))?;
+        let folding_ranges = self.lsp_folding_ranges.get(&buffer.remote_id())?;
+
+        let ix = folding_ranges.partition_point(|range| {
+            text::ToPoint::to_point(&range.start, buffer).row < buffer_point.row
+        });
+        let fold_range = folding_ranges
+            .get(ix)
+            .filter(|range| text::ToPoint::to_point(&range.start, buffer).row == buffer_point.row)
+            .and_then(|range| buffer_snapshot.anchor_range_in_excerpt(excerpt_id, range.clone()))
+            .map(|range| range.end.to_point(buffer_snapshot).row)
+            .filter(|end_row| *end_row > buffer_row.0)
+            .map(|end_row| {
+                Point::new(buffer_row.0, buffer_snapshot.line_len(buffer_row))
+                    ..Point::new(end_row, buffer_snapshot.line_len(MultiBufferRow(end_row)))
+            });
+        Some(fold_range)
+    }
+
     pub fn crease_for_buffer_row(&self, buffer_row: MultiBufferRow) -> Option<Crease<Point>> {
         let start =
             MultiBufferPoint::new(buffer_row.0, self.buffer_snapshot().line_len(buffer_row));
@@ -1358,6 +1432,17 @@ impl DisplaySnapshot {
                     render_toggle: render_toggle.clone(),
                 }),
             }
+        } else if let Some(fold_range) = self.lsp_fold_range_for_buffer_row(buffer_row) {
+            if self.is_line_folded(buffer_row) {
+                return None;
+            }
+            Some(Crease::Inline {
+                range: fold_range?,
+                placeholder: self.fold_placeholder.clone(),
+                render_toggle: None,
+                render_trailer: None,
+                metadata: None,
+            })
         } else if self.starts_indent(MultiBufferRow(start.row))
             && !self.is_line_folded(MultiBufferRow(start.row))
         {
@@ -1426,6 +1511,7 @@ impl DisplaySnapshot {
         self.text_highlights
             .iter()
             .filter(|(key, _)| match key {
+                HighlightKey::SemanticToken(_) => false,
                 HighlightKey::Type(type_id) => type_id == &required_type_id,
                 HighlightKey::TypePlus(type_id, _) => type_id == &required_type_id,
             })
@@ -1443,6 +1529,24 @@ impl DisplaySnapshot {
             .collect()
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn semantic_token_highlight_ranges(&self) -> Vec<(HighlightStyle, Range<Point>)> {
+        use itertools::Itertools;
+
+        self.text_highlights
+            .iter()
+            .filter(|(key, _)| matches!(key, HighlightKey::SemanticToken(_)))
+            .flat_map(|(_, highlights)| {
+                highlights
+                    .1
+                    .iter()
+                    .map(|range| (highlights.0, range.to_point(self.buffer_snapshot())))
+                    .collect::<Vec<_>>()
+            })
+            .sorted_by_key(|(_, range)| range.start)
+            .collect()
+    }
+
     #[allow(unused)]
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) fn inlay_highlights<Tag: ?Sized + 'static>(
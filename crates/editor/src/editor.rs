@@ -30,12 +30,15 @@ mod jsx_tag_auto_close;
 mod linked_editing_ranges;
 mod lsp_colors;
 mod lsp_ext;
+mod lsp_folding_ranges;
+mod lsp_selection_ranges;
 mod mouse_context_menu;
 pub mod movement;
 mod persistence;
 mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
+mod semantic_tokens;
 mod split;
 pub mod tasks;
 
@@ -145,9 +148,9 @@ use parking_lot::Mutex;
 use persistence::DB;
 use project::{
     BreakpointWithPosition, CodeAction, Completion, CompletionDisplayOptions, CompletionIntent,
-    CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint, InlayId,
-    InvalidationStrategy, Location, LocationLink, LspAction, PrepareRenameResponse, Project,
-    ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
+    CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, DocumentLink,
+    InlayHint, InlayId, InvalidationStrategy, Location, LocationLink, LspAction,
+    PrepareRenameResponse, Project, ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -166,6 +169,7 @@ use rand::seq::SliceRandom;
 use rpc::{ErrorCode, ErrorExt, proto::PeerId};
 use scroll::{Autoscroll, OngoingScroll, ScrollAnchor, ScrollManager};
 use selections_collection::{MutableSelectionsCollection, SelectionsCollection};
+use semantic_tokens::BufferSemanticTokens;
 use serde::{Deserialize, Serialize};
 use settings::{
     GitGutterSetting, RelativeLineNumbers, Settings, SettingsLocation, SettingsStore,
@@ -236,6 +240,8 @@ pub(crate) const CODE_ACTION_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) const FORMAT_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) const SCROLL_CENTER_TOP_BOTTOM_DEBOUNCE_TIMEOUT: Duration = Duration::from_secs(1);
 pub const FETCH_COLORS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+pub const FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
+pub const FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(150);
 
 pub(crate) const EDIT_PREDICTION_KEY_CONTEXT: &str = "edit_prediction";
 pub(crate) const EDIT_PREDICTION_CONFLICT_KEY_CONTEXT: &str = "edit_prediction_conflict";
@@ -1203,6 +1209,10 @@ pub struct Editor {
     colors: Option<LspColorData>,
     post_scroll_update: Task<()>,
     refresh_colors_task: Task<()>,
+    semantic_tokens: HashMap<BufferId, BufferSemanticTokens>,
+    refresh_semantic_tokens_task: Task<()>,
+    refresh_lsp_folding_ranges_task: Task<()>,
+    lsp_selection_ranges_task: Task<()>,
     inlay_hints: Option<LspInlayHintData>,
     folding_newlines: Task<()>,
     select_next_is_case_sensitive: Option<bool>,
@@ -1968,6 +1978,9 @@ impl Editor {
                     project::Event::RefreshCodeLens => {
                         // we always query lens with actions, without storing them, always refreshing them
                     }
+                    project::Event::RefreshSemanticTokens => {
+                        editor.refresh_semantic_tokens(None, cx);
+                    }
                     project::Event::RefreshInlayHints {
                         server_id,
                         request_id,
@@ -2372,6 +2385,10 @@ impl Editor {
             pull_diagnostics_background_task: Task::ready(()),
             colors: None,
             refresh_colors_task: Task::ready(()),
+            semantic_tokens: HashMap::default(),
+            refresh_semantic_tokens_task: Task::ready(()),
+            refresh_lsp_folding_ranges_task: Task::ready(()),
+            lsp_selection_ranges_task: Task::ready(()),
             inlay_hints: None,
             next_color_inlay_id: 0,
             post_scroll_update: Task::ready(()),
@@ -2450,6 +2467,8 @@ impl Editor {
                                 .update_in(cx, |editor, window, cx| {
                                     editor.register_visible_buffers(cx);
                                     editor.refresh_colors_for_visible_range(None, window, cx);
+                                    editor.refresh_semantic_tokens(None, cx);
+                                    editor.refresh_lsp_folding_ranges(None, cx);
                                     editor.refresh_inlay_hints(
                                         InlayHintRefreshReason::NewLinesShown,
                                         cx,
@@ -15695,6 +15714,14 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.lsp_selection_ranges_enabled(cx) {
+            self.select_larger_lsp_selection_range(window, cx);
+        } else {
+            self.select_larger_syntax_tree_node(window, cx);
+        }
+    }
+
+    fn select_larger_syntax_tree_node(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(visible_row_count) = self.visible_row_count() else {
             return;
         };
@@ -15712,7 +15739,7 @@ impl Editor {
         let buffer = self.buffer.read(cx).snapshot(cx);
 
         let mut selected_larger_node = false;
-        let mut new_selections = old_selections
+        let new_selections = old_selections
             .iter()
             .map(|selection| {
                 let old_range = selection.start..selection.end;
@@ -15767,6 +15794,27 @@ impl Editor {
             return; // don't put this call in the history
         }
 
+        self.select_larger_ranges(
+            old_selections,
+            new_selections,
+            visible_row_count,
+            window,
+            cx,
+        );
+    }
+
+    /// Selects the ranges enclosing the old selections, scrolling to the last one
+    /// and remembering the old selections for `SelectSmallerSyntaxNode`.
+    fn select_larger_ranges(
+        &mut self,
+        old_selections: Box<[Selection<MultiBufferOffset>]>,
+        mut new_selections: Vec<Selection<MultiBufferOffset>>,
+        visible_row_count: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+
         // scroll based on transformation done to the last selection created by the user
         let (last_old, last_new) = old_selections
             .last()
@@ -15781,13 +15829,11 @@ impl Editor {
             should_newest_selection_be_reversed
         };
 
-        if selected_larger_node {
-            self.select_syntax_node_history.disable_clearing = true;
-            self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
-                s.select(new_selections.clone());
-            });
-            self.select_syntax_node_history.disable_clearing = false;
-        }
+        self.select_syntax_node_history.disable_clearing = true;
+        self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select(new_selections.clone());
+        });
+        self.select_syntax_node_history.disable_clearing = false;
 
         let start_row = last_new.start.to_display_point(&display_map).row().0;
         let end_row = last_new.end.to_display_point(&display_map).row().0;
@@ -19641,6 +19687,53 @@ impl Editor {
         self.do_stage_or_unstage_and_next(false, window, cx);
     }
 
+    pub fn stage_lines(&mut self, _: &::git::StageLines, _: &mut Window, cx: &mut Context<Self>) {
+        self.stage_or_unstage_lines(true, cx);
+    }
+
+    pub fn unstage_lines(
+        &mut self,
+        _: &::git::UnstageLines,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stage_or_unstage_lines(false, cx);
+    }
+
+    /// Stages or unstages the changed lines touched by the selections, rather
+    /// than the whole hunks containing them.
+    pub fn stage_or_unstage_lines(&mut self, stage: bool, cx: &mut Context<Self>) {
+        let ranges = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let task = self.save_buffers_for_ranges_if_needed(&ranges, cx);
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                let snapshot = this.buffer.read(cx).snapshot(cx);
+                let mut rows_by_buffer = HashMap::<BufferId, Vec<Range<u32>>>::default();
+                for range in &ranges {
+                    for (buffer, range, _) in snapshot.range_to_buffer_ranges(range.clone()) {
+                        let start = buffer.offset_to_point(range.start.0);
+                        let end = buffer.offset_to_point(range.end.0);
+                        // A selection ending at the start of a line doesn't include that line.
+                        let end_row = if end.column == 0 && end.row > start.row {
+                            end.row
+                        } else {
+                            end.row + 1
+                        };
+                        rows_by_buffer
+                            .entry(buffer.remote_id())
+                            .or_default()
+                            .push(start.row..end_row);
+                    }
+                }
+                for (buffer_id, rows) in rows_by_buffer {
+                    this.do_stage_or_unstage_lines(stage, buffer_id, &rows, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     pub fn stage_or_unstage_diff_hunks(
         &mut self,
         stage: bool,
@@ -19780,6 +19873,28 @@ impl Editor {
         None
     }
 
+    fn do_stage_or_unstage_lines(
+        &self,
+        stage: bool,
+        buffer_id: BufferId,
+        rows: &[Range<u32>],
+        cx: &mut App,
+    ) {
+        let Some(project) = self.project() else {
+            return;
+        };
+        let Some(buffer) = project.read(cx).buffer_for_id(buffer_id, cx) else {
+            return;
+        };
+        let Some(diff) = self.buffer.read(cx).diff_for(buffer_id) else {
+            return;
+        };
+        let buffer_snapshot = buffer.read(cx).text_snapshot();
+        diff.update(cx, |diff, cx| {
+            diff.stage_or_unstage_lines(stage, rows, &buffer_snapshot, cx)
+        });
+    }
+
     pub fn expand_selected_diff_hunks(&mut self, cx: &mut Context<Self>) {
         let ranges: Vec<_> = self
             .selections
@@ -21807,6 +21922,10 @@ impl Editor {
                 self.refresh_inlay_hints(InlayHintRefreshReason::ExcerptsRemoved(ids.clone()), cx);
                 for buffer_id in removed_buffer_ids {
                     self.registered_buffers.remove(buffer_id);
+                    self.semantic_tokens.remove(buffer_id);
+                    self.display_map.update(cx, |display_map, _| {
+                        display_map.remove_lsp_folding_ranges(*buffer_id)
+                    });
                 }
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
                 cx.emit(EditorEvent::ExcerptsRemoved {
@@ -22047,6 +22166,10 @@ impl Editor {
                 self.colorize_brackets(true, cx);
             }
 
+            self.update_semantic_token_highlights(cx);
+            self.refresh_semantic_tokens(None, cx);
+            self.refresh_lsp_folding_ranges(None, cx);
+
             if let Some(inlay_splice) = self.colors.as_mut().and_then(|colors| {
                 colors.render_mode_updated(EditorSettings::get_global(cx).lsp_document_colors)
             }) {
@@ -22962,6 +23085,8 @@ impl Editor {
     ) {
         self.pull_diagnostics(for_buffer, window, cx);
         self.refresh_colors_for_visible_range(for_buffer, window, cx);
+        self.refresh_semantic_tokens(for_buffer, cx);
+        self.refresh_lsp_folding_ranges(for_buffer, cx);
     }
 
     fn register_visible_buffers(&mut self, cx: &mut Context<Self>) {
@@ -23735,6 +23860,19 @@ pub trait SemanticsProvider {
         cx: &mut App,
     ) -> Option<Task<Result<Option<Vec<LocationLink>>>>>;
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Arc<[DocumentLink]>>>>;
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut App,
+    ) -> Option<Task<Result<DocumentLink>>>;
+
     fn range_for_rename(
         &self,
         buffer: &Entity<Buffer>,
@@ -24232,6 +24370,25 @@ impl SemanticsProvider for Entity<Project> {
         }))
     }
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Arc<[DocumentLink]>>>> {
+        Some(self.update(cx, |project, cx| project.document_links(buffer, cx)))
+    }
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut App,
+    ) -> Option<Task<Result<DocumentLink>>> {
+        Some(self.update(cx, |project, cx| {
+            project.resolve_document_link(buffer, link, cx)
+        }))
+    }
+
     fn supports_inlay_hints(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
         self.update(cx, |project, cx| {
             if project
@@ -24659,7 +24816,7 @@ impl EditorSnapshot {
             }
         }
 
-        is_foldable |= self.starts_indent(buffer_row);
+        is_foldable |= self.starts_fold(buffer_row);
 
         if folded || (is_foldable && (row_contains_cursor || self.gutter_hovered)) {
             Some(
@@ -27291,6 +27291,271 @@ async fn test_document_colors(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_semantic_tokens(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": "fn main() { helper(); }\nfn helper() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                semantic_tokens_provider: Some(
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        lsp::SemanticTokensOptions {
+                            legend: lsp::SemanticTokensLegend {
+                                token_types: vec![lsp::SemanticTokenType::FUNCTION],
+                                token_modifiers: Vec::new(),
+                            },
+                            full: Some(lsp::SemanticTokensFullOptions::Bool(true)),
+                            ..lsp::SemanticTokensOptions::default()
+                        },
+                    ),
+                ),
+                ..lsp::ServerCapabilities::default()
+            },
+            name: "rust-analyzer",
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_abs_path(
+                PathBuf::from(path!("/a/main.rs")),
+                OpenOptions::default(),
+                window,
+                cx,
+            )
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    let mut tokens_request = fake_server
+        .set_request_handler::<lsp::request::SemanticTokensFullRequest, _, _>(
+            move |params, _| async move {
+                assert_eq!(
+                    params.text_document.uri,
+                    lsp::Uri::from_file_path(path!("/a/main.rs")).unwrap()
+                );
+                Ok(Some(lsp::SemanticTokensResult::Tokens(
+                    lsp::SemanticTokens {
+                        result_id: None,
+                        data: vec![lsp::SemanticToken {
+                            delta_line: 0,
+                            delta_start: 12,
+                            length: 6,
+                            token_type: 0,
+                            token_modifiers_bitset: 0,
+                        }],
+                    },
+                )))
+            },
+        );
+
+    cx.executor()
+        .advance_clock(FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT + Duration::from_millis(100));
+    tokens_request.next().await.unwrap();
+    cx.run_until_parked();
+    let function_style = cx.update(|_, cx| cx.theme().syntax().get("function"));
+    editor.update(cx, |editor, cx| {
+        assert_eq!(
+            vec![(function_style, Point::new(0, 12)..Point::new(0, 18))],
+            editor
+                .display_map
+                .update(cx, |display_map, cx| display_map.snapshot(cx))
+                .semantic_token_highlight_ranges(),
+            "Function token should be highlighted with the theme's function style"
+        );
+    });
+
+    update_test_language_settings(cx, |settings| {
+        settings.defaults.semantic_tokens = Some(false);
+    });
+    cx.run_until_parked();
+    editor.update(cx, |editor, cx| {
+        assert_eq!(
+            Vec::<(HighlightStyle, Range<Point>)>::new(),
+            editor
+                .display_map
+                .update(cx, |display_map, cx| display_map.snapshot(cx))
+                .semantic_token_highlight_ranges(),
+            "Disabling semantic tokens should remove their highlights"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_lsp_folding_and_selection_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.lsp_folding_ranges = Some(true);
+        settings.defaults.lsp_selection_ranges = Some(true);
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": "fn main() {\n    // #region setup\n    let a = 1;\n    // #endregion\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            name: "rust-analyzer",
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_abs_path(
+                PathBuf::from(path!("/a/main.rs")),
+                OpenOptions::default(),
+                window,
+                cx,
+            )
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    let mut folding_request = fake_server
+        .set_request_handler::<lsp::request::FoldingRangeRequest, _, _>(
+            move |params, _| async move {
+                assert_eq!(
+                    params.text_document.uri,
+                    lsp::Uri::from_file_path(path!("/a/main.rs")).unwrap()
+                );
+                Ok(Some(vec![
+                    lsp::FoldingRange {
+                        start_line: 0,
+                        end_line: 3,
+                        ..lsp::FoldingRange::default()
+                    },
+                    lsp::FoldingRange {
+                        start_line: 1,
+                        end_line: 3,
+                        kind: Some(lsp::FoldingRangeKind::Region),
+                        ..lsp::FoldingRange::default()
+                    },
+                ]))
+            },
+        );
+    fake_server.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(
+        move |params, _| async move {
+            let lsp_range = |start: (u32, u32), end: (u32, u32)| {
+                lsp::Range::new(
+                    lsp::Position::new(start.0, start.1),
+                    lsp::Position::new(end.0, end.1),
+                )
+            };
+            Ok(Some(
+                params
+                    .positions
+                    .into_iter()
+                    .map(|_| lsp::SelectionRange {
+                        range: lsp_range((2, 8), (2, 9)),
+                        parent: Some(Box::new(lsp::SelectionRange {
+                            range: lsp_range((2, 4), (2, 14)),
+                            parent: Some(Box::new(lsp::SelectionRange {
+                                range: lsp_range((0, 0), (4, 1)),
+                                parent: None,
+                            })),
+                        })),
+                    })
+                    .collect(),
+            ))
+        },
+    );
+
+    cx.executor()
+        .advance_clock(FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT + Duration::from_millis(100));
+    folding_request.next().await.unwrap();
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(1, 0)..Point::new(1, 0)]);
+        });
+        editor.fold(&Fold, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            "fn main() {\n    // #region setup⋯\n}",
+            "The region reported by the language server should fold, unlike its indentation"
+        );
+        editor.unfold_lines(&UnfoldLines, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            "fn main() {\n    // #region setup\n    let a = 1;\n    // #endregion\n}"
+        );
+
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(2, 8)..Point::new(2, 8)]);
+        });
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    editor.update_in(cx, |editor, window, cx| {
+        assert_eq!(
+            editor
+                .selections
+                .ranges::<Point>(&editor.display_snapshot(cx)),
+            vec![Point::new(2, 8)..Point::new(2, 9)]
+        );
+        editor.select_larger_syntax_node(&SelectLargerSyntaxNode, window, cx);
+    });
+    cx.run_until_parked();
+    editor.update_in(cx, |editor, window, cx| {
+        assert_eq!(
+            editor
+                .selections
+                .ranges::<Point>(&editor.display_snapshot(cx)),
+            vec![Point::new(2, 4)..Point::new(2, 14)],
+            "Selection should expand to the parent selection range"
+        );
+        editor.select_smaller_syntax_node(&SelectSmallerSyntaxNode, window, cx);
+        assert_eq!(
+            editor
+                .selections
+                .ranges::<Point>(&editor.display_snapshot(cx)),
+            vec![Point::new(2, 8)..Point::new(2, 9)]
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_newline_replacement_in_single_line(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
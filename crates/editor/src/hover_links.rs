@@ -1,8 +1,8 @@
 use crate::{
     Anchor, Editor, EditorSettings, EditorSnapshot, FindAllReferences, GoToDefinition,
     GoToDefinitionSplit, GoToTypeDefinition, GoToTypeDefinitionSplit, GotoDefinitionKind,
-    Navigated, PointForPosition, SelectPhase, editor_settings::GoToDefinitionFallback,
-    scroll::ScrollAmount,
+    Navigated, PointForPosition, SelectPhase, SemanticsProvider,
+    editor_settings::GoToDefinitionFallback, scroll::ScrollAmount,
 };
 use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, Window, px};
 use language::{Bias, ToOffset};
@@ -10,7 +10,7 @@ use linkify::{LinkFinder, LinkKind};
 use lsp::LanguageServerId;
 use project::{InlayId, LocationLink, Project, ResolvedPath};
 use settings::Settings;
-use std::ops::Range;
+use std::{ops::Range, rc::Rc};
 use theme::ActiveTheme as _;
 use util::{ResultExt, TryFutureExt as _, maybe};
 
@@ -374,7 +374,7 @@ pub fn show_link_definition(
                         let task = cx.update(|_, cx| {
                             provider.definitions(&buffer, text_anchor, preferred_kind, cx)
                         })?;
-                        if let Some(task) = task {
+                        let definitions = if let Some(task) = task {
                             task.await.ok().flatten().map(|definition_result| {
                                 (
                                     definition_result.iter().find_map(|link| {
@@ -391,6 +391,24 @@ pub fn show_link_definition(
                             })
                         } else {
                             None
+                        };
+                        // Document links only fill in for symbols without definitions, so that
+                        // servers linking imports to their docs don't replace go to definition.
+                        if definitions
+                            .as_ref()
+                            .is_none_or(|(_, definitions)| definitions.is_empty())
+                            && let Some((link_range, link)) =
+                                find_document_link(&buffer, &provider, project, text_anchor, cx)
+                                    .await
+                        {
+                            let range = maybe!({
+                                let range =
+                                    snapshot.anchor_range_in_excerpt(excerpt_id, link_range)?;
+                                Some(RangeInEditor::Text(range))
+                            });
+                            Some((range, vec![link]))
+                        } else {
+                            definitions
                         }
                     } else {
                         None
@@ -466,6 +484,52 @@ pub fn show_link_definition(
     editor.hovered_link_state = Some(hovered_link_state);
 }
 
+/// Finds the document link a language server reported at the position, resolving its target if needed.
+pub(crate) async fn find_document_link(
+    buffer: &Entity<language::Buffer>,
+    provider: &Rc<dyn SemanticsProvider>,
+    project: Option<Entity<Project>>,
+    position: text::Anchor,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let links = cx
+        .update(|_, cx| provider.document_links(buffer, cx))
+        .ok()??
+        .await
+        .log_err()?;
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot()).ok()?;
+    let link = links
+        .iter()
+        .find(|link| {
+            link.range.start.cmp(&position, &snapshot).is_le()
+                && link.range.end.cmp(&position, &snapshot).is_ge()
+        })?
+        .clone();
+    let link = if link.resolved {
+        link
+    } else {
+        cx.update(|_, cx| provider.resolve_document_link(buffer, link, cx))
+            .ok()??
+            .await
+            .log_err()?
+    };
+
+    let target = link.target()?;
+    let hover_link = if target.scheme() == "file" {
+        let path = target.to_file_path().ok()?;
+        let resolved_path = project?
+            .update(cx, |project, cx| {
+                project.resolve_abs_path(&path.to_string_lossy(), cx)
+            })
+            .ok()?
+            .await?;
+        HoverLink::File(resolved_path)
+    } else {
+        HoverLink::Url(target.to_string())
+    };
+    Some((link.range, hover_link))
+}
+
 pub(crate) fn find_url(
     buffer: &Entity<language::Buffer>,
     position: text::Anchor,
@@ -1706,4 +1770,127 @@ mod tests {
         cx.simulate_click(screen_coord, Modifiers::secondary_key());
         cx.update_workspace(|workspace, _, cx| assert_eq!(workspace.items(cx).count(), 1));
     }
+
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            use serde;ˇ
+        "});
+        let link_range = cx.lsp_range(indoc! {"
+            use «serde»;
+        "});
+
+        let mut link_requests = cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(
+            move |_, _, _| async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: link_range,
+                    target: None,
+                    tooltip: None,
+                    data: Some(serde_json::json!({ "crate": "serde" })),
+                }]))
+            },
+        );
+        let mut resolve_requests = cx
+            .set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(
+                move |_, link, _| async move {
+                    assert_eq!(link.data, Some(serde_json::json!({ "crate": "serde" })));
+                    Ok(lsp::DocumentLink {
+                        target: Some("https://docs.rs/serde".parse().unwrap()),
+                        ..link
+                    })
+                },
+            );
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            use seˇrde;
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        link_requests.next().await;
+        resolve_requests.next().await;
+        cx.run_until_parked();
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            use «serdeˇ»;
+        "});
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://docs.rs/serde".into()));
+    }
+
+    #[gpui::test]
+    async fn test_definitions_take_precedence_over_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                definition_provider: Some(lsp::OneOf::Left(true)),
+                document_link_provider: Some(lsp::DocumentLinkOptions::default()),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            use serde;ˇ
+            mod serde {}
+        "});
+        let link_range = cx.lsp_range(indoc! {"
+            use «serde»;
+            mod serde {}
+        "});
+        let target_range = cx.lsp_range(indoc! {"
+            use serde;
+            mod «serde» {}
+        "});
+
+        cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(
+            move |_, _, _| async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: link_range,
+                    target: Some("https://docs.rs/serde".parse().unwrap()),
+                    tooltip: None,
+                    data: None,
+                }]))
+            },
+        );
+        let mut definition_requests =
+            cx.set_request_handler::<GotoDefinition, _, _>(move |url, _, _| async move {
+                Ok(Some(lsp::GotoDefinitionResponse::Link(vec![
+                    lsp::LocationLink {
+                        origin_selection_range: Some(link_range),
+                        target_uri: url,
+                        target_range,
+                        target_selection_range: target_range,
+                    },
+                ])))
+            });
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            use seˇrde;
+            mod serde {}
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        definition_requests.next().await;
+        cx.run_until_parked();
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        cx.run_until_parked();
+
+        assert_eq!(cx.opened_url(), None);
+        cx.assert_editor_state(indoc! {"
+            use serde;
+            mod «serdeˇ» {}
+        "});
+    }
 }
@@ -0,0 +1,103 @@
+//! Folding ranges, reported by language servers.
+//! Used instead of the indentation to find foldable ranges, in languages that opt in with the `lsp_folding_ranges` setting.
+
+use futures::future::join_all;
+use gpui::{Context, Task};
+use itertools::Itertools;
+use language::language_settings::language_settings;
+use text::BufferId;
+
+use crate::{Editor, FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT};
+
+impl Editor {
+    pub(super) fn refresh_lsp_folding_ranges(
+        &mut self,
+        buffer_id: Option<BufferId>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ignore_lsp_data() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let mut disabled_buffers = Vec::new();
+        let visible_buffers = self
+            .visible_excerpts(true, cx)
+            .into_values()
+            .map(|(buffer, ..)| buffer)
+            .filter(|editor_buffer| {
+                let editor_buffer_id = editor_buffer.read(cx).remote_id();
+                buffer_id.is_none_or(|buffer_id| buffer_id == editor_buffer_id)
+                    && self.registered_buffers.contains_key(&editor_buffer_id)
+            })
+            .unique_by(|buffer| buffer.read(cx).remote_id())
+            .filter(|buffer| {
+                let buffer = buffer.read(cx);
+                let enabled = language_settings(
+                    buffer.language().map(|language| language.name()),
+                    buffer.file(),
+                    cx,
+                )
+                .lsp_folding_ranges;
+                if !enabled {
+                    disabled_buffers.push(buffer.remote_id());
+                }
+                enabled
+            })
+            .collect::<Vec<_>>();
+
+        let removed_ranges = self.display_map.update(cx, |display_map, _| {
+            let mut removed_ranges = false;
+            for buffer_id in disabled_buffers {
+                removed_ranges |= display_map.remove_lsp_folding_ranges(buffer_id);
+            }
+            removed_ranges
+        });
+        if removed_ranges {
+            cx.notify();
+        }
+
+        let all_ranges_task = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+            visible_buffers
+                .into_iter()
+                .map(|buffer| {
+                    let buffer_id = buffer.read(cx).remote_id();
+                    let ranges_task = lsp_store.folding_ranges(&buffer, cx);
+                    async move { (buffer_id, ranges_task.await) }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        if all_ranges_task.is_empty() {
+            self.refresh_lsp_folding_ranges_task = Task::ready(());
+            return;
+        }
+
+        self.refresh_lsp_folding_ranges_task = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(FETCH_FOLDING_RANGES_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let all_ranges = join_all(all_ranges_task).await;
+            editor
+                .update(cx, |editor, cx| {
+                    editor.display_map.update(cx, |display_map, _| {
+                        for (buffer_id, ranges) in all_ranges {
+                            match ranges {
+                                // Servers without folding ranges for the buffer leave its folds to the indentation.
+                                Ok(ranges) if ranges.is_empty() => {
+                                    display_map.remove_lsp_folding_ranges(buffer_id);
+                                }
+                                Ok(ranges) => display_map.set_lsp_folding_ranges(buffer_id, ranges),
+                                Err(e) => log::error!("Failed to retrieve folding ranges: {e}"),
+                            }
+                        }
+                    });
+                    cx.notify();
+                })
+                .ok();
+        });
+    }
+}
@@ -0,0 +1,174 @@
+//! Selection ranges, reported by language servers.
+//! Used instead of the syntax tree to expand selections, in languages that opt in with the `lsp_selection_ranges` setting.
+
+use std::ops::Range;
+
+use collections::HashMap;
+use futures::future::join_all;
+use gpui::{App, Context, Entity, Window};
+use language::{Buffer, Selection, SelectionGoal};
+use multi_buffer::{Anchor, AnchorRangeExt as _, MultiBufferOffset};
+use text::BufferId;
+
+use crate::{Editor, HideMouseCursorOrigin};
+
+impl Editor {
+    /// Whether the newest selection is in a language that expands selections with language server selection ranges.
+    pub(super) fn lsp_selection_ranges_enabled(&self, cx: &App) -> bool {
+        if self.ignore_lsp_data() || self.project.is_none() {
+            return false;
+        }
+        let newest_selection_head = self.selections.newest_anchor().head();
+        self.buffer
+            .read(cx)
+            .language_settings_at(newest_selection_head, cx)
+            .lsp_selection_ranges
+    }
+
+    /// Expands every selection to the smallest enclosing selection range from language servers.
+    /// Falls back to the syntax tree if no language server reports selection ranges.
+    pub(super) fn select_larger_lsp_selection_range(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let Some(visible_row_count) = self.visible_row_count() else {
+            return;
+        };
+        let old_selections: Box<[_]> = self
+            .selections
+            .all::<MultiBufferOffset>(&self.display_snapshot(cx))
+            .into();
+        if old_selections.is_empty() {
+            return;
+        }
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let mut positions_by_buffer =
+            HashMap::<BufferId, (Entity<Buffer>, Vec<(usize, Anchor)>)>::default();
+        for (ix, selection) in old_selections.iter().enumerate() {
+            let position = snapshot.anchor_after(selection.start);
+            let Some(buffer_id) = position.text_anchor.buffer_id else {
+                continue;
+            };
+            let Some(buffer) = multi_buffer.buffer(buffer_id) else {
+                continue;
+            };
+            positions_by_buffer
+                .entry(buffer_id)
+                .or_insert_with(|| (buffer, Vec::new()))
+                .1
+                .push((ix, position));
+        }
+
+        let requests = project.update(cx, |project, cx| {
+            positions_by_buffer
+                .into_values()
+                .map(|(buffer, positions)| {
+                    let request = project.selection_ranges(
+                        &buffer,
+                        positions
+                            .iter()
+                            .map(|(_, position)| position.text_anchor)
+                            .collect(),
+                        cx,
+                    );
+                    async move { (positions, request.await) }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        self.lsp_selection_ranges_task = cx.spawn_in(window, async move |editor, cx| {
+            let responses = join_all(requests).await;
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    let mut ranges_by_selection =
+                        HashMap::<usize, (Anchor, Vec<Range<text::Anchor>>)>::default();
+                    for (positions, response) in responses {
+                        match response {
+                            Ok(selection_ranges) => ranges_by_selection.extend(
+                                positions
+                                    .into_iter()
+                                    .zip(selection_ranges)
+                                    .map(|((ix, position), ranges)| (ix, (position, ranges))),
+                            ),
+                            Err(e) => log::error!("Failed to fetch selection ranges: {e:#}"),
+                        }
+                    }
+                    if ranges_by_selection.is_empty() {
+                        editor.select_larger_syntax_tree_node(window, cx);
+                        return;
+                    }
+
+                    let current_selections = editor
+                        .selections
+                        .all::<MultiBufferOffset>(&editor.display_snapshot(cx));
+                    let selections_changed = current_selections.len() != old_selections.len()
+                        || current_selections
+                            .iter()
+                            .zip(old_selections.iter())
+                            .any(|(current, old)| current.range() != old.range());
+                    if selections_changed {
+                        return;
+                    }
+
+                    let snapshot = editor.buffer.read(cx).snapshot(cx);
+                    let mut selected_larger_range = false;
+                    let new_selections = old_selections
+                        .iter()
+                        .enumerate()
+                        .map(|(ix, selection)| {
+                            let old_range = selection.range();
+                            let larger_range =
+                                ranges_by_selection.get(&ix).and_then(|(position, ranges)| {
+                                    ranges
+                                        .iter()
+                                        .filter_map(|range| {
+                                            snapshot.anchor_range_in_excerpt(
+                                                position.excerpt_id,
+                                                range.clone(),
+                                            )
+                                        })
+                                        .map(|range| range.to_offset(&snapshot))
+                                        .find(|range| {
+                                            range.start <= old_range.start
+                                                && old_range.end <= range.end
+                                                && *range != old_range
+                                        })
+                                });
+                            match larger_range {
+                                Some(range) => {
+                                    selected_larger_range = true;
+                                    Selection {
+                                        id: selection.id,
+                                        start: range.start,
+                                        end: range.end,
+                                        goal: SelectionGoal::None,
+                                        reversed: selection.reversed,
+                                    }
+                                }
+                                None => selection.clone(),
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    if selected_larger_range {
+                        editor.select_larger_ranges(
+                            old_selections,
+                            new_selections,
+                            visible_row_count,
+                            window,
+                            cx,
+                        );
+                    }
+                })
+                .ok();
+        });
+    }
+}
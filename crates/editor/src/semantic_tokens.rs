@@ -0,0 +1,225 @@
+//! Semantic token highlights, reported by language servers.
+//! Tokens are mapped to theme syntax highlights via the `semantic_token_highlights` language setting,
+//! and displayed on top of the tree-sitter highlights.
+
+use std::ops::Range;
+
+use collections::{BTreeMap, HashMap};
+use futures::future::join_all;
+use gpui::{Context, HighlightStyle, Task};
+use itertools::Itertools;
+use language::language_settings::language_settings;
+use multi_buffer::{Anchor, ExcerptId};
+use project::lsp_store::SemanticToken;
+use text::BufferId;
+use theme::SyntaxTheme;
+use ui::ActiveTheme;
+
+use crate::{Editor, FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT};
+
+#[derive(Debug, Default)]
+pub(super) struct BufferSemanticTokens {
+    tokens: Vec<(Range<Anchor>, SemanticToken)>,
+    cache_version_used: Option<usize>,
+}
+
+impl Editor {
+    pub(super) fn refresh_semantic_tokens(
+        &mut self,
+        buffer_id: Option<BufferId>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.ignore_lsp_data() {
+            return;
+        }
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+
+        let mut disabled_buffers = Vec::new();
+        let visible_buffers = self
+            .visible_excerpts(true, cx)
+            .into_values()
+            .map(|(buffer, ..)| buffer)
+            .filter(|editor_buffer| {
+                let editor_buffer_id = editor_buffer.read(cx).remote_id();
+                buffer_id.is_none_or(|buffer_id| buffer_id == editor_buffer_id)
+                    && self.registered_buffers.contains_key(&editor_buffer_id)
+            })
+            .unique_by(|buffer| buffer.read(cx).remote_id())
+            .filter(|buffer| {
+                let buffer = buffer.read(cx);
+                let enabled = language_settings(
+                    buffer.language().map(|language| language.name()),
+                    buffer.file(),
+                    cx,
+                )
+                .semantic_tokens;
+                if !enabled {
+                    disabled_buffers.push(buffer.remote_id());
+                }
+                enabled
+            })
+            .collect::<Vec<_>>();
+
+        let mut removed_tokens = false;
+        for buffer_id in disabled_buffers {
+            removed_tokens |= self.semantic_tokens.remove(&buffer_id).is_some();
+        }
+        if removed_tokens {
+            self.update_semantic_token_highlights(cx);
+        }
+
+        let all_tokens_task = project.read(cx).lsp_store().update(cx, |lsp_store, cx| {
+            visible_buffers
+                .into_iter()
+                .filter_map(|buffer| {
+                    let buffer_id = buffer.read(cx).remote_id();
+                    let known_cache_version = self
+                        .semantic_tokens
+                        .get(&buffer_id)
+                        .and_then(|buffer_tokens| buffer_tokens.cache_version_used);
+                    let tokens_task = lsp_store.semantic_tokens(known_cache_version, buffer, cx)?;
+                    Some(async move { (buffer_id, tokens_task.await) })
+                })
+                .collect::<Vec<_>>()
+        });
+
+        if all_tokens_task.is_empty() {
+            self.refresh_semantic_tokens_task = Task::ready(());
+            return;
+        }
+
+        self.refresh_semantic_tokens_task = cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(FETCH_SEMANTIC_TOKENS_DEBOUNCE_TIMEOUT)
+                .await;
+
+            let all_tokens = join_all(all_tokens_task).await;
+            editor
+                .update(cx, |editor, cx| {
+                    let multi_buffer_snapshot = editor.buffer().read(cx).snapshot(cx);
+                    let mut buffer_excerpts = HashMap::<BufferId, Vec<ExcerptId>>::default();
+                    for (excerpt_id, buffer_snapshot, _) in multi_buffer_snapshot.excerpts() {
+                        buffer_excerpts
+                            .entry(buffer_snapshot.remote_id())
+                            .or_default()
+                            .push(excerpt_id);
+                    }
+
+                    let mut updated = false;
+                    for (buffer_id, tokens) in all_tokens {
+                        let Some(excerpts) = buffer_excerpts.get(&buffer_id) else {
+                            continue;
+                        };
+                        let tokens = match tokens {
+                            Ok(tokens) => tokens,
+                            Err(e) => {
+                                log::error!("Failed to retrieve semantic tokens: {e}");
+                                continue;
+                            }
+                        };
+
+                        let new_tokens = tokens
+                            .tokens
+                            .values()
+                            .flat_map(|server_tokens| server_tokens.iter())
+                            .flat_map(|token| {
+                                excerpts.iter().filter_map(|excerpt_id| {
+                                    let range = multi_buffer_snapshot.anchor_range_in_excerpt(
+                                        *excerpt_id,
+                                        token.range.clone(),
+                                    )?;
+                                    Some((range, token.clone()))
+                                })
+                            })
+                            .collect();
+                        editor.semantic_tokens.insert(
+                            buffer_id,
+                            BufferSemanticTokens {
+                                tokens: new_tokens,
+                                cache_version_used: tokens.cache_version,
+                            },
+                        );
+                        updated = true;
+                    }
+
+                    if updated {
+                        editor.update_semantic_token_highlights(cx);
+                    }
+                })
+                .ok();
+        });
+    }
+
+    /// Resolves the highlight of every known token and replaces the semantic token layer of the display map.
+    pub(super) fn update_semantic_token_highlights(&mut self, cx: &mut Context<Self>) {
+        let mut ranges_by_highlight = BTreeMap::<String, Vec<Range<Anchor>>>::default();
+        let multi_buffer = self.buffer.read(cx);
+        for (buffer_id, buffer_tokens) in &self.semantic_tokens {
+            let Some(buffer) = multi_buffer.buffer(*buffer_id) else {
+                continue;
+            };
+            let buffer = buffer.read(cx);
+            let settings = language_settings(
+                buffer.language().map(|language| language.name()),
+                buffer.file(),
+                cx,
+            );
+            if !settings.semantic_tokens {
+                continue;
+            }
+            for (range, token) in &buffer_tokens.tokens {
+                if let Some(highlight) = token_highlight(token, &settings.semantic_token_highlights)
+                {
+                    ranges_by_highlight
+                        .entry(highlight.to_owned())
+                        .or_default()
+                        .push(range.clone());
+                }
+            }
+        }
+
+        let syntax_theme = cx.theme().syntax().clone();
+        let multi_buffer_snapshot = multi_buffer.snapshot(cx);
+        let highlights = ranges_by_highlight
+            .into_iter()
+            .filter_map(|(highlight, mut ranges)| {
+                let style = highlight_style(&syntax_theme, &highlight)?;
+                ranges.sort_by(|a, b| a.start.cmp(&b.start, &multi_buffer_snapshot));
+                Some((style, ranges))
+            })
+            .collect();
+        self.display_map.update(cx, |display_map, _| {
+            display_map.set_semantic_token_highlights(highlights)
+        });
+        cx.notify();
+    }
+}
+
+/// Picks the highlight name for a token: mappings for a token type with one of its modifiers
+/// take precedence over the mapping for the token type alone.
+fn token_highlight<'a>(
+    token: &SemanticToken,
+    mapping: &'a HashMap<String, String>,
+) -> Option<&'a str> {
+    token
+        .token_modifiers
+        .iter()
+        .find_map(|modifier| mapping.get(&format!("{}.{modifier}", token.token_type)))
+        .or_else(|| mapping.get(token.token_type.as_ref()))
+        .map(String::as_str)
+        .filter(|highlight| !highlight.is_empty())
+}
+
+/// Looks up the theme style for a highlight name, falling back to less specific names
+/// the same way tree-sitter captures do, e.g. `variable.mutable` to `variable`.
+fn highlight_style(syntax_theme: &SyntaxTheme, highlight: &str) -> Option<HighlightStyle> {
+    let mut highlight = highlight;
+    loop {
+        if syntax_theme.highlight_id(highlight).is_some() {
+            return Some(syntax_theme.get(highlight));
+        }
+        highlight = highlight.rsplit_once('.')?.0;
+    }
+}
@@ -6,12 +6,15 @@ use git::{
     Oid, RunHook,
     blame::Blame,
     repository::{
-        AskPassDelegate, Branch, CommitDetails, CommitOptions, FetchOptions, GitRepository,
-        GitRepositoryCheckpoint, PushOptions, Remote, RepoPath, ResetMode, Worktree,
+        AskPassDelegate, BisectCandidate, BisectMark, BisectStatus, Branch, CommitDetails,
+        CommitLogEntry, CommitLogSearch, CommitOptions, ConflictStages, FetchOptions,
+        GitRepository, GitRepositoryCheckpoint, PushOptions, RebaseAction, RebaseEntry,
+        RebaseStatus, Remote, RepoPath, ResetMode, Submodule, SubmoduleAction, SubmoduleState, Tag,
+        Worktree,
     },
     status::{
         DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
-        UnmergedStatus,
+        UnmergedStatus, UnmergedStatusCode,
     },
 };
 use gpui::{AsyncApp, BackgroundExecutor, SharedString, Task, TaskLabel};
@@ -54,6 +57,31 @@ pub struct FakeGitRepositoryState {
     pub remotes: HashMap<String, String>,
     pub simulated_index_write_error_message: Option<String>,
     pub refs: HashMap<String, String>,
+    /// Commits between the rebase base and HEAD, oldest first.
+    pub rebase_entries: Vec<RebaseEntry>,
+    pub rebase_status: Option<RebaseStatus>,
+    pub tags: Vec<Tag>,
+    pub conflict_stages: HashMap<RepoPath, ConflictStages>,
+    pub submodules: Vec<Submodule>,
+    /// The `git submodule` commands run, with the paths they were run for.
+    pub submodule_commands: Vec<(SubmoduleAction, Vec<RepoPath>)>,
+    /// The history a bisect runs over, newest first.
+    pub bisect_commits: Vec<BisectCandidate>,
+    pub bisect_status: Option<BisectStatus>,
+    /// The files each commit changes, keyed by sha, for cherry-picks and reverts to replay.
+    pub commit_changes: HashMap<String, Vec<FakeCommitChange>>,
+    /// Names of the tags pushed to each remote, keyed by remote name.
+    pub pushed_tags: HashMap<String, HashSet<String>>,
+    /// The repository-wide log, newest first.
+    pub commit_log: Vec<CommitLogEntry>,
+}
+
+/// A file changed by a commit, as its contents before and after the commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeCommitChange {
+    pub path: RepoPath,
+    pub old_text: Option<String>,
+    pub new_text: Option<String>,
 }
 
 impl FakeGitRepositoryState {
@@ -71,6 +99,17 @@ impl FakeGitRepositoryState {
             merge_base_contents: Default::default(),
             oids: Default::default(),
             remotes: HashMap::default(),
+            rebase_entries: Default::default(),
+            rebase_status: Default::default(),
+            tags: Default::default(),
+            conflict_stages: Default::default(),
+            submodules: Default::default(),
+            submodule_commands: Default::default(),
+            bisect_commits: Default::default(),
+            bisect_status: Default::default(),
+            commit_changes: Default::default(),
+            pushed_tags: Default::default(),
+            commit_log: Default::default(),
         }
     }
 }
@@ -90,6 +129,92 @@ impl FakeGitRepository {
         }
         .boxed()
     }
+
+    /// Replays the changes of `commit`, or undoes them when `revert` is set. Files whose
+    /// HEAD contents don't match what the commit expects are left conflicted.
+    fn apply_commit(&self, commit: String, revert: bool) -> BoxFuture<'_, Result<bool>> {
+        let fs = self.fs.clone();
+        let workdir_path = self.dot_git_path.parent().unwrap().to_path_buf();
+        let apply = self.with_state_async(true, move |state| {
+            let changes = state
+                .commit_changes
+                .get(&commit)
+                .with_context(|| format!("unknown revision {commit}"))?
+                .clone();
+            let mut clean = Vec::new();
+            let mut conflicted = Vec::new();
+            for change in changes {
+                let (base, target) = if revert {
+                    (change.new_text, change.old_text)
+                } else {
+                    (change.old_text, change.new_text)
+                };
+                let ours = state.head_contents.get(&change.path).cloned();
+                if ours == base {
+                    clean.push((change.path, target));
+                } else {
+                    conflicted.push((
+                        change.path,
+                        ConflictStages {
+                            base,
+                            ours,
+                            theirs: target,
+                        },
+                    ));
+                }
+            }
+
+            let has_conflicts = !conflicted.is_empty();
+            let mut written = Vec::new();
+            for (path, text) in clean {
+                match &text {
+                    Some(text) => {
+                        state.index_contents.insert(path.clone(), text.clone());
+                        if !has_conflicts {
+                            state.head_contents.insert(path.clone(), text.clone());
+                        }
+                    }
+                    None => {
+                        state.index_contents.remove(&path);
+                        if !has_conflicts {
+                            state.head_contents.remove(&path);
+                        }
+                    }
+                }
+                written.push((path, text));
+            }
+            for (path, stages) in conflicted {
+                let text = format!(
+                    "<<<<<<< HEAD\n{}=======\n{}>>>>>>> {commit}\n",
+                    stages.ours.as_deref().unwrap_or_default(),
+                    stages.theirs.as_deref().unwrap_or_default(),
+                );
+                state.unmerged_paths.insert(
+                    path.clone(),
+                    UnmergedStatus {
+                        first_head: UnmergedStatusCode::Updated,
+                        second_head: UnmergedStatusCode::Updated,
+                    },
+                );
+                state.conflict_stages.insert(path.clone(), stages);
+                written.push((path, Some(text)));
+            }
+            Ok((has_conflicts, written))
+        });
+
+        async move {
+            let (has_conflicts, written) = apply.await?;
+            for (path, text) in written {
+                let abs_path = workdir_path.join(path.as_std_path());
+                match text {
+                    Some(text) => fs.write(&abs_path, text.as_bytes()).await?,
+                    None => fs.remove_file(&abs_path, Default::default()).await?,
+                }
+            }
+            Ok(has_conflicts)
+        }
+        .boxed()
+    }
 }
 
 impl GitRepository for FakeGitRepository {
@@ -108,6 +233,17 @@ impl GitRepository for FakeGitRepository {
             .boxed()
     }
 
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>> {
+        self.with_state_async(false, move |state| {
+            Ok(state
+                .conflict_stages
+                .get(&path)
+                .cloned()
+                .unwrap_or_default())
+        })
+        .boxed()
+    }
+
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
         let fut = self.with_state_async(false, move |state| {
             state
@@ -411,6 +547,34 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| Ok(state.submodules.clone()))
+    }
+
+    fn submodule_command(
+        &self,
+        action: SubmoduleAction,
+        paths: Vec<RepoPath>,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            state.submodule_commands.push((action, paths.clone()));
+            if action == SubmoduleAction::Update {
+                for submodule in &mut state.submodules {
+                    if paths.is_empty() || paths.contains(&submodule.path) {
+                        submodule.state = SubmoduleState::UpToDate;
+                    }
+                }
+            }
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
     fn change_branch(&self, name: String) -> BoxFuture<'_, Result<()>> {
         self.with_state_async(true, |state| {
             state.current_branch_name = Some(name);
@@ -480,6 +644,34 @@ impl GitRepository for FakeGitRepository {
         .boxed()
     }
 
+    fn commit_log_paginated(
+        &self,
+        search: Option<CommitLogSearch>,
+        skip: usize,
+        limit: Option<usize>,
+    ) -> BoxFuture<'_, Result<Vec<CommitLogEntry>>> {
+        self.with_state_async(false, move |state| {
+            // Like `git log --regexp-ignore-case --fixed-strings`.
+            let contains =
+                |text: &str, query: &str| text.to_lowercase().contains(&query.to_lowercase());
+            Ok(state
+                .commit_log
+                .iter()
+                .filter(|entry| match &search {
+                    Some(CommitLogSearch::Author(author)) => {
+                        contains(&entry.author_name, author)
+                            || contains(&entry.author_email, author)
+                    }
+                    Some(CommitLogSearch::Message(message)) => contains(&entry.subject, message),
+                    None => true,
+                })
+                .skip(skip)
+                .take(limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect())
+        })
+    }
+
     fn stage_paths(
         &self,
         paths: Vec<RepoPath>,
@@ -500,6 +692,8 @@ impl GitRepository for FakeGitRepository {
             let contents = join_all(contents).await;
             self.with_state_async(true, move |state| {
                 for (path, content) in contents {
+                    state.unmerged_paths.remove(&path);
+                    state.conflict_stages.remove(&path);
                     if let Some(content) = content {
                         state.index_contents.insert(path, content);
                     } else {
@@ -560,6 +754,244 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn rebase_entries(&self, _base: String) -> BoxFuture<'_, Result<Vec<RebaseEntry>>> {
+        self.with_state_async(false, |state| Ok(state.rebase_entries.clone()))
+    }
+
+    fn rebase_interactive(
+        &self,
+        _base: String,
+        entries: Vec<RebaseEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.rebase_status.is_some() {
+                bail!("a rebase is already in progress");
+            }
+            let mut rebased: Vec<RebaseEntry> = Vec::new();
+            for entry in entries {
+                match entry.action {
+                    RebaseAction::Pick => rebased.push(entry),
+                    RebaseAction::Reword => {
+                        let subject = entry
+                            .message
+                            .as_ref()
+                            .and_then(|message| message.lines().next())
+                            .map(|line| SharedString::from(line.to_string()))
+                            .unwrap_or(entry.subject.clone());
+                        rebased.push(RebaseEntry {
+                            action: RebaseAction::Pick,
+                            subject,
+                            message: None,
+                            ..entry
+                        });
+                    }
+                    RebaseAction::Squash | RebaseAction::Fixup => {
+                        if rebased.is_empty() {
+                            bail!(
+                                "cannot '{}' without a previous commit",
+                                entry.action.command()
+                            );
+                        }
+                    }
+                    RebaseAction::Drop => {}
+                }
+            }
+            state.rebase_entries = rebased;
+            Ok(())
+        })
+    }
+
+    fn rebase_continue(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state
+                .rebase_status
+                .take()
+                .context("no rebase in progress")?;
+            Ok(())
+        })
+    }
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.rebase_continue(env)
+    }
+
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.rebase_continue(env)
+    }
+
+    fn rebase_status(&self) -> BoxFuture<'_, Result<Option<RebaseStatus>>> {
+        self.with_state_async(false, |state| Ok(state.rebase_status.clone()))
+    }
+
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.bisect_status.is_some() {
+                bail!("a bisect is already in progress");
+            }
+            let position = |sha: &str| {
+                state
+                    .bisect_commits
+                    .iter()
+                    .position(|commit| commit.sha.as_ref() == sha)
+                    .with_context(|| format!("unknown revision {sha}"))
+            };
+            let bad_ix = position(&bad)?;
+            let mut good_ix = state.bisect_commits.len();
+            for good in &good {
+                good_ix = good_ix.min(position(good)?);
+            }
+            if good_ix <= bad_ix {
+                bail!("the bad commit must be newer than the good ones");
+            }
+            let mut status = BisectStatus {
+                bad: Some(bad.into()),
+                good: good.into_iter().map(Into::into).collect(),
+                remaining: state.bisect_commits[bad_ix..good_ix].to_vec(),
+                ..Default::default()
+            };
+            status.current = next_bisect_commit(&status);
+            state.bisect_status = Some(status);
+            Ok(())
+        })
+    }
+
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let status = state.bisect_status.as_mut().context("not bisecting")?;
+            let current = status.current.clone().context("no commit to test")?;
+            let ix = status
+                .remaining
+                .iter()
+                .position(|commit| commit.sha == current)
+                .context("the current commit is not being bisected")?;
+            match mark {
+                BisectMark::Good => {
+                    status.remaining.truncate(ix);
+                    status.good.push(current);
+                }
+                BisectMark::Bad => {
+                    status.remaining.drain(..ix);
+                    status.bad = Some(current);
+                }
+                BisectMark::Skip => status.skipped.push(current),
+            }
+            if let Some(next) = next_bisect_commit(status) {
+                status.current = Some(next);
+            }
+            Ok(())
+        })
+    }
+
+    fn bisect_reset(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state.bisect_status.take().context("not bisecting")?;
+            Ok(())
+        })
+    }
+
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>> {
+        self.with_state_async(false, |state| Ok(state.bisect_status.clone()))
+    }
+
+    fn cherry_pick(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        self.apply_commit(commit, false)
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        self.apply_commit(commit, true)
+    }
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
+        self.with_state_async(false, |state| Ok(state.tags.clone()))
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.tags.iter().any(|tag| tag.name.as_ref() == name) {
+                bail!("tag '{name}' already exists");
+            }
+            let sha = match commit {
+                Some(commit) => commit,
+                None => state.refs.get("HEAD").context("HEAD is not set")?.clone(),
+            };
+            state.tags.insert(
+                0,
+                Tag {
+                    name: name.into(),
+                    sha: sha.into(),
+                    message: message.map(Into::into),
+                    timestamp: 0,
+                },
+            );
+            Ok(())
+        })
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let Some(ix) = state.tags.iter().position(|tag| tag.name.as_ref() == name) else {
+                bail!("no such tag: {name}");
+            };
+            state.tags.remove(ix);
+            Ok(())
+        })
+    }
+
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        delete: bool,
+        _askpass: AskPassDelegate,
+        _env: Arc<HashMap<String, String>>,
+        _cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<git::repository::RemoteCommandOutput>> {
+        self.with_state_async(true, move |state| {
+            if !state.remotes.contains_key(&remote_name) {
+                bail!("'{remote_name}' does not appear to be a git repository");
+            }
+            let pushed_tags = state.pushed_tags.entry(remote_name).or_default();
+            if delete {
+                if !pushed_tags.remove(&name) {
+                    bail!("unable to delete '{name}': remote ref does not exist");
+                }
+            } else {
+                if !state.tags.iter().any(|tag| tag.name.as_ref() == name) {
+                    bail!("src refspec refs/tags/{name} does not match any");
+                }
+                pushed_tags.insert(name);
+            }
+            Ok(git::repository::RemoteCommandOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            })
+        })
+    }
+
     fn commit(
         &self,
         _message: gpui::SharedString,
@@ -723,9 +1155,30 @@ impl GitRepository for FakeGitRepository {
     }
 }
 
+/// Picks the middle of the commits that are neither known bad nor skipped.
+fn next_bisect_commit(status: &BisectStatus) -> Option<SharedString> {
+    if status.remaining.len() <= 1 {
+        return None;
+    }
+    let untested = status
+        .remaining
+        .iter()
+        .filter(|commit| {
+            Some(&commit.sha) != status.bad.as_ref() && !status.skipped.contains(&commit.sha)
+        })
+        .collect::<Vec<_>>();
+    untested
+        .get(untested.len() / 2)
+        .map(|commit| commit.sha.clone())
+}
+
 #[cfg(test)]
 mod tests {
+    use super::FakeCommitChange;
     use crate::{FakeFs, Fs};
+    use git::repository::{
+        CommitLogEntry, CommitLogSearch, ConflictStages, RebaseAction, RebaseEntry, repo_path,
+    };
     use gpui::BackgroundExecutor;
     use serde_json::json;
     use std::path::Path;
@@ -783,4 +1236,190 @@ mod tests {
             ]
         );
     }
+
+    #[gpui::test]
+    async fn test_commit_log_pagination_and_search(executor: BackgroundExecutor) {
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(path!("/foo"), json!({ ".git": {}, "a": "lorem" }))
+            .await;
+        let entry = |sha: &str, author: &str, subject: &str| CommitLogEntry {
+            sha: sha.to_string().into(),
+            parents: Vec::new(),
+            subject: subject.to_string().into(),
+            commit_timestamp: 0,
+            author_name: author.to_string().into(),
+            author_email: format!("{}@example.com", author.to_lowercase()).into(),
+            refs: Vec::new(),
+        };
+        fs.with_git_state(Path::new("/foo/.git"), true, |state| {
+            state.commit_log = vec![
+                entry("d4", "Alice", "Fix the parser"),
+                entry("c3", "Bob", "Add a parser"),
+                entry("b2", "Alice", "Update docs"),
+                entry("a1", "Bob", "Initial commit"),
+            ];
+        })
+        .unwrap();
+        let repository = fs
+            .open_repo(Path::new("/foo/.git"), Some("git".as_ref()))
+            .unwrap();
+        let shas = |entries: Vec<CommitLogEntry>| {
+            entries
+                .into_iter()
+                .map(|entry| entry.sha)
+                .collect::<Vec<_>>()
+        };
+
+        let first_page = repository.commit_log_paginated(None, 0, Some(3));
+        assert_eq!(shas(first_page.await.unwrap()), ["d4", "c3", "b2"]);
+        let second_page = repository.commit_log_paginated(None, 3, Some(3));
+        assert_eq!(shas(second_page.await.unwrap()), ["a1"]);
+
+        let search = Some(CommitLogSearch::Message("PARSER".into()));
+        let by_message = repository.commit_log_paginated(search, 0, None);
+        assert_eq!(shas(by_message.await.unwrap()), ["d4", "c3"]);
+
+        let search = Some(CommitLogSearch::Author("bob@".into()));
+        let by_author = repository.commit_log_paginated(search, 1, Some(1));
+        assert_eq!(shas(by_author.await.unwrap()), ["a1"]);
+    }
+
+    #[gpui::test]
+    async fn test_interactive_rebase(executor: BackgroundExecutor) {
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(path!("/foo"), json!({ ".git": {}, "a": "lorem" }))
+            .await;
+        let entry = |sha: &str, subject: &str| RebaseEntry {
+            action: RebaseAction::Pick,
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+            message: None,
+        };
+        fs.with_git_state(Path::new("/foo/.git"), true, |state| {
+            state.rebase_entries = vec![
+                entry("a1", "first"),
+                entry("b2", "second"),
+                entry("c3", "third"),
+                entry("d4", "fourth"),
+            ];
+        })
+        .unwrap();
+        let repository = fs
+            .open_repo(Path::new("/foo/.git"), Some("git".as_ref()))
+            .unwrap();
+
+        let mut entries = repository.rebase_entries("main".into()).await.unwrap();
+        entries.swap(0, 1);
+        entries[1].action = RebaseAction::Reword;
+        entries[1].message = Some("First, reworded\n\nWith a body".into());
+        entries[2].action = RebaseAction::Fixup;
+        entries[3].action = RebaseAction::Drop;
+        repository
+            .rebase_interactive("main".into(), entries, Default::default())
+            .await
+            .unwrap();
+
+        let subjects = repository
+            .rebase_entries("main".into())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.subject)
+            .collect::<Vec<_>>();
+        assert_eq!(subjects, ["second", "First, reworded"]);
+        assert_eq!(repository.rebase_status().await.unwrap(), None);
+        assert!(
+            repository
+                .rebase_continue(Default::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_and_revert(executor: BackgroundExecutor) {
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(
+            path!("/foo"),
+            json!({ ".git": {}, "a": "one\n", "b": "two\n" }),
+        )
+        .await;
+        fs.set_head_and_index_for_repo(
+            Path::new(path!("/foo/.git")),
+            &[("a", "one\n".into()), ("b", "two\n".into())],
+        );
+        fs.with_git_state(Path::new(path!("/foo/.git")), true, |state| {
+            state.commit_changes.insert(
+                "a1".into(),
+                vec![FakeCommitChange {
+                    path: repo_path("a"),
+                    old_text: Some("one\n".into()),
+                    new_text: Some("ONE\n".into()),
+                }],
+            );
+            state.commit_changes.insert(
+                "b2".into(),
+                vec![FakeCommitChange {
+                    path: repo_path("b"),
+                    old_text: Some("zwei\n".into()),
+                    new_text: Some("drei\n".into()),
+                }],
+            );
+        })
+        .unwrap();
+        let repository = fs
+            .open_repo(Path::new(path!("/foo/.git")), Some("git".as_ref()))
+            .unwrap();
+
+        let has_conflicts = repository
+            .cherry_pick("a1".into(), Default::default())
+            .await
+            .unwrap();
+        assert!(!has_conflicts);
+        assert_eq!(fs.load(Path::new(path!("/foo/a"))).await.unwrap(), "ONE\n");
+        assert_eq!(
+            repository.load_committed_text(repo_path("a")).await,
+            Some("ONE\n".into())
+        );
+
+        let has_conflicts = repository
+            .revert("a1".into(), Default::default())
+            .await
+            .unwrap();
+        assert!(!has_conflicts);
+        assert_eq!(fs.load(Path::new(path!("/foo/a"))).await.unwrap(), "one\n");
+
+        // HEAD doesn't contain what `b2` changed, so replaying it conflicts.
+        let has_conflicts = repository
+            .cherry_pick("b2".into(), Default::default())
+            .await
+            .unwrap();
+        assert!(has_conflicts);
+        assert_eq!(
+            fs.load(Path::new(path!("/foo/b"))).await.unwrap(),
+            "<<<<<<< HEAD\ntwo\n=======\ndrei\n>>>>>>> b2\n"
+        );
+        assert_eq!(
+            repository
+                .load_conflict_stages(repo_path("b"))
+                .await
+                .unwrap(),
+            ConflictStages {
+                base: Some("zwei\n".into()),
+                ours: Some("two\n".into()),
+                theirs: Some("drei\n".into()),
+            }
+        );
+        assert_eq!(
+            repository.load_committed_text(repo_path("b")).await,
+            Some("two\n".into())
+        );
+
+        assert!(
+            repository
+                .cherry_pick("c3".into(), Default::default())
+                .await
+                .is_err()
+        );
+    }
 }
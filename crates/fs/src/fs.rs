@@ -64,7 +64,7 @@ use smol::io::AsyncReadExt;
 use std::ffi::OsStr;
 
 #[cfg(any(test, feature = "test-support"))]
-pub use fake_git_repo::{LOAD_HEAD_TEXT_TASK, LOAD_INDEX_TEXT_TASK};
+pub use fake_git_repo::{FakeCommitChange, LOAD_HEAD_TEXT_TASK, LOAD_INDEX_TEXT_TASK};
 
 pub trait Watcher: Send + Sync {
     fn add(&self, path: &Path) -> Result<()>;
@@ -1874,6 +1874,22 @@ impl FakeFs {
         .unwrap();
     }
 
+    pub fn set_conflict_stages_for_repo(
+        &self,
+        dot_git: &Path,
+        stages: &[(&str, git::repository::ConflictStages)],
+    ) {
+        self.with_git_state(dot_git, true, |state| {
+            state.conflict_stages.clear();
+            state.conflict_stages.extend(
+                stages
+                    .iter()
+                    .map(|(path, stages)| (repo_path(path), stages.clone())),
+            );
+        })
+        .unwrap();
+    }
+
     pub fn set_index_for_repo(&self, dot_git: &Path, index_state: &[(&str, String)]) {
         self.with_git_state(dot_git, true, |state| {
             state.index_contents.clear();
@@ -36,6 +36,10 @@ actions!(
         StageAndNext,
         /// Unstages the current hunk and moves to the next one.
         UnstageAndNext,
+        /// Stages only the selected lines of the changed hunks.
+        StageLines,
+        /// Unstages only the selected lines of the changed hunks.
+        UnstageLines,
         /// Restores the selected hunks to their original state.
         #[action(deprecated_aliases = ["editor::RevertSelectedHunks"])]
         Restore,
@@ -45,11 +49,15 @@ actions!(
         Blame,
         /// Shows the git history for the current file.
         FileHistory,
+        /// Opens the three-way merge editor for the current conflicted file.
+        OpenMergeEditor,
         /// Stages the current file.
         StageFile,
         /// Unstages the current file.
         UnstageFile,
         // repo-wide
+        /// Shows the commit graph of all branches in the repository.
+        CommitGraph,
         /// Stages all changes in the repository.
         StageAll,
         /// Unstages all changes in the repository.
@@ -66,6 +74,20 @@ actions!(
         TrashUntrackedFiles,
         /// Undoes the last commit, keeping changes in the working directory.
         Uncommit,
+        /// Continues the rebase in progress after resolving conflicts.
+        RebaseContinue,
+        /// Skips the commit the rebase in progress stopped at.
+        RebaseSkip,
+        /// Aborts the rebase in progress, restoring the branch to its original state.
+        RebaseAbort,
+        /// Marks the commit being tested in the bisect in progress as good.
+        BisectGood,
+        /// Marks the commit being tested in the bisect in progress as bad.
+        BisectBad,
+        /// Skips the commit being tested in the bisect in progress.
+        BisectSkip,
+        /// Ends the bisect in progress, checking out the commit it started from.
+        BisectReset,
         /// Pushes commits to the remote repository.
         Push,
         /// Pushes commits to a specific remote branch.
@@ -80,6 +102,12 @@ actions!(
         Fetch,
         /// Fetches changes from a specific remote.
         FetchFrom,
+        /// Checks out the recorded commit in every submodule, cloning them if needed.
+        UpdateSubmodules,
+        /// Registers every submodule in the repository's config.
+        InitSubmodules,
+        /// Copies the submodule URLs from `.gitmodules` into the repository's config.
+        SyncSubmodules,
         /// Creates a new commit with staged changes.
         Commit,
         /// Amends the last commit with staged changes.
@@ -115,6 +143,33 @@ pub struct RenameBranch {
     pub branch: Option<String>,
 }
 
+/// Opens the interactive rebase editor for the commits on the current branch.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct InteractiveRebase {
+    /// The commit or branch to rebase onto.
+    ///
+    /// Default: the upstream of the current branch, or the default branch.
+    #[serde(default)]
+    pub base: Option<String>,
+}
+
+/// Opens the bisect view to find the commit that introduced a regression.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct Bisect {
+    /// A revision known to be bad. When set along with `good`, the bisect starts right away.
+    ///
+    /// Default: HEAD
+    #[serde(default)]
+    pub bad: Option<String>,
+    /// The revisions known to be good.
+    #[serde(default)]
+    pub good: Vec<String>,
+}
+
 /// Restores a file to its last committed state, discarding local changes.
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
 #[action(namespace = git, deprecated_aliases = ["editor::RevertFile"])]
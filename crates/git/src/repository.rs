@@ -231,6 +231,227 @@ pub struct FileHistory {
     pub path: RepoPath,
 }
 
+/// The versions of a conflicted file that are recorded in the index during a merge.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictStages {
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+/// A submodule of the repository, as reported by `git submodule status`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Submodule {
+    pub path: RepoPath,
+    /// The commit checked out in the submodule, or the recorded commit if it isn't initialized.
+    pub sha: SharedString,
+    pub state: SubmoduleState,
+    /// Whether tracked files in the submodule have uncommitted changes.
+    pub has_modified_content: bool,
+    /// Whether the submodule contains untracked files.
+    pub has_untracked_content: bool,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SubmoduleState {
+    /// The submodule hasn't been cloned into the working tree.
+    Uninitialized,
+    /// The submodule has the commit recorded by the superproject checked out.
+    UpToDate,
+    /// The submodule has a different commit checked out than the one recorded by the superproject.
+    NewCommits,
+    /// The commit recorded for the submodule has merge conflicts.
+    Conflicted,
+}
+
+impl Submodule {
+    pub fn is_dirty(&self) -> bool {
+        self.has_modified_content || self.has_untracked_content
+    }
+
+    /// Describes how the submodule differs from the superproject, in the words of `git status`.
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        match self.state {
+            SubmoduleState::Uninitialized => parts.push("not initialized"),
+            SubmoduleState::NewCommits => parts.push("new commits"),
+            SubmoduleState::Conflicted => parts.push("conflicted"),
+            SubmoduleState::UpToDate => {}
+        }
+        if self.has_modified_content {
+            parts.push("modified content");
+        }
+        if self.has_untracked_content {
+            parts.push("untracked content");
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SubmoduleAction {
+    /// Registers the submodules in the repository's config, without cloning them.
+    Init,
+    /// Checks out the recorded commit in each submodule, cloning it first if needed.
+    Update,
+    /// Copies the submodule URLs from `.gitmodules` into the repository's config.
+    Sync,
+}
+
+impl SubmoduleAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SubmoduleAction::Init => "init",
+            SubmoduleAction::Update => "update",
+            SubmoduleAction::Sync => "sync",
+        }
+    }
+
+    fn args(&self) -> &'static [&'static str] {
+        match self {
+            SubmoduleAction::Init => &["submodule", "init"],
+            SubmoduleAction::Update => &["submodule", "update", "--init", "--recursive"],
+            SubmoduleAction::Sync => &["submodule", "sync", "--recursive"],
+        }
+    }
+}
+
+/// A commit in the repository-wide log, with enough information to draw the branch graph.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CommitLogEntry {
+    pub sha: SharedString,
+    pub parents: Vec<SharedString>,
+    pub subject: SharedString,
+    pub commit_timestamp: i64,
+    pub author_name: SharedString,
+    pub author_email: SharedString,
+    /// Full names of the refs pointing at this commit, e.g. `HEAD`, `refs/heads/main`
+    /// or `refs/tags/v1.0`.
+    pub refs: Vec<SharedString>,
+}
+
+/// Narrows the repository-wide log down to matching commits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommitLogSearch {
+    /// Commits whose author name or email contains the text.
+    Author(String),
+    /// Commits whose message contains the text.
+    Message(String),
+}
+
+/// What an interactive rebase does with a commit.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    Reword,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [RebaseAction; 5] = [
+        RebaseAction::Pick,
+        RebaseAction::Reword,
+        RebaseAction::Squash,
+        RebaseAction::Fixup,
+        RebaseAction::Drop,
+    ];
+
+    /// The command used for this action in a rebase todo list.
+    pub fn command(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+}
+
+/// A line of an interactive rebase todo list.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RebaseEntry {
+    pub action: RebaseAction,
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// The new message for a reworded commit. When `None`, the original message is kept.
+    pub message: Option<SharedString>,
+}
+
+/// The state of a rebase that stopped before finishing, e.g. because of conflicts.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct RebaseStatus {
+    /// The branch being rebased, or `None` if HEAD was detached.
+    pub head_name: Option<SharedString>,
+    pub onto: SharedString,
+    /// The commit the rebase stopped at.
+    pub stopped_sha: Option<SharedString>,
+    /// The 1-based index of the current step.
+    pub step: usize,
+    pub total_steps: usize,
+}
+
+/// How the commit being tested in a bisect behaves.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum BisectMark {
+    Good,
+    Bad,
+    /// The commit can't be tested, e.g. because it doesn't build.
+    Skip,
+}
+
+impl BisectMark {
+    pub fn command(&self) -> &'static str {
+        match self {
+            BisectMark::Good => "good",
+            BisectMark::Bad => "bad",
+            BisectMark::Skip => "skip",
+        }
+    }
+}
+
+/// A commit that may still be the first bad one.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BisectCandidate {
+    pub sha: SharedString,
+    pub subject: SharedString,
+}
+
+/// The state of a bisect in progress.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct BisectStatus {
+    /// The commit checked out for testing.
+    pub current: Option<SharedString>,
+    pub bad: Option<SharedString>,
+    pub good: Vec<SharedString>,
+    pub skipped: Vec<SharedString>,
+    /// The commits between the good ones and the bad one, newest first.
+    pub remaining: Vec<BisectCandidate>,
+}
+
+impl BisectStatus {
+    /// The first bad commit, once every other candidate has been ruled out.
+    pub fn first_bad(&self) -> Option<&SharedString> {
+        match self.remaining.as_slice() {
+            [candidate] if !self.good.is_empty() => Some(&candidate.sha),
+            _ => None,
+        }
+    }
+
+    /// Whether all the remaining candidates but the bad commit were skipped, so git can't
+    /// narrow the range down any further.
+    pub fn only_skipped_left(&self) -> bool {
+        self.first_bad().is_none()
+            && !self.good.is_empty()
+            && self.remaining.iter().all(|candidate| {
+                Some(&candidate.sha) == self.bad.as_ref() || self.skipped.contains(&candidate.sha)
+            })
+    }
+}
+
 #[derive(Debug)]
 pub struct CommitDiff {
     pub files: Vec<CommitFile>,
@@ -254,6 +475,23 @@ pub struct Remote {
     pub name: SharedString,
 }
 
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Tag {
+    pub name: SharedString,
+    /// The commit the tag points at.
+    pub sha: SharedString,
+    /// The subject of the tag message, only set for annotated tags.
+    pub message: Option<SharedString>,
+    /// This is a unix timestamp: the tagger date for annotated tags and the commit date otherwise.
+    pub timestamp: i64,
+}
+
+impl Tag {
+    pub fn is_annotated(&self) -> bool {
+        self.message.is_some()
+    }
+}
+
 pub enum ResetMode {
     /// Reset the branch pointer, leave index and worktree unchanged (this will make it look like things that were
     /// committed are now staged).
@@ -413,6 +651,11 @@ pub trait GitRepository: Send + Sync {
     /// Also returns `None` for symlinks.
     fn load_index_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>>;
 
+    /// Returns the common ancestor, our and their version of a conflicted path from the index.
+    ///
+    /// Versions that don't exist, such as the base of a file added on both sides, are `None`.
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>>;
+
     /// Returns the contents of an entry in the repository's HEAD, or None if HEAD does not exist or has no entry for the given path.
     ///
     /// Also returns `None` for symlinks.
@@ -470,6 +713,20 @@ pub trait GitRepository: Send + Sync {
         from_commit: Option<String>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Returns the submodules of the repository, with how each one differs from its recorded commit.
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
+    /// Runs `git submodule <action>` for the given submodules, or for all of them if `paths` is empty.
+    fn submodule_command(
+        &self,
+        action: SubmoduleAction,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // Updating submodules may fetch, so like `fetch` this must be invoked on the main thread.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
     fn reset(
         &self,
         commit: String,
@@ -495,6 +752,14 @@ pub trait GitRepository: Send + Sync {
         skip: usize,
         limit: Option<usize>,
     ) -> BoxFuture<'_, Result<FileHistory>>;
+    /// Returns the commits reachable from any branch, tag or HEAD in topological order,
+    /// optionally filtered by `search`.
+    fn commit_log_paginated(
+        &self,
+        search: Option<CommitLogSearch>,
+        skip: usize,
+        limit: Option<usize>,
+    ) -> BoxFuture<'_, Result<Vec<CommitLogEntry>>>;
 
     /// Returns the absolute path to the repository. For worktrees, this will be the path to the
     /// worktree's gitdir within the main repository (typically `.git/worktrees/<name>`).
@@ -558,6 +823,96 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Lists the commits that rebasing HEAD onto `base` would replay, oldest first.
+    fn rebase_entries(&self, base: String) -> BoxFuture<'_, Result<Vec<RebaseEntry>>>;
+
+    /// Rebases HEAD onto `base`, applying `entries` in order instead of the default todo list.
+    ///
+    /// Succeeds when the rebase stops for conflicts; use [`GitRepository::rebase_status`] to tell.
+    fn rebase_interactive(
+        &self,
+        base: String,
+        entries: Vec<RebaseEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_continue(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the state of the rebase in progress, if any.
+    fn rebase_status(&self) -> BoxFuture<'_, Result<Option<RebaseStatus>>>;
+
+    /// Starts bisecting the commits between `bad` and the `good` revisions, checking out the
+    /// first one to test.
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Marks the commit being tested and checks out the next one, if any.
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Ends the bisect, checking out the commit HEAD pointed to when it started.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the state of the bisect in progress, if any.
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>>;
+
+    /// Applies the changes introduced by `commit` on top of HEAD.
+    ///
+    /// Returns whether the cherry-pick stopped because of conflicts.
+    fn cherry_pick(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>>;
+
+    /// Creates a commit that undoes the changes introduced by `commit`.
+    ///
+    /// Returns whether the revert stopped because of conflicts.
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>>;
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>>;
+
+    /// Creates a tag pointing at `commit`, or at HEAD when it's `None`.
+    ///
+    /// The tag is annotated when a `message` is given and lightweight otherwise.
+    fn create_tag(
+        &self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
+
+    /// Pushes the tag to the remote, or deletes it from the remote when `delete` is set.
+    fn push_tag(
+        &self,
+        name: String,
+        remote_name: String,
+        delete: bool,
+        askpass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        // This method takes an AsyncApp to ensure it's invoked on the main thread,
+        // otherwise git-credentials-manager won't work.
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>>;
+
     fn push(
         &self,
         branch_name: String,
@@ -680,6 +1035,101 @@ impl RealGitRepository {
             .context("failed to read git work directory")
             .map(Path::to_path_buf)
     }
+
+    /// Runs `git <command> <commit>`, which stops with `head_file` present in
+    /// the git directory when the commit doesn't apply cleanly.
+    fn apply_commit(
+        &self,
+        command: &'static str,
+        head_file: &'static str,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(git_binary_path);
+                cmd.current_dir(&working_directory?).envs(env.iter()).args([
+                    command,
+                    "--no-edit",
+                    &commit,
+                ]);
+
+                let output = cmd.output().await?;
+                if output.status.success() {
+                    return Ok(false);
+                }
+                anyhow::ensure!(
+                    smol::fs::metadata(git_dir.join(head_file)).await.is_ok(),
+                    "Failed to {command}:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(true)
+            })
+            .boxed()
+    }
+
+    fn run_rebase_command(
+        &self,
+        flag: &'static str,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(git_binary_path);
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", "true")
+                    .args(["rebase", flag]);
+
+                let output = cmd.output().await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git rebase {flag}:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn run_bisect_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let output = new_smol_command(git_binary_path)
+                    .current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .arg("bisect")
+                    .args(&args)
+                    .output()
+                    .await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git bisect {}:\n{}",
+                    args[0],
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+}
+
+/// Quotes `arg` for the POSIX shell git uses to run editors and `exec` lines.
+fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
 }
 
 #[derive(Clone, Debug)]
@@ -967,36 +1417,11 @@ impl GitRepository for RealGitRepository {
     }
 
     fn load_index_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
-        // https://git-scm.com/book/en/v2/Git-Internals-Git-Objects
-        const GIT_MODE_SYMLINK: u32 = 0o120000;
-
         let repo = self.repository.clone();
         self.executor
             .spawn(async move {
-                fn logic(repo: &git2::Repository, path: &RepoPath) -> Result<Option<String>> {
-                    // This check is required because index.get_path() unwraps internally :(
-                    let mut index = repo.index()?;
-                    index.read(false)?;
-
-                    const STAGE_NORMAL: i32 = 0;
-                    let path = path.as_std_path();
-                    // `RepoPath` contains a `RelPath` which normalizes `.` into an empty path
-                    // `get_path` unwraps on empty paths though, so undo that normalization here
-                    let path = if path.components().next().is_none() {
-                        ".".as_ref()
-                    } else {
-                        path
-                    };
-                    let oid = match index.get_path(path, STAGE_NORMAL) {
-                        Some(entry) if entry.mode != GIT_MODE_SYMLINK => entry.id,
-                        _ => return Ok(None),
-                    };
-
-                    let content = repo.find_blob(oid)?.content().to_owned();
-                    Ok(String::from_utf8(content).ok())
-                }
-
-                match logic(&repo.lock(), &path) {
+                const STAGE_NORMAL: i32 = 0;
+                match load_index_stage_text(&repo.lock(), &path, STAGE_NORMAL) {
                     Ok(value) => return value,
                     Err(err) => log::error!("Error loading index text: {:?}", err),
                 }
@@ -1005,6 +1430,20 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>> {
+        let repo = self.repository.clone();
+        self.executor
+            .spawn(async move {
+                let repo = repo.lock();
+                Ok(ConflictStages {
+                    base: load_index_stage_text(&repo, &path, 1)?,
+                    ours: load_index_stage_text(&repo, &path, 2)?,
+                    theirs: load_index_stage_text(&repo, &path, 3)?,
+                })
+            })
+            .boxed()
+    }
+
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
         let repo = self.repository.clone();
         self.executor
@@ -1386,6 +1825,91 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                if !working_directory.join(".gitmodules").exists() {
+                    return Ok(Vec::new());
+                }
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(["--no-optional-locks", "submodule", "status"])
+                    .output()
+                    .await?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    anyhow::bail!("git submodule status failed: {stderr}");
+                }
+                let mut submodules =
+                    parse_submodule_status(&String::from_utf8_lossy(&output.stdout));
+                if submodules.is_empty() {
+                    return Ok(submodules);
+                }
+
+                // `git submodule status` doesn't report uncommitted changes inside the submodules.
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args([
+                        "--no-optional-locks",
+                        "status",
+                        "--porcelain=v2",
+                        "--ignore-submodules=none",
+                        "--no-renames",
+                        "-z",
+                        "--",
+                    ])
+                    .args(
+                        submodules
+                            .iter()
+                            .map(|submodule| submodule.path.as_std_path()),
+                    )
+                    .output()
+                    .await?;
+                if output.status.success() {
+                    apply_submodule_changes(
+                        &mut submodules,
+                        &String::from_utf8_lossy(&output.stdout),
+                    );
+                }
+                Ok(submodules)
+            })
+            .boxed()
+    }
+
+    fn submodule_command(
+        &self,
+        action: SubmoduleAction,
+        paths: Vec<RepoPath>,
+        ask_pass: AskPassDelegate,
+        env: Arc<HashMap<String, String>>,
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.system_git_binary_path.clone();
+        let executor = cx.background_executor().clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path =
+                git_binary_path.context("git not found on $PATH, can't update submodules")?;
+            let mut command = new_smol_command(git_binary_path);
+            command
+                .envs(env.iter())
+                .current_dir(&working_directory?)
+                .args(action.args())
+                .arg("--")
+                .args(paths.iter().map(|path| path.as_std_path()))
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
     fn change_branch(&self, name: String) -> BoxFuture<'_, Result<()>> {
         let repo = self.repository.clone();
         let working_directory = self.working_directory();
@@ -1595,6 +2119,60 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn commit_log_paginated(
+        &self,
+        search: Option<CommitLogSearch>,
+        skip: usize,
+        limit: Option<usize>,
+    ) -> BoxFuture<'_, Result<Vec<CommitLogEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let working_directory = working_directory?;
+                let mut args = vec![
+                    "--no-optional-locks".to_string(),
+                    "log".to_string(),
+                    "--branches".to_string(),
+                    "--remotes".to_string(),
+                    "--tags".to_string(),
+                    "HEAD".to_string(),
+                    "--topo-order".to_string(),
+                    "--decorate=full".to_string(),
+                    "--format=%H%x00%P%x00%D%x00%at%x00%an%x00%ae%x00%s".to_string(),
+                ];
+                if skip > 0 {
+                    args.push(format!("--skip={skip}"));
+                }
+                if let Some(limit) = limit {
+                    args.push(format!("--max-count={limit}"));
+                }
+                if let Some(search) = search {
+                    args.push("--regexp-ignore-case".to_string());
+                    args.push("--fixed-strings".to_string());
+                    args.push(match search {
+                        CommitLogSearch::Author(author) => format!("--author={author}"),
+                        CommitLogSearch::Message(message) => format!("--grep={message}"),
+                    });
+                }
+                args.push("--".to_string());
+
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(&working_directory)
+                    .args(&args)
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    bail!("git log failed: {stderr}");
+                }
+
+                Ok(parse_commit_log(&String::from_utf8_lossy(&output.stdout)))
+            })
+            .boxed()
+    }
+
     fn diff(&self, diff: DiffType) -> BoxFuture<'_, Result<String>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
@@ -1680,58 +2258,316 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn stash_paths(
+    fn stash_paths(
+        &self,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(&git_binary_path);
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(["stash", "push", "--quiet"])
+                    .arg("--include-untracked");
+
+                cmd.args(paths.iter().map(|p| p.as_unix_str()));
+
+                let output = cmd.output().await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to stash:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn stash_pop(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(git_binary_path);
+                let mut args = vec!["stash".to_string(), "pop".to_string()];
+                if let Some(index) = index {
+                    args.push(format!("stash@{{{}}}", index));
+                }
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(args);
+
+                let output = cmd.output().await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to stash pop:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn stash_apply(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(git_binary_path);
+                let mut args = vec!["stash".to_string(), "apply".to_string()];
+                if let Some(index) = index {
+                    args.push(format!("stash@{{{}}}", index));
+                }
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(args);
+
+                let output = cmd.output().await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to apply stash:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn stash_drop(
+        &self,
+        index: Option<usize>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_smol_command(git_binary_path);
+                let mut args = vec!["stash".to_string(), "drop".to_string()];
+                if let Some(index) = index {
+                    args.push(format!("stash@{{{}}}", index));
+                }
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(args);
+
+                let output = cmd.output().await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to stash drop:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn rebase_entries(&self, base: String) -> BoxFuture<'_, Result<Vec<RebaseEntry>>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                let output = GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&[
+                        "log",
+                        "--reverse",
+                        "--no-merges",
+                        "--format=%H%x00%s",
+                        &format!("{base}..HEAD"),
+                    ])
+                    .await?;
+                Ok(output
+                    .lines()
+                    .filter_map(|line| {
+                        let (sha, subject) = line.split_once('\0')?;
+                        Some(RebaseEntry {
+                            action: RebaseAction::Pick,
+                            sha: sha.to_string().into(),
+                            subject: subject.to_string().into(),
+                            message: None,
+                        })
+                    })
+                    .collect())
+            })
+            .boxed()
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: String,
+        entries: Vec<RebaseEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let git_dir = self.path();
+        let executor = self.executor.clone();
+        let rebase_status = self.rebase_status();
+        async move {
+            if rebase_status.await?.is_some() {
+                anyhow::bail!("A rebase is already in progress");
+            }
+            executor
+                .spawn(async move {
+                    // Git hands the todo list to the sequence editor, which replaces it with ours.
+                    let mut todo = String::new();
+                    for entry in &entries {
+                        match (&entry.action, &entry.message) {
+                            (RebaseAction::Reword, Some(message)) => {
+                                // A todo line can't contain line breaks, so printf expands escaped ones.
+                                let message = message.replace('\\', "\\\\").replace('\n', "\\n");
+                                todo.push_str(&format!("pick {}\n", entry.sha));
+                                todo.push_str(&format!(
+                                    "exec printf '%b' {} | git commit --amend --only --no-verify --cleanup=strip -F -\n",
+                                    shell_quote(&message)
+                                ));
+                            }
+                            (action, _) => {
+                                todo.push_str(&format!("{} {}\n", action.command(), entry.sha));
+                            }
+                        }
+                    }
+                    let todo_path = git_dir.join("ZED_REBASE_TODO");
+                    smol::fs::write(&todo_path, todo).await?;
+
+                    let mut cmd = new_smol_command(&git_binary_path);
+                    cmd.current_dir(&working_directory?)
+                        .envs(env.iter())
+                        .env(
+                            "GIT_SEQUENCE_EDITOR",
+                            format!("cp {}", shell_quote(&todo_path.to_string_lossy())),
+                        )
+                        // Keep the combined message of squashed commits instead of prompting for it.
+                        .env("GIT_EDITOR", "true")
+                        .args(["rebase", "--interactive", &base]);
+                    let output = cmd.output().await?;
+
+                    smol::fs::remove_file(&todo_path).await.log_err();
+                    // No rebase was in progress before, so a state directory means git stopped
+                    // for an edit or a conflict rather than failing outright.
+                    if !output.status.success()
+                        && !smol::fs::metadata(git_dir.join("rebase-merge"))
+                            .await
+                            .is_ok_and(|metadata| metadata.is_dir())
+                    {
+                        anyhow::bail!(
+                            "Failed to rebase:\n{}",
+                            String::from_utf8_lossy(&output.stderr)
+                        );
+                    }
+                    Ok(())
+                })
+                .await
+        }
+        .boxed()
+    }
+
+    fn rebase_continue(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_rebase_command("--continue", env)
+    }
+
+    fn rebase_skip(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_rebase_command("--skip", env)
+    }
+
+    fn rebase_abort(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_rebase_command("--abort", env)
+    }
+
+    fn cherry_pick(
         &self,
-        paths: Vec<RepoPath>,
+        commit: String,
         env: Arc<HashMap<String, String>>,
-    ) -> BoxFuture<'_, Result<()>> {
+    ) -> BoxFuture<'_, Result<bool>> {
+        self.apply_commit("cherry-pick", "CHERRY_PICK_HEAD", commit, env)
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<bool>> {
+        self.apply_commit("revert", "REVERT_HEAD", commit, env)
+    }
+
+    fn tags(&self) -> BoxFuture<'_, Result<Vec<Tag>>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
         self.executor
             .spawn(async move {
-                let mut cmd = new_smol_command(&git_binary_path);
-                cmd.current_dir(&working_directory?)
-                    .envs(env.iter())
-                    .args(["stash", "push", "--quiet"])
-                    .arg("--include-untracked");
-
-                cmd.args(paths.iter().map(|p| p.as_unix_str()));
-
-                let output = cmd.output().await?;
+                let fields = [
+                    "%(refname:strip=2)",
+                    "%(objecttype)",
+                    "%(objectname)",
+                    "%(*objectname)",
+                    "%(creatordate:unix)",
+                    "%(contents:subject)",
+                ]
+                .join("%00");
+                let output = new_smol_command(&git_binary_path)
+                    .current_dir(working_directory?)
+                    .args([
+                        "for-each-ref",
+                        "refs/tags",
+                        "--sort=-creatordate",
+                        "--format",
+                    ])
+                    .arg(fields)
+                    .output()
+                    .await?;
 
                 anyhow::ensure!(
                     output.status.success(),
-                    "Failed to stash:\n{}",
+                    "Failed to get git tags:\n{}",
                     String::from_utf8_lossy(&output.stderr)
                 );
-                Ok(())
+
+                Ok(parse_tag_input(&String::from_utf8_lossy(&output.stdout)))
             })
             .boxed()
     }
 
-    fn stash_pop(
+    fn create_tag(
         &self,
-        index: Option<usize>,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
         self.executor
             .spawn(async move {
-                let mut cmd = new_smol_command(git_binary_path);
-                let mut args = vec!["stash".to_string(), "pop".to_string()];
-                if let Some(index) = index {
-                    args.push(format!("stash@{{{}}}", index));
-                }
-                cmd.current_dir(&working_directory?)
+                let mut command = new_smol_command(&git_binary_path);
+                command
+                    .current_dir(working_directory?)
                     .envs(env.iter())
-                    .args(args);
-
-                let output = cmd.output().await?;
+                    .arg("tag");
+                if let Some(message) = message {
+                    command.args(["--annotate", "--message", &message]);
+                }
+                let output = command.arg(&name).args(commit).output().await?;
 
                 anyhow::ensure!(
                     output.status.success(),
-                    "Failed to stash pop:\n{}",
+                    "Failed to create tag:\n{}",
                     String::from_utf8_lossy(&output.stderr)
                 );
                 Ok(())
@@ -1739,62 +2575,165 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
-    fn stash_apply(
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary_path = self.any_git_binary_path.clone();
+        let working_directory = self.working_directory();
+        let executor = self.executor.clone();
+
+        self.executor
+            .spawn(async move {
+                GitBinary::new(git_binary_path, working_directory?, executor)
+                    .run(&["tag", "-d", &name])
+                    .await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
+    fn push_tag(
         &self,
-        index: Option<usize>,
+        name: String,
+        remote_name: String,
+        delete: bool,
+        ask_pass: AskPassDelegate,
         env: Arc<HashMap<String, String>>,
-    ) -> BoxFuture<'_, Result<()>> {
+        cx: AsyncApp,
+    ) -> BoxFuture<'_, Result<RemoteCommandOutput>> {
         let working_directory = self.working_directory();
-        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = cx.background_executor().clone();
+        let git_binary_path = self.system_git_binary_path.clone();
+        // Note: Do not spawn this command on the background thread, it might pop open the credential helper
+        // which we want to block on.
+        async move {
+            let git_binary_path = git_binary_path.context("git not found on $PATH, can't push")?;
+            let mut command = new_smol_command(git_binary_path);
+            command
+                .envs(env.iter())
+                .current_dir(working_directory?)
+                .arg("push")
+                .args(delete.then_some("--delete"))
+                .arg(remote_name)
+                .arg(format!("refs/tags/{name}"))
+                .stdin(smol::process::Stdio::null())
+                .stdout(smol::process::Stdio::piped())
+                .stderr(smol::process::Stdio::piped());
+
+            run_git_command(env, ask_pass, command, &executor).await
+        }
+        .boxed()
+    }
+
+    fn rebase_status(&self) -> BoxFuture<'_, Result<Option<RebaseStatus>>> {
+        let git_dir = self.path();
         self.executor
             .spawn(async move {
-                let mut cmd = new_smol_command(git_binary_path);
-                let mut args = vec!["stash".to_string(), "apply".to_string()];
-                if let Some(index) = index {
-                    args.push(format!("stash@{{{}}}", index));
+                async fn read(dir: &Path, name: &str) -> Option<String> {
+                    let content = smol::fs::read_to_string(dir.join(name)).await.ok()?;
+                    Some(content.trim().to_string())
                 }
-                cmd.current_dir(&working_directory?)
-                    .envs(env.iter())
-                    .args(args);
 
-                let output = cmd.output().await?;
+                // `git rebase` keeps its state in `rebase-merge`, `git rebase --apply` in `rebase-apply`.
+                let (dir, step_file, total_file) =
+                    if smol::fs::metadata(git_dir.join("rebase-merge"))
+                        .await
+                        .is_ok()
+                    {
+                        (git_dir.join("rebase-merge"), "msgnum", "end")
+                    } else if smol::fs::metadata(git_dir.join("rebase-apply").join("onto"))
+                        .await
+                        .is_ok()
+                    {
+                        (git_dir.join("rebase-apply"), "next", "last")
+                    } else {
+                        return Ok(None);
+                    };
 
-                anyhow::ensure!(
-                    output.status.success(),
-                    "Failed to apply stash:\n{}",
-                    String::from_utf8_lossy(&output.stderr)
-                );
-                Ok(())
+                let head_name = read(&dir, "head-name")
+                    .await
+                    .filter(|name| name != "detached HEAD")
+                    .map(|name| {
+                        name.strip_prefix("refs/heads/")
+                            .unwrap_or(&name)
+                            .to_string()
+                            .into()
+                    });
+                Ok(Some(RebaseStatus {
+                    head_name,
+                    onto: read(&dir, "onto").await.unwrap_or_default().into(),
+                    stopped_sha: read(&dir, "stopped-sha").await.map(Into::into),
+                    step: read(&dir, step_file)
+                        .await
+                        .and_then(|step| step.parse().ok())
+                        .unwrap_or(0),
+                    total_steps: read(&dir, total_file)
+                        .await
+                        .and_then(|total| total.parse().ok())
+                        .unwrap_or(0),
+                }))
             })
             .boxed()
     }
 
-    fn stash_drop(
+    fn bisect_start(
         &self,
-        index: Option<usize>,
+        bad: String,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec!["start".to_string(), bad];
+        args.extend(good);
+        args.push("--".to_string());
+        self.run_bisect_command(args, env)
+    }
+
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>> {
+        self.run_bisect_command(vec![mark.command().to_string()], env)
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_bisect_command(vec!["reset".to_string()], env)
+    }
+
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>> {
+        let git_dir = self.path();
         let working_directory = self.working_directory();
         let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
         self.executor
             .spawn(async move {
-                let mut cmd = new_smol_command(git_binary_path);
-                let mut args = vec!["stash".to_string(), "drop".to_string()];
-                if let Some(index) = index {
-                    args.push(format!("stash@{{{}}}", index));
+                if smol::fs::metadata(git_dir.join("BISECT_START"))
+                    .await
+                    .is_err()
+                {
+                    return Ok(None);
                 }
-                cmd.current_dir(&working_directory?)
-                    .envs(env.iter())
-                    .args(args);
-
-                let output = cmd.output().await?;
 
-                anyhow::ensure!(
-                    output.status.success(),
-                    "Failed to stash drop:\n{}",
-                    String::from_utf8_lossy(&output.stderr)
-                );
-                Ok(())
+                let git = GitBinary::new(git_binary_path, working_directory?, executor);
+                let refs = git
+                    .run(&[
+                        "for-each-ref",
+                        "refs/bisect/",
+                        "--format=%(refname)%00%(objectname)",
+                    ])
+                    .await?;
+                let mut status = parse_bisect_refs(&refs);
+                status.current = git.run(&["rev-parse", "HEAD"]).await.ok().map(Into::into);
+                if let Some(bad) = &status.bad {
+                    let mut args = vec![
+                        "log".to_string(),
+                        "--format=%H%x00%s".to_string(),
+                        bad.to_string(),
+                        "--not".to_string(),
+                    ];
+                    args.extend(status.good.iter().map(ToString::to_string));
+                    args.push("--".to_string());
+                    status.remaining = parse_bisect_candidates(&git.run(&args).await?);
+                }
+                Ok(Some(status))
             })
             .boxed()
     }
@@ -2706,6 +3645,199 @@ fn parse_branch_input(input: &str) -> Result<Vec<Branch>> {
     Ok(branches)
 }
 
+/// Returns the contents of the index entry for `path` at the given merge stage, where 0 is
+/// the entry of a path without conflicts and 1, 2 and 3 are the common ancestor, our and
+/// their version of a conflicted path.
+fn load_index_stage_text(
+    repo: &git2::Repository,
+    path: &RepoPath,
+    stage: i32,
+) -> Result<Option<String>> {
+    // https://git-scm.com/book/en/v2/Git-Internals-Git-Objects
+    const GIT_MODE_SYMLINK: u32 = 0o120000;
+
+    // This check is required because index.get_path() unwraps internally :(
+    let mut index = repo.index()?;
+    index.read(false)?;
+
+    let path = path.as_std_path();
+    // `RepoPath` contains a `RelPath` which normalizes `.` into an empty path
+    // `get_path` unwraps on empty paths though, so undo that normalization here
+    let path = if path.components().next().is_none() {
+        ".".as_ref()
+    } else {
+        path
+    };
+    let oid = match index.get_path(path, stage) {
+        Some(entry) if entry.mode != GIT_MODE_SYMLINK => entry.id,
+        _ => return Ok(None),
+    };
+
+    let content = repo.find_blob(oid)?.content().to_owned();
+    Ok(String::from_utf8(content).ok())
+}
+
+fn parse_submodule_status(input: &str) -> Vec<Submodule> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let state = match line.chars().next()? {
+                ' ' => SubmoduleState::UpToDate,
+                '-' => SubmoduleState::Uninitialized,
+                '+' => SubmoduleState::NewCommits,
+                'U' => SubmoduleState::Conflicted,
+                _ => return None,
+            };
+            let (sha, rest) = line[1..].split_once(' ')?;
+            // Initialized submodules are followed by the output of `git describe` in parentheses.
+            let path = match rest.rfind(" (") {
+                Some(ix) if rest.ends_with(')') => &rest[..ix],
+                _ => rest,
+            };
+            Some(Submodule {
+                path: RepoPath::from_rel_path(RelPath::unix(path).log_err()?),
+                sha: sha.to_string().into(),
+                state,
+                has_modified_content: false,
+                has_untracked_content: false,
+            })
+        })
+        .collect()
+}
+
+/// Reads the state of the submodules' working trees from `git status --porcelain=v2 -z`.
+fn apply_submodule_changes(submodules: &mut [Submodule], input: &str) {
+    for entry in input.split('\0') {
+        let fields = match entry.as_bytes().first() {
+            Some(b'1') => entry.splitn(9, ' ').collect::<Vec<_>>(),
+            Some(b'u') => entry.splitn(11, ' ').collect::<Vec<_>>(),
+            _ => continue,
+        };
+        let (Some(flags), Some(path)) = (fields.get(2), fields.last()) else {
+            continue;
+        };
+        // Submodules are flagged `S<c><m><u>`, where `m` and `u` mark modified and untracked content.
+        let flags = flags.as_bytes();
+        if flags.len() != 4 || flags[0] != b'S' {
+            continue;
+        }
+        if let Some(submodule) = submodules
+            .iter_mut()
+            .find(|submodule| submodule.path.as_unix_str() == *path)
+        {
+            submodule.has_modified_content = flags[2] == b'M';
+            submodule.has_untracked_content = flags[3] == b'U';
+        }
+    }
+}
+
+/// Reads the `refs/bisect/` refs listed by `git for-each-ref`.
+fn parse_bisect_refs(input: &str) -> BisectStatus {
+    let mut status = BisectStatus::default();
+    for line in input.lines() {
+        let Some((name, sha)) = line.split_once('\0') else {
+            continue;
+        };
+        let sha = SharedString::from(sha.to_string());
+        match name.strip_prefix("refs/bisect/") {
+            Some("bad") => status.bad = Some(sha),
+            Some(name) if name.starts_with("good-") => status.good.push(sha),
+            Some(name) if name.starts_with("skip-") => status.skipped.push(sha),
+            _ => {}
+        }
+    }
+    status
+}
+
+fn parse_bisect_candidates(input: &str) -> Vec<BisectCandidate> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let (sha, subject) = line.split_once('\0')?;
+            Some(BisectCandidate {
+                sha: sha.to_string().into(),
+                subject: subject.to_string().into(),
+            })
+        })
+        .collect()
+}
+
+fn parse_commit_log(input: &str) -> Vec<CommitLogEntry> {
+    let mut entries = Vec::new();
+    for line in input.lines() {
+        let mut fields = line.split('\0');
+        let (
+            Some(sha),
+            Some(parents),
+            Some(refs),
+            Some(timestamp),
+            Some(author_name),
+            Some(author_email),
+            Some(subject),
+        ) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        )
+        else {
+            continue;
+        };
+        let mut ref_names = Vec::new();
+        for decoration in refs.split(", ").filter(|decoration| !decoration.is_empty()) {
+            if let Some(branch) = decoration.strip_prefix("HEAD -> ") {
+                ref_names.push(SharedString::new_static("HEAD"));
+                ref_names.push(branch.to_string().into());
+            } else {
+                let name = decoration.strip_prefix("tag: ").unwrap_or(decoration);
+                ref_names.push(name.to_string().into());
+            }
+        }
+        entries.push(CommitLogEntry {
+            sha: sha.to_string().into(),
+            parents: parents
+                .split_whitespace()
+                .map(|parent| parent.to_string().into())
+                .collect(),
+            subject: subject.to_string().into(),
+            commit_timestamp: timestamp.parse().unwrap_or(0),
+            author_name: author_name.to_string().into(),
+            author_email: author_email.to_string().into(),
+            refs: ref_names,
+        });
+    }
+    entries
+}
+
+/// Parses the output of `git for-each-ref refs/tags` in the format used by [`GitRepository::tags`].
+fn parse_tag_input(input: &str) -> Vec<Tag> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x00');
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            let object_type = fields.next()?;
+            let object_sha = fields.next()?;
+            let peeled_sha = fields.next()?;
+            let timestamp = fields.next()?.parse::<i64>().unwrap_or_default();
+            let subject = fields.next().unwrap_or_default();
+
+            // Annotated tags are objects of their own, so the commit is the peeled object.
+            let annotated = object_type == "tag";
+            let sha = if annotated { peeled_sha } else { object_sha };
+            Some(Tag {
+                name: name.to_string().into(),
+                sha: sha.to_string().into(),
+                message: annotated.then(|| subject.to_string().into()),
+                timestamp,
+            })
+        })
+        .collect()
+}
+
 fn parse_upstream_track(upstream_track: &str) -> Result<UpstreamTracking> {
     if upstream_track.is_empty() {
         return Ok(UpstreamTracking::Tracked(UpstreamTrackingStatus {
@@ -2997,6 +4129,34 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_rebase_interactive_refuses_rebase_in_progress(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+
+        smol::fs::create_dir(repo_dir.path().join(".git/rebase-merge"))
+            .await
+            .unwrap();
+        let error = repo
+            .rebase_interactive("HEAD".into(), Vec::new(), Arc::new(HashMap::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "A rebase is already in progress");
+        // The todo list is never written, so the existing rebase is left untouched.
+        assert!(!repo_dir.path().join(".git/ZED_REBASE_TODO").exists());
+    }
+
     #[test]
     fn test_branches_parsing() {
         // suppress "help: octal escapes are not supported, `\0` is always null"
@@ -3063,6 +4223,161 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_tags_parsing() {
+        #[allow(clippy::octal_escapes)]
+        let input = "v1.0.0\0tag\0a1b2c3d4e5f60718293a4b5c6d7e8f9012345678\0895951d681e5561478c0acdd6905e8aacdfd2249\01762948725\0Release 1.0.0\nnightly\0commit\0eb0cae33272689bd11030822939dd2701c52f81e\0\01762948695\0Add feature\n";
+
+        assert_eq!(
+            parse_tag_input(input),
+            vec![
+                Tag {
+                    name: "v1.0.0".into(),
+                    sha: "895951d681e5561478c0acdd6905e8aacdfd2249".into(),
+                    message: Some("Release 1.0.0".into()),
+                    timestamp: 1762948725,
+                },
+                Tag {
+                    name: "nightly".into(),
+                    sha: "eb0cae33272689bd11030822939dd2701c52f81e".into(),
+                    message: None,
+                    timestamp: 1762948695,
+                }
+            ]
+        )
+    }
+
+    #[test]
+    fn test_commit_log_parsing() {
+        #[allow(clippy::octal_escapes)]
+        let input = "1111111111111111111111111111111111111111\02222222222222222222222222222222222222222 3333333333333333333333333333333333333333\0HEAD -> refs/heads/main, refs/remotes/origin/main, tag: refs/tags/v1.0\01762948725\0Jane Doe\0jane@example.com\0Merge branch 'feature'\n2222222222222222222222222222222222222222\0\0\01762948695\0John Doe\0john@example.com\0Initial commit\n";
+
+        assert_eq!(
+            parse_commit_log(input),
+            vec![
+                CommitLogEntry {
+                    sha: "1111111111111111111111111111111111111111".into(),
+                    parents: vec![
+                        "2222222222222222222222222222222222222222".into(),
+                        "3333333333333333333333333333333333333333".into(),
+                    ],
+                    subject: "Merge branch 'feature'".into(),
+                    commit_timestamp: 1762948725,
+                    author_name: "Jane Doe".into(),
+                    author_email: "jane@example.com".into(),
+                    refs: vec![
+                        "HEAD".into(),
+                        "refs/heads/main".into(),
+                        "refs/remotes/origin/main".into(),
+                        "refs/tags/v1.0".into(),
+                    ],
+                },
+                CommitLogEntry {
+                    sha: "2222222222222222222222222222222222222222".into(),
+                    parents: Vec::new(),
+                    subject: "Initial commit".into(),
+                    commit_timestamp: 1762948695,
+                    author_name: "John Doe".into(),
+                    author_email: "john@example.com".into(),
+                    refs: Vec::new(),
+                }
+            ]
+        )
+    }
+
+    #[test]
+    fn test_submodule_status_parsing() {
+        let status = " 1111111111111111111111111111111111111111 libs/up-to-date (v1.0)\n\
+            +2222222222222222222222222222222222222222 libs/moved (v1.0-3-g2222222)\n\
+            -3333333333333333333333333333333333333333 libs/uninitialized\n\
+            U0000000000000000000000000000000000000000 libs/conflicted\n";
+        let mut submodules = parse_submodule_status(status);
+        apply_submodule_changes(
+            &mut submodules,
+            "1 .M S.M. 160000 160000 160000 1111111111111111111111111111111111111111 1111111111111111111111111111111111111111 libs/up-to-date\0\
+            1 .M SCMU 160000 160000 160000 4444444444444444444444444444444444444444 4444444444444444444444444444444444444444 libs/moved\0\
+            1 .M N... 100644 100644 100644 5555555555555555555555555555555555555555 5555555555555555555555555555555555555555 libs/file.txt\0",
+        );
+
+        let submodule = |path: &str, sha: &str, state, modified, untracked| Submodule {
+            path: repo_path(path),
+            sha: sha.to_string().into(),
+            state,
+            has_modified_content: modified,
+            has_untracked_content: untracked,
+        };
+        assert_eq!(
+            submodules,
+            vec![
+                submodule(
+                    "libs/up-to-date",
+                    "1111111111111111111111111111111111111111",
+                    SubmoduleState::UpToDate,
+                    true,
+                    false,
+                ),
+                submodule(
+                    "libs/moved",
+                    "2222222222222222222222222222222222222222",
+                    SubmoduleState::NewCommits,
+                    true,
+                    true,
+                ),
+                submodule(
+                    "libs/uninitialized",
+                    "3333333333333333333333333333333333333333",
+                    SubmoduleState::Uninitialized,
+                    false,
+                    false,
+                ),
+                submodule(
+                    "libs/conflicted",
+                    "0000000000000000000000000000000000000000",
+                    SubmoduleState::Conflicted,
+                    false,
+                    false,
+                ),
+            ]
+        );
+        assert_eq!(
+            submodules[1].summary().as_deref(),
+            Some("new commits, modified content, untracked content")
+        );
+        assert_eq!(submodules[3].summary().as_deref(), Some("conflicted"));
+    }
+
+    #[test]
+    fn test_bisect_status_parsing() {
+        let refs = "refs/bisect/bad\0cccc\n\
+                    refs/bisect/good-aaaa\0aaaa\n\
+                    refs/bisect/skip-bbbb\0bbbb\n";
+        let mut status = parse_bisect_refs(refs);
+        assert_eq!(status.bad.as_deref(), Some("cccc"));
+        assert_eq!(status.good, ["aaaa"]);
+        assert_eq!(status.skipped, ["bbbb"]);
+
+        status.remaining = parse_bisect_candidates("cccc\0Break everything\nbbbb\0Fail to build\n");
+        assert_eq!(
+            status.remaining,
+            [
+                BisectCandidate {
+                    sha: "cccc".into(),
+                    subject: "Break everything".into(),
+                },
+                BisectCandidate {
+                    sha: "bbbb".into(),
+                    subject: "Fail to build".into(),
+                },
+            ]
+        );
+        assert_eq!(status.first_bad(), None);
+        assert!(status.only_skipped_left());
+
+        status.remaining.pop();
+        assert_eq!(status.first_bad().map(|sha| sha.as_ref()), Some("cccc"));
+        assert!(!status.only_skipped_left());
+    }
+
     impl RealGitRepository {
         /// Force a Git garbage collection on the repository.
         fn gc(&self) -> BoxFuture<'_, Result<()>> {
@@ -0,0 +1,952 @@
+use std::any::{Any, TypeId};
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use git::repository::{BisectCandidate, BisectMark, BisectStatus};
+use gpui::{
+    AnyEntity, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement, Render,
+    Task, UniformListScrollHandle, WeakEntity, Window, uniform_list,
+};
+use project::{
+    Project, ProjectPath,
+    git_store::{Repository, RepositoryEvent},
+};
+use task::{TaskContext, TaskTemplate, TaskVariables, VariableName};
+use ui::{ContextMenu, Divider, PopoverMenu, Tooltip, WithScrollbar, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+    notifications::DetachAndPromptErr,
+};
+
+use crate::git_panel::GitPanel;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+    workspace.register_action(|workspace, _: &git::BisectGood, window, cx| {
+        run_bisect_command(workspace, BisectCommand::Mark(BisectMark::Good), window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::BisectBad, window, cx| {
+        run_bisect_command(workspace, BisectCommand::Mark(BisectMark::Bad), window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::BisectSkip, window, cx| {
+        run_bisect_command(workspace, BisectCommand::Mark(BisectMark::Skip), window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::BisectReset, window, cx| {
+        run_bisect_command(workspace, BisectCommand::Reset, window, cx);
+    });
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    action: &git::Bisect,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = active_repository(workspace, cx) else {
+        return;
+    };
+
+    let view = match bisect_view_for(workspace, &repository, cx) {
+        Some(view) => {
+            workspace.activate_item(&view, true, true, window, cx);
+            view
+        }
+        None => {
+            let view =
+                cx.new(|cx| BisectView::new(repository, workspace.weak_handle(), window, cx));
+            workspace.add_item_to_active_pane(Box::new(view.clone()), None, true, window, cx);
+            view
+        }
+    };
+
+    if !action.good.is_empty() {
+        let bad = action.bad.clone().unwrap_or_else(|| "HEAD".to_string());
+        let good = action.good.clone();
+        view.update(cx, |view, cx| view.start(bad, good, window, cx));
+    }
+}
+
+fn active_repository(workspace: &Workspace, cx: &App) -> Option<Entity<Repository>> {
+    workspace
+        .panel::<GitPanel>(cx)
+        .and_then(|panel| panel.read(cx).active_repository.clone())
+        .or_else(|| workspace.project().read(cx).active_repository(cx))
+}
+
+fn bisect_view_for(
+    workspace: &Workspace,
+    repository: &Entity<Repository>,
+    cx: &App,
+) -> Option<Entity<BisectView>> {
+    workspace
+        .items_of_type::<BisectView>(cx)
+        .find(|view| view.read(cx).repository == repository.downgrade())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BisectCommand {
+    Mark(BisectMark),
+    Reset,
+}
+
+impl BisectCommand {
+    fn run(self, repository: &Entity<Repository>, cx: &mut App) -> Task<Result<()>> {
+        let receiver = repository.update(cx, |repository, _| match self {
+            BisectCommand::Mark(mark) => repository.bisect_mark(mark),
+            BisectCommand::Reset => repository.bisect_reset(),
+        });
+        cx.background_spawn(async move { receiver.await.context("bisect was canceled")? })
+    }
+
+    fn error_message(self) -> &'static str {
+        match self {
+            BisectCommand::Mark(BisectMark::Good) => "Failed to mark commit as good",
+            BisectCommand::Mark(BisectMark::Bad) => "Failed to mark commit as bad",
+            BisectCommand::Mark(BisectMark::Skip) => "Failed to skip commit",
+            BisectCommand::Reset => "Failed to reset bisect",
+        }
+    }
+}
+
+fn run_bisect_command(
+    workspace: &mut Workspace,
+    command: BisectCommand,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = active_repository(workspace, cx) else {
+        return;
+    };
+    // Let an open view know, since the final step of a bisect doesn't move HEAD.
+    if let Some(view) = bisect_view_for(workspace, &repository, cx) {
+        view.update(cx, |view, cx| view.run_command(command, window, cx));
+        return;
+    }
+    command.run(&repository, cx).detach_and_prompt_err(
+        command.error_message(),
+        window,
+        cx,
+        |_, _, _| None,
+    );
+}
+
+/// Maps the exit code of the task used to test a commit the same way `git bisect run` does.
+///
+/// Returns `None` when the run should stop, e.g. because the task was interrupted.
+fn mark_for_exit_code(code: Option<i32>) -> Option<BisectMark> {
+    match code? {
+        0 => Some(BisectMark::Good),
+        125 => Some(BisectMark::Skip),
+        1..=127 => Some(BisectMark::Bad),
+        _ => None,
+    }
+}
+
+fn task_context(work_directory: &Path) -> TaskContext {
+    let mut task_variables = TaskVariables::default();
+    task_variables.insert(
+        VariableName::WorktreeRoot,
+        work_directory.to_string_lossy().into_owned(),
+    );
+    TaskContext {
+        cwd: Some(work_directory.to_path_buf()),
+        task_variables,
+        project_env: Default::default(),
+    }
+}
+
+/// Drives a `git bisect` in a repository: starting it, marking the commits it checks out, and
+/// optionally letting a task decide whether each of them is good.
+pub struct BisectView {
+    repository: WeakEntity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    status: Option<BisectStatus>,
+    bad_editor: Entity<Editor>,
+    good_editor: Entity<Editor>,
+    /// The tasks that can be used to test commits.
+    tasks: Vec<TaskTemplate>,
+    /// The label of the task testing commits, while a run is in progress.
+    running_task: Option<SharedString>,
+    /// Why the last run stopped before finding the first bad commit.
+    run_error: Option<SharedString>,
+    pending: bool,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    load_task: Task<()>,
+    run_task: Task<()>,
+    _load_tasks: Task<()>,
+    _subscriptions: Vec<gpui::Subscription>,
+}
+
+impl BisectView {
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let bad_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("HEAD", window, cx);
+            editor
+        });
+        let good_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Known good revisions, e.g. v1.2.0", window, cx);
+            editor
+        });
+        let subscriptions =
+            vec![
+                cx.subscribe(&repository, |this, _, event: &RepositoryEvent, cx| {
+                    if let RepositoryEvent::BranchChanged = event {
+                        this.reload(cx);
+                    }
+                }),
+            ];
+
+        let mut this = Self {
+            repository: repository.downgrade(),
+            workspace,
+            status: None,
+            bad_editor,
+            good_editor,
+            tasks: Vec::new(),
+            running_task: None,
+            run_error: None,
+            pending: false,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            load_task: Task::ready(()),
+            run_task: Task::ready(()),
+            _load_tasks: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.reload(cx);
+        this.load_tasks(cx);
+        this
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        let status = repository.update(cx, |repository, _| repository.bisect_status());
+        self.load_task = cx.spawn(async move |this, cx| {
+            let status = status
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|status| status);
+            this.update(cx, |this, cx| {
+                match status {
+                    Ok(status) => this.status = status,
+                    Err(error) => log::error!("failed to load bisect status: {error:#}"),
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn load_tasks(&mut self, cx: &mut Context<Self>) {
+        let (Some(repository), Some(workspace)) =
+            (self.repository.upgrade(), self.workspace.upgrade())
+        else {
+            return;
+        };
+        let work_directory = repository.read(cx).work_directory_abs_path.clone();
+        let project = workspace.read(cx).project().read(cx);
+        let worktree_id = project
+            .find_worktree(&work_directory, cx)
+            .map(|(worktree, _)| worktree.read(cx).id());
+        let Some(inventory) = project.task_store().read(cx).task_inventory().cloned() else {
+            return;
+        };
+        let tasks = inventory.read(cx).list_tasks(None, None, worktree_id, cx);
+        self._load_tasks = cx.spawn(async move |this, cx| {
+            let tasks = tasks.await;
+            this.update(cx, |this, cx| {
+                this.tasks = tasks.into_iter().map(|(_, template)| template).collect();
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn start_from_editors(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let bad = self.bad_editor.read(cx).text(cx).trim().to_string();
+        let bad = if bad.is_empty() {
+            "HEAD".to_string()
+        } else {
+            bad
+        };
+        let good = self
+            .good_editor
+            .read(cx)
+            .text(cx)
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if !good.is_empty() {
+            self.start(bad, good, window, cx);
+        }
+    }
+
+    fn start(
+        &mut self,
+        bad: String,
+        good: Vec<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        if self.status.is_some() {
+            return;
+        }
+        let receiver = repository.update(cx, |repository, _| repository.bisect_start(bad, good));
+        self.run_error = None;
+        self.finish_after(
+            cx.background_spawn(async move { receiver.await.context("bisect was canceled")? }),
+            "Failed to start bisect",
+            window,
+            cx,
+        );
+    }
+
+    fn run_command(&mut self, command: BisectCommand, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        if command == BisectCommand::Reset {
+            self.stop_run(cx);
+        }
+        let task = command.run(&repository, cx);
+        self.finish_after(task, command.error_message(), window, cx);
+    }
+
+    fn finish_after(
+        &mut self,
+        task: Task<Result<()>>,
+        error_message: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pending = true;
+        cx.notify();
+        cx.spawn_in(window, async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                this.pending = false;
+                this.reload(cx);
+            })?;
+            result
+        })
+        .detach_and_prompt_err(error_message, window, cx, |_, _, _| None);
+    }
+
+    /// Tests the commits checked out by the bisect with `template` until the first bad one is
+    /// found, like `git bisect run` does.
+    fn run_with_task(
+        &mut self,
+        template: TaskTemplate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+        let label = SharedString::from(template.label.clone());
+        let task_context = task_context(&repository.read(cx).work_directory_abs_path);
+        self.running_task = Some(label.clone());
+        self.run_error = None;
+        cx.notify();
+
+        self.run_task = cx.spawn_in(window, async move |this, cx| {
+            let result = async {
+                loop {
+                    let status = repository
+                        .update(cx, |repository, _| repository.bisect_status())?
+                        .await??;
+                    let Some(status) = status else {
+                        break;
+                    };
+                    if status.first_bad().is_some() || status.only_skipped_left() {
+                        break;
+                    }
+
+                    let resolved = template
+                        .resolve_task("bisect", &task_context)
+                        .with_context(|| format!("failed to resolve task {label}"))?;
+                    let exit_status = workspace
+                        .update_in(cx, |workspace, window, cx| {
+                            workspace.spawn_in_terminal(resolved.resolved, window, cx)
+                        })?
+                        .await;
+                    // The terminal was closed before the task finished.
+                    let Some(exit_status) = exit_status else {
+                        break;
+                    };
+                    let exit_status = exit_status?;
+                    let mark = mark_for_exit_code(exit_status.code())
+                        .with_context(|| format!("{label} stopped the run ({exit_status})"))?;
+                    repository
+                        .update(cx, |repository, _| repository.bisect_mark(mark))?
+                        .await??;
+                }
+                anyhow::Ok(())
+            }
+            .await;
+
+            this.update(cx, |this, cx| {
+                this.running_task = None;
+                if let Err(error) = result {
+                    this.run_error = Some(format!("{error:#}").into());
+                }
+                this.reload(cx);
+            })
+            .ok();
+        });
+    }
+
+    fn stop_run(&mut self, cx: &mut Context<Self>) {
+        if self.running_task.take().is_some() {
+            self.run_task = Task::ready(());
+            cx.notify();
+        }
+    }
+
+    fn render_start_form(&self, cx: &Context<Self>) -> impl IntoElement {
+        let field = |label: &'static str, editor: &Entity<Editor>| {
+            v_flex()
+                .gap_1()
+                .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+                .child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .rounded_sm()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .child(editor.clone()),
+                )
+        };
+
+        v_flex()
+            .p_3()
+            .gap_3()
+            .max_w(rems(36.))
+            .child(
+                Label::new(
+                    "Find the commit that introduced a bug by testing the commits between a bad \
+                     revision and good ones.",
+                )
+                .color(Color::Muted),
+            )
+            .child(field("Bad revision", &self.bad_editor))
+            .child(field("Good revisions", &self.good_editor))
+            .child(
+                h_flex().justify_end().child(
+                    Button::new("start-bisect", "Start Bisect")
+                        .disabled(self.pending)
+                        .on_click(
+                            cx.listener(|this, _, window, cx| this.start_from_editors(window, cx)),
+                        ),
+                ),
+            )
+    }
+
+    fn render_summary(&self, status: &BisectStatus, cx: &Context<Self>) -> impl IntoElement {
+        let subject_of = |sha: &SharedString| {
+            status
+                .remaining
+                .iter()
+                .find(|candidate| &candidate.sha == sha)
+                .map(|candidate| candidate.subject.clone())
+                .unwrap_or_default()
+        };
+        let (icon, color, title, subject) = if let Some(first_bad) = status.first_bad() {
+            (
+                IconName::Check,
+                Color::Success,
+                format!("{} is the first bad commit", short_sha(first_bad)),
+                subject_of(first_bad),
+            )
+        } else if status.only_skipped_left() {
+            (
+                IconName::Warning,
+                Color::Warning,
+                "Only skipped commits are left; the first bad commit is one of them".to_string(),
+                SharedString::default(),
+            )
+        } else if let Some(current) = status.current.as_ref() {
+            let untested = status
+                .remaining
+                .iter()
+                .filter(|candidate| {
+                    Some(&candidate.sha) != status.bad.as_ref()
+                        && !status.skipped.contains(&candidate.sha)
+                })
+                .count();
+            let steps = (untested + 1).next_power_of_two().trailing_zeros();
+            (
+                IconName::GitBranch,
+                Color::Accent,
+                format!(
+                    "Testing {}, {untested} commits left (about {steps} steps)",
+                    short_sha(current)
+                ),
+                subject_of(current),
+            )
+        } else {
+            (
+                IconName::GitBranch,
+                Color::Muted,
+                "Waiting for a good and a bad revision".to_string(),
+                SharedString::default(),
+            )
+        };
+
+        h_flex()
+            .px_3()
+            .py_2()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Icon::new(icon).size(IconSize::Small).color(color))
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .child(Label::new(title))
+                    .when(!subject.is_empty(), |this| {
+                        this.child(
+                            Label::new(subject)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    })
+                    .when_some(self.running_task.as_ref(), |this, label| {
+                        this.child(
+                            Label::new(format!("Testing commits with {label}…"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .when_some(self.run_error.as_ref(), |this, error| {
+                        this.child(
+                            Label::new(error.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Error),
+                        )
+                    }),
+            )
+    }
+
+    fn render_controls(&self, status: &BisectStatus, cx: &Context<Self>) -> impl IntoElement {
+        let finished = status.first_bad().is_some() || status.only_skipped_left();
+        let can_mark =
+            !self.pending && self.running_task.is_none() && !finished && status.current.is_some();
+        let mark_button = |id: &'static str, label: &'static str, mark: BisectMark| {
+            Button::new(id, label)
+                .disabled(!can_mark)
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.run_command(BisectCommand::Mark(mark), window, cx)
+                }))
+        };
+
+        h_flex()
+            .px_3()
+            .py_1p5()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(mark_button("bisect-good", "Good", BisectMark::Good))
+            .child(mark_button("bisect-bad", "Bad", BisectMark::Bad))
+            .child(mark_button("bisect-skip", "Skip", BisectMark::Skip))
+            .child(Divider::vertical())
+            .map(|this| {
+                if self.running_task.is_some() {
+                    this.child(
+                        Button::new("stop-bisect-run", "Stop")
+                            .on_click(cx.listener(|this, _, _, cx| this.stop_run(cx))),
+                    )
+                } else {
+                    this.child(self.render_task_menu(can_mark, cx))
+                }
+            })
+            .child(div().flex_1())
+            .child(
+                Button::new("reset-bisect", "Reset")
+                    .disabled(self.pending)
+                    .tooltip(Tooltip::text(
+                        "End the bisect and check out the original HEAD",
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.run_command(BisectCommand::Reset, window, cx)
+                    })),
+            )
+    }
+
+    fn render_task_menu(&self, enabled: bool, cx: &Context<Self>) -> impl IntoElement {
+        let view = cx.weak_entity();
+        let tasks = self.tasks.clone();
+        PopoverMenu::new("bisect-run-menu")
+            .trigger(
+                Button::new("bisect-run", "Run Task…")
+                    .disabled(!enabled)
+                    .tooltip(Tooltip::text(
+                        "Test each commit with a task: exit code 0 means good, 125 skips the \
+                         commit and any other code up to 127 means bad",
+                    )),
+            )
+            .menu(move |window, cx| {
+                let view = view.clone();
+                let tasks = tasks.clone();
+                Some(ContextMenu::build(window, cx, move |menu, _, _| {
+                    let menu = menu.header("Test Commits With");
+                    if tasks.is_empty() {
+                        return menu.label("No tasks defined");
+                    }
+                    tasks.into_iter().fold(menu, |menu, template| {
+                        let view = view.clone();
+                        let label = template.label.clone();
+                        menu.entry(label, None, move |window, cx| {
+                            view.update(cx, |view, cx| {
+                                view.run_with_task(template.clone(), window, cx)
+                            })
+                            .ok();
+                        })
+                    })
+                }))
+            })
+    }
+
+    fn render_candidate(
+        &self,
+        candidate: &BisectCandidate,
+        status: &BisectStatus,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let (marker, color) = if Some(&candidate.sha) == status.bad.as_ref() {
+            ("bad", Color::Error)
+        } else if status.skipped.contains(&candidate.sha) {
+            ("skipped", Color::Warning)
+        } else if Some(&candidate.sha) == status.current.as_ref() {
+            ("testing", Color::Accent)
+        } else {
+            ("", Color::Muted)
+        };
+
+        h_flex()
+            .px_3()
+            .py_0p5()
+            .gap_2()
+            .w_full()
+            .when(Some(&candidate.sha) == status.current.as_ref(), |this| {
+                this.bg(cx.theme().colors().element_selected)
+            })
+            .child(
+                div().w(rems(4.)).child(
+                    Label::new(marker)
+                        .size(LabelSize::Small)
+                        .color(color)
+                        .buffer_font(cx),
+                ),
+            )
+            .child(
+                Label::new(short_sha(&candidate.sha))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .child(Label::new(candidate.subject.clone()).truncate())
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..git::SHORT_SHA_LENGTH).unwrap_or(sha)
+}
+
+impl EventEmitter<ItemEvent> for BisectView {}
+
+impl Focusable for BisectView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for BisectView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = match self.status.clone() {
+            None => self.render_start_form(cx).into_any_element(),
+            Some(status) => {
+                let candidate_count = status.remaining.len();
+                let view = cx.weak_entity();
+                v_flex()
+                    .size_full()
+                    .child(self.render_summary(&status, cx))
+                    .child(self.render_controls(&status, cx))
+                    .child(
+                        v_flex()
+                            .flex_1()
+                            .size_full()
+                            .child(
+                                uniform_list(
+                                    "bisect-candidates",
+                                    candidate_count,
+                                    move |range, _window, cx| {
+                                        let Some(view) = view.upgrade() else {
+                                            return Vec::new();
+                                        };
+                                        view.update(cx, |this, cx| {
+                                            range
+                                                .filter_map(|ix| {
+                                                    let candidate = status.remaining.get(ix)?;
+                                                    Some(
+                                                        this.render_candidate(
+                                                            candidate, &status, cx,
+                                                        )
+                                                        .into_any_element(),
+                                                    )
+                                                })
+                                                .collect()
+                                        })
+                                    },
+                                )
+                                .flex_1()
+                                .size_full()
+                                .py_1()
+                                .track_scroll(&self.scroll_handle),
+                            )
+                            .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+                    )
+                    .into_any_element()
+            }
+        };
+
+        v_flex()
+            .key_context("Bisect")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(content)
+    }
+}
+
+impl Item for BisectView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Bisect".into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let repository = self.repository.upgrade()?;
+        let path = repository.read(cx).work_directory_abs_path.clone();
+        Some(format!("Git bisect for {}", path.display()).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("git bisect")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        Task::ready(None)
+    }
+
+    fn navigate(&mut self, _: Box<dyn Any>, _window: &mut Window, _: &mut Context<Self>) -> bool {
+        false
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        false
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn save_as(
+        &mut self,
+        _project: Entity<Project>,
+        _path: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        BisectView::reload(self, cx);
+        Task::ready(Ok(()))
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn has_conflict(&self, _: &App) -> bool {
+        false
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.status.is_none() {
+            window.focus(&self.good_editor.focus_handle(cx));
+        }
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[test]
+    fn test_mark_for_exit_code() {
+        assert_eq!(mark_for_exit_code(Some(0)), Some(BisectMark::Good));
+        assert_eq!(mark_for_exit_code(Some(1)), Some(BisectMark::Bad));
+        assert_eq!(mark_for_exit_code(Some(125)), Some(BisectMark::Skip));
+        assert_eq!(mark_for_exit_code(Some(127)), Some(BisectMark::Bad));
+        assert_eq!(mark_for_exit_code(Some(128)), None);
+        assert_eq!(mark_for_exit_code(None), None);
+    }
+
+    #[gpui::test]
+    async fn test_bisect_view(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "text" }))
+            .await;
+        let dot_git = Path::new(path!("/dir/.git"));
+        fs.with_git_state(dot_git, true, |state| {
+            state.bisect_commits = (0..8)
+                .rev()
+                .map(|ix| BisectCandidate {
+                    sha: format!("{ix}{ix}{ix}{ix}{ix}{ix}{ix}{ix}").into(),
+                    subject: format!("Commit {ix}").into(),
+                })
+                .collect();
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            open(
+                workspace,
+                &git::Bisect {
+                    bad: Some("77777777".into()),
+                    good: vec!["00000000".into()],
+                },
+                window,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        let view = workspace.update(cx, |workspace, cx| {
+            workspace.active_item_as::<BisectView>(cx).unwrap()
+        });
+
+        // Commit 5 introduced the bug.
+        loop {
+            let current = view.read_with(cx, |view, _| {
+                let status = view.status.as_ref().unwrap();
+                if status.first_bad().is_some() {
+                    return None;
+                }
+                status.current.clone()
+            });
+            let Some(current) = current else {
+                break;
+            };
+            let mark = if current.as_ref() < "55555555" {
+                BisectMark::Good
+            } else {
+                BisectMark::Bad
+            };
+            view.update_in(cx, |view, window, cx| {
+                view.run_command(BisectCommand::Mark(mark), window, cx)
+            });
+            cx.run_until_parked();
+        }
+        view.read_with(cx, |view, _| {
+            let first_bad = view.status.as_ref().and_then(|status| status.first_bad());
+            assert_eq!(first_bad.map(|sha| sha.as_ref()), Some("55555555"));
+        });
+
+        view.update_in(cx, |view, window, cx| {
+            view.run_command(BisectCommand::Reset, window, cx)
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| assert_eq!(view.status, None));
+        fs.with_git_state(dot_git, false, |state| {
+            assert_eq!(state.bisect_status, None);
+        })
+        .unwrap();
+    }
+}
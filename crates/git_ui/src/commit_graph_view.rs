@@ -0,0 +1,1060 @@
+use std::any::{Any, TypeId};
+use std::time::Duration;
+
+use anyhow::Result;
+use editor::{Editor, EditorEvent};
+use git::repository::{CommitLogEntry, CommitLogSearch};
+use gpui::{
+    AnyElement, AnyEntity, App, Context, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
+    IntoElement, PathBuilder, Pixels, Render, Task, UniformListScrollHandle, WeakEntity, Window,
+    canvas, fill, point, px, size, uniform_list,
+};
+use project::{
+    Project, ProjectPath,
+    git_store::{Repository, RepositoryEvent},
+};
+use time::OffsetDateTime;
+use ui::{Chip, ContextMenu, Divider, Tooltip, WithScrollbar, prelude::*, right_click_menu};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+};
+
+use crate::commit_view::{self, CommitOperation, CommitView};
+use crate::git_panel::GitPanel;
+
+const PAGE_SIZE: usize = 200;
+/// How close to the end of the loaded commits the list may scroll before the next page is loaded.
+const LOAD_MORE_THRESHOLD: usize = 50;
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+const ROW_HEIGHT: Pixels = px(28.);
+const LANE_WIDTH: Pixels = px(14.);
+const MAX_VISIBLE_LANES: usize = 24;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &git::CommitGraph,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = workspace
+        .panel::<GitPanel>(cx)
+        .and_then(|panel| panel.read(cx).active_repository.clone())
+        .or_else(|| workspace.project().read(cx).active_repository(cx))
+    else {
+        return;
+    };
+
+    let existing = workspace.active_pane().read(cx).items().position(|item| {
+        item.downcast::<CommitGraphView>()
+            .is_some_and(|view| view.read(cx).repository == repository.downgrade())
+    });
+    let pane = workspace.active_pane().clone();
+    if let Some(ix) = existing {
+        pane.update(cx, |pane, cx| {
+            pane.activate_item(ix, true, true, window, cx)
+        });
+        return;
+    }
+
+    let view = cx.new(|cx| CommitGraphView::new(repository, workspace.weak_handle(), window, cx));
+    pane.update(cx, |pane, cx| {
+        pane.add_item(Box::new(view), true, true, None, window, cx);
+    });
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SearchField {
+    Author,
+    Message,
+}
+
+pub struct CommitGraphView {
+    repository: WeakEntity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    entries: Vec<CommitLogEntry>,
+    layout: GraphLayout,
+    search_editor: Entity<Editor>,
+    search_field: SearchField,
+    /// The search the loaded entries were filtered with.
+    search: Option<CommitLogSearch>,
+    selected_entry: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    loading: bool,
+    has_more: bool,
+    load_task: Option<Task<()>>,
+    search_task: Option<Task<()>>,
+    _subscriptions: Vec<gpui::Subscription>,
+}
+
+impl CommitGraphView {
+    fn new(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let search_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Search commits…", window, cx);
+            editor
+        });
+        let subscriptions = vec![
+            cx.subscribe_in(
+                &search_editor,
+                window,
+                |this, _, event: &EditorEvent, window, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.schedule_search(window, cx);
+                    }
+                },
+            ),
+            cx.subscribe(&repository, |this, _, event: &RepositoryEvent, cx| {
+                if let RepositoryEvent::BranchChanged = event {
+                    this.reload(cx);
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            repository: repository.downgrade(),
+            workspace,
+            entries: Vec::new(),
+            layout: GraphLayout::default(),
+            search_editor,
+            search_field: SearchField::Message,
+            search: None,
+            selected_entry: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            loading: false,
+            has_more: true,
+            load_task: None,
+            search_task: None,
+            _subscriptions: subscriptions,
+        };
+        this.load_more(cx);
+        this
+    }
+
+    fn schedule_search(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.search_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(SEARCH_DEBOUNCE).await;
+            this.update(cx, |this, cx| this.apply_search(cx)).ok();
+        }));
+    }
+
+    fn set_search_field(&mut self, field: SearchField, cx: &mut Context<Self>) {
+        if self.search_field != field {
+            self.search_field = field;
+            self.apply_search(cx);
+        }
+    }
+
+    fn apply_search(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx).trim().to_string();
+        let search = (!query.is_empty()).then(|| match self.search_field {
+            SearchField::Author => CommitLogSearch::Author(query),
+            SearchField::Message => CommitLogSearch::Message(query),
+        });
+        if search != self.search {
+            self.search = search;
+            self.reload(cx);
+        }
+    }
+
+    /// Drops the loaded commits and loads the first page again.
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.entries.clear();
+        self.layout = GraphLayout::default();
+        self.selected_entry = None;
+        self.has_more = true;
+        self.loading = false;
+        self.load_task = None;
+        self.load_more(cx);
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if self.loading || !self.has_more {
+            return;
+        }
+        let Some(repository) = self.repository.upgrade() else {
+            return;
+        };
+
+        self.loading = true;
+        cx.notify();
+
+        let skip = self.entries.len();
+        let search = self.search.clone();
+        let commits = repository.update(cx, |repository, _| {
+            repository.commit_log_paginated(search, skip, Some(PAGE_SIZE))
+        });
+        self.load_task = Some(cx.spawn(async move |this, cx| {
+            let commits = commits
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|commits| commits);
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match commits {
+                    Ok(commits) => {
+                        this.has_more = commits.len() >= PAGE_SIZE;
+                        for commit in &commits {
+                            this.layout.push(&commit.sha, &commit.parents);
+                        }
+                        this.entries.extend(commits);
+                    }
+                    Err(error) => {
+                        log::error!("failed to load commit log: {error:#}");
+                        this.has_more = false;
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn open_commit(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        self.selected_entry = Some(ix);
+        cx.notify();
+        CommitView::open(
+            entry.sha.to_string(),
+            self.repository.clone(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+    }
+
+    fn apply_commit(
+        &mut self,
+        operation: CommitOperation,
+        sha: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(repository), Some(workspace)) =
+            (self.repository.upgrade(), self.workspace.upgrade())
+        else {
+            return;
+        };
+        workspace.update(cx, |workspace, cx| {
+            commit_view::apply_commit(operation, sha, repository, workspace, window, cx);
+        });
+    }
+
+    fn graph_width(&self) -> Pixels {
+        LANE_WIDTH * self.layout.max_lane_count().clamp(1, MAX_VISIBLE_LANES) as f32
+    }
+
+    fn render_graph(&self, ix: usize, cx: &App) -> AnyElement {
+        let Some(row) = self.layout.rows.get(ix).cloned() else {
+            return div().into_any_element();
+        };
+        let accents = cx.theme().accents().clone();
+        let background = cx.theme().colors().editor_background;
+        canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| {
+                let lane_x = |lane: usize| bounds.origin.x + LANE_WIDTH * (lane as f32 + 0.5);
+                let top = bounds.origin.y;
+                let middle = bounds.origin.y + bounds.size.height / 2.;
+                let bottom = bounds.origin.y + bounds.size.height;
+                let color =
+                    |line: &GraphLine| -> Hsla { accents.color_for_index(line.color as u32) };
+
+                for (lines, start_y, end_y) in
+                    [(&row.upper, top, middle), (&row.lower, middle, bottom)]
+                {
+                    for line in lines {
+                        let start = point(lane_x(line.start), start_y);
+                        let end = point(lane_x(line.end), end_y);
+                        let mut builder = PathBuilder::stroke(px(1.5));
+                        builder.move_to(start);
+                        if line.start == line.end {
+                            builder.line_to(end);
+                        } else {
+                            let control_y = (start_y + end_y) / 2.;
+                            builder.cubic_bezier_to(
+                                end,
+                                point(start.x, control_y),
+                                point(end.x, control_y),
+                            );
+                        }
+                        if let Ok(path) = builder.build() {
+                            window.paint_path(path, color(line));
+                        }
+                    }
+                }
+
+                let radius = px(4.);
+                let center = point(lane_x(row.lane), middle);
+                let node_color = accents.color_for_index(row.color as u32);
+                let node_bounds = gpui::Bounds::new(
+                    point(center.x - radius, center.y - radius),
+                    size(radius * 2., radius * 2.),
+                );
+                window.paint_quad(fill(node_bounds, node_color).corner_radii(radius));
+                if row.is_merge {
+                    let inner = radius / 2.;
+                    let inner_bounds = gpui::Bounds::new(
+                        point(center.x - inner, center.y - inner),
+                        size(inner * 2., inner * 2.),
+                    );
+                    window.paint_quad(fill(inner_bounds, background).corner_radii(inner));
+                }
+            },
+        )
+        .flex_none()
+        .h_full()
+        .w(self.graph_width())
+        .into_any_element()
+    }
+
+    fn render_refs(&self, entry: &CommitLogEntry) -> impl IntoElement {
+        let head_branch = entry
+            .refs
+            .iter()
+            .position(|name| name.as_ref() == "HEAD")
+            .and_then(|ix| entry.refs.get(ix + 1))
+            .filter(|name| name.starts_with("refs/heads/"));
+        h_flex()
+            .flex_none()
+            .gap_1()
+            .children(entry.refs.iter().filter_map(|name| {
+                let chip = if let Some(branch) = name.strip_prefix("refs/heads/") {
+                    let color = if head_branch == Some(name) {
+                        Color::Accent
+                    } else {
+                        Color::Default
+                    };
+                    Chip::new(branch.to_string()).label_color(color)
+                } else if let Some(remote) = name.strip_prefix("refs/remotes/") {
+                    if remote.ends_with("/HEAD") {
+                        return None;
+                    }
+                    Chip::new(remote.to_string()).label_color(Color::Muted)
+                } else if let Some(tag) = name.strip_prefix("refs/tags/") {
+                    Chip::new(tag.to_string()).label_color(Color::Warning)
+                } else if name.as_ref() == "HEAD" && head_branch.is_none() {
+                    Chip::new("HEAD").label_color(Color::Accent)
+                } else {
+                    return None;
+                };
+                Some(chip)
+            }))
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: &CommitLogEntry,
+        show_graph: bool,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let commit_time = OffsetDateTime::from_unix_timestamp(entry.commit_timestamp)
+            .unwrap_or_else(|_| OffsetDateTime::UNIX_EPOCH);
+        let relative_timestamp = time_format::format_localized_timestamp(
+            commit_time,
+            OffsetDateTime::now_utc(),
+            time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+            time_format::TimestampFormat::Relative,
+        );
+        let short_sha = entry.sha.get(..7).unwrap_or(entry.sha.as_ref()).to_string();
+        let selected = self.selected_entry == Some(ix);
+        let colors = cx.theme().colors();
+
+        let row = h_flex()
+            .id(("commit", ix))
+            .h(ROW_HEIGHT)
+            .w_full()
+            .pr_2p5()
+            .gap_2()
+            .cursor_pointer()
+            .when(selected, |this| this.bg(colors.element_selected))
+            .hover(|this| this.bg(colors.element_hover))
+            .when(show_graph, |this| {
+                this.pl_1().child(self.render_graph(ix, cx))
+            })
+            .when(!show_graph, |this| this.pl_2p5())
+            .child(
+                h_flex()
+                    .min_w_0()
+                    .w_full()
+                    .gap_1p5()
+                    .child(self.render_refs(entry))
+                    .child(
+                        Label::new(entry.subject.clone())
+                            .size(LabelSize::Small)
+                            .truncate(),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .flex_none()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id(("author", ix))
+                            .child(
+                                Label::new(entry.author_name.clone())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .tooltip(Tooltip::text(entry.author_email.clone())),
+                    )
+                    .child(
+                        Label::new(relative_timestamp)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(short_sha)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    ),
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.open_commit(ix, window, cx);
+            }));
+
+        let view = cx.entity();
+        let sha = entry.sha.clone();
+        right_click_menu(("commit-graph-context-menu", ix))
+            .trigger(move |_, _, _| row)
+            .menu(move |window, cx| {
+                let view = view.clone();
+                let sha = sha.clone();
+                ContextMenu::build(window, cx, move |menu, window, _| {
+                    menu.entry(
+                        "View Commit",
+                        None,
+                        window.handler_for(&view, move |this, window, cx| {
+                            this.open_commit(ix, window, cx)
+                        }),
+                    )
+                    .separator()
+                    .entry(
+                        "Cherry-pick onto Current Branch",
+                        None,
+                        window.handler_for(&view, {
+                            let sha = sha.clone();
+                            move |this, window, cx| {
+                                this.apply_commit(
+                                    CommitOperation::CherryPick,
+                                    sha.clone(),
+                                    window,
+                                    cx,
+                                )
+                            }
+                        }),
+                    )
+                    .entry(
+                        "Revert Commit",
+                        None,
+                        window.handler_for(&view, move |this, window, cx| {
+                            this.apply_commit(CommitOperation::Revert, sha.clone(), window, cx)
+                        }),
+                    )
+                })
+            })
+            .into_any_element()
+    }
+
+    fn render_search_field_toggle(
+        &self,
+        field: SearchField,
+        label: &'static str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        Button::new(label, label)
+            .label_size(LabelSize::Small)
+            .toggle_state(self.search_field == field)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.set_search_field(field, cx);
+            }))
+    }
+}
+
+impl EventEmitter<ItemEvent> for CommitGraphView {}
+
+impl Focusable for CommitGraphView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for CommitGraphView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+        // Searching skips commits, so the lanes wouldn't connect.
+        let show_graph = self.search.is_none();
+        let count_label = if self.has_more {
+            format!("{entry_count}+ commits")
+        } else {
+            format!("{entry_count} commits")
+        };
+
+        v_flex()
+            .key_context("CommitGraph")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .h(rems_from_px(41.))
+                    .pl_3()
+                    .pr_2()
+                    .gap_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        h_flex()
+                            .flex_1()
+                            .gap_2()
+                            .child(
+                                Icon::new(IconName::MagnifyingGlass)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(div().flex_1().child(self.search_editor.clone())),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_1p5()
+                            .child(self.render_search_field_toggle(
+                                SearchField::Message,
+                                "Message",
+                                cx,
+                            ))
+                            .child(self.render_search_field_toggle(
+                                SearchField::Author,
+                                "Author",
+                                cx,
+                            ))
+                            .child(Divider::vertical())
+                            .child(
+                                Label::new(count_label)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .flex_1()
+                    .size_full()
+                    .child({
+                        let view = cx.weak_entity();
+                        uniform_list(
+                            "commit-graph-list",
+                            entry_count,
+                            move |range, window, cx| {
+                                let Some(view) = view.upgrade() else {
+                                    return Vec::new();
+                                };
+                                view.update(cx, |this, cx| {
+                                    if range.end + LOAD_MORE_THRESHOLD >= this.entries.len()
+                                        && this.has_more
+                                        && !this.loading
+                                    {
+                                        cx.defer_in(window, |this, _, cx| this.load_more(cx));
+                                    }
+                                    range
+                                        .filter_map(|ix| {
+                                            let entry = this.entries.get(ix)?;
+                                            Some(this.render_entry(ix, entry, show_graph, cx))
+                                        })
+                                        .collect()
+                                })
+                            },
+                        )
+                        .flex_1()
+                        .size_full()
+                        .track_scroll(&self.scroll_handle)
+                    })
+                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+            )
+    }
+}
+
+impl Item for CommitGraphView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Commit Graph".into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let repository = self.repository.upgrade()?;
+        let path = repository.read(cx).work_directory_abs_path.clone();
+        Some(format!("Git commit graph for {}", path.display()).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("commit graph")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        Task::ready(None)
+    }
+
+    fn navigate(&mut self, _: Box<dyn Any>, _window: &mut Window, _: &mut Context<Self>) -> bool {
+        false
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        false
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn save_as(
+        &mut self,
+        _project: Entity<Project>,
+        _path: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        CommitGraphView::reload(self, cx);
+        Task::ready(Ok(()))
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn has_conflict(&self, _: &App) -> bool {
+        false
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.search_editor.focus_handle(cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
+
+/// A line through one half of a graph row, from a lane at the top of the half to a lane at
+/// its bottom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct GraphLine {
+    start: usize,
+    end: usize,
+    color: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct GraphRow {
+    /// The lane the commit itself is drawn in.
+    lane: usize,
+    color: usize,
+    is_merge: bool,
+    /// Lines from the top edge of the row to its middle.
+    upper: Vec<GraphLine>,
+    /// Lines from the middle of the row to its bottom edge.
+    lower: Vec<GraphLine>,
+}
+
+impl GraphRow {
+    fn lane_count(&self) -> usize {
+        self.upper
+            .iter()
+            .chain(&self.lower)
+            .map(|line| line.start.max(line.end) + 1)
+            .max()
+            .unwrap_or(0)
+            .max(self.lane + 1)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Lane {
+    /// The commit the lane leads to.
+    sha: SharedString,
+    color: usize,
+}
+
+/// Assigns commits to lanes as they arrive in topological order, so the graph can be
+/// extended page by page.
+#[derive(Default)]
+struct GraphLayout {
+    lanes: Vec<Option<Lane>>,
+    rows: Vec<GraphRow>,
+    next_color: usize,
+    max_lane_count: usize,
+}
+
+impl GraphLayout {
+    fn max_lane_count(&self) -> usize {
+        self.max_lane_count
+    }
+
+    fn allocate_color(&mut self) -> usize {
+        util::post_inc(&mut self.next_color)
+    }
+
+    fn free_lane(&self, excluding: Option<usize>) -> usize {
+        (0..)
+            .find(|ix| Some(*ix) != excluding && self.lanes.get(*ix).is_none_or(Option::is_none))
+            .unwrap_or_default()
+    }
+
+    fn set_lane(&mut self, ix: usize, lane: Option<Lane>) {
+        if ix >= self.lanes.len() {
+            self.lanes.resize(ix + 1, None);
+        }
+        self.lanes[ix] = lane;
+    }
+
+    fn push(&mut self, sha: &SharedString, parents: &[SharedString]) {
+        let incoming = self.lanes.clone();
+        let existing = incoming
+            .iter()
+            .position(|lane| lane.as_ref().is_some_and(|lane| &lane.sha == sha));
+        let (lane, color) = match existing {
+            Some(ix) => (ix, incoming[ix].as_ref().map_or(0, |lane| lane.color)),
+            None => (self.free_lane(None), self.allocate_color()),
+        };
+
+        let mut upper = Vec::new();
+        for (ix, incoming_lane) in incoming.iter().enumerate() {
+            let Some(incoming_lane) = incoming_lane else {
+                continue;
+            };
+            if &incoming_lane.sha == sha {
+                upper.push(GraphLine {
+                    start: ix,
+                    end: lane,
+                    color: incoming_lane.color,
+                });
+                self.lanes[ix] = None;
+            } else {
+                upper.push(GraphLine {
+                    start: ix,
+                    end: ix,
+                    color: incoming_lane.color,
+                });
+            }
+        }
+
+        let passing = self.lanes.clone();
+        // A lane further right that leads to the first parent is pulled into this one.
+        let mut joined_lane = None;
+        let mut lower = Vec::new();
+        for (ix, parent) in parents.iter().enumerate() {
+            let target = self
+                .lanes
+                .iter()
+                .position(|lane| lane.as_ref().is_some_and(|lane| &lane.sha == parent));
+            match target {
+                Some(target) if ix == 0 && target > lane => {
+                    self.lanes[target] = None;
+                    self.set_lane(
+                        lane,
+                        Some(Lane {
+                            sha: parent.clone(),
+                            color,
+                        }),
+                    );
+                    joined_lane = Some(target);
+                    lower.push(GraphLine {
+                        start: lane,
+                        end: lane,
+                        color,
+                    });
+                }
+                Some(target) => {
+                    let target_color = self.lanes[target].as_ref().map_or(color, |l| l.color);
+                    lower.push(GraphLine {
+                        start: lane,
+                        end: target,
+                        color: if ix == 0 { color } else { target_color },
+                    });
+                }
+                None => {
+                    let (target, parent_color) = if ix == 0 {
+                        (lane, color)
+                    } else {
+                        (self.free_lane(Some(lane)), self.allocate_color())
+                    };
+                    self.set_lane(
+                        target,
+                        Some(Lane {
+                            sha: parent.clone(),
+                            color: parent_color,
+                        }),
+                    );
+                    lower.push(GraphLine {
+                        start: lane,
+                        end: target,
+                        color: parent_color,
+                    });
+                }
+            }
+        }
+        for (ix, passing_lane) in passing.iter().enumerate() {
+            if let Some(passing_lane) = passing_lane {
+                lower.push(GraphLine {
+                    start: ix,
+                    end: if joined_lane == Some(ix) { lane } else { ix },
+                    color: passing_lane.color,
+                });
+            }
+        }
+
+        while self.lanes.last().is_some_and(Option::is_none) {
+            self.lanes.pop();
+        }
+
+        let row = GraphRow {
+            lane,
+            color,
+            is_merge: parents.len() > 1,
+            upper,
+            lower,
+        };
+        self.max_lane_count = self.max_lane_count.max(row.lane_count());
+        self.rows.push(row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    use super::*;
+
+    fn layout(commits: &[(&str, &[&str])]) -> GraphLayout {
+        let mut layout = GraphLayout::default();
+        for (sha, parents) in commits {
+            let parents = parents
+                .iter()
+                .map(|parent| SharedString::from(parent.to_string()))
+                .collect::<Vec<_>>();
+            layout.push(&SharedString::from(sha.to_string()), &parents);
+        }
+        layout
+    }
+
+    fn lines(row: &GraphRow) -> (Vec<(usize, usize)>, Vec<(usize, usize)>) {
+        let ends = |lines: &[GraphLine]| {
+            let mut ends = lines
+                .iter()
+                .map(|line| (line.start, line.end))
+                .collect::<Vec<_>>();
+            ends.sort();
+            ends
+        };
+        (ends(&row.upper), ends(&row.lower))
+    }
+
+    #[test]
+    fn test_linear_history_uses_one_lane() {
+        let layout = layout(&[("c", &["b"]), ("b", &["a"]), ("a", &[])]);
+        assert_eq!(layout.max_lane_count(), 1);
+        assert!(layout.rows.iter().all(|row| row.lane == 0));
+        assert_eq!(lines(&layout.rows[0]), (vec![], vec![(0, 0)]));
+        assert_eq!(lines(&layout.rows[1]), (vec![(0, 0)], vec![(0, 0)]));
+        assert_eq!(lines(&layout.rows[2]), (vec![(0, 0)], vec![]));
+        assert!(layout.lanes.is_empty());
+    }
+
+    #[test]
+    fn test_merge_opens_and_closes_a_lane() {
+        // m merges f into b; f and b both descend from a.
+        let layout = layout(&[("m", &["b", "f"]), ("f", &["a"]), ("b", &["a"]), ("a", &[])]);
+        let rows = &layout.rows;
+        assert_eq!(layout.max_lane_count(), 2);
+        assert!(rows[0].is_merge);
+        assert_eq!(lines(&rows[0]), (vec![], vec![(0, 0), (0, 1)]));
+
+        assert_eq!(rows[1].lane, 1);
+        assert_eq!(
+            lines(&rows[1]),
+            (vec![(0, 0), (1, 1)], vec![(0, 0), (1, 1)])
+        );
+
+        // Both lanes lead to a, so f's lane joins b's.
+        assert_eq!(rows[2].lane, 0);
+        assert_eq!(
+            lines(&rows[2]),
+            (vec![(0, 0), (1, 1)], vec![(0, 0), (1, 0)])
+        );
+        assert!(rows[2].lower.contains(&GraphLine {
+            start: 1,
+            end: 0,
+            color: rows[1].color,
+        }));
+
+        assert_eq!(rows[3].lane, 0);
+        assert_eq!(lines(&rows[3]), (vec![(0, 0)], vec![]));
+        assert!(layout.lanes.is_empty());
+    }
+
+    #[test]
+    fn test_parent_already_in_a_lane() {
+        // Two branch tips, where the second tip's parent is the first tip.
+        let layout = layout(&[("x", &["y"]), ("z", &["y"]), ("y", &[])]);
+        let rows = &layout.rows;
+        assert_eq!(rows[1].lane, 1);
+        assert_eq!(lines(&rows[1]), (vec![(0, 0)], vec![(0, 0), (1, 0)]));
+        assert_eq!(rows[2].lane, 0);
+        assert_eq!(lines(&rows[2]), (vec![(0, 0)], vec![]));
+    }
+
+    #[test]
+    fn test_layout_can_be_extended_page_by_page() {
+        let commits: &[(&str, &[&str])] =
+            &[("m", &["b", "f"]), ("f", &["a"]), ("b", &["a"]), ("a", &[])];
+        let whole = layout(commits);
+        let mut paged = layout(&commits[..2]);
+        for (sha, parents) in &commits[2..] {
+            let parents = parents
+                .iter()
+                .map(|parent| SharedString::from(parent.to_string()))
+                .collect::<Vec<_>>();
+            paged.push(&SharedString::from(sha.to_string()), &parents);
+        }
+        assert_eq!(whole.rows, paged.rows);
+    }
+
+    #[gpui::test]
+    async fn test_loading_pages_and_searching(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+
+        let commit_count = PAGE_SIZE + 10;
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "content" }))
+            .await;
+        fs.with_git_state(Path::new(path!("/dir/.git")), true, |state| {
+            state.commit_log = (0..commit_count)
+                .rev()
+                .map(|ix| CommitLogEntry {
+                    sha: format!("{ix:04}").into(),
+                    parents: (ix > 0)
+                        .then(|| format!("{:04}", ix - 1).into())
+                        .into_iter()
+                        .collect(),
+                    subject: if ix % 50 == 0 { "Fix" } else { "Add" }.into(),
+                    commit_timestamp: ix as i64,
+                    author_name: "Alice".into(),
+                    author_email: "alice@example.com".into(),
+                    refs: Vec::new(),
+                })
+                .collect();
+        })
+        .unwrap();
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+        let repository = project
+            .read_with(cx, |project, cx| project.active_repository(cx))
+            .unwrap();
+        let view = workspace.update_in(cx, |workspace, window, cx| {
+            let workspace_handle = workspace.weak_handle();
+            cx.new(|cx| CommitGraphView::new(repository, workspace_handle, window, cx))
+        });
+        cx.run_until_parked();
+
+        view.update(cx, |view, cx| {
+            assert_eq!(view.entries.len(), PAGE_SIZE);
+            assert!(view.has_more);
+            view.load_more(cx);
+        });
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            assert_eq!(view.entries.len(), commit_count);
+            assert_eq!(view.layout.rows.len(), commit_count);
+            assert!(!view.has_more);
+        });
+
+        let search_editor = view.read_with(cx, |view, _| view.search_editor.clone());
+        search_editor.update_in(cx, |editor, window, cx| editor.set_text("fix", window, cx));
+        cx.executor().advance_clock(SEARCH_DEBOUNCE);
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| {
+            let shas = view
+                .entries
+                .iter()
+                .map(|entry| entry.sha.as_ref())
+                .collect::<Vec<_>>();
+            assert_eq!(shas, ["0200", "0150", "0100", "0050", "0000"]);
+            assert_eq!(view.layout.rows.len(), shas.len());
+            assert!(!view.has_more);
+        });
+    }
+}
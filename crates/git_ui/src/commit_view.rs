@@ -4,7 +4,7 @@ use editor::display_map::{BlockPlacement, BlockProperties, BlockStyle};
 use editor::{
     Editor, EditorEvent, ExcerptId, ExcerptRange, MultiBuffer, multibuffer_context_lines,
 };
-use git::repository::{CommitDetails, CommitDiff, RepoPath};
+use git::repository::{CommitDetails, CommitDiff, RepoPath, Tag};
 use git::{GitHostingProviderRegistry, GitRemote, parse_git_remote_url};
 use gpui::{
     AnyElement, App, AppContext as _, Asset, AsyncApp, AsyncWindowContext, Context, Element,
@@ -16,6 +16,7 @@ use language::{
     ReplicaId, Rope, TextBuffer,
 };
 use multi_buffer::PathKey;
+use notifications::status_toast::{StatusToast, ToastIcon};
 use project::{Project, WorktreeId, git_store::Repository};
 use std::{
     any::{Any, TypeId},
@@ -23,7 +24,7 @@ use std::{
     sync::Arc,
 };
 use theme::ActiveTheme;
-use ui::{Avatar, DiffStat, Tooltip, prelude::*};
+use ui::{Avatar, Chip, DiffStat, Tooltip, prelude::*};
 use util::{ResultExt, paths::PathStyle, rel_path::RelPath, truncate_and_trailoff};
 use workspace::item::TabTooltipContent;
 use workspace::{
@@ -36,9 +37,23 @@ use workspace::{
 };
 
 use crate::git_panel::GitPanel;
+use crate::project_diff::ProjectDiff;
+use crate::tag_picker;
 
 actions!(git, [ApplyCurrentStash, PopCurrentStash, DropCurrentStash,]);
 
+actions!(
+    git,
+    [
+        /// Applies the changes of the commit being viewed onto the current branch.
+        CherryPickCurrentCommit,
+        /// Creates a commit that undoes the changes of the commit being viewed.
+        RevertCurrentCommit,
+        /// Opens the tag picker to tag the commit being viewed.
+        TagCurrentCommit,
+    ]
+);
+
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
         workspace.register_action(|workspace, _: &ApplyCurrentStash, window, cx| {
@@ -50,6 +65,15 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &PopCurrentStash, window, cx| {
             CommitView::pop_stash(workspace, window, cx);
         });
+        workspace.register_action(|workspace, _: &CherryPickCurrentCommit, window, cx| {
+            CommitView::apply_current_commit(CommitOperation::CherryPick, workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &RevertCurrentCommit, window, cx| {
+            CommitView::apply_current_commit(CommitOperation::Revert, workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &TagCurrentCommit, window, cx| {
+            CommitView::tag_current_commit(workspace, window, cx);
+        });
     })
     .detach();
 }
@@ -61,6 +85,8 @@ pub struct CommitView {
     multibuffer: Entity<MultiBuffer>,
     repository: Entity<Repository>,
     remote: Option<GitRemote>,
+    /// The tags pointing at the commit.
+    tags: Vec<Tag>,
 }
 
 struct GitBlob {
@@ -299,14 +325,36 @@ impl CommitView {
             })
         });
 
-        Self {
+        let mut this = Self {
             commit,
             editor,
             multibuffer,
             stash,
             repository,
             remote,
+            tags: Vec::new(),
+        };
+        this.reload_tags(cx);
+        this
+    }
+
+    pub(crate) fn reload_tags(&mut self, cx: &mut Context<Self>) {
+        if self.stash.is_some() {
+            return;
         }
+        let tags = self
+            .repository
+            .update(cx, |repository, _| repository.tags());
+        let sha = self.commit.sha.clone();
+        cx.spawn(async move |this, cx| {
+            let mut tags = tags.await??;
+            tags.retain(|tag| tag.sha == sha);
+            this.update(cx, |this, cx| {
+                this.tags = tags;
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
     }
 
     fn render_commit_avatar(
@@ -462,16 +510,86 @@ impl CommitView {
                                             .size(LabelSize::Small),
                                     )
                                     .children(commit_diff_stat),
-                            ),
+                            )
+                            .when(!self.tags.is_empty(), |this| {
+                                this.child(h_flex().pt_1().gap_1().flex_wrap().children(
+                                    self.tags.iter().map(|tag| {
+                                        div()
+                                            .id(ElementId::Name(tag.name.clone()))
+                                            .child(
+                                                Chip::new(tag.name.clone())
+                                                    .label_color(Color::Muted),
+                                            )
+                                            .when_some(tag.message.clone(), |this, message| {
+                                                this.tooltip(Tooltip::text(message))
+                                            })
+                                    }),
+                                ))
+                            }),
                     )
-                    .children(github_url.map(|url| {
-                        Button::new("view_on_github", "View on GitHub")
-                            .icon(IconName::Github)
-                            .icon_color(Color::Muted)
-                            .icon_size(IconSize::Small)
-                            .icon_position(IconPosition::Start)
-                            .on_click(move |_, _, cx| cx.open_url(&url))
-                    })),
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .when(self.stash.is_none(), |this| {
+                                this.child(
+                                    Button::new("tag_commit", "Tag")
+                                        .icon(IconName::Hash)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Tag Commit",
+                                            &TagCurrentCommit,
+                                        ))
+                                        .on_click(|_, window, cx| {
+                                            window
+                                                .dispatch_action(TagCurrentCommit.boxed_clone(), cx)
+                                        }),
+                                )
+                                .child(
+                                    Button::new("cherry_pick", "Cherry-pick")
+                                        .icon(IconName::GitBranch)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Cherry-pick onto Current Branch",
+                                            &CherryPickCurrentCommit,
+                                        ))
+                                        .on_click(|_, window, cx| {
+                                            window.dispatch_action(
+                                                CherryPickCurrentCommit.boxed_clone(),
+                                                cx,
+                                            )
+                                        }),
+                                )
+                                .child(
+                                    Button::new("revert", "Revert")
+                                        .icon(IconName::Undo)
+                                        .icon_color(Color::Muted)
+                                        .icon_size(IconSize::Small)
+                                        .icon_position(IconPosition::Start)
+                                        .tooltip(Tooltip::for_action_title(
+                                            "Revert Commit",
+                                            &RevertCurrentCommit,
+                                        ))
+                                        .on_click(|_, window, cx| {
+                                            window.dispatch_action(
+                                                RevertCurrentCommit.boxed_clone(),
+                                                cx,
+                                            )
+                                        }),
+                                )
+                            })
+                            .children(github_url.map(|url| {
+                                Button::new("view_on_github", "View on GitHub")
+                                    .icon(IconName::Github)
+                                    .icon_color(Color::Muted)
+                                    .icon_size(IconSize::Small)
+                                    .icon_position(IconPosition::Start)
+                                    .on_click(move |_, _, cx| cx.open_url(&url))
+                            })),
+                    ),
             )
     }
 
@@ -617,6 +735,41 @@ impl CommitView {
             .detach_and_notify_err(window, cx);
     }
 
+    fn apply_current_commit(
+        operation: CommitOperation,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(commit_view) = workspace.active_item_as::<CommitView>(cx) else {
+            return;
+        };
+        let commit_view = commit_view.read(cx);
+        if commit_view.stash.is_some() {
+            return;
+        }
+        let sha = commit_view.commit.sha.clone();
+        let repository = commit_view.repository.clone();
+        apply_commit(operation, sha, repository, workspace, window, cx);
+    }
+
+    fn tag_current_commit(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(commit_view) = workspace.active_item_as::<CommitView>(cx) else {
+            return;
+        };
+        let commit_view = commit_view.read(cx);
+        if commit_view.stash.is_some() {
+            return;
+        }
+        let sha = commit_view.commit.sha.clone();
+        let repository = commit_view.repository.clone();
+        tag_picker::open_for_commit(workspace, Some(repository), Some(sha), window, cx);
+    }
+
     async fn close_commit_view(
         commit_view: Entity<CommitView>,
         workspace: WeakEntity<Workspace>,
@@ -996,6 +1149,7 @@ impl Item for CommitView {
                 stash: self.stash,
                 repository: self.repository.clone(),
                 remote: self.remote.clone(),
+                tags: self.tags.clone(),
             }
         })))
     }
@@ -1057,6 +1211,97 @@ impl ToolbarItemView for CommitViewToolbar {
     }
 }
 
+/// An operation that replays a commit on top of the current branch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CommitOperation {
+    CherryPick,
+    Revert,
+}
+
+impl CommitOperation {
+    fn verb(self) -> &'static str {
+        match self {
+            CommitOperation::CherryPick => "Cherry-pick",
+            CommitOperation::Revert => "Revert",
+        }
+    }
+}
+
+/// Cherry-picks or reverts `sha` after asking for confirmation. When the commit doesn't
+/// apply cleanly, the conflicts are left in the working tree and the project diff is
+/// opened so that they can be resolved there.
+pub(crate) fn apply_commit(
+    operation: CommitOperation,
+    sha: SharedString,
+    repository: Entity<Repository>,
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let short_sha = sha
+        .get(..git::SHORT_SHA_LENGTH)
+        .unwrap_or(sha.as_ref())
+        .to_string();
+    let prompt = match operation {
+        CommitOperation::CherryPick => {
+            let branch = repository
+                .read(cx)
+                .branch
+                .as_ref()
+                .map(|branch| branch.name().to_string())
+                .unwrap_or_else(|| "HEAD".to_string());
+            format!("Cherry-pick {short_sha} onto {branch}?")
+        }
+        CommitOperation::Revert => format!("Revert {short_sha}?"),
+    };
+    let answer = window.prompt(
+        PromptLevel::Info,
+        &prompt,
+        None,
+        &[operation.verb(), "Cancel"],
+        cx,
+    );
+
+    let workspace_weak = workspace.weak_handle();
+    window
+        .spawn(cx, async move |cx| {
+            if answer.await != Ok(0) {
+                return anyhow::Ok(());
+            }
+
+            let has_conflicts = repository
+                .update(cx, |repository, _| match operation {
+                    CommitOperation::CherryPick => repository.cherry_pick(sha.to_string()),
+                    CommitOperation::Revert => repository.revert(sha.to_string()),
+                })?
+                .await??;
+            if !has_conflicts {
+                return Ok(());
+            }
+
+            workspace_weak.update_in(cx, |workspace, window, cx| {
+                let message = format!(
+                    "{} of {short_sha} stopped with conflicts. Resolve them and commit to finish.",
+                    operation.verb()
+                );
+                let workspace_weak = cx.weak_entity();
+                let toast = StatusToast::new(message, cx, move |this, _| {
+                    this.icon(ToastIcon::new(IconName::Warning).color(Color::Warning))
+                        .action("View Conflicts", move |window, cx| {
+                            workspace_weak
+                                .update(cx, |workspace, cx| {
+                                    ProjectDiff::deploy_at(workspace, None, window, cx)
+                                })
+                                .ok();
+                        })
+                });
+                workspace.toggle_status_toast(toast, cx);
+                ProjectDiff::deploy_at(workspace, None, window, cx);
+            })
+        })
+        .detach_and_notify_err(window, cx);
+}
+
 fn stash_matches_index(sha: &str, stash_index: usize, repo: &Repository) -> bool {
     repo.stash_entries
         .entries
@@ -1064,3 +1309,130 @@ fn stash_matches_index(sha: &str, stash_index: usize, repo: &Repository) -> bool
         .map(|entry| entry.oid.to_string() == sha)
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::repository::repo_path;
+    use gpui::TestAppContext;
+    use project::{FakeCommitChange, FakeFs};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_apply_commit(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({ ".git": {}, "a.txt": "one\n", "b.txt": "two\n" }),
+        )
+        .await;
+        fs.set_head_and_index_for_repo(
+            Path::new(path!("/project/.git")),
+            &[("a.txt", "one\n".into()), ("b.txt", "two\n".into())],
+        );
+        fs.with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.commit_changes.insert(
+                "1111111111".into(),
+                vec![FakeCommitChange {
+                    path: repo_path("a.txt"),
+                    old_text: Some("one\n".into()),
+                    new_text: Some("ONE\n".into()),
+                }],
+            );
+            state.commit_changes.insert(
+                "2222222222".into(),
+                vec![FakeCommitChange {
+                    path: repo_path("b.txt"),
+                    old_text: Some("zwei\n".into()),
+                    new_text: Some("drei\n".into()),
+                }],
+            );
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+        let repository = project
+            .read_with(cx, |project, cx| project.active_repository(cx))
+            .unwrap();
+
+        // A commit that applies cleanly is committed without opening anything.
+        workspace.update_in(cx, |workspace, window, cx| {
+            apply_commit(
+                CommitOperation::CherryPick,
+                "1111111111".into(),
+                repository.clone(),
+                workspace,
+                window,
+                cx,
+            );
+        });
+        cx.simulate_prompt_answer("Cherry-pick");
+        cx.run_until_parked();
+        assert_eq!(
+            fs.load(Path::new(path!("/project/a.txt"))).await.unwrap(),
+            "ONE\n"
+        );
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_item_as::<ProjectDiff>(cx).is_none());
+            assert!(workspace.active_status_toast::<StatusToast>(cx).is_none());
+        });
+
+        // Cancelling the prompt leaves the repository alone.
+        workspace.update_in(cx, |workspace, window, cx| {
+            apply_commit(
+                CommitOperation::Revert,
+                "1111111111".into(),
+                repository.clone(),
+                workspace,
+                window,
+                cx,
+            );
+        });
+        cx.simulate_prompt_answer("Cancel");
+        cx.run_until_parked();
+        assert_eq!(
+            fs.load(Path::new(path!("/project/a.txt"))).await.unwrap(),
+            "ONE\n"
+        );
+
+        // A conflicting commit opens the project diff and explains what happened.
+        workspace.update_in(cx, |workspace, window, cx| {
+            apply_commit(
+                CommitOperation::CherryPick,
+                "2222222222".into(),
+                repository.clone(),
+                workspace,
+                window,
+                cx,
+            );
+        });
+        cx.simulate_prompt_answer("Cherry-pick");
+        cx.run_until_parked();
+        assert_eq!(
+            fs.load(Path::new(path!("/project/b.txt"))).await.unwrap(),
+            "<<<<<<< HEAD\ntwo\n=======\ndrei\n>>>>>>> 2222222222\n"
+        );
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_item_as::<ProjectDiff>(cx).is_some());
+            assert!(workspace.active_status_toast::<StatusToast>(cx).is_some());
+        });
+    }
+}
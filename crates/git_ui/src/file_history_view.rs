@@ -13,14 +13,16 @@ use project::{
 use std::any::{Any, TypeId};
 
 use time::OffsetDateTime;
-use ui::{Avatar, Chip, Divider, ListItem, WithScrollbar, prelude::*};
+use ui::{
+    Avatar, Chip, ContextMenu, Divider, ListItem, WithScrollbar, prelude::*, right_click_menu,
+};
 use util::ResultExt;
 use workspace::{
     Item, Workspace,
     item::{ItemEvent, SaveOptions},
 };
 
-use crate::commit_view::CommitView;
+use crate::commit_view::{self, CommitOperation, CommitView};
 
 actions!(git, [ViewCommitFromHistory, LoadMoreHistory]);
 
@@ -249,8 +251,10 @@ impl FileHistoryView {
         let repo = self.repository.clone();
         let workspace = self.workspace.clone();
         let file_path = self.history.path.clone();
+        let menu_sha = entry.sha.clone();
+        let view = cx.entity();
 
-        ListItem::new(("commit", ix))
+        let item = ListItem::new(("commit", ix))
             .child(
                 h_flex()
                     .h_8()
@@ -313,9 +317,57 @@ impl FileHistoryView {
                         cx,
                     );
                 }
-            }))
+            }));
+
+        right_click_menu(("commit-context-menu", ix))
+            .trigger(move |_, _, _| item)
+            .menu(move |window, cx| {
+                let view = view.clone();
+                let sha = menu_sha.clone();
+                ContextMenu::build(window, cx, move |menu, window, _| {
+                    menu.entry(
+                        "Cherry-pick onto Current Branch",
+                        None,
+                        window.handler_for(&view, {
+                            let sha = sha.clone();
+                            move |this, window, cx| {
+                                this.apply_commit(
+                                    CommitOperation::CherryPick,
+                                    sha.clone(),
+                                    window,
+                                    cx,
+                                )
+                            }
+                        }),
+                    )
+                    .entry(
+                        "Revert Commit",
+                        None,
+                        window.handler_for(&view, move |this, window, cx| {
+                            this.apply_commit(CommitOperation::Revert, sha.clone(), window, cx)
+                        }),
+                    )
+                })
+            })
             .into_any_element()
     }
+
+    fn apply_commit(
+        &mut self,
+        operation: CommitOperation,
+        sha: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(repository), Some(workspace)) =
+            (self.repository.upgrade(), self.workspace.upgrade())
+        else {
+            return;
+        };
+        workspace.update(cx, |workspace, cx| {
+            commit_view::apply_commit(operation, sha, repository, workspace, window, cx);
+        });
+    }
 }
 
 #[derive(Clone, Debug)]
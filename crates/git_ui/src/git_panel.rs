@@ -2,6 +2,7 @@ use crate::askpass_modal::AskPassModal;
 use crate::commit_modal::CommitModal;
 use crate::commit_tooltip::CommitTooltip;
 use crate::commit_view::CommitView;
+use crate::merge_editor::MergeEditor;
 use crate::project_diff::{self, Diff, ProjectDiff};
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
 use crate::{branch_picker, picker_prompt, render_remote_button};
@@ -22,8 +23,8 @@ use futures::StreamExt as _;
 use git::blame::ParsedCommitMessage;
 use git::repository::{
     Branch, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions, GitCommitter,
-    PushOptions, Remote, RemoteCommandOutput, ResetMode, Upstream, UpstreamTracking,
-    UpstreamTrackingStatus, get_git_committer,
+    PushOptions, Remote, RemoteCommandOutput, ResetMode, SubmoduleAction, SubmoduleState, Upstream,
+    UpstreamTracking, UpstreamTrackingStatus, get_git_committer,
 };
 use git::stash::GitStash;
 use git::status::StageStatus;
@@ -122,6 +123,7 @@ struct GitMenuState {
     has_new_changes: bool,
     sort_by_path: bool,
     has_stash_items: bool,
+    has_submodules: bool,
 }
 
 fn git_panel_context_menu(
@@ -152,6 +154,11 @@ fn git_panel_context_menu(
             .action_disabled_when(!state.has_stash_items, "Stash Pop", StashPop.boxed_clone())
             .action("View Stash", zed_actions::git::ViewStash.boxed_clone())
             .separator()
+            .when(state.has_submodules, |menu| {
+                menu.action("Update Submodules", git::UpdateSubmodules.boxed_clone())
+                    .action("Sync Submodules", git::SyncSubmodules.boxed_clone())
+                    .separator()
+            })
             .action("Open Diff", project_diff::Diff.boxed_clone())
             .separator()
             .action_disabled_when(
@@ -478,7 +485,8 @@ impl GitPanel {
                         _,
                         RepositoryEvent::StatusesChanged
                         | RepositoryEvent::BranchChanged
-                        | RepositoryEvent::MergeHeadsChanged,
+                        | RepositoryEvent::MergeHeadsChanged
+                        | RepositoryEvent::SubmodulesChanged,
                         true,
                     )
                     | GitStoreEvent::RepositoryAdded
@@ -863,6 +871,35 @@ impl GitPanel {
         });
     }
 
+    fn open_merge_editor(
+        &mut self,
+        _: &git::OpenMergeEditor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        maybe!({
+            let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
+            if !entry.status.is_conflicted() {
+                return None;
+            }
+            let active_repo = self.active_repository.clone()?;
+            MergeEditor::open(
+                entry.repo_path.clone(),
+                active_repo,
+                self.workspace.clone(),
+                window,
+                cx,
+            )
+            .detach_and_prompt_err(
+                "Failed to open merge editor",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+            Some(())
+        });
+    }
+
     fn open_file(
         &mut self,
         _: &menu::SecondaryConfirm,
@@ -2212,6 +2249,48 @@ impl GitPanel {
             .detach_and_log_err(cx);
     }
 
+    /// Runs `git submodule <action>` for the given submodules, or for all of them if `paths` is empty.
+    pub(crate) fn submodule_command(
+        &mut self,
+        action: SubmoduleAction,
+        paths: Vec<RepoPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_push_and_pull(cx) {
+            return;
+        }
+
+        let Some(repo) = self.active_repository.clone() else {
+            return;
+        };
+        telemetry::event!("Git Submodule Command", action = action.name());
+        let askpass = self.askpass_delegate(format!("git submodule {}", action.name()), window, cx);
+        let this = cx.weak_entity();
+
+        window
+            .spawn(cx, async move |cx| {
+                let command = repo.update(cx, |repo, cx| {
+                    repo.submodule_command(action, paths, askpass, cx)
+                })?;
+
+                let remote_message = command.await?;
+                this.update(cx, |this, cx| {
+                    let action = RemoteAction::Submodule(action);
+                    match remote_message {
+                        Ok(remote_message) => this.show_remote_output(action, remote_message, cx),
+                        Err(e) => {
+                            log::error!("Error while running git submodule: {:?}", e);
+                            this.show_error_toast(action.name(), e, cx)
+                        }
+                    }
+                })
+                .ok();
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
     pub(crate) fn git_clone(&mut self, repo: String, window: &mut Window, cx: &mut Context<Self>) {
         let path = cx.prompt_for_paths(gpui::PathPromptOptions {
             files: false,
@@ -3131,6 +3210,7 @@ impl GitPanel {
         let has_unstaged_changes = self.has_unstaged_changes();
         let has_new_changes = self.new_count > 0;
         let has_stash_items = self.stash_entries.entries.len() > 0;
+        let active_repository = self.active_repository.clone();
 
         PopoverMenu::new(id.into())
             .trigger(
@@ -3148,6 +3228,9 @@ impl GitPanel {
                         has_new_changes,
                         sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                         has_stash_items,
+                        has_submodules: active_repository
+                            .as_ref()
+                            .is_some_and(|repo| !repo.read(cx).submodules.is_empty()),
                     },
                     window,
                     cx,
@@ -3992,6 +4075,11 @@ impl GitPanel {
         let Some(entry) = self.entries.get(ix).and_then(|e| e.status_entry()) else {
             return;
         };
+        let submodule = self.active_repository.as_ref().and_then(|repo| {
+            repo.read(cx)
+                .submodule_for_path(&entry.repo_path)
+                .map(|submodule| submodule.state)
+        });
         let stage_title = if entry.status.staging().is_fully_staged() {
             "Unstage File"
         } else {
@@ -4002,10 +4090,46 @@ impl GitPanel {
         } else {
             "Restore File"
         };
+        let this = cx.weak_entity();
         let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
             let is_created = entry.status.is_created();
+            let is_conflicted = entry.status.is_conflicted();
+            let submodule_handler = |action| {
+                let this = this.clone();
+                let repo_path = entry.repo_path.clone();
+                move |window: &mut Window, cx: &mut App| {
+                    this.update(cx, |this, cx| {
+                        this.submodule_command(action, vec![repo_path.clone()], window, cx)
+                    })
+                    .ok();
+                }
+            };
             context_menu
                 .context(self.focus_handle.clone())
+                .when(is_conflicted, |menu| {
+                    menu.action("Open Merge Editor", git::OpenMergeEditor.boxed_clone())
+                        .separator()
+                })
+                .when_some(submodule, |menu, state| {
+                    menu.when(state == SubmoduleState::Uninitialized, |menu| {
+                        menu.entry(
+                            "Initialize Submodule",
+                            None,
+                            submodule_handler(SubmoduleAction::Init),
+                        )
+                    })
+                    .entry(
+                        "Update Submodule",
+                        None,
+                        submodule_handler(SubmoduleAction::Update),
+                    )
+                    .entry(
+                        "Sync Submodule",
+                        None,
+                        submodule_handler(SubmoduleAction::Sync),
+                    )
+                    .separator()
+                })
                 .action(stage_title, ToggleStaged.boxed_clone())
                 .action(restore_title, git::RestoreFile::default().boxed_clone())
                 .action_disabled_when(
@@ -4038,6 +4162,10 @@ impl GitPanel {
                 has_new_changes: self.new_count > 0,
                 sort_by_path: GitPanelSettings::get_global(cx).sort_by_path,
                 has_stash_items: self.stash_entries.entries.len() > 0,
+                has_submodules: self
+                    .active_repository
+                    .as_ref()
+                    .is_some_and(|repo| !repo.read(cx).submodules.is_empty()),
             },
             window,
             cx,
@@ -4139,6 +4267,9 @@ impl GitPanel {
                     .and_then(|status| status.status.staging().as_bool())
             })
             .or_else(|| entry.staging.as_bool());
+        let submodule_summary = repo
+            .submodule_for_path(&entry.repo_path)
+            .and_then(|submodule| submodule.summary());
         let mut is_staged: ToggleState = is_staging_or_staged.into();
         if self.show_placeholders && !self.has_staged_changes() && !entry.status.is_created() {
             is_staged = ToggleState::Selected;
@@ -4286,7 +4417,15 @@ impl GitPanel {
                             git_path_style,
                             status.is_deleted(),
                         )
-                    })),
+                    }))
+                    .when_some(submodule_summary, |this, summary| {
+                        this.child(
+                            Label::new(summary)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    }),
             )
             .into_any_element()
     }
@@ -4521,6 +4660,7 @@ impl Render for GitPanel {
             .on_action(cx.listener(Self::open_diff))
             .on_action(cx.listener(Self::open_file))
             .on_action(cx.listener(Self::file_history))
+            .on_action(cx.listener(Self::open_merge_editor))
             .on_action(cx.listener(Self::focus_changes_list))
             .on_action(cx.listener(Self::focus_editor))
             .on_action(cx.listener(Self::expand_commit_editor))
@@ -5211,7 +5351,7 @@ pub(crate) fn show_error_toast(
 #[cfg(test)]
 mod tests {
     use git::{
-        repository::repo_path,
+        repository::{Submodule, repo_path},
         status::{StatusCode, UnmergedStatus, UnmergedStatusCode},
     };
     use gpui::{TestAppContext, UpdateGlobal, VisualTestContext};
@@ -5976,6 +6116,111 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[gpui::test]
+    async fn test_submodules(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        // The fake repository only tracks files, so the submodule is checked in as one.
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                ".gitmodules": "[submodule \"lib\"]\n\tpath = lib\n",
+                "lib": "Subproject commit abc\n",
+                "main.rs": "fn main() {}\n",
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_status_for_repo(dot_git, &[("lib", StatusCode::Modified.worktree())]);
+        let submodule = |path: &str, state| Submodule {
+            path: repo_path(path),
+            sha: "abc".into(),
+            state,
+            has_modified_content: false,
+            has_untracked_content: true,
+        };
+        fs.with_git_state(dot_git, true, |state| {
+            state.submodules = vec![
+                submodule("lib", SubmoduleState::NewCommits),
+                submodule("vendor", SubmoduleState::Uninitialized),
+            ];
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let workspace =
+            cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let cx = &mut VisualTestContext::from_window(*workspace, cx);
+        let panel = workspace.update(cx, GitPanel::new).unwrap();
+        workspace
+            .update(cx, |workspace, window, cx| {
+                workspace.add_panel(panel.clone(), window, cx)
+            })
+            .unwrap();
+
+        let handle = cx.update_window_entity(&panel, |panel, _, _| {
+            std::mem::replace(&mut panel.update_visible_entries_task, Task::ready(()))
+        });
+        cx.executor().advance_clock(2 * UPDATE_DEBOUNCE);
+        handle.await;
+
+        let repository = panel.read_with(cx, |panel, _| panel.active_repository.clone().unwrap());
+        let summaries = |cx: &mut VisualTestContext| {
+            repository.read_with(cx, |repository, _| {
+                ["lib", "vendor"].map(|path| {
+                    repository
+                        .submodule_for_path(&repo_path(path))
+                        .and_then(|submodule| submodule.summary())
+                })
+            })
+        };
+        panel.read_with(cx, |panel, _| {
+            let paths = panel
+                .entries
+                .iter()
+                .filter_map(|entry| Some(entry.status_entry()?.repo_path.as_unix_str()))
+                .collect::<Vec<_>>();
+            assert_eq!(paths, ["lib"]);
+        });
+        assert_eq!(
+            summaries(cx),
+            [
+                Some("new commits, untracked content".to_string()),
+                Some("not initialized, untracked content".to_string()),
+            ]
+        );
+
+        cx.dispatch_action(git::InitSubmodules);
+        cx.run_until_parked();
+        cx.dispatch_action(git::UpdateSubmodules);
+        cx.run_until_parked();
+        cx.dispatch_action(git::SyncSubmodules);
+        cx.run_until_parked();
+
+        let commands = fs
+            .with_git_state(dot_git, false, |state| state.submodule_commands.clone())
+            .unwrap();
+        assert_eq!(
+            commands,
+            [
+                (SubmoduleAction::Init, Vec::new()),
+                (SubmoduleAction::Update, Vec::new()),
+                (SubmoduleAction::Sync, Vec::new()),
+            ]
+        );
+        // Updating the submodules doesn't change the superproject's statuses, but the
+        // submodules are still listed again.
+        assert_eq!(
+            summaries(cx),
+            [
+                Some("untracked content".to_string()),
+                Some("untracked content".to_string()),
+            ]
+        );
+    }
+
     #[gpui::test]
     async fn test_suggest_commit_message(cx: &mut TestAppContext) {
         init_test(cx);
@@ -12,7 +12,7 @@ use ui::{
 mod blame_ui;
 
 use git::{
-    repository::{Branch, Upstream, UpstreamTracking, UpstreamTrackingStatus},
+    repository::{Branch, SubmoduleAction, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
 };
 use gpui::{
@@ -30,7 +30,9 @@ use zed_actions;
 use crate::{git_panel::GitPanel, text_diff_view::TextDiffView};
 
 mod askpass_modal;
+pub mod bisect_view;
 pub mod branch_picker;
+pub mod commit_graph_view;
 mod commit_modal;
 pub mod commit_tooltip;
 pub mod commit_view;
@@ -39,12 +41,15 @@ pub mod file_diff_view;
 pub mod file_history_view;
 pub mod git_panel;
 mod git_panel_settings;
+pub mod merge_editor;
 pub mod onboarding;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod rebase_editor;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 pub mod stash_picker;
+pub mod tag_picker;
 pub mod text_diff_view;
 pub mod worktree_picker;
 
@@ -74,6 +79,11 @@ pub fn init(cx: &mut App) {
         branch_picker::register(workspace);
         worktree_picker::register(workspace);
         stash_picker::register(workspace);
+        tag_picker::register(workspace);
+        commit_graph_view::register(workspace);
+        merge_editor::register(workspace);
+        rebase_editor::register(workspace);
+        bisect_view::register(workspace);
 
         let project = workspace.project().read(cx);
         if project.is_read_only(cx) {
@@ -96,6 +106,30 @@ pub fn init(cx: &mut App) {
                     panel.fetch(false, window, cx);
                 });
             });
+            workspace.register_action(|workspace, _: &git::UpdateSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.submodule_command(SubmoduleAction::Update, Vec::new(), window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::InitSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.submodule_command(SubmoduleAction::Init, Vec::new(), window, cx);
+                });
+            });
+            workspace.register_action(|workspace, _: &git::SyncSubmodules, window, cx| {
+                let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                    return;
+                };
+                panel.update(cx, |panel, cx| {
+                    panel.submodule_command(SubmoduleAction::Sync, Vec::new(), window, cx);
+                });
+            });
             workspace.register_action(|workspace, _: &git::Push, window, cx| {
                 let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                     return;
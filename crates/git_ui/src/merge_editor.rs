@@ -0,0 +1,992 @@
+//! MergeEditor shows the common ancestor, our and their version of a conflicted file next to
+//! the file itself, and lets the conflicts in it be resolved one by one.
+
+use anyhow::{Context as _, Result};
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent, MultiBuffer, SelectionEffects, scroll::Autoscroll};
+use git::repository::RepoPath;
+use gpui::{
+    AnyElement, App, AppContext as _, AsyncWindowContext, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, PromptLevel, Render, Subscription, Task, WeakEntity, Window, actions,
+};
+use language::{Buffer, Capability, Language, Point, ToPoint as _};
+use project::{
+    ConflictRegion, ConflictSet, ConflictSetUpdate, Project, ProjectItem as _, ProjectPath,
+    git_store::Repository,
+};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
+use ui::{Divider, KeyBinding, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Item, ItemNavHistory, Workspace,
+    item::{ItemEvent, SaveOptions, TabContentParams},
+    notifications::DetachAndPromptErr,
+};
+
+use crate::file_diff_view::build_buffer_diff;
+
+const SAVE: workspace::Save = workspace::Save { save_intent: None };
+
+actions!(
+    merge_editor,
+    [
+        /// Resolves the conflict at the cursor with our version.
+        AcceptOurs,
+        /// Resolves the conflict at the cursor with their version.
+        AcceptTheirs,
+        /// Resolves the conflict at the cursor with our version followed by theirs.
+        AcceptBoth,
+        /// Resolves the conflict at the cursor with the common ancestor's version.
+        AcceptBase,
+        /// Resolves all conflicts in the file with our version.
+        AcceptAllOurs,
+        /// Resolves all conflicts in the file with their version.
+        AcceptAllTheirs,
+        /// Moves the cursor to the next conflict.
+        NextConflict,
+        /// Moves the cursor to the previous conflict.
+        PreviousConflict,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open_for_active_editor);
+}
+
+fn open_for_active_editor(
+    workspace: &mut Workspace,
+    _: &git::OpenMergeEditor,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return;
+    };
+    let Some(project_path) = buffer.read(cx).project_path(cx) else {
+        return;
+    };
+    let Some((repository, repo_path)) = workspace
+        .project()
+        .read(cx)
+        .git_store()
+        .read(cx)
+        .repository_and_path_for_project_path(&project_path, cx)
+    else {
+        return;
+    };
+    let is_conflicted = repository
+        .read(cx)
+        .status_for_path(&repo_path)
+        .is_some_and(|entry| entry.status.is_conflicted());
+    if !is_conflicted {
+        return;
+    }
+    MergeEditor::open(repo_path, repository, workspace.weak_handle(), window, cx)
+        .detach_and_prompt_err("Failed to open merge editor", window, cx, |_, _, _| None);
+}
+
+/// Which versions of a conflict to keep, in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Resolution {
+    Ours,
+    Theirs,
+    Both,
+    Base,
+}
+
+impl Resolution {
+    fn ranges(self, conflict: &ConflictRegion) -> Option<Vec<std::ops::Range<language::Anchor>>> {
+        Some(match self {
+            Resolution::Ours => vec![conflict.ours.clone()],
+            Resolution::Theirs => vec![conflict.theirs.clone()],
+            Resolution::Both => vec![conflict.ours.clone(), conflict.theirs.clone()],
+            Resolution::Base => vec![conflict.base.clone()?],
+        })
+    }
+}
+
+/// Read-only buffers with the versions of the file from the index, and the diffs of our and
+/// their version against the common ancestor.
+struct StageBuffers {
+    base: Entity<Buffer>,
+    ours: Entity<Buffer>,
+    theirs: Entity<Buffer>,
+    ours_diff: Entity<BufferDiff>,
+    theirs_diff: Entity<BufferDiff>,
+}
+
+pub struct MergeEditor {
+    repo_path: RepoPath,
+    repository: WeakEntity<Repository>,
+    result_buffer: Entity<Buffer>,
+    conflict_set: Entity<ConflictSet>,
+    ours_editor: Entity<Editor>,
+    base_editor: Entity<Editor>,
+    theirs_editor: Entity<Editor>,
+    result_editor: Entity<Editor>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl MergeEditor {
+    pub fn open(
+        repo_path: RepoPath,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let project_path = repository
+            .read(cx)
+            .repo_path_to_project_path(&repo_path, cx);
+        let stages = repository.update(cx, |repository, _| {
+            repository.load_conflict_stages(repo_path.clone())
+        });
+        window.spawn(cx, async move |cx| {
+            let project_path = project_path.context("conflicted file is not in the project")?;
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            if let Some(existing) = Self::activate_existing(&workspace, &project_path, cx) {
+                return Ok(existing);
+            }
+
+            let result_buffer = project
+                .update(cx, |project, cx| {
+                    project.open_buffer(project_path.clone(), cx)
+                })?
+                .await?;
+            let stages = stages.await??;
+            let language = result_buffer.read_with(cx, |buffer, _| buffer.language().cloned())?;
+
+            let [base, ours, theirs] = [stages.base, stages.ours, stages.theirs]
+                .map(|text| read_only_buffer(text.unwrap_or_default(), language.clone(), cx));
+            let (base, ours, theirs) = (base?, ours?, theirs?);
+            let stages = StageBuffers {
+                ours_diff: build_buffer_diff(&base, &ours, cx).await?,
+                theirs_diff: build_buffer_diff(&base, &theirs, cx).await?,
+                base,
+                ours,
+                theirs,
+            };
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let merge_editor = cx.new(|cx| {
+                    MergeEditor::new(
+                        repo_path,
+                        repository,
+                        project.clone(),
+                        result_buffer,
+                        stages,
+                        window,
+                        cx,
+                    )
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.add_item(Box::new(merge_editor.clone()), true, true, None, window, cx);
+                });
+                merge_editor
+            })
+        })
+    }
+
+    fn activate_existing(
+        workspace: &WeakEntity<Workspace>,
+        project_path: &ProjectPath,
+        cx: &mut AsyncWindowContext,
+    ) -> Option<Entity<Self>> {
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                let pane = workspace.active_pane().clone();
+                let (ix, existing) = pane.read(cx).items().enumerate().find_map(|(ix, item)| {
+                    let merge_editor = item.downcast::<MergeEditor>()?;
+                    let path = merge_editor
+                        .read(cx)
+                        .result_buffer
+                        .read(cx)
+                        .project_path(cx);
+                    (path.as_ref() == Some(project_path)).then_some((ix, merge_editor))
+                })?;
+                pane.update(cx, |pane, cx| {
+                    pane.activate_item(ix, true, true, window, cx)
+                });
+                Some(existing)
+            })
+            .ok()
+            .flatten()
+    }
+
+    fn new(
+        repo_path: RepoPath,
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        result_buffer: Entity<Buffer>,
+        stages: StageBuffers,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let side_editor = |buffer: Entity<Buffer>,
+                           diff: Option<Entity<BufferDiff>>,
+                           window: &mut Window,
+                           cx: &mut Context<Self>| {
+            let multibuffer = cx.new(|cx| {
+                let mut multibuffer = MultiBuffer::singleton(buffer, cx);
+                if let Some(diff) = diff {
+                    multibuffer.add_diff(diff, cx);
+                }
+                multibuffer
+            });
+            cx.new(|cx| {
+                let mut editor =
+                    Editor::for_multibuffer(multibuffer, Some(project.clone()), window, cx);
+                editor.set_read_only(true);
+                editor.disable_diagnostics(cx);
+                editor.start_temporary_diff_override();
+                editor.set_expand_all_diff_hunks(cx);
+                editor.set_render_diff_hunk_controls(
+                    Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                    cx,
+                );
+                editor
+            })
+        };
+        let ours_editor = side_editor(stages.ours, Some(stages.ours_diff), window, cx);
+        let base_editor = side_editor(stages.base, None, window, cx);
+        let theirs_editor = side_editor(stages.theirs, Some(stages.theirs_diff), window, cx);
+        let result_editor = cx
+            .new(|cx| Editor::for_buffer(result_buffer.clone(), Some(project.clone()), window, cx));
+
+        let conflict_set = project.update(cx, |project, cx| {
+            project.git_store().update(cx, |git_store, cx| {
+                git_store.open_conflict_set(result_buffer.clone(), cx)
+            })
+        });
+
+        let subscriptions = vec![
+            cx.subscribe(&conflict_set, |_, _, _: &ConflictSetUpdate, cx| cx.notify()),
+            cx.subscribe_in(
+                &result_editor,
+                window,
+                |this, _, event: &EditorEvent, window, cx| match event {
+                    EditorEvent::SelectionsChanged { local: true } => {
+                        this.reveal_conflict_in_sides(window, cx);
+                    }
+                    EditorEvent::DirtyChanged | EditorEvent::Saved | EditorEvent::TitleChanged => {
+                        cx.emit(event.clone());
+                    }
+                    _ => {}
+                },
+            ),
+        ];
+
+        Self {
+            repo_path,
+            repository: repository.downgrade(),
+            result_buffer,
+            conflict_set,
+            ours_editor,
+            base_editor,
+            theirs_editor,
+            result_editor,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    fn conflicts(&self, cx: &App) -> Arc<[ConflictRegion]> {
+        self.conflict_set.read(cx).snapshot().conflicts
+    }
+
+    fn cursor(&self, cx: &mut App) -> Point {
+        self.result_editor.update(cx, |editor, cx| {
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head()
+        })
+    }
+
+    /// The conflict the cursor is in, or else the next one after the cursor.
+    fn current_conflict(&self, cx: &mut App) -> Option<ConflictRegion> {
+        let cursor = self.cursor(cx);
+        let snapshot = self.result_buffer.read(cx).snapshot();
+        self.conflicts(cx)
+            .iter()
+            .find(|conflict| conflict.range.end.to_point(&snapshot) >= cursor)
+            .cloned()
+    }
+
+    fn resolve_current(&mut self, resolution: Resolution, cx: &mut Context<Self>) {
+        let Some(conflict) = self.current_conflict(cx) else {
+            return;
+        };
+        let Some(ranges) = resolution.ranges(&conflict) else {
+            return;
+        };
+        conflict.resolve(self.result_buffer.clone(), &ranges, cx);
+    }
+
+    fn resolve_all(&mut self, resolution: Resolution, cx: &mut Context<Self>) {
+        // Resolve from the last conflict to the first, so the anchors of the remaining
+        // conflicts stay in place.
+        for conflict in self.conflicts(cx).iter().rev() {
+            if let Some(ranges) = resolution.ranges(conflict) {
+                conflict.resolve(self.result_buffer.clone(), &ranges, cx);
+            }
+        }
+    }
+
+    fn accept_ours(&mut self, _: &AcceptOurs, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Ours, cx);
+    }
+
+    fn accept_theirs(&mut self, _: &AcceptTheirs, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Theirs, cx);
+    }
+
+    fn accept_both(&mut self, _: &AcceptBoth, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Both, cx);
+    }
+
+    fn accept_base(&mut self, _: &AcceptBase, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Base, cx);
+    }
+
+    fn accept_all_ours(&mut self, _: &AcceptAllOurs, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_all(Resolution::Ours, cx);
+    }
+
+    fn accept_all_theirs(&mut self, _: &AcceptAllTheirs, _: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_all(Resolution::Theirs, cx);
+    }
+
+    fn next_conflict(&mut self, _: &NextConflict, window: &mut Window, cx: &mut Context<Self>) {
+        self.go_to_conflict(true, window, cx);
+    }
+
+    fn previous_conflict(
+        &mut self,
+        _: &PreviousConflict,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_conflict(false, window, cx);
+    }
+
+    fn go_to_conflict(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let cursor = self.cursor(cx);
+        let snapshot = self.result_buffer.read(cx).snapshot();
+        let starts = self
+            .conflicts(cx)
+            .iter()
+            .map(|conflict| conflict.range.start.to_point(&snapshot))
+            .collect::<Vec<_>>();
+        let target = if forward {
+            starts
+                .iter()
+                .find(|start| start.row > cursor.row)
+                .or(starts.first())
+        } else {
+            starts
+                .iter()
+                .rev()
+                .find(|start| start.row < cursor.row)
+                .or(starts.last())
+        };
+        let Some(&target) = target else {
+            return;
+        };
+        self.result_editor.update(cx, |editor, cx| {
+            editor.change_selections(
+                SelectionEffects::scroll(Autoscroll::center()),
+                window,
+                cx,
+                |s| s.select_ranges([target..target]),
+            );
+        });
+        window.focus(&self.result_editor.focus_handle(cx));
+    }
+
+    /// Selects the text of the conflict at the cursor in the ours, base and theirs panes.
+    fn reveal_conflict_in_sides(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(conflict) = self.current_conflict(cx) else {
+            return;
+        };
+        let snapshot = self.result_buffer.read(cx).snapshot();
+        let side_text = |range: &std::ops::Range<language::Anchor>| {
+            snapshot.text_for_range(range.clone()).collect::<String>()
+        };
+        let sides = [
+            (self.ours_editor.clone(), Some(side_text(&conflict.ours))),
+            (
+                self.base_editor.clone(),
+                conflict.base.as_ref().map(side_text),
+            ),
+            (
+                self.theirs_editor.clone(),
+                Some(side_text(&conflict.theirs)),
+            ),
+        ];
+        for (editor, text) in sides {
+            let Some(text) = text.filter(|text| !text.trim().is_empty()) else {
+                continue;
+            };
+            editor.update(cx, |editor, cx| {
+                let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+                    return;
+                };
+                let buffer = buffer.read(cx).snapshot();
+                let Some(start) = buffer.text().find(&text) else {
+                    return;
+                };
+                let range =
+                    buffer.offset_to_point(start)..buffer.offset_to_point(start + text.len());
+                editor.change_selections(
+                    SelectionEffects::scroll(Autoscroll::center()),
+                    window,
+                    cx,
+                    |s| s.select_ranges([range]),
+                );
+            });
+        }
+    }
+
+    /// Stages the file to mark its conflicts as resolved, asking first if the file still
+    /// contains conflict markers.
+    fn mark_resolved(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Task<Result<()>> {
+        // Parse the saved text, since the conflict set may not have caught up with the last edits.
+        let remaining = ConflictSet::parse(&self.result_buffer.read(cx).snapshot())
+            .conflicts
+            .len();
+        let answer = (remaining > 0).then(|| {
+            window.prompt(
+                PromptLevel::Warning,
+                &format!(
+                    "{} still has {remaining} unresolved conflict{}",
+                    self.repo_path.as_unix_str(),
+                    if remaining == 1 { "" } else { "s" }
+                ),
+                Some("The file was saved. Do you want to mark it as resolved anyway?"),
+                &["Mark as Resolved", "Cancel"],
+                cx,
+            )
+        });
+        let repository = self.repository.clone();
+        let repo_path = self.repo_path.clone();
+        cx.spawn(async move |_, cx| {
+            if let Some(answer) = answer
+                && answer.await != Ok(0)
+            {
+                return Ok(());
+            }
+            repository
+                .update(cx, |repository, cx| {
+                    repository.stage_entries(vec![repo_path], cx)
+                })?
+                .await
+        })
+    }
+
+    fn render_pane_header(
+        &self,
+        title: impl Into<SharedString>,
+        detail: Option<SharedString>,
+        cx: &App,
+    ) -> impl IntoElement {
+        h_flex()
+            .h_7()
+            .px_2()
+            .gap_1p5()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().editor_subheader_background)
+            .child(Label::new(title).size(LabelSize::Small))
+            .when_some(detail, |this, detail| {
+                this.child(
+                    Label::new(detail)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                )
+            })
+    }
+
+    fn render_pane(
+        &self,
+        header: impl IntoElement,
+        editor: &Entity<Editor>,
+        cx: &App,
+    ) -> impl IntoElement {
+        v_flex()
+            .flex_1()
+            .min_w_0()
+            .h_full()
+            .border_r_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(header)
+            .child(div().flex_1().min_h_0().child(editor.clone()))
+    }
+
+    fn render_toolbar(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let remaining = self.conflicts(cx).len();
+        let focus_handle = self.result_editor.focus_handle(cx);
+        let button = |id: &'static str, label: &'static str, action: Box<dyn gpui::Action>| {
+            let focus_handle = focus_handle.clone();
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .disabled(remaining == 0)
+                .key_binding(
+                    KeyBinding::for_action_in(action.as_ref(), &focus_handle, cx)
+                        .map(|kb| kb.size(rems_from_px(12.))),
+                )
+                .on_click(move |_, window, cx| {
+                    focus_handle.dispatch_action(action.as_ref(), window, cx);
+                })
+        };
+        let status = if remaining == 0 {
+            "No conflicts remaining".to_string()
+        } else if remaining == 1 {
+            "1 conflict remaining".to_string()
+        } else {
+            format!("{remaining} conflicts remaining")
+        };
+
+        h_flex()
+            .h(rems_from_px(41.))
+            .pl_3()
+            .pr_2()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Label::new(self.repo_path.as_unix_str().to_string())
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(status)
+                            .size(LabelSize::Small)
+                            .color(if remaining == 0 {
+                                Color::Success
+                            } else {
+                                Color::Warning
+                            }),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new("previous-conflict", IconName::ArrowUp)
+                            .icon_size(IconSize::Small)
+                            .disabled(remaining == 0)
+                            .tooltip(Tooltip::for_action_title_in(
+                                "Previous Conflict",
+                                &PreviousConflict,
+                                &focus_handle,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.go_to_conflict(false, window, cx)
+                            })),
+                    )
+                    .child(
+                        IconButton::new("next-conflict", IconName::ArrowDown)
+                            .icon_size(IconSize::Small)
+                            .disabled(remaining == 0)
+                            .tooltip(Tooltip::for_action_title_in(
+                                "Next Conflict",
+                                &NextConflict,
+                                &focus_handle,
+                            ))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.go_to_conflict(true, window, cx)
+                            })),
+                    )
+                    .child(Divider::vertical())
+                    .child(button("accept-ours", "Accept Ours", Box::new(AcceptOurs)))
+                    .child(button(
+                        "accept-theirs",
+                        "Accept Theirs",
+                        Box::new(AcceptTheirs),
+                    ))
+                    .child(button("accept-both", "Accept Both", Box::new(AcceptBoth)))
+                    .child(Divider::vertical())
+                    .child(
+                        Button::new("mark-resolved", "Save and Mark as Resolved")
+                            .label_size(LabelSize::Small)
+                            .key_binding(
+                                KeyBinding::for_action_in(&SAVE, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(cx.listener(|_, _, window, cx| {
+                                window.dispatch_action(SAVE.boxed_clone(), cx)
+                            })),
+                    ),
+            )
+    }
+}
+
+fn read_only_buffer(
+    text: String,
+    language: Option<Arc<Language>>,
+    cx: &mut AsyncWindowContext,
+) -> Result<Entity<Buffer>> {
+    cx.new(|cx| {
+        let mut buffer = Buffer::local(text, cx);
+        buffer.set_language(language, cx);
+        buffer.set_capability(Capability::ReadOnly, cx);
+        buffer
+    })
+}
+
+impl EventEmitter<EditorEvent> for MergeEditor {}
+
+impl Focusable for MergeEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.result_editor.focus_handle(cx)
+    }
+}
+
+impl Render for MergeEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (ours_branch, theirs_branch) = self
+            .conflicts(cx)
+            .first()
+            .map(|conflict| {
+                (
+                    Some(conflict.ours_branch_name.clone()),
+                    Some(conflict.theirs_branch_name.clone()),
+                )
+            })
+            .unwrap_or_default();
+
+        v_flex()
+            .key_context("MergeEditor")
+            .on_action(cx.listener(Self::accept_ours))
+            .on_action(cx.listener(Self::accept_theirs))
+            .on_action(cx.listener(Self::accept_both))
+            .on_action(cx.listener(Self::accept_base))
+            .on_action(cx.listener(Self::accept_all_ours))
+            .on_action(cx.listener(Self::accept_all_theirs))
+            .on_action(cx.listener(Self::next_conflict))
+            .on_action(cx.listener(Self::previous_conflict))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_toolbar(window, cx))
+            .child(
+                h_flex()
+                    .h(relative(0.45))
+                    .w_full()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(self.render_pane(
+                        self.render_pane_header("Ours", ours_branch, cx),
+                        &self.ours_editor,
+                        cx,
+                    ))
+                    .child(self.render_pane(
+                        self.render_pane_header("Base", None, cx),
+                        &self.base_editor,
+                        cx,
+                    ))
+                    .child(self.render_pane(
+                        self.render_pane_header("Theirs", theirs_branch, cx),
+                        &self.theirs_editor,
+                        cx,
+                    )),
+            )
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(self.render_pane_header("Result", None, cx))
+                    .child(div().flex_1().min_h_0().child(self.result_editor.clone())),
+            )
+    }
+}
+
+impl Item for MergeEditor {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let file_name = self.repo_path.file_name().unwrap_or("File");
+        format!("Merge: {file_name}").into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Resolve conflicts in {}", self.repo_path.as_unix_str()).into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Merge Editor Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.result_editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.result_editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.result_editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.result_editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.result_editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        self.result_editor.read(cx).can_save(cx)
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let save = self
+            .result_editor
+            .update(cx, |editor, cx| editor.save(options, project, window, cx));
+        cx.spawn_in(window, async move |this, cx| {
+            save.await?;
+            this.update_in(cx, |this, window, cx| this.mark_resolved(window, cx))?
+                .await
+                .log_err();
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::{
+        repository::ConflictStages,
+        status::{UnmergedStatus, UnmergedStatusCode},
+    };
+    use gpui::TestAppContext;
+    use project::{FakeFs, Fs};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_resolve_and_mark_resolved(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let conflicted = "\
+            one\n\
+            <<<<<<< HEAD\n\
+            two ours\n\
+            =======\n\
+            two theirs\n\
+            >>>>>>> feature\n\
+            three\n\
+            <<<<<<< HEAD\n\
+            four ours\n\
+            =======\n\
+            four theirs\n\
+            >>>>>>> feature\n";
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": conflicted }))
+            .await;
+        let repo_path = RepoPath::new("file.txt").unwrap();
+        let dot_git = Path::new(path!("/dir/.git"));
+        fs.set_unmerged_paths_for_repo(
+            dot_git,
+            &[(
+                repo_path.clone(),
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                },
+            )],
+        );
+        fs.set_conflict_stages_for_repo(
+            dot_git,
+            &[(
+                "file.txt",
+                ConflictStages {
+                    base: Some("one\ntwo\nthree\nfour\n".into()),
+                    ours: Some("one\ntwo ours\nthree\nfour ours\n".into()),
+                    theirs: Some("one\ntwo theirs\nthree\nfour theirs\n".into()),
+                },
+            )],
+        );
+
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+        let repository = project
+            .read_with(cx, |project, cx| project.active_repository(cx))
+            .unwrap();
+
+        let merge_editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                MergeEditor::open(
+                    repo_path.clone(),
+                    repository,
+                    workspace.weak_handle(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        merge_editor.update(cx, |merge_editor, cx| {
+            let text =
+                |editor: &Entity<Editor>| editor.read(cx).buffer().read(cx).snapshot(cx).text();
+            assert_eq!(text(&merge_editor.base_editor), "one\ntwo\nthree\nfour\n");
+            assert_eq!(
+                text(&merge_editor.ours_editor),
+                "one\ntwo ours\nthree\nfour ours\n"
+            );
+            assert_eq!(
+                text(&merge_editor.theirs_editor),
+                "one\ntwo theirs\nthree\nfour theirs\n"
+            );
+            assert_eq!(merge_editor.conflicts(cx).len(), 2);
+        });
+
+        // Accepting resolves the conflict at the cursor, then moving on resolves the next one.
+        merge_editor.update_in(cx, |merge_editor, window, cx| {
+            merge_editor.accept_ours(&AcceptOurs, window, cx);
+        });
+        cx.run_until_parked();
+        merge_editor.update_in(cx, |merge_editor, window, cx| {
+            assert_eq!(merge_editor.conflicts(cx).len(), 1);
+            merge_editor.next_conflict(&NextConflict, window, cx);
+            merge_editor.accept_theirs(&AcceptTheirs, window, cx);
+        });
+        cx.run_until_parked();
+        merge_editor.read_with(cx, |merge_editor, cx| {
+            assert_eq!(
+                merge_editor.result_buffer.read(cx).text(),
+                "one\ntwo ours\nthree\nfour theirs\n"
+            );
+            assert_eq!(merge_editor.conflicts(cx).len(), 0);
+            assert!(merge_editor.is_dirty(cx));
+        });
+
+        // Saving writes the result and stages the file.
+        merge_editor
+            .update_in(cx, |merge_editor, window, cx| {
+                merge_editor.save(SaveOptions::default(), project.clone(), window, cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        assert_eq!(
+            fs.load(Path::new(path!("/dir/file.txt"))).await.unwrap(),
+            "one\ntwo ours\nthree\nfour theirs\n"
+        );
+        let (index_text, is_unmerged) = fs
+            .with_git_state(dot_git, false, |state| {
+                (
+                    state.index_contents.get(&repo_path).cloned(),
+                    state.unmerged_paths.contains_key(&repo_path),
+                )
+            })
+            .unwrap();
+        assert_eq!(
+            index_text.as_deref(),
+            Some("one\ntwo ours\nthree\nfour theirs\n")
+        );
+        assert!(!is_unmerged);
+    }
+}
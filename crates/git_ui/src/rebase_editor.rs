@@ -0,0 +1,781 @@
+use anyhow::Context as _;
+use editor::Editor;
+use git::repository::{RebaseAction, RebaseEntry, RebaseStatus};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, KeyContext,
+    SharedString, Task, UniformListScrollHandle, Window, actions, uniform_list,
+};
+use menu::{Cancel, Confirm, SelectNext, SelectPrevious};
+use project::git_store::Repository;
+use ui::{
+    Headline, HeadlineSize, KeyBinding, ListItem, ListItemSpacing, WithScrollbar, prelude::*,
+};
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+use crate::git_panel::GitPanel;
+
+actions!(
+    rebase_editor,
+    [
+        /// Moves the selected commit up, so that it is applied earlier.
+        MoveEntryUp,
+        /// Moves the selected commit down, so that it is applied later.
+        MoveEntryDown,
+        /// Keeps the selected commit as is.
+        PickCommit,
+        /// Keeps the selected commit and edits its message.
+        RewordCommit,
+        /// Melds the selected commit into the previous one, combining their messages.
+        SquashCommit,
+        /// Melds the selected commit into the previous one, discarding its message.
+        FixupCommit,
+        /// Removes the selected commit.
+        DropCommit,
+        /// Saves the message of the reworded commit and returns to the commit list.
+        ConfirmMessage,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+    workspace.register_action(|workspace, _: &git::RebaseContinue, window, cx| {
+        run_rebase_command(workspace, RebaseCommand::Continue, window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::RebaseSkip, window, cx| {
+        run_rebase_command(workspace, RebaseCommand::Skip, window, cx);
+    });
+    workspace.register_action(|workspace, _: &git::RebaseAbort, window, cx| {
+        run_rebase_command(workspace, RebaseCommand::Abort, window, cx);
+    });
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    action: &git::InteractiveRebase,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = active_repository(workspace, cx) else {
+        return;
+    };
+    let base = action.base.clone();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        RebaseEditor::new(repository, base, window, cx)
+    });
+}
+
+fn active_repository(workspace: &Workspace, cx: &App) -> Option<Entity<Repository>> {
+    workspace
+        .panel::<GitPanel>(cx)
+        .and_then(|panel| panel.read(cx).active_repository.clone())
+        .or_else(|| workspace.project().read(cx).active_repository(cx))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RebaseCommand {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl RebaseCommand {
+    fn run(self, repository: &Entity<Repository>, cx: &mut App) -> Task<anyhow::Result<()>> {
+        let receiver = repository.update(cx, |repository, _| match self {
+            RebaseCommand::Continue => repository.rebase_continue(),
+            RebaseCommand::Skip => repository.rebase_skip(),
+            RebaseCommand::Abort => repository.rebase_abort(),
+        });
+        cx.background_spawn(async move { receiver.await.context("rebase was canceled")? })
+    }
+
+    fn error_message(self) -> &'static str {
+        match self {
+            RebaseCommand::Continue => "Failed to continue rebase",
+            RebaseCommand::Skip => "Failed to skip commit",
+            RebaseCommand::Abort => "Failed to abort rebase",
+        }
+    }
+}
+
+fn run_rebase_command(
+    workspace: &mut Workspace,
+    command: RebaseCommand,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(repository) = active_repository(workspace, cx) else {
+        return;
+    };
+    command.run(&repository, cx).detach_and_prompt_err(
+        command.error_message(),
+        window,
+        cx,
+        |_, _, _| None,
+    );
+}
+
+/// Lets the user reorder the commits of the current branch and choose what to do with each
+/// of them before rebasing, or resume a rebase that stopped.
+pub struct RebaseEditor {
+    repository: Entity<Repository>,
+    base: Option<SharedString>,
+    entries: Vec<RebaseEntry>,
+    selected_index: usize,
+    /// Set while a rebase is in progress, in which case no new one can be started.
+    status: Option<RebaseStatus>,
+    loading: bool,
+    editing_message: bool,
+    message_editor: Entity<Editor>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    _load: Task<()>,
+}
+
+impl RebaseEditor {
+    fn new(
+        repository: Entity<Repository>,
+        base: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let message_editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(1, 6, window, cx);
+            editor.set_placeholder_text("Commit message", window, cx);
+            editor
+        });
+        let focus_handle = cx.focus_handle();
+        window.focus(&focus_handle);
+
+        let mut this = Self {
+            repository,
+            base: base.map(Into::into),
+            entries: Vec::new(),
+            selected_index: 0,
+            status: None,
+            loading: true,
+            editing_message: false,
+            message_editor,
+            focus_handle,
+            scroll_handle: UniformListScrollHandle::new(),
+            _load: Task::ready(()),
+        };
+        this.reload(window, cx);
+        this
+    }
+
+    fn reload(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let repository = self.repository.clone();
+        let base = self.base.clone();
+        self.loading = true;
+        self._load = cx.spawn_in(window, async move |this, cx| {
+            let result = async {
+                let status = repository
+                    .update(cx, |repository, _| repository.rebase_status())?
+                    .await??;
+                if status.is_some() {
+                    return anyhow::Ok((status, None, Vec::new()));
+                }
+
+                let base = match base {
+                    Some(base) => base,
+                    None => {
+                        let upstream = repository.read_with(cx, |repository, _| {
+                            repository
+                                .branch
+                                .as_ref()
+                                .and_then(|branch| branch.upstream.as_ref())
+                                .map(|upstream| upstream.ref_name.clone())
+                        })?;
+                        match upstream {
+                            Some(upstream) => upstream,
+                            None => repository
+                                .update(cx, |repository, _| repository.default_branch())?
+                                .await??
+                                .context("no upstream or default branch to rebase onto")?,
+                        }
+                    }
+                };
+                let entries = repository
+                    .update(cx, |repository, _| {
+                        repository.rebase_entries(base.to_string())
+                    })?
+                    .await??;
+                Ok((None, Some(base), entries))
+            }
+            .await;
+
+            this.update_in(cx, |this, window, cx| {
+                this.loading = false;
+                match result {
+                    Ok((status, base, entries)) => {
+                        this.status = status;
+                        if base.is_some() {
+                            this.base = base;
+                        }
+                        this.entries = entries;
+                        this.selected_index = 0;
+                    }
+                    Err(error) => {
+                        log::error!("failed to load commits to rebase: {error:#}");
+                        this.entries.clear();
+                    }
+                }
+                if !this.editing_message {
+                    window.focus(&this.focus_handle);
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_index + 1 < self.entries.len() {
+            self.select_index(self.selected_index + 1, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_index > 0 {
+            self.select_index(self.selected_index - 1, cx);
+        }
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = ix;
+        self.scroll_handle
+            .scroll_to_item(ix, gpui::ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn move_entry_up(&mut self, _: &MoveEntryUp, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index;
+        if ix > 0 && ix < self.entries.len() {
+            self.entries.swap(ix, ix - 1);
+            self.select_index(ix - 1, cx);
+        }
+    }
+
+    fn move_entry_down(&mut self, _: &MoveEntryDown, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.selected_index;
+        if ix + 1 < self.entries.len() {
+            self.entries.swap(ix, ix + 1);
+            self.select_index(ix + 1, cx);
+        }
+    }
+
+    fn pick_commit(&mut self, _: &PickCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Pick, window, cx);
+    }
+
+    fn reword_commit(&mut self, _: &RewordCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Reword, window, cx);
+    }
+
+    fn squash_commit(&mut self, _: &SquashCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Squash, window, cx);
+    }
+
+    fn fixup_commit(&mut self, _: &FixupCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Fixup, window, cx);
+    }
+
+    fn drop_commit(&mut self, _: &DropCommit, window: &mut Window, cx: &mut Context<Self>) {
+        self.set_action(self.selected_index, RebaseAction::Drop, window, cx);
+    }
+
+    fn set_action(
+        &mut self,
+        ix: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get_mut(ix) else {
+            return;
+        };
+        entry.action = action;
+        if action != RebaseAction::Reword {
+            entry.message = None;
+        } else {
+            let message = entry
+                .message
+                .clone()
+                .unwrap_or_else(|| entry.subject.clone());
+            self.selected_index = ix;
+            self.editing_message = true;
+            self.message_editor.update(cx, |editor, cx| {
+                editor.set_text(message, window, cx);
+                editor.select_all(&Default::default(), window, cx);
+            });
+            window.focus(&self.message_editor.focus_handle(cx));
+        }
+        cx.notify();
+    }
+
+    fn confirm_message(&mut self, _: &ConfirmMessage, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.editing_message {
+            return;
+        }
+        let message = self.message_editor.read(cx).text(cx);
+        if let Some(entry) = self.entries.get_mut(self.selected_index) {
+            let message = message.trim();
+            if message.is_empty() || message == entry.subject.as_ref() {
+                entry.message = None;
+            } else {
+                entry.message = Some(message.to_string().into());
+            }
+        }
+        self.editing_message = false;
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_message {
+            self.editing_message = false;
+            window.focus(&self.focus_handle);
+            cx.notify();
+        } else {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_message {
+            self.confirm_message(&ConfirmMessage, window, cx);
+        } else if self.status.is_some() {
+            self.run_command(RebaseCommand::Continue, window, cx);
+        } else {
+            self.start_rebase(window, cx);
+        }
+    }
+
+    fn start_rebase(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(base) = self.base.clone() else {
+            return;
+        };
+        if self.loading || self.entries.is_empty() {
+            return;
+        }
+        let receiver = self.repository.update(cx, |repository, _| {
+            repository.rebase_interactive(base.to_string(), self.entries.clone())
+        });
+        self.finish_after(
+            cx.background_spawn(async move { receiver.await.context("rebase was canceled")? }),
+            "Failed to rebase",
+            window,
+            cx,
+        );
+    }
+
+    fn run_command(&mut self, command: RebaseCommand, window: &mut Window, cx: &mut Context<Self>) {
+        let task = command.run(&self.repository, cx);
+        self.finish_after(task, command.error_message(), window, cx);
+    }
+
+    /// Closes the editor once `task` succeeds, unless the rebase stopped again.
+    fn finish_after(
+        &mut self,
+        task: Task<anyhow::Result<()>>,
+        error_message: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.loading = true;
+        cx.notify();
+        cx.spawn_in(window, async move |this, cx| {
+            let result = task.await;
+            this.update_in(cx, |this, window, cx| {
+                this.loading = false;
+                if result.is_ok() {
+                    this.reload(window, cx);
+                }
+            })?;
+            result?;
+
+            let repository = this.read_with(cx, |this, _| this.repository.clone())?;
+            let status = repository
+                .update(cx, |repository, _| repository.rebase_status())?
+                .await??;
+            if status.is_none() {
+                this.update(cx, |_, cx| cx.emit(DismissEvent))?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err(error_message, window, cx, |_, _, _| None);
+    }
+
+    fn dispatch_context(&self, window: &Window, cx: &App) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("RebaseEditor");
+        if self.focus_handle.is_focused(window) {
+            dispatch_context.add("menu");
+            dispatch_context.add("CommitList");
+        }
+        if self
+            .message_editor
+            .focus_handle(cx)
+            .contains_focused(window, cx)
+        {
+            dispatch_context.add("MessageEditor");
+        }
+        dispatch_context
+    }
+
+    fn render_entry(&self, ix: usize, entry: &RebaseEntry, cx: &Context<Self>) -> ListItem {
+        let subject = entry
+            .message
+            .as_ref()
+            .and_then(|message| message.lines().next())
+            .map(|line| SharedString::from(line.to_string()))
+            .unwrap_or_else(|| entry.subject.clone());
+        let short_sha = entry
+            .sha
+            .get(..git::SHORT_SHA_LENGTH)
+            .unwrap_or(entry.sha.as_ref())
+            .to_string();
+        let dropped = entry.action == RebaseAction::Drop;
+        let melded = matches!(entry.action, RebaseAction::Squash | RebaseAction::Fixup);
+
+        ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(ix == self.selected_index)
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        div().w(rems(4.)).child(
+                            Label::new(entry.action.command())
+                                .size(LabelSize::Small)
+                                .color(action_color(entry.action))
+                                .buffer_font(cx),
+                        ),
+                    )
+                    .child(
+                        Label::new(short_sha)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(subject)
+                            .truncate()
+                            .when(melded, |label| label.color(Color::Muted))
+                            .when(dropped, |label| label.color(Color::Muted).strikethrough()),
+                    ),
+            )
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.select_index(ix, cx);
+            }))
+            .on_secondary_mouse_down(cx.listener(move |this, _, window, cx| {
+                // Cycle through the actions, like the todo editors of most git clients.
+                if let Some(entry) = this.entries.get(ix) {
+                    let next = RebaseAction::ALL
+                        .iter()
+                        .position(|action| *action == entry.action)
+                        .map_or(RebaseAction::Pick, |position| {
+                            RebaseAction::ALL[(position + 1) % RebaseAction::ALL.len()]
+                        });
+                    this.set_action(ix, next, window, cx);
+                }
+            }))
+    }
+
+    fn render_header(&self, cx: &Context<Self>) -> impl IntoElement {
+        let title: SharedString = match (&self.status, &self.base) {
+            (Some(status), _) => format!(
+                "Rebasing {} onto {}",
+                status.head_name.as_deref().unwrap_or("detached HEAD"),
+                status
+                    .onto
+                    .get(..git::SHORT_SHA_LENGTH)
+                    .unwrap_or(status.onto.as_ref())
+            )
+            .into(),
+            (None, Some(base)) => format!("Interactive Rebase onto {base}").into(),
+            (None, None) => "Interactive Rebase".into(),
+        };
+        h_flex()
+            .px_3()
+            .pt_2()
+            .pb_1()
+            .w_full()
+            .gap_1p5()
+            .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+            .child(Headline::new(title).size(HeadlineSize::XSmall))
+            .when_some(self.status.as_ref(), |this, status| {
+                this.child(
+                    Label::new(format!("{}/{}", status.step, status.total_steps))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .when(self.loading, |this| {
+                this.child(
+                    Label::new("Loading…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+    }
+
+    fn render_footer(&self, cx: &Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let key_binding = |action: &dyn gpui::Action, cx: &Context<Self>| {
+            KeyBinding::for_action_in(action, &focus_handle, cx).size(rems_from_px(12.))
+        };
+
+        h_flex()
+            .w_full()
+            .p_1p5()
+            .gap_0p5()
+            .justify_end()
+            .border_t_1()
+            .border_color(cx.theme().colors().border_variant)
+            .map(|this| {
+                if self.status.is_some() {
+                    this.child(
+                        Button::new("abort-rebase", "Abort")
+                            .disabled(self.loading)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.run_command(RebaseCommand::Abort, window, cx)
+                            })),
+                    )
+                    .child(
+                        Button::new("skip-commit", "Skip")
+                            .disabled(self.loading)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.run_command(RebaseCommand::Skip, window, cx)
+                            })),
+                    )
+                    .child(
+                        Button::new("continue-rebase", "Continue")
+                            .disabled(self.loading)
+                            .key_binding(key_binding(&Confirm, cx))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.run_command(RebaseCommand::Continue, window, cx)
+                            })),
+                    )
+                } else {
+                    this.child(
+                        Button::new("reword-commit", "Reword")
+                            .key_binding(key_binding(&RewordCommit, cx))
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(RewordCommit.boxed_clone(), cx)
+                            }),
+                    )
+                    .child(
+                        Button::new("start-rebase", "Rebase")
+                            .disabled(self.loading || self.entries.is_empty())
+                            .key_binding(key_binding(&Confirm, cx))
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.start_rebase(window, cx)),
+                            ),
+                    )
+                }
+            })
+    }
+}
+
+fn action_color(action: RebaseAction) -> Color {
+    match action {
+        RebaseAction::Pick => Color::Default,
+        RebaseAction::Reword => Color::Accent,
+        RebaseAction::Squash | RebaseAction::Fixup => Color::Warning,
+        RebaseAction::Drop => Color::Error,
+    }
+}
+
+impl EventEmitter<DismissEvent> for RebaseEditor {}
+impl ModalView for RebaseEditor {}
+
+impl Focusable for RebaseEditor {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RebaseEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+        let view = cx.weak_entity();
+
+        v_flex()
+            .key_context(self.dispatch_context(window, cx))
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::move_entry_up))
+            .on_action(cx.listener(Self::move_entry_down))
+            .on_action(cx.listener(Self::pick_commit))
+            .on_action(cx.listener(Self::reword_commit))
+            .on_action(cx.listener(Self::squash_commit))
+            .on_action(cx.listener(Self::fixup_commit))
+            .on_action(cx.listener(Self::drop_commit))
+            .on_action(cx.listener(Self::confirm_message))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_2(cx)
+            .w(rems(40.))
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.status.is_some() {
+                    this.child(
+                        div().px_3().py_2().child(
+                            Label::new(
+                                "Resolve any conflicts and stage the result, then continue the rebase.",
+                            )
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                        ),
+                    )
+                } else if entry_count == 0 && !self.loading {
+                    this.child(
+                        div().px_3().py_2().child(
+                            Label::new("No commits to rebase")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        div()
+                            .h(rems(20.))
+                            .child(
+                                uniform_list("rebase-entries", entry_count, {
+                                    let view = view.clone();
+                                    move |range, _window, cx| {
+                                        let Some(view) = view.upgrade() else {
+                                            return Vec::new();
+                                        };
+                                        view.update(cx, |this, cx| {
+                                            range
+                                                .filter_map(|ix| {
+                                                    let entry = this.entries.get(ix)?;
+                                                    Some(this.render_entry(ix, entry, cx))
+                                                })
+                                                .collect()
+                                        })
+                                    }
+                                })
+                                .size_full()
+                                .py_1()
+                                .track_scroll(&self.scroll_handle),
+                            )
+                            .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+                    )
+                }
+            })
+            .when(self.editing_message, |this| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .border_t_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .child(self.message_editor.clone()),
+                )
+            })
+            .child(self.render_footer(cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+    }
+
+    fn entry(sha: &str, subject: &str) -> RebaseEntry {
+        RebaseEntry {
+            action: RebaseAction::Pick,
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+            message: None,
+        }
+    }
+
+    #[gpui::test]
+    async fn test_rebase_editor(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "text" }))
+            .await;
+        fs.with_git_state(path!("/dir/.git").as_ref(), true, |state| {
+            state.rebase_entries = vec![
+                entry("aaaaaaaa1", "first"),
+                entry("bbbbbbbb2", "second"),
+                entry("cccccccc3", "third"),
+            ];
+        })
+        .unwrap();
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let repository = cx
+            .read(|cx| project.read(cx).active_repository(cx))
+            .unwrap();
+
+        let window = cx.add_window(|window, cx| {
+            RebaseEditor::new(repository.clone(), Some("main".into()), window, cx)
+        });
+        let rebase_editor = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        cx.run_until_parked();
+
+        rebase_editor.update_in(cx, |rebase_editor, window, cx| {
+            assert_eq!(rebase_editor.entries.len(), 3);
+            rebase_editor.move_entry_down(&MoveEntryDown, window, cx);
+            rebase_editor.squash_commit(&SquashCommit, window, cx);
+            rebase_editor.select_previous(&SelectPrevious, window, cx);
+            rebase_editor.reword_commit(&RewordCommit, window, cx);
+            assert!(rebase_editor.editing_message);
+            rebase_editor.message_editor.update(cx, |editor, cx| {
+                editor.set_text("Second, reworded", window, cx)
+            });
+            rebase_editor.confirm_message(&ConfirmMessage, window, cx);
+
+            let actions = rebase_editor
+                .entries
+                .iter()
+                .map(|entry| (entry.sha.as_ref(), entry.action))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                actions,
+                [
+                    ("bbbbbbbb2", RebaseAction::Reword),
+                    ("aaaaaaaa1", RebaseAction::Squash),
+                    ("cccccccc3", RebaseAction::Pick),
+                ]
+            );
+            rebase_editor.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+
+        let subjects = fs
+            .with_git_state(path!("/dir/.git").as_ref(), false, |state| {
+                state
+                    .rebase_entries
+                    .iter()
+                    .map(|entry| entry.subject.to_string())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        assert_eq!(subjects, ["Second, reworded", "third"]);
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::Context as _;
 
-use git::repository::{Remote, RemoteCommandOutput};
+use git::repository::{Remote, RemoteCommandOutput, SubmoduleAction};
 use linkify::{LinkFinder, LinkKind};
 use ui::SharedString;
 use util::ResultExt as _;
@@ -10,6 +10,7 @@ pub enum RemoteAction {
     Fetch(Option<Remote>),
     Pull(Remote),
     Push(SharedString, Remote),
+    Submodule(SubmoduleAction),
 }
 
 impl RemoteAction {
@@ -18,6 +19,7 @@ impl RemoteAction {
             RemoteAction::Fetch(_) => "fetch",
             RemoteAction::Pull(_) => "pull",
             RemoteAction::Push(_, _) => "push",
+            RemoteAction::Submodule(_) => "submodule",
         }
     }
 }
@@ -157,6 +159,21 @@ pub fn format_output(action: &RemoteAction, output: RemoteCommandOutput) -> Succ
                 style: style.unwrap_or(SuccessStyle::ToastWithLog { output }),
             }
         }
+        RemoteAction::Submodule(action) => {
+            let message = match action {
+                SubmoduleAction::Init => "Initialized submodules",
+                SubmoduleAction::Update => "Updated submodules",
+                SubmoduleAction::Sync => "Synchronized submodule URLs",
+            };
+            SuccessMessage {
+                message: message.into(),
+                style: if output.is_empty() {
+                    SuccessStyle::Toast
+                } else {
+                    SuccessStyle::ToastWithLog { output }
+                },
+            }
+        }
     }
 }
 
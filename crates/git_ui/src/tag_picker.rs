@@ -0,0 +1,1059 @@
+use anyhow::Context as _;
+use editor::Editor;
+use fuzzy::StringMatchCandidate;
+
+use git::repository::{AskPassDelegate, Tag};
+use gpui::{
+    Action, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, ParentElement, Render, SharedString, Styled, Subscription,
+    Task, WeakEntity, Window, actions, rems,
+};
+use notifications::status_toast::{StatusToast, ToastIcon};
+use picker::{Picker, PickerDelegate};
+use project::git_store::Repository;
+use std::sync::Arc;
+use time::OffsetDateTime;
+use ui::{
+    Divider, HighlightedLabel, KeyBinding, ListHeader, ListItem, ListItemSpacing, prelude::*,
+};
+use util::ResultExt;
+use workspace::notifications::DetachAndPromptErr;
+use workspace::{ModalView, Workspace};
+
+use crate::askpass_modal::AskPassModal;
+use crate::commit_view::CommitView;
+use crate::{git_panel::show_error_toast, picker_prompt, tag_picker};
+
+actions!(
+    tag_picker,
+    [
+        /// Deletes the selected git tag.
+        DeleteTag,
+        /// Pushes the selected git tag to a remote.
+        PushTag,
+        /// Deletes the selected git tag from a remote.
+        DeleteRemoteTag,
+    ]
+);
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(open);
+}
+
+pub fn open(
+    workspace: &mut Workspace,
+    _: &zed_actions::git::Tag,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let repository = workspace.project().read(cx).active_repository(cx);
+    open_for_commit(workspace, repository, None, window, cx);
+}
+
+/// Opens the tag picker, creating new tags at `commit` instead of HEAD when it's given.
+pub(crate) fn open_for_commit(
+    workspace: &mut Workspace,
+    repository: Option<Entity<Repository>>,
+    commit: Option<SharedString>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let workspace_handle = workspace.weak_handle();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        TagList::new(workspace_handle, repository, commit, rems(34.), window, cx)
+    })
+}
+
+pub struct TagList {
+    width: Rems,
+    pub picker: Entity<Picker<TagListDelegate>>,
+    picker_focus_handle: FocusHandle,
+    _subscription: Subscription,
+}
+
+impl TagList {
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        repository: Option<Entity<Repository>>,
+        commit: Option<SharedString>,
+        width: Rems,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let tags_request = repository
+            .clone()
+            .map(|repository| repository.update(cx, |repository, _| repository.tags()));
+
+        cx.spawn_in(window, async move |this, cx| {
+            let tags = tags_request.context("No active repository")?.await??;
+
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.all_tags = Some(tags);
+                    picker.refresh(window, cx);
+                })
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+
+        let delegate = TagListDelegate::new(workspace, repository, commit, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, _| {
+            picker.delegate.focus_handle = picker_focus_handle.clone();
+        });
+
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        Self {
+            picker,
+            picker_focus_handle,
+            width,
+            _subscription,
+        }
+    }
+
+    fn handle_delete(&mut self, _: &DeleteTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .delete_at(picker.delegate.selected_index, window, cx)
+        })
+    }
+
+    fn handle_push(&mut self, _: &PushTag, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_at(picker.delegate.selected_index, false, window, cx)
+        })
+    }
+
+    fn handle_delete_remote(
+        &mut self,
+        _: &DeleteRemoteTag,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.picker.update(cx, |picker, cx| {
+            picker
+                .delegate
+                .push_at(picker.delegate.selected_index, true, window, cx)
+        })
+    }
+}
+
+impl ModalView for TagList {}
+impl EventEmitter<DismissEvent> for TagList {}
+
+impl Focusable for TagList {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.picker_focus_handle.clone()
+    }
+}
+
+impl Render for TagList {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("GitTagSelector")
+            .w(self.width)
+            .on_action(cx.listener(Self::handle_delete))
+            .on_action(cx.listener(Self::handle_push))
+            .on_action(cx.listener(Self::handle_delete_remote))
+            .child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Entry {
+    Tag { tag: Tag, positions: Vec<usize> },
+    NewTag { name: String },
+}
+
+impl Entry {
+    fn name(&self) -> &str {
+        match self {
+            Entry::Tag { tag, .. } => tag.name.as_ref(),
+            Entry::NewTag { name } => name.as_str(),
+        }
+    }
+
+    #[cfg(test)]
+    fn is_new_tag(&self) -> bool {
+        matches!(self, Self::NewTag { .. })
+    }
+}
+
+#[derive(Debug)]
+enum PickerState {
+    /// When we display the list of tags
+    List,
+    /// When we type the message of a new annotated tag
+    AnnotateTag(SharedString),
+}
+
+pub struct TagListDelegate {
+    workspace: WeakEntity<Workspace>,
+    matches: Vec<Entry>,
+    all_tags: Option<Vec<Tag>>,
+    repo: Option<Entity<Repository>>,
+    /// The commit new tags are created at, HEAD when `None`.
+    commit: Option<SharedString>,
+    selected_index: usize,
+    last_query: String,
+    state: PickerState,
+    focus_handle: FocusHandle,
+}
+
+impl TagListDelegate {
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        repo: Option<Entity<Repository>>,
+        commit: Option<SharedString>,
+        cx: &mut Context<TagList>,
+    ) -> Self {
+        Self {
+            workspace,
+            matches: vec![],
+            all_tags: None,
+            repo,
+            commit,
+            selected_index: 0,
+            last_query: Default::default(),
+            state: PickerState::List,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        message: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let name = name.replace(' ', "-");
+        let commit = self.commit.as_ref().map(ToString::to_string);
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |_, cx| {
+            repo.update(cx, |repo, _| repo.create_tag(name, commit, message))?
+                .await??;
+            workspace
+                .update(cx, |workspace, cx| refresh_commit_views(workspace, cx))
+                .ok();
+
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to create tag", window, cx, |e, _, _| {
+            Some(e.to_string())
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn delete_at(&self, ix: usize, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(Entry::Tag { tag, .. }) = self.matches.get(ix).cloned() else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let result = repo
+                .update(cx, |repo, _| repo.delete_tag(tag.name.to_string()))?
+                .await?;
+
+            if let Err(e) = result {
+                log::error!("Failed to delete tag: {}", e);
+                if let Some(workspace) = workspace.upgrade() {
+                    cx.update(|_window, cx| {
+                        show_error_toast(workspace, format!("tag -d {}", tag.name), e, cx)
+                    })?;
+                }
+                return Ok(());
+            }
+
+            workspace
+                .update(cx, |workspace, cx| refresh_commit_views(workspace, cx))
+                .ok();
+            picker.update_in(cx, |picker, _, cx| {
+                picker
+                    .delegate
+                    .matches
+                    .retain(|entry| entry.name() != tag.name.as_ref());
+                if let Some(all_tags) = &mut picker.delegate.all_tags {
+                    all_tags.retain(|other| other.name != tag.name);
+                }
+
+                if picker.delegate.matches.is_empty() {
+                    picker.delegate.selected_index = 0;
+                } else if picker.delegate.selected_index >= picker.delegate.matches.len() {
+                    picker.delegate.selected_index = picker.delegate.matches.len() - 1;
+                }
+
+                cx.notify();
+            })?;
+
+            anyhow::Ok(())
+        })
+        .detach();
+    }
+
+    fn push_at(
+        &self,
+        ix: usize,
+        delete: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(Entry::Tag { tag, .. }) = self.matches.get(ix) else {
+            return;
+        };
+        let Some(repo) = self.repo.clone() else {
+            return;
+        };
+        push_tag(
+            tag.name.clone(),
+            delete,
+            repo,
+            self.workspace.clone(),
+            window,
+            cx,
+        );
+        cx.emit(DismissEvent);
+    }
+}
+
+impl PickerDelegate for TagListDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select tag…".into()
+    }
+
+    fn render_editor(
+        &self,
+        editor: &Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Div {
+        cx.update_entity(editor, move |editor, cx| {
+            let placeholder = match self.state {
+                PickerState::List => "Select tag…",
+                PickerState::AnnotateTag(_) => "Write a tag message…",
+            };
+            editor.set_placeholder_text(placeholder, window, cx);
+        });
+
+        v_flex()
+            .child(
+                h_flex()
+                    .overflow_hidden()
+                    .flex_none()
+                    .h_9()
+                    .px_2p5()
+                    .child(editor.clone()),
+            )
+            .child(Divider::horizontal())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let Some(all_tags) = self.all_tags.clone() else {
+            return Task::ready(());
+        };
+
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches: Vec<Entry> = if query.is_empty() {
+                all_tags
+                    .into_iter()
+                    .map(|tag| Entry::Tag {
+                        tag,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = all_tags
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, tag)| StringMatchCandidate::new(ix, &tag.name))
+                    .collect::<Vec<StringMatchCandidate>>();
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| Entry::Tag {
+                    tag: all_tags[candidate.candidate_id].clone(),
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+            picker
+                .update(cx, |picker, _| {
+                    if matches!(picker.delegate.state, PickerState::AnnotateTag(_)) {
+                        picker.delegate.last_query = query;
+                        picker.delegate.matches = Vec::new();
+                        picker.delegate.selected_index = 0;
+
+                        return;
+                    }
+
+                    if !query.is_empty()
+                        && !matches.first().is_some_and(|entry| entry.name() == query)
+                    {
+                        matches.push(Entry::NewTag {
+                            name: query.replace(' ', "-"),
+                        });
+                    }
+                    let delegate = &mut picker.delegate;
+                    delegate.matches = matches;
+                    if delegate.matches.is_empty() {
+                        delegate.selected_index = 0;
+                    } else {
+                        delegate.selected_index =
+                            core::cmp::min(delegate.selected_index, delegate.matches.len() - 1);
+                    }
+                    delegate.last_query = query;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let PickerState::AnnotateTag(name) = &self.state {
+            let message = self.last_query.trim();
+            if message.is_empty() {
+                return;
+            }
+            self.create_tag(name.to_string(), Some(message.to_string()), window, cx);
+            self.state = PickerState::List;
+            return;
+        }
+
+        let Some(entry) = self.matches.get(self.selected_index()) else {
+            return;
+        };
+
+        match entry {
+            Entry::Tag { tag, .. } => {
+                let Some(repo) = self.repo.clone() else {
+                    return;
+                };
+                CommitView::open(
+                    tag.sha.to_string(),
+                    repo.downgrade(),
+                    self.workspace.clone(),
+                    None,
+                    None,
+                    window,
+                    cx,
+                );
+                cx.emit(DismissEvent);
+            }
+            Entry::NewTag { name } => {
+                if secondary {
+                    self.create_tag(name.clone(), None, window, cx);
+                    return;
+                }
+                self.state = PickerState::AnnotateTag(name.clone().into());
+                self.matches = Vec::new();
+                self.selected_index = 0;
+                cx.spawn_in(window, async move |this, cx| {
+                    this.update_in(cx, |picker, window, cx| {
+                        picker.set_query("", window, cx);
+                    })
+                })
+                .detach_and_log_err(cx);
+                cx.notify();
+            }
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.state = PickerState::List;
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let entry = self.matches.get(ix)?;
+
+        let (icon, title, details) = match entry {
+            Entry::Tag { tag, positions } => {
+                let tag_time = OffsetDateTime::from_unix_timestamp(tag.timestamp)
+                    .unwrap_or_else(|_| OffsetDateTime::now_utc());
+                let local_offset =
+                    time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
+                let formatted_time = time_format::format_localized_timestamp(
+                    tag_time,
+                    OffsetDateTime::now_utc(),
+                    local_offset,
+                    time_format::TimestampFormat::Relative,
+                );
+                let short_sha = tag.sha.get(..7).unwrap_or(tag.sha.as_ref());
+                let details = match &tag.message {
+                    Some(message) => format!("{short_sha}  •  {message}"),
+                    None => short_sha.to_string(),
+                };
+                (
+                    Icon::new(IconName::Hash).color(Color::Muted),
+                    HighlightedLabel::new(tag.name.clone(), positions.clone())
+                        .single_line()
+                        .truncate()
+                        .into_any_element(),
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .gap_1p5()
+                        .child(
+                            div().max_w_96().child(
+                                Label::new(details)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .truncate(),
+                            ),
+                        )
+                        .child(
+                            Label::new(formatted_time)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+            }
+            Entry::NewTag { name } => {
+                let target = match &self.commit {
+                    Some(commit) => {
+                        format!("At commit {}", commit.get(..7).unwrap_or(commit.as_ref()))
+                    }
+                    None => "At the current commit".to_string(),
+                };
+                (
+                    Icon::new(IconName::Plus).color(Color::Muted),
+                    Label::new(format!("Create Tag: \"{name}\"…"))
+                        .single_line()
+                        .truncate()
+                        .into_any_element(),
+                    h_flex().w_full().child(
+                        Label::new(target)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    ),
+                )
+            }
+        };
+
+        Some(
+            ListItem::new(SharedString::from(format!("tag-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_3()
+                        .flex_grow()
+                        .child(icon)
+                        .child(v_flex().w_full().child(title).child(details)),
+                ),
+        )
+    }
+
+    fn render_header(
+        &self,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        matches!(self.state, PickerState::List)
+            .then(|| ListHeader::new("Tags").inset(true).into_any_element())
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        match self.state {
+            PickerState::List => Some("No tags found".into()),
+            PickerState::AnnotateTag(_) => None,
+        }
+    }
+
+    fn render_footer(&self, _: &mut Window, cx: &mut Context<Picker<Self>>) -> Option<AnyElement> {
+        let focus_handle = self.focus_handle.clone();
+        let footer_container = || {
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+        };
+
+        match &self.state {
+            PickerState::AnnotateTag(name) => Some(
+                footer_container()
+                    .justify_between()
+                    .child(
+                        Label::new(format!("Annotating tag {name}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Button::new("create-annotated-tag", "Create")
+                            .key_binding(
+                                KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                            )
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.delegate.confirm(false, window, cx);
+                            })),
+                    )
+                    .into_any_element(),
+            ),
+            PickerState::List => match self.matches.get(self.selected_index) {
+                Some(Entry::NewTag { .. }) => Some(
+                    footer_container()
+                        .child(
+                            Button::new("create-lightweight-tag", "Create Lightweight")
+                                .key_binding(
+                                    KeyBinding::for_action_in(
+                                        &menu::SecondaryConfirm,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.delegate.confirm(true, window, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("create-annotated-tag", "Create Annotated")
+                                .key_binding(
+                                    KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.delegate.confirm(false, window, cx);
+                                })),
+                        )
+                        .into_any_element(),
+                ),
+                Some(Entry::Tag { .. }) => Some(
+                    footer_container()
+                        .child(
+                            Button::new("delete-tag", "Delete")
+                                .key_binding(
+                                    KeyBinding::for_action_in(
+                                        &tag_picker::DeleteTag,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(tag_picker::DeleteTag.boxed_clone(), cx)
+                                }),
+                        )
+                        .child(
+                            Button::new("delete-remote-tag", "Delete on Remote")
+                                .key_binding(
+                                    KeyBinding::for_action_in(
+                                        &tag_picker::DeleteRemoteTag,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(
+                                        tag_picker::DeleteRemoteTag.boxed_clone(),
+                                        cx,
+                                    )
+                                }),
+                        )
+                        .child(
+                            Button::new("push-tag", "Push")
+                                .key_binding(
+                                    KeyBinding::for_action_in(
+                                        &tag_picker::PushTag,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                    .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(tag_picker::PushTag.boxed_clone(), cx)
+                                }),
+                        )
+                        .child(
+                            Button::new("view-tag", "View")
+                                .key_binding(
+                                    KeyBinding::for_action_in(&menu::Confirm, &focus_handle, cx)
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                )
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.delegate.confirm(false, window, cx);
+                                })),
+                        )
+                        .into_any_element(),
+                ),
+                None => None,
+            },
+        }
+    }
+}
+
+/// Pushes `tag` to a remote picked by the user, or deletes it from that remote when `delete` is set.
+fn push_tag(
+    tag: SharedString,
+    delete: bool,
+    repo: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let remotes = repo.update(cx, |repo, _| repo.get_remotes(None, true));
+    let action = if delete {
+        format!("push --delete {tag}")
+    } else {
+        format!("push {tag}")
+    };
+
+    window
+        .spawn(cx, async move |cx| {
+            let remotes: Vec<SharedString> = remotes
+                .await??
+                .into_iter()
+                .map(|remote| remote.name)
+                .collect();
+            anyhow::ensure!(!remotes.is_empty(), "No remotes configured");
+
+            let prompt = if delete {
+                "Pick which remote to delete the tag from"
+            } else {
+                "Pick which remote to push the tag to"
+            };
+            let selection = cx
+                .update(|window, cx| {
+                    picker_prompt::prompt(prompt, remotes.clone(), workspace.clone(), window, cx)
+                })?
+                .await;
+            let Some(selection) = selection else {
+                return Ok(());
+            };
+            let remote = remotes[selection].clone();
+
+            let askpass = cx.update(|window, cx| {
+                askpass_delegate(format!("git push {remote}"), workspace.clone(), window, cx)
+            })?;
+            let output = repo
+                .update(cx, |repo, cx| {
+                    if delete {
+                        repo.delete_remote_tag(tag.clone(), remote.clone(), askpass, cx)
+                    } else {
+                        repo.push_tag(tag.clone(), remote.clone(), askpass, cx)
+                    }
+                })?
+                .await?;
+
+            let Some(workspace) = workspace.upgrade() else {
+                return Ok(());
+            };
+            cx.update(|_, cx| match output {
+                Ok(_) => {
+                    let message = if delete {
+                        format!("Deleted tag {tag} from {remote}")
+                    } else {
+                        format!("Pushed tag {tag} to {remote}")
+                    };
+                    workspace.update(cx, |workspace, cx| {
+                        let toast = StatusToast::new(message, cx, |this, _| {
+                            this.icon(ToastIcon::new(IconName::Check).color(Color::Success))
+                        });
+                        workspace.toggle_status_toast(toast, cx);
+                    })
+                }
+                Err(e) => {
+                    log::error!("Error while pushing tag {:?}", e);
+                    show_error_toast(workspace, action, e, cx)
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+}
+
+fn askpass_delegate(
+    operation: impl Into<SharedString>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) -> AskPassDelegate {
+    let operation = operation.into();
+    let window = window.window_handle();
+    AskPassDelegate::new(&mut cx.to_async(), move |prompt, tx, cx| {
+        window
+            .update(cx, |_, window, cx| {
+                workspace.update(cx, |workspace, cx| {
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        AskPassModal::new(operation.clone(), prompt.into(), tx, window, cx)
+                    });
+                })
+            })
+            .ok();
+    })
+}
+
+/// Reloads the tags shown by the open commit views after a tag was created or deleted.
+fn refresh_commit_views(workspace: &mut Workspace, cx: &mut Context<Workspace>) {
+    for commit_view in workspace
+        .items_of_type::<CommitView>(cx)
+        .collect::<Vec<_>>()
+    {
+        commit_view.update(cx, |commit_view, cx| commit_view.reload_tags(cx));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    fn tag(name: &str, sha: &str, message: Option<&str>) -> Tag {
+        Tag {
+            name: name.to_string().into(),
+            sha: sha.to_string().into(),
+            message: message.map(|message| message.to_string().into()),
+            timestamp: 0,
+        }
+    }
+
+    #[gpui::test]
+    async fn test_create_and_delete_tags(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "content" }))
+            .await;
+        fs.with_git_state(Path::new(path!("/dir/.git")), true, |state| {
+            state.tags = vec![
+                tag("v1.0.0", "abc", Some("First release")),
+                tag("nightly", "abc", None),
+            ];
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| project.active_repository(cx));
+
+        let tag_list = workspace.update_in(cx, |workspace, window, cx| {
+            let workspace_handle = workspace.weak_handle();
+            cx.new(|cx| {
+                TagList::new(
+                    workspace_handle,
+                    repository.clone(),
+                    None,
+                    rems(34.),
+                    window,
+                    cx,
+                )
+            })
+        });
+        cx.run_until_parked();
+
+        let picker = tag_list.read_with(cx, |tag_list, _| tag_list.picker.clone());
+        picker.update(cx, |picker, _| {
+            let names = picker
+                .delegate
+                .matches
+                .iter()
+                .map(Entry::name)
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["v1.0.0", "nightly"]);
+        });
+
+        // Typing a name that doesn't exist offers to create it.
+        picker.update_in(cx, |picker, window, cx| {
+            picker.set_query("v2.0.0", window, cx);
+        });
+        cx.run_until_parked();
+        picker.update(cx, |picker, _| {
+            assert!(picker.delegate.matches.last().unwrap().is_new_tag());
+            picker.delegate.selected_index = picker.delegate.matches.len() - 1;
+        });
+
+        // Confirming asks for a message and creates an annotated tag.
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+        picker.update_in(cx, |picker, window, cx| {
+            assert!(matches!(
+                picker.delegate.state,
+                PickerState::AnnotateTag(ref name) if name.as_ref() == "v2.0.0"
+            ));
+            picker.set_query("Second release", window, cx);
+        });
+        cx.run_until_parked();
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.confirm(false, window, cx);
+        });
+        cx.run_until_parked();
+
+        let tags = repository
+            .clone()
+            .unwrap()
+            .update(cx, |repository, _| repository.tags())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tags[0], tag("v2.0.0", "abc", Some("Second release")));
+
+        // Deleting removes the tag from the repository and the list.
+        let tag_list = workspace.update_in(cx, |workspace, window, cx| {
+            let workspace_handle = workspace.weak_handle();
+            cx.new(|cx| {
+                TagList::new(
+                    workspace_handle,
+                    repository.clone(),
+                    None,
+                    rems(34.),
+                    window,
+                    cx,
+                )
+            })
+        });
+        cx.run_until_parked();
+        let picker = tag_list.read_with(cx, |tag_list, _| tag_list.picker.clone());
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.delete_at(0, window, cx);
+        });
+        cx.run_until_parked();
+        picker.update(cx, |picker, _| {
+            let names = picker
+                .delegate
+                .matches
+                .iter()
+                .map(Entry::name)
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["v1.0.0", "nightly"]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_push_tag(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/dir"), json!({ ".git": {}, "file.txt": "content" }))
+            .await;
+        fs.with_git_state(Path::new(path!("/dir/.git")), true, |state| {
+            state.tags = vec![tag("v1.0.0", "abc", None)];
+            state
+                .remotes
+                .insert("origin".into(), "git@example.com:zed.git".into());
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        cx.run_until_parked();
+        let repository = project
+            .read_with(cx, |project, cx| project.active_repository(cx))
+            .unwrap();
+        let pushed_tags = || {
+            fs.with_git_state(Path::new(path!("/dir/.git")), false, |state| {
+                state.pushed_tags.get("origin").cloned().unwrap_or_default()
+            })
+            .unwrap()
+        };
+
+        // With a single remote, the tag is pushed there without asking.
+        workspace.update_in(cx, |workspace, window, cx| {
+            push_tag(
+                "v1.0.0".into(),
+                false,
+                repository.clone(),
+                workspace.weak_handle(),
+                window,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        assert!(pushed_tags().contains("v1.0.0"));
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_status_toast::<StatusToast>(cx).is_some());
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            push_tag(
+                "v1.0.0".into(),
+                true,
+                repository.clone(),
+                workspace.weak_handle(),
+                window,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        assert!(pushed_tags().is_empty());
+    }
+}
@@ -162,6 +162,14 @@ pub struct LanguageSettings {
     pub word_diff_enabled: bool,
     /// Whether to use tree-sitter bracket queries to detect and colorize the brackets in the editor.
     pub colorize_brackets: bool,
+    /// Whether to highlight the code with semantic tokens from language servers.
+    pub semantic_tokens: bool,
+    /// Which theme syntax highlight to use for each semantic token type (and modifier).
+    pub semantic_token_highlights: HashMap<String, String>,
+    /// Whether to fold code using the folding ranges from language servers instead of indentation.
+    pub lsp_folding_ranges: bool,
+    /// Whether to expand selections using the selection ranges from language servers instead of tree-sitter.
+    pub lsp_selection_ranges: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -596,6 +604,10 @@ impl settings::Settings for AllLanguageSettings {
                 show_completions_on_input: settings.show_completions_on_input.unwrap(),
                 show_completion_documentation: settings.show_completion_documentation.unwrap(),
                 colorize_brackets: settings.colorize_brackets.unwrap(),
+                semantic_tokens: settings.semantic_tokens.unwrap(),
+                semantic_token_highlights: settings.semantic_token_highlights.unwrap(),
+                lsp_folding_ranges: settings.lsp_folding_ranges.unwrap(),
+                lsp_selection_ranges: settings.lsp_selection_ranges.unwrap(),
                 completions: CompletionSettings {
                     words: completions.words.unwrap(),
                     words_min_length: completions.words_min_length.unwrap() as usize,
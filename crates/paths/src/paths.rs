@@ -235,6 +235,12 @@ pub fn keymap_file() -> &'static PathBuf {
     KEYMAP_FILE.get_or_init(|| config_dir().join("keymap.json"))
 }
 
+/// Returns the path to the vim init file, which is read when vim mode is enabled.
+pub fn vim_init_file() -> &'static PathBuf {
+    static VIM_INIT_FILE: OnceLock<PathBuf> = OnceLock::new();
+    VIM_INIT_FILE.get_or_init(|| config_dir().join("vimrc"))
+}
+
 /// Returns the path to the `keymap_backup.json` file.
 pub fn keymap_backup_file() -> &'static PathBuf {
     static KEYMAP_FILE: OnceLock<PathBuf> = OnceLock::new();
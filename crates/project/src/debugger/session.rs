@@ -11,7 +11,9 @@ use super::dap_command::{
 };
 use super::dap_store::DapStore;
 use crate::debugger::breakpoint_store::BreakpointSessionState;
-use crate::debugger::dap_command::{DataBreakpointContext, ReadMemory};
+use crate::debugger::dap_command::{
+    DataBreakpointContext, DisassembleCommand, ReadMemory, SetInstructionBreakpointsCommand,
+};
 use crate::debugger::memory::{self, Memory, MemoryIterator, MemoryPageBuilder, PageAddress};
 use anyhow::{Context as _, Result, anyhow, bail};
 use base64::Engine;
@@ -140,9 +142,24 @@ impl From<dap::Thread> for Thread {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Watcher {
     pub expression: SharedString,
+    /// The result of the last evaluation, or its error message when `is_error` is set.
     pub value: SharedString,
     pub variables_reference: u64,
     pub presentation_hint: Option<VariablePresentationHint>,
+    pub is_error: bool,
+}
+
+impl Watcher {
+    /// A watcher whose expression hasn't been evaluated yet.
+    fn pending(expression: SharedString) -> Self {
+        Self {
+            expression,
+            value: SharedString::default(),
+            variables_reference: 0,
+            presentation_hint: None,
+            is_error: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -703,13 +720,15 @@ pub struct Session {
     parent_session: Option<Entity<Session>>,
     output_token: OutputToken,
     output: Box<circular_buffer::CircularBuffer<MAX_TRACKED_OUTPUT_EVENTS, dap::OutputEvent>>,
-    watchers: HashMap<SharedString, Watcher>,
+    watchers: IndexMap<SharedString, Watcher>,
     is_session_terminated: bool,
     requests: HashMap<TypeId, HashMap<RequestSlot, Shared<Task<Option<()>>>>>,
     pub(crate) breakpoint_store: Entity<BreakpointStore>,
     ignore_breakpoints: bool,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
     data_breakpoints: BTreeMap<String, DataBreakpointState>,
+    instruction_breakpoints: BTreeMap<String, dap::InstructionBreakpoint>,
+    disassembly: HashMap<DisassembleCommand, Arc<[dap::DisassembledInstruction]>>,
     background_tasks: Vec<Task<()>>,
     restart_task: Option<Task<()>>,
     task_context: TaskContext,
@@ -807,6 +826,7 @@ pub enum SessionEvent {
         sender: mpsc::Sender<Result<u32>>,
     },
     DataBreakpointInfo,
+    Disassembly,
     ConsoleOutput,
     HistoricSnapshotSelected,
 }
@@ -874,7 +894,7 @@ impl Session {
                 child_session_ids: HashSet::default(),
                 parent_session,
                 capabilities: Capabilities::default(),
-                watchers: HashMap::default(),
+                watchers: IndexMap::default(),
                 output_token: OutputToken(0),
                 output: circular_buffer::CircularBuffer::boxed(),
                 requests: HashMap::default(),
@@ -884,6 +904,8 @@ impl Session {
                 ignore_breakpoints: false,
                 breakpoint_store,
                 data_breakpoints: Default::default(),
+                instruction_breakpoints: Default::default(),
+                disassembly: HashMap::default(),
                 exception_breakpoints: Default::default(),
                 label,
                 adapter,
@@ -1772,6 +1794,7 @@ impl Session {
         self.invalidate_command_type::<ThreadsCommand>();
         self.invalidate_command_type::<DataBreakpointInfoCommand>();
         self.invalidate_command_type::<ReadMemory>();
+        self.invalidate_command_type::<DisassembleCommand>();
         let executor = self.as_running().map(|running| running.executor.clone());
         if let Some(executor) = executor {
             self.memory.clear(&executor);
@@ -1981,6 +2004,82 @@ impl Session {
         );
     }
 
+    /// Disassembles `instruction_count` instructions starting `instruction_offset` instructions
+    /// away from `memory_reference`. Returns the last known instructions for that range, if any.
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Option<Arc<[dap::DisassembledInstruction]>> {
+        let command = DisassembleCommand {
+            memory_reference,
+            instruction_offset,
+            instruction_count,
+        };
+        self.fetch(
+            command.clone(),
+            {
+                let command = command.clone();
+                move |this, result, cx| {
+                    let Some(instructions) = result.log_err() else {
+                        return;
+                    };
+                    this.disassembly.insert(command, instructions);
+                    cx.emit(SessionEvent::Disassembly);
+                    cx.notify();
+                }
+            },
+            cx,
+        );
+
+        self.disassembly.get(&command).cloned()
+    }
+
+    pub fn instruction_breakpoints(&self) -> impl Iterator<Item = &dap::InstructionBreakpoint> {
+        self.instruction_breakpoints.values()
+    }
+
+    pub fn has_instruction_breakpoint(&self, instruction_reference: &str) -> bool {
+        self.instruction_breakpoints
+            .contains_key(instruction_reference)
+    }
+
+    pub fn toggle_instruction_breakpoint(
+        &mut self,
+        instruction_reference: String,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .instruction_breakpoints
+            .remove(&instruction_reference)
+            .is_none()
+        {
+            self.instruction_breakpoints.insert(
+                instruction_reference.clone(),
+                dap::InstructionBreakpoint {
+                    instruction_reference,
+                    offset: None,
+                    condition: None,
+                    hit_condition: None,
+                    mode: None,
+                },
+            );
+        }
+        self.send_instruction_breakpoints(cx);
+        cx.notify();
+    }
+
+    fn send_instruction_breakpoints(&mut self, cx: &mut Context<Self>) {
+        if let Some(mode) = self.as_running() {
+            let command = SetInstructionBreakpointsCommand {
+                breakpoints: self.instruction_breakpoints.values().cloned().collect(),
+            };
+            mode.request(command).detach_and_log_err(cx);
+        }
+    }
+
     pub fn ignore_breakpoints(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -2583,7 +2682,7 @@ impl Session {
             .collect()
     }
 
-    pub fn watchers(&self) -> &HashMap<SharedString, Watcher> {
+    pub fn watchers(&self) -> &IndexMap<SharedString, Watcher> {
         &self.watchers
     }
 
@@ -2601,23 +2700,64 @@ impl Session {
         });
 
         cx.spawn(async move |this, cx| {
-            let response = request.await?;
+            let response = request.await;
 
             this.update(cx, |session, cx| {
-                session.watchers.insert(
-                    expression.clone(),
-                    Watcher {
-                        expression,
+                // A watch that can't be evaluated in this frame stays in the list with its error.
+                let watcher = match response {
+                    Ok(response) => Watcher {
+                        expression: expression.clone(),
                         value: response.result.into(),
                         variables_reference: response.variables_reference,
                         presentation_hint: response.presentation_hint,
+                        is_error: false,
                     },
-                );
+                    Err(error) => Watcher {
+                        value: error.to_string().into(),
+                        is_error: true,
+                        ..Watcher::pending(expression.clone())
+                    },
+                };
+                session.watchers.insert(expression, watcher);
                 cx.emit(SessionEvent::Watchers);
             })
         })
     }
 
+    /// Replaces the expression of a watcher, keeping its position in the list.
+    pub fn edit_watcher(
+        &mut self,
+        old_expression: SharedString,
+        new_expression: SharedString,
+        frame_id: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if let Some(ix) = self.watchers.get_index_of(&old_expression) {
+            self.watchers.shift_remove_index(ix);
+            self.watchers.shift_insert(
+                ix.min(self.watchers.len()),
+                new_expression.clone(),
+                Watcher::pending(new_expression.clone()),
+            );
+        }
+        self.add_watcher(new_expression, frame_id, cx)
+    }
+
+    /// Adds watchers without evaluating them, e.g. when restoring them from a previous session.
+    /// They get evaluated the next time the watchers are refreshed.
+    pub fn restore_watchers(
+        &mut self,
+        expressions: impl IntoIterator<Item = SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        for expression in expressions {
+            self.watchers
+                .entry(expression.clone())
+                .or_insert_with(|| Watcher::pending(expression));
+        }
+        cx.emit(SessionEvent::Watchers);
+    }
+
     pub fn refresh_watchers(&mut self, frame_id: u64, cx: &mut Context<Self>) {
         let watches = self.watchers.clone();
         for (_, watch) in watches.into_iter() {
@@ -2626,8 +2766,9 @@ impl Session {
         }
     }
 
-    pub fn remove_watcher(&mut self, expression: SharedString) {
-        self.watchers.remove(&expression);
+    pub fn remove_watcher(&mut self, expression: SharedString, cx: &mut Context<Self>) {
+        self.watchers.shift_remove(&expression);
+        cx.emit(SessionEvent::Watchers);
     }
 
     pub fn variables(
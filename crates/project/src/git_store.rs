@@ -29,9 +29,11 @@ use git::{
     blame::Blame,
     parse_git_remote_url,
     repository::{
-        Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
-        GitRepository, GitRepositoryCheckpoint, PushOptions, Remote, RemoteCommandOutput, RepoPath,
-        ResetMode, UpstreamTrackingStatus, Worktree as GitWorktree,
+        BisectCandidate, BisectMark, BisectStatus, Branch, CommitDetails, CommitDiff, CommitFile,
+        CommitLogEntry, CommitLogSearch, CommitOptions, ConflictStages, DiffType, FetchOptions,
+        GitRepository, GitRepositoryCheckpoint, PushOptions, RebaseAction, RebaseEntry,
+        RebaseStatus, Remote, RemoteCommandOutput, RepoPath, ResetMode, Submodule, SubmoduleAction,
+        SubmoduleState, Tag, UpstreamTrackingStatus, Worktree as GitWorktree,
     },
     stash::{GitStash, StashEntry},
     status::{
@@ -52,7 +54,10 @@ use pending_op::{PendingOp, PendingOpId, PendingOps, PendingOpsSummary};
 use postage::stream::Stream as _;
 use rpc::{
     AnyProtoClient, TypedEnvelope,
-    proto::{self, git_reset, split_repository_update},
+    proto::{
+        self, git_bisect_mark, git_rebase_command, git_rebase_entry, git_reset,
+        split_repository_update,
+    },
 };
 use serde::Deserialize;
 use settings::WorktreeId;
@@ -265,6 +270,7 @@ pub struct RepositorySnapshot {
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
+    pub submodules: Arc<[Submodule]>,
 }
 
 type JobId = u64;
@@ -283,6 +289,8 @@ pub struct Repository {
     // For a local repository, holds paths that have had worktree events since the last status scan completed,
     // and that should be examined during the next status scan.
     paths_needing_status_update: BTreeSet<RepoPath>,
+    // Whether the next scan has to list the submodules even if nothing else suggests they changed.
+    submodules_need_refresh: bool,
     job_sender: mpsc::UnboundedSender<GitJob>,
     active_jobs: HashMap<JobId, JobInfo>,
     pending_ops: SumTree<PendingOps>,
@@ -366,6 +374,7 @@ pub enum RepositoryEvent {
     MergeHeadsChanged,
     BranchChanged,
     StashEntriesChanged,
+    SubmodulesChanged,
     PendingOpsChanged { pending_ops: SumTree<PendingOps> },
 }
 
@@ -475,6 +484,20 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_create_remote);
         client.add_entity_request_handler(Self::handle_remove_remote);
         client.add_entity_request_handler(Self::handle_delete_branch);
+        client.add_entity_request_handler(Self::handle_get_rebase_entries);
+        client.add_entity_request_handler(Self::handle_rebase);
+        client.add_entity_request_handler(Self::handle_rebase_command);
+        client.add_entity_request_handler(Self::handle_get_rebase_status);
+        client.add_entity_request_handler(Self::handle_bisect_start);
+        client.add_entity_request_handler(Self::handle_bisect_mark);
+        client.add_entity_request_handler(Self::handle_bisect_reset);
+        client.add_entity_request_handler(Self::handle_get_bisect_status);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_get_tags);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
+        client.add_entity_request_handler(Self::handle_push_tag);
         client.add_entity_request_handler(Self::handle_git_init);
         client.add_entity_request_handler(Self::handle_push);
         client.add_entity_request_handler(Self::handle_pull);
@@ -491,6 +514,9 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_show);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_file_history);
+        client.add_entity_request_handler(Self::handle_commit_log);
+        client.add_entity_request_handler(Self::handle_load_conflict_stages);
+        client.add_entity_request_handler(Self::handle_submodule_command);
         client.add_entity_request_handler(Self::handle_checkout_files);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
@@ -1842,6 +1868,46 @@ impl GitStore {
         })
     }
 
+    async fn handle_submodule_command(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleCommand>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let action = match envelope.payload.action() {
+            proto::git_submodule_command::Action::Init => SubmoduleAction::Init,
+            proto::git_submodule_command::Action::Update => SubmoduleAction::Update,
+            proto::git_submodule_command::Action::Sync => SubmoduleAction::Sync,
+        };
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|path| RepoPath::from_proto(path))
+            .collect::<Result<Vec<_>>>()?;
+        let askpass_id = envelope.payload.askpass_id;
+
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.submodule_command(action, paths, askpass, cx)
+            })?
+            .await??;
+
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
     async fn handle_push(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::Push>,
@@ -2313,6 +2379,294 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_get_rebase_entries(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetRebaseEntries>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseEntriesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let base = envelope.payload.base;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_entries(base)
+            })?
+            .await??;
+
+        Ok(proto::GitRebaseEntriesResponse {
+            entries: entries.iter().map(rebase_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_rebase(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebase>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let base = envelope.payload.base;
+        let entries = envelope
+            .payload
+            .entries
+            .iter()
+            .map(proto_to_rebase_entry)
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_interactive(base, entries)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_command(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseCommand>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let command = envelope.payload.command();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_command(command)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_rebase_status(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetRebaseStatus>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseStatusResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let status = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_status()
+            })?
+            .await??;
+
+        Ok(proto::GitRebaseStatusResponse {
+            status: status.as_ref().map(rebase_status_to_proto),
+        })
+    }
+
+    async fn handle_bisect_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let bad = envelope.payload.bad;
+        let good = envelope.payload.good;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_start(bad, good)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_mark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let mark = match envelope.payload.mark() {
+            git_bisect_mark::Mark::Good => BisectMark::Good,
+            git_bisect_mark::Mark::Bad => BisectMark::Bad,
+            git_bisect_mark::Mark::Skip => BisectMark::Skip,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_mark(mark)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_reset()
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_get_bisect_status(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetBisectStatus>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitBisectStatusResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let status = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_status()
+            })?
+            .await??;
+
+        Ok(proto::GitBisectStatusResponse {
+            status: status.as_ref().map(bisect_status_to_proto),
+        })
+    }
+
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitApplyCommitResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let commit = envelope.payload.commit;
+
+        let has_conflicts = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.cherry_pick(commit)
+            })?
+            .await??;
+
+        Ok(proto::GitApplyCommitResponse { has_conflicts })
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitApplyCommitResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let commit = envelope.payload.commit;
+
+        let has_conflicts = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.revert(commit)
+            })?
+            .await??;
+
+        Ok(proto::GitApplyCommitResponse { has_conflicts })
+    }
+
+    async fn handle_get_tags(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitGetTags>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitTagsResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let tags = repository_handle
+            .update(&mut cx, |repository_handle, _| repository_handle.tags())?
+            .await??;
+
+        Ok(proto::GitTagsResponse {
+            tags: tags.iter().map(tag_to_proto).collect(),
+        })
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let proto::GitCreateTag {
+            name,
+            commit,
+            message,
+            ..
+        } = envelope.payload;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.create_tag(name, commit, message)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let name = envelope.payload.name;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.delete_tag(name)
+            })?
+            .await??;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_push_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitPushTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::RemoteMessageResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let askpass_id = envelope.payload.askpass_id;
+        let askpass = make_remote_delegate(
+            this,
+            envelope.payload.project_id,
+            repository_id,
+            askpass_id,
+            &mut cx,
+        );
+
+        let name = envelope.payload.name.into();
+        let remote_name = envelope.payload.remote_name.into();
+        let delete = envelope.payload.delete;
+
+        let remote_output = repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                if delete {
+                    repository_handle.delete_remote_tag(name, remote_name, askpass, cx)
+                } else {
+                    repository_handle.push_tag(name, remote_name, askpass, cx)
+                }
+            })?
+            .await??;
+        Ok(proto::RemoteMessageResponse {
+            stdout: remote_output.stdout,
+            stderr: remote_output.stderr,
+        })
+    }
+
     async fn handle_remove_remote(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitRemoveRemote>,
@@ -2413,30 +2767,77 @@ impl GitStore {
         })
     }
 
-    async fn handle_reset(
+    async fn handle_load_conflict_stages(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitReset>,
+        envelope: TypedEnvelope<proto::GitLoadConflictStages>,
         mut cx: AsyncApp,
-    ) -> Result<proto::Ack> {
+    ) -> Result<proto::GitConflictStagesResponse> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
         let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
 
-        let mode = match envelope.payload.mode() {
-            git_reset::ResetMode::Soft => ResetMode::Soft,
-            git_reset::ResetMode::Mixed => ResetMode::Mixed,
-        };
-
-        repository_handle
-            .update(&mut cx, |repository_handle, cx| {
-                repository_handle.reset(envelope.payload.commit, mode, cx)
+        let stages = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_conflict_stages(path)
             })?
             .await??;
-        Ok(proto::Ack {})
+
+        Ok(proto::GitConflictStagesResponse {
+            base: stages.base,
+            ours: stages.ours,
+            theirs: stages.theirs,
+        })
     }
 
-    async fn handle_checkout_files(
+    async fn handle_commit_log(
         this: Entity<Self>,
-        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
+        envelope: TypedEnvelope<proto::GitCommitLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitCommitLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let skip = envelope.payload.skip as usize;
+        let limit = envelope.payload.limit.map(|l| l as usize);
+        let search = envelope.payload.search.map(|search| match search {
+            proto::git_commit_log::Search::Author(author) => CommitLogSearch::Author(author),
+            proto::git_commit_log::Search::Message(message) => CommitLogSearch::Message(message),
+        });
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.commit_log_paginated(search, skip, limit)
+            })?
+            .await??;
+
+        Ok(proto::GitCommitLogResponse {
+            entries: entries.into_iter().map(commit_log_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let mode = match envelope.payload.mode() {
+            git_reset::ResetMode::Soft => ResetMode::Soft,
+            git_reset::ResetMode::Mixed => ResetMode::Mixed,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.reset(envelope.payload.commit, mode, cx)
+            })?
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_checkout_files(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCheckoutFiles>,
         mut cx: AsyncApp,
     ) -> Result<proto::Ack> {
         let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
@@ -3263,6 +3664,7 @@ impl RepositorySnapshot {
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
+            submodules: Default::default(),
             path_style,
         }
     }
@@ -3298,6 +3700,7 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
         }
     }
 
@@ -3369,6 +3772,7 @@ impl RepositorySnapshot {
                 .collect(),
             remote_upstream_url: self.remote_upstream_url.clone(),
             remote_origin_url: self.remote_origin_url.clone(),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
         }
     }
 
@@ -3386,6 +3790,12 @@ impl RepositorySnapshot {
             .cloned()
     }
 
+    pub fn submodule_for_path(&self, path: &RepoPath) -> Option<&Submodule> {
+        self.submodules
+            .iter()
+            .find(|submodule| &submodule.path == path)
+    }
+
     pub fn abs_path_to_repo_path(&self, abs_path: &Path) -> Option<RepoPath> {
         Self::abs_path_to_repo_path_inner(&self.work_directory_abs_path, abs_path, self.path_style)
     }
@@ -3451,6 +3861,39 @@ pub fn proto_to_stash(entry: &proto::StashEntry) -> Result<StashEntry> {
     })
 }
 
+fn submodule_to_proto(submodule: &Submodule) -> proto::Submodule {
+    use proto::submodule::State;
+    proto::Submodule {
+        path: submodule.path.to_proto(),
+        sha: submodule.sha.to_string(),
+        state: match submodule.state {
+            SubmoduleState::UpToDate => State::UpToDate,
+            SubmoduleState::Uninitialized => State::Uninitialized,
+            SubmoduleState::NewCommits => State::NewCommits,
+            SubmoduleState::Conflicted => State::Conflicted,
+        } as i32,
+        has_modified_content: submodule.has_modified_content,
+        has_untracked_content: submodule.has_untracked_content,
+    }
+}
+
+fn proto_to_submodule(submodule: &proto::Submodule) -> Result<Submodule> {
+    use proto::submodule::State;
+    Ok(Submodule {
+        path: RepoPath::from_proto(&submodule.path)?,
+        sha: submodule.sha.clone().into(),
+        state: match State::from_i32(submodule.state) {
+            Some(State::UpToDate) => SubmoduleState::UpToDate,
+            Some(State::Uninitialized) => SubmoduleState::Uninitialized,
+            Some(State::NewCommits) => SubmoduleState::NewCommits,
+            Some(State::Conflicted) => SubmoduleState::Conflicted,
+            None => anyhow::bail!("invalid submodule state: {}", submodule.state),
+        },
+        has_modified_content: submodule.has_modified_content,
+        has_untracked_content: submodule.has_untracked_content,
+    })
+}
+
 impl MergeDetails {
     async fn load(
         backend: &Arc<dyn GitRepository>,
@@ -3574,6 +4017,7 @@ impl Repository {
             commit_message_buffer: None,
             askpass_delegates: Default::default(),
             paths_needing_status_update: Default::default(),
+            submodules_need_refresh: false,
             latest_askpass_id: 0,
             job_sender,
             job_id: 0,
@@ -3601,6 +4045,7 @@ impl Repository {
             git_store,
             pending_ops: Default::default(),
             paths_needing_status_update: Default::default(),
+            submodules_need_refresh: false,
             job_sender,
             repository_state,
             askpass_delegates: Default::default(),
@@ -4168,6 +4613,73 @@ impl Repository {
         })
     }
 
+    pub fn load_conflict_stages(
+        &mut self,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<ConflictStages>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_conflict_stages(path).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitLoadConflictStages {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                        })
+                        .await?;
+                    Ok(ConflictStages {
+                        base: response.base,
+                        ours: response.ours,
+                        theirs: response.theirs,
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn commit_log_paginated(
+        &mut self,
+        search: Option<CommitLogSearch>,
+        skip: usize,
+        limit: Option<usize>,
+    ) -> oneshot::Receiver<Result<Vec<CommitLogEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.commit_log_paginated(search, skip, limit).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { client, project_id }) => {
+                    let response = client
+                        .request(proto::GitCommitLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            skip: skip as u64,
+                            limit: limit.map(|l| l as u64),
+                            search: search.map(|search| match search {
+                                CommitLogSearch::Author(author) => {
+                                    proto::git_commit_log::Search::Author(author)
+                                }
+                                CommitLogSearch::Message(message) => {
+                                    proto::git_commit_log::Search::Message(message)
+                                }
+                            }),
+                        })
+                        .await?;
+                    Ok(response
+                        .entries
+                        .into_iter()
+                        .map(proto_to_commit_log_entry)
+                        .collect())
+                }
+            }
+        })
+    }
+
     fn buffer_store(&self, cx: &App) -> Option<Entity<BufferStore>> {
         Some(self.git_store.upgrade()?.read(cx).buffer_store.clone())
     }
@@ -4663,6 +5175,66 @@ impl Repository {
         })
     }
 
+    /// Runs `git submodule <action>` for the given submodules, or for all of them if `paths` is empty.
+    pub fn submodule_command(
+        &mut self,
+        action: SubmoduleAction,
+        paths: Vec<RepoPath>,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+        // The scan following the command's changes to the `.git` directory picks up the new state.
+        self.submodules_need_refresh = true;
+
+        self.send_job(
+            Some(format!("git submodule {}", action.name()).into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .submodule_command(action, paths, askpass, environment, cx)
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+
+                        let action = match action {
+                            SubmoduleAction::Init => proto::git_submodule_command::Action::Init,
+                            SubmoduleAction::Update => proto::git_submodule_command::Action::Update,
+                            SubmoduleAction::Sync => proto::git_submodule_command::Action::Sync,
+                        };
+                        let response = client
+                            .request(proto::GitSubmoduleCommand {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                askpass_id,
+                                action: action as i32,
+                                paths: paths.iter().map(|path| path.to_proto()).collect(),
+                            })
+                            .await
+                            .context("sending submodule request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
     pub fn push(
         &mut self,
         branch: SharedString,
@@ -5286,98 +5858,554 @@ impl Repository {
         )
     }
 
-    pub fn rename_branch(
-        &mut self,
-        branch: String,
-        new_name: String,
-    ) -> oneshot::Receiver<Result<()>> {
+    /// Cherry-picks `commit` onto HEAD, resolving to whether it stopped because of conflicts.
+    pub fn cherry_pick(&mut self, commit: String) -> oneshot::Receiver<Result<bool>> {
         let id = self.id;
         self.send_job(
-            Some(format!("git branch -m {branch} {new_name}").into()),
+            Some(format!("git cherry-pick {commit}").into()),
             move |repo, _cx| async move {
                 match repo {
-                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                        backend.rename_branch(branch, new_name).await
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(response.has_conflicts)
                     }
+                }
+            },
+        )
+    }
+
+    /// Reverts `commit`, resolving to whether it stopped because of conflicts.
+    pub fn revert(&mut self, commit: String) -> oneshot::Receiver<Result<bool>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git revert {commit}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.revert(commit, environment).await,
                     RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                        client
-                            .request(proto::GitRenameBranch {
+                        let response = client
+                            .request(proto::GitRevert {
                                 project_id: project_id.0,
                                 repository_id: id.to_proto(),
-                                branch,
-                                new_name,
+                                commit,
                             })
                             .await?;
 
-                        Ok(())
+                        Ok(response.has_conflicts)
                     }
                 }
             },
         )
     }
 
-    pub fn check_for_pushed_commits(&mut self) -> oneshot::Receiver<Result<Vec<SharedString>>> {
+    pub fn tags(&mut self) -> oneshot::Receiver<Result<Vec<Tag>>> {
         let id = self.id;
         self.send_job(None, move |repo, _cx| async move {
             match repo {
                 RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.check_for_pushed_commit().await
+                    backend.tags().await
                 }
                 RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
                     let response = client
-                        .request(proto::CheckForPushedCommits {
+                        .request(proto::GitGetTags {
                             project_id: project_id.0,
                             repository_id: id.to_proto(),
                         })
                         .await?;
 
-                    let branches = response.pushed_to.into_iter().map(Into::into).collect();
-
-                    Ok(branches)
+                    Ok(response.tags.iter().map(proto_to_tag).collect())
                 }
             }
         })
     }
 
-    pub fn checkpoint(&mut self) -> oneshot::Receiver<Result<GitRepositoryCheckpoint>> {
-        self.send_job(None, |repo, _cx| async move {
-            match repo {
-                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.checkpoint().await
+    /// Creates a tag at `commit` (or HEAD), annotated with `message` when one is given.
+    pub fn create_tag(
+        &mut self,
+        name: String,
+        commit: Option<String>,
+        message: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.create_tag(name, commit, message, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                                commit,
+                                message,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
                 }
-                RepositoryState::Remote(..) => anyhow::bail!("not implemented yet"),
-            }
-        })
+            },
+        )
     }
 
-    pub fn restore_checkpoint(
-        &mut self,
-        checkpoint: GitRepositoryCheckpoint,
-    ) -> oneshot::Receiver<Result<()>> {
-        self.send_job(None, move |repo, _cx| async move {
-            match repo {
-                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.restore_checkpoint(checkpoint).await
+    pub fn delete_tag(&mut self, name: String) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git tag -d {name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(state) => state.backend.delete_tag(name).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
                 }
-                RepositoryState::Remote { .. } => anyhow::bail!("not implemented yet"),
-            }
-        })
+            },
+        )
     }
 
-    pub(crate) fn apply_remote_update(
+    pub fn push_tag(
         &mut self,
-        update: proto::UpdateRepository,
-        cx: &mut Context<Self>,
-    ) -> Result<()> {
-        let conflicted_paths = TreeSet::from_ordered_entries(
-            update
-                .current_merge_conflicts
-                .into_iter()
-                .filter_map(|path| RepoPath::from_proto(&path).log_err()),
-        );
-        let new_branch = update.branch_summary.as_ref().map(proto_to_branch);
-        let new_head_commit = update
-            .head_commit_details
+        name: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        self.push_tag_ref(name, remote, false, askpass)
+    }
+
+    pub fn delete_remote_tag(
+        &mut self,
+        name: SharedString,
+        remote: SharedString,
+        askpass: AskPassDelegate,
+        _cx: &mut App,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        self.push_tag_ref(name, remote, true, askpass)
+    }
+
+    fn push_tag_ref(
+        &mut self,
+        name: SharedString,
+        remote: SharedString,
+        delete: bool,
+        askpass: AskPassDelegate,
+    ) -> oneshot::Receiver<Result<RemoteCommandOutput>> {
+        let askpass_delegates = self.askpass_delegates.clone();
+        let askpass_id = util::post_inc(&mut self.latest_askpass_id);
+        let id = self.id;
+        let args = if delete { " --delete" } else { "" };
+
+        self.send_job(
+            Some(format!("git push{args} {remote} refs/tags/{name}").into()),
+            move |git_repo, cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .push_tag(
+                                name.to_string(),
+                                remote.to_string(),
+                                delete,
+                                askpass,
+                                environment,
+                                cx,
+                            )
+                            .await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        askpass_delegates.lock().insert(askpass_id, askpass);
+                        let _defer = util::defer(|| {
+                            let askpass_delegate = askpass_delegates.lock().remove(&askpass_id);
+                            debug_assert!(askpass_delegate.is_some());
+                        });
+                        let response = client
+                            .request(proto::GitPushTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                name: name.to_string(),
+                                remote_name: remote.to_string(),
+                                delete,
+                                askpass_id,
+                            })
+                            .await
+                            .context("sending push tag request")?;
+
+                        Ok(RemoteCommandOutput {
+                            stdout: response.stdout,
+                            stderr: response.stderr,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_entries(&mut self, base: String) -> oneshot::Receiver<Result<Vec<RebaseEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_entries(base).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetRebaseEntries {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
+
+                    Ok(response.entries.iter().map(proto_to_rebase_entry).collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_interactive(
+        &mut self,
+        base: String,
+        entries: Vec<RebaseEntry>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git rebase --interactive {base}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_interactive(base, entries, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebase {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                base,
+                                entries: entries.iter().map(rebase_entry_to_proto).collect(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_continue(&mut self) -> oneshot::Receiver<Result<()>> {
+        self.rebase_command(git_rebase_command::Command::Continue)
+    }
+
+    pub fn rebase_skip(&mut self) -> oneshot::Receiver<Result<()>> {
+        self.rebase_command(git_rebase_command::Command::Skip)
+    }
+
+    pub fn rebase_abort(&mut self) -> oneshot::Receiver<Result<()>> {
+        self.rebase_command(git_rebase_command::Command::Abort)
+    }
+
+    fn rebase_command(
+        &mut self,
+        command: git_rebase_command::Command,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let flag = match command {
+            git_rebase_command::Command::Continue => "--continue",
+            git_rebase_command::Command::Skip => "--skip",
+            git_rebase_command::Command::Abort => "--abort",
+        };
+        self.send_job(
+            Some(format!("git rebase {flag}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => match command {
+                        git_rebase_command::Command::Continue => {
+                            backend.rebase_continue(environment).await
+                        }
+                        git_rebase_command::Command::Skip => backend.rebase_skip(environment).await,
+                        git_rebase_command::Command::Abort => {
+                            backend.rebase_abort(environment).await
+                        }
+                    },
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseCommand {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                command: command.into(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_status(&mut self) -> oneshot::Receiver<Result<Option<RebaseStatus>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_status().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetRebaseStatus {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.status.as_ref().map(proto_to_rebase_status))
+                }
+            }
+        })
+    }
+
+    pub fn bisect_start(
+        &mut self,
+        bad: String,
+        good: Vec<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git bisect start {bad}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_start(bad, good, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectStart {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                bad,
+                                good,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_mark(&mut self, mark: BisectMark) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git bisect {}", mark.command()).into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_mark(mark, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let mark = match mark {
+                            BisectMark::Good => git_bisect_mark::Mark::Good,
+                            BisectMark::Bad => git_bisect_mark::Mark::Bad,
+                            BisectMark::Skip => git_bisect_mark::Mark::Skip,
+                        };
+                        client
+                            .request(proto::GitBisectMark {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                mark: mark.into(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_reset(&mut self) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some("git bisect reset".into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_reset(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectReset {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_status(&mut self) -> oneshot::Receiver<Result<Option<BisectStatus>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.bisect_status().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitGetBisectStatus {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.status.as_ref().map(proto_to_bisect_status))
+                }
+            }
+        })
+    }
+
+    pub fn rename_branch(
+        &mut self,
+        branch: String,
+        new_name: String,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            Some(format!("git branch -m {branch} {new_name}").into()),
+            move |repo, _cx| async move {
+                match repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.rename_branch(branch, new_name).await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRenameBranch {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                branch,
+                                new_name,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn check_for_pushed_commits(&mut self) -> oneshot::Receiver<Result<Vec<SharedString>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.check_for_pushed_commit().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::CheckForPushedCommits {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    let branches = response.pushed_to.into_iter().map(Into::into).collect();
+
+                    Ok(branches)
+                }
+            }
+        })
+    }
+
+    pub fn checkpoint(&mut self) -> oneshot::Receiver<Result<GitRepositoryCheckpoint>> {
+        self.send_job(None, |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.checkpoint().await
+                }
+                RepositoryState::Remote(..) => anyhow::bail!("not implemented yet"),
+            }
+        })
+    }
+
+    pub fn restore_checkpoint(
+        &mut self,
+        checkpoint: GitRepositoryCheckpoint,
+    ) -> oneshot::Receiver<Result<()>> {
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.restore_checkpoint(checkpoint).await
+                }
+                RepositoryState::Remote { .. } => anyhow::bail!("not implemented yet"),
+            }
+        })
+    }
+
+    pub(crate) fn apply_remote_update(
+        &mut self,
+        update: proto::UpdateRepository,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let conflicted_paths = TreeSet::from_ordered_entries(
+            update
+                .current_merge_conflicts
+                .into_iter()
+                .filter_map(|path| RepoPath::from_proto(&path).log_err()),
+        );
+        let new_branch = update.branch_summary.as_ref().map(proto_to_branch);
+        let new_head_commit = update
+            .head_commit_details
             .as_ref()
             .map(proto_to_commit_details);
         if self.snapshot.branch != new_branch || self.snapshot.head_commit != new_head_commit {
@@ -5399,6 +6427,15 @@ impl Repository {
             cx.emit(RepositoryEvent::StashEntriesChanged)
         }
         self.snapshot.stash_entries = new_stash_entries;
+        let new_submodules = update
+            .submodules
+            .iter()
+            .filter_map(|submodule| proto_to_submodule(submodule).log_err())
+            .collect::<Arc<[_]>>();
+        if self.snapshot.submodules != new_submodules {
+            cx.emit(RepositoryEvent::SubmodulesChanged)
+        }
+        self.snapshot.submodules = new_submodules;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
 
@@ -5513,6 +6550,7 @@ impl Repository {
                             this.id,
                             this.work_directory_abs_path.clone(),
                             this.snapshot.clone(),
+                            mem::take(&mut this.submodules_need_refresh),
                             backend.clone(),
                         )
                     })?
@@ -5737,6 +6775,11 @@ impl Repository {
                 }
                 let statuses = backend.status(&paths).await?;
                 let stash_entries = backend.stash_entries().await?;
+                let submodules = if paths.iter().any(|path| path.as_unix_str() == ".gitmodules") {
+                    Some(load_submodules(&backend).await)
+                } else {
+                    None
+                };
 
                 let changed_path_statuses = cx
                     .background_spawn(async move {
@@ -5774,6 +6817,13 @@ impl Repository {
                         this.snapshot.stash_entries = stash_entries;
                     }
 
+                    if let Some(submodules) = submodules
+                        && this.snapshot.submodules != submodules
+                    {
+                        cx.emit(RepositoryEvent::SubmodulesChanged);
+                        this.snapshot.submodules = submodules;
+                    }
+
                     if !changed_path_statuses.is_empty() {
                         cx.emit(RepositoryEvent::StatusesChanged);
                         this.snapshot
@@ -6098,10 +7148,148 @@ fn proto_to_commit_details(proto: &proto::GitCommitDetails) -> CommitDetails {
     }
 }
 
+fn rebase_entry_to_proto(entry: &RebaseEntry) -> proto::GitRebaseEntry {
+    let action = match entry.action {
+        RebaseAction::Pick => git_rebase_entry::Action::Pick,
+        RebaseAction::Reword => git_rebase_entry::Action::Reword,
+        RebaseAction::Squash => git_rebase_entry::Action::Squash,
+        RebaseAction::Fixup => git_rebase_entry::Action::Fixup,
+        RebaseAction::Drop => git_rebase_entry::Action::Drop,
+    };
+    proto::GitRebaseEntry {
+        action: action.into(),
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        message: entry.message.as_ref().map(ToString::to_string),
+    }
+}
+
+fn proto_to_rebase_entry(proto: &proto::GitRebaseEntry) -> RebaseEntry {
+    let action = match proto.action() {
+        git_rebase_entry::Action::Pick => RebaseAction::Pick,
+        git_rebase_entry::Action::Reword => RebaseAction::Reword,
+        git_rebase_entry::Action::Squash => RebaseAction::Squash,
+        git_rebase_entry::Action::Fixup => RebaseAction::Fixup,
+        git_rebase_entry::Action::Drop => RebaseAction::Drop,
+    };
+    RebaseEntry {
+        action,
+        sha: proto.sha.clone().into(),
+        subject: proto.subject.clone().into(),
+        message: proto.message.clone().map(Into::into),
+    }
+}
+
+fn rebase_status_to_proto(status: &RebaseStatus) -> proto::GitRebaseStatus {
+    proto::GitRebaseStatus {
+        head_name: status.head_name.as_ref().map(ToString::to_string),
+        onto: status.onto.to_string(),
+        stopped_sha: status.stopped_sha.as_ref().map(ToString::to_string),
+        step: status.step as u64,
+        total_steps: status.total_steps as u64,
+    }
+}
+
+fn proto_to_rebase_status(proto: &proto::GitRebaseStatus) -> RebaseStatus {
+    RebaseStatus {
+        head_name: proto.head_name.clone().map(Into::into),
+        onto: proto.onto.clone().into(),
+        stopped_sha: proto.stopped_sha.clone().map(Into::into),
+        step: proto.step as usize,
+        total_steps: proto.total_steps as usize,
+    }
+}
+
+fn bisect_status_to_proto(status: &BisectStatus) -> proto::GitBisectStatus {
+    proto::GitBisectStatus {
+        current: status.current.as_ref().map(ToString::to_string),
+        bad: status.bad.as_ref().map(ToString::to_string),
+        good: status.good.iter().map(ToString::to_string).collect(),
+        skipped: status.skipped.iter().map(ToString::to_string).collect(),
+        remaining: status
+            .remaining
+            .iter()
+            .map(|candidate| proto::GitBisectCandidate {
+                sha: candidate.sha.to_string(),
+                subject: candidate.subject.to_string(),
+            })
+            .collect(),
+    }
+}
+
+fn proto_to_bisect_status(proto: &proto::GitBisectStatus) -> BisectStatus {
+    BisectStatus {
+        current: proto.current.clone().map(Into::into),
+        bad: proto.bad.clone().map(Into::into),
+        good: proto.good.iter().cloned().map(Into::into).collect(),
+        skipped: proto.skipped.iter().cloned().map(Into::into).collect(),
+        remaining: proto
+            .remaining
+            .iter()
+            .map(|candidate| BisectCandidate {
+                sha: candidate.sha.clone().into(),
+                subject: candidate.subject.clone().into(),
+            })
+            .collect(),
+    }
+}
+
+fn tag_to_proto(tag: &Tag) -> proto::GitTag {
+    proto::GitTag {
+        name: tag.name.to_string(),
+        sha: tag.sha.to_string(),
+        message: tag.message.as_ref().map(ToString::to_string),
+        timestamp: tag.timestamp,
+    }
+}
+
+fn proto_to_tag(proto: &proto::GitTag) -> Tag {
+    Tag {
+        name: proto.name.clone().into(),
+        sha: proto.sha.clone().into(),
+        message: proto.message.clone().map(Into::into),
+        timestamp: proto.timestamp,
+    }
+}
+
+fn commit_log_entry_to_proto(entry: CommitLogEntry) -> proto::CommitLogEntry {
+    proto::CommitLogEntry {
+        sha: entry.sha.to_string(),
+        parents: entry.parents.iter().map(ToString::to_string).collect(),
+        subject: entry.subject.to_string(),
+        commit_timestamp: entry.commit_timestamp,
+        author_name: entry.author_name.to_string(),
+        author_email: entry.author_email.to_string(),
+        refs: entry.refs.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn proto_to_commit_log_entry(entry: proto::CommitLogEntry) -> CommitLogEntry {
+    CommitLogEntry {
+        sha: entry.sha.into(),
+        parents: entry.parents.into_iter().map(Into::into).collect(),
+        subject: entry.subject.into(),
+        commit_timestamp: entry.commit_timestamp,
+        author_name: entry.author_name.into(),
+        author_email: entry.author_email.into(),
+        refs: entry.refs.into_iter().map(Into::into).collect(),
+    }
+}
+
+async fn load_submodules(backend: &Arc<dyn GitRepository>) -> Arc<[Submodule]> {
+    backend
+        .submodules()
+        .await
+        .log_err()
+        .unwrap_or_default()
+        .into()
+}
+
 async fn compute_snapshot(
     id: RepositoryId,
     work_directory_abs_path: Arc<Path>,
     prev_snapshot: RepositorySnapshot,
+    refresh_submodules: bool,
     backend: Arc<dyn GitRepository>,
 ) -> Result<(RepositorySnapshot, Vec<RepositoryEvent>)> {
     let mut events = Vec::new();
@@ -6145,6 +7333,25 @@ async fn compute_snapshot(
         events.push(RepositoryEvent::BranchChanged);
     }
 
+    // Listing the submodules runs git twice, so it's only done on the first scan, when the
+    // submodules were acted on, or when the checkout changed.
+    let refresh_submodules = refresh_submodules
+        || prev_snapshot.scan_id == 0
+        || events.iter().any(|event| {
+            matches!(
+                event,
+                RepositoryEvent::BranchChanged | RepositoryEvent::StatusesChanged
+            )
+        });
+    let submodules = if refresh_submodules {
+        load_submodules(&backend).await
+    } else {
+        prev_snapshot.submodules.clone()
+    };
+    if submodules != prev_snapshot.submodules {
+        events.push(RepositoryEvent::SubmodulesChanged);
+    }
+
     let remote_origin_url = backend.remote_url("origin").await;
     let remote_upstream_url = backend.remote_url("upstream").await;
 
@@ -6160,6 +7367,7 @@ async fn compute_snapshot(
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
+        submodules,
     };
 
     Ok((snapshot, events))
@@ -64,6 +64,15 @@ impl<'a> GitTraversal<'a> {
         None
     }
 
+    /// The status of a submodule in the repository that contains it, such as a change to its
+    /// recorded commit, given the absolute path of the submodule's working directory.
+    fn submodule_status(&self, abs_path: &Path) -> Option<GitSummary> {
+        let (superproject, _) = self.repo_root_for_path(abs_path.parent()?)?;
+        let repo_path = superproject.abs_path_to_repo_path(abs_path)?;
+        superproject.submodule_for_path(&repo_path)?;
+        Some(superproject.status_for_path(&repo_path)?.status.into())
+    }
+
     fn synchronize_statuses(&mut self, reset: bool) {
         self.current_entry_summary = None;
 
@@ -89,6 +98,12 @@ impl<'a> GitTraversal<'a> {
             self.repo_location = Some((repo.id, repo.statuses_by_path.cursor::<PathProgress>(())));
         }
 
+        let submodule_status = if entry.is_dir() && repo_path.is_empty() {
+            self.submodule_status(&abs_path)
+        } else {
+            None
+        };
+
         let Some((_, statuses)) = &mut self.repo_location else {
             return;
         };
@@ -96,7 +111,11 @@ impl<'a> GitTraversal<'a> {
         if entry.is_dir() {
             let mut statuses = statuses.clone();
             statuses.seek_forward(&PathTarget::Path(&repo_path), Bias::Left);
-            let summary = statuses.summary(&PathTarget::Successor(&repo_path), Bias::Left);
+            let mut summary = statuses.summary(&PathTarget::Successor(&repo_path), Bias::Left);
+            // A submodule's directory also shows how the submodule changed in its superproject.
+            if let Some(submodule_status) = submodule_status {
+                summary += submodule_status;
+            }
 
             self.current_entry_summary = Some(summary);
         } else if entry.is_file() {
@@ -259,7 +278,10 @@ mod tests {
 
     use super::*;
     use fs::FakeFs;
-    use git::status::{FileStatus, StatusCode, TrackedSummary, UnmergedStatus, UnmergedStatusCode};
+    use git::{
+        repository::{Submodule, SubmoduleState},
+        status::{FileStatus, StatusCode, TrackedSummary, UnmergedStatus, UnmergedStatusCode},
+    };
     use gpui::TestAppContext;
     use serde_json::json;
     use settings::SettingsStore;
@@ -479,6 +501,93 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_git_traversal_with_submodules(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "x": {
+                    ".git": {},
+                    ".gitmodules": "",
+                    "x1.txt": "foo",
+                    "x2.txt": "bar",
+                    "sub": {
+                        ".git": {},
+                        "s1.txt": "baz",
+                        "s2.txt": "qux"
+                    },
+                    "y": {
+                        ".git": {},
+                        "y1.txt": "quux"
+                    }
+                }
+            }),
+        )
+        .await;
+
+        fs.set_status_for_repo(
+            Path::new(path!("/root/x/.git")),
+            &[("x2.txt", StatusCode::Modified.index())],
+        );
+        fs.set_status_for_repo(
+            Path::new(path!("/root/x/sub/.git")),
+            &[("s1.txt", StatusCode::Added.index())],
+        );
+        fs.set_status_for_repo(
+            Path::new(path!("/root/x/y/.git")),
+            &[("y1.txt", StatusCode::Added.index())],
+        );
+        // Both sides of a merge changed the commit recorded for the submodule.
+        fs.with_git_state(Path::new(path!("/root/x/.git")), true, |state| {
+            let sub = RepoPath::from_rel_path(rel_path("sub"));
+            state.head_contents.insert(sub.clone(), "abc".into());
+            state.index_contents.insert(sub.clone(), "abc".into());
+            state.unmerged_paths.insert(
+                sub.clone(),
+                UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                },
+            );
+            state.submodules = vec![Submodule {
+                path: sub,
+                sha: "abc".into(),
+                state: SubmoduleState::Conflicted,
+                has_modified_content: false,
+                has_untracked_content: false,
+            }];
+        })
+        .unwrap();
+
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        cx.executor().run_until_parked();
+
+        let (repo_snapshots, worktree_snapshot) = project.read_with(cx, |project, cx| {
+            (
+                project.git_store().read(cx).repo_snapshots(cx),
+                project.worktrees(cx).next().unwrap().read(cx).snapshot(),
+            )
+        });
+
+        // The submodule's directory combines its own changes with the change to it in the
+        // superproject, while a nested repository that isn't a submodule only shows its own.
+        check_git_statuses(
+            &repo_snapshots,
+            &worktree_snapshot,
+            &[
+                ("x", MODIFIED + GitSummary::CONFLICT),
+                ("x/sub", ADDED + GitSummary::CONFLICT),
+                ("x/sub/s1.txt", ADDED),
+                ("x/sub/s2.txt", GitSummary::UNCHANGED),
+                ("x/x2.txt", MODIFIED),
+                ("x/y", ADDED),
+                ("x/y/y1.txt", ADDED),
+            ],
+        );
+    }
+
     #[gpui::test]
     async fn test_git_traversal_simple(cx: &mut TestAppContext) {
         init_test(cx);
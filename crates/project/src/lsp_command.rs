@@ -1,12 +1,16 @@
 mod signature_help;
 
 use crate::{
-    CodeAction, CompletionSource, CoreCompletion, CoreCompletionResponse, DocumentColor,
-    DocumentHighlight, DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint,
-    InlayHintLabel, InlayHintLabelPart, InlayHintLabelPartTooltip, InlayHintTooltip, Location,
-    LocationLink, LspAction, LspPullDiagnostics, MarkupContent, PrepareRenameResponse,
-    ProjectTransaction, PulledDiagnostics, ResolveState,
-    lsp_store::{LocalLspStore, LspStore},
+    CallHierarchyCall, CallHierarchyItem, CodeAction, CompletionSource, CoreCompletion,
+    CoreCompletionResponse, DocumentColor, DocumentHighlight, DocumentLink, DocumentSymbol, Hover,
+    HoverBlock, HoverBlockKind, InlayHint, InlayHintLabel, InlayHintLabelPart,
+    InlayHintLabelPartTooltip, InlayHintTooltip, Location, LocationLink, LspAction,
+    LspPullDiagnostics, MarkupContent, PrepareRenameResponse, ProjectTransaction,
+    PulledDiagnostics, ResolveState, TypeHierarchyItem,
+    lsp_store::{
+        LocalLspStore, LspStore, SemanticTokensEdit, SemanticTokensUpdate, flatten_lsp_tokens,
+        semantic_tokens_options,
+    },
 };
 use anyhow::{Context as _, Result};
 use async_trait::async_trait;
@@ -17,10 +21,13 @@ use futures::future;
 use gpui::{App, AsyncApp, Entity, SharedString, Task};
 use language::{
     Anchor, Bias, Buffer, BufferSnapshot, CachedLspAdapter, CharKind, CharScopeContext,
-    OffsetRangeExt, PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
+    OffsetRangeExt, Point, PointUtf16, ToOffset, ToPointUtf16, Transaction, Unclipped,
     language_settings::{InlayHintKind, LanguageSettings, language_settings},
     point_from_lsp, point_to_lsp,
-    proto::{deserialize_anchor, deserialize_version, serialize_anchor, serialize_version},
+    proto::{
+        deserialize_anchor, deserialize_anchor_range, deserialize_version, serialize_anchor,
+        serialize_anchor_range, serialize_version,
+    },
     range_from_lsp, range_to_lsp,
 };
 use lsp::{
@@ -85,6 +92,12 @@ pub trait LspCommand: 'static + Sized + Send + std::fmt::Debug {
         None
     }
 
+    /// The language server to send the request to, when it can only be handled
+    /// by the server that produced its arguments.
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        None
+    }
+
     fn to_lsp_params_or_response(
         &self,
         path: &Path,
@@ -204,6 +217,63 @@ pub(crate) struct GetDocumentHighlights {
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct GetDocumentSymbols;
 
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct PrepareCallHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetIncomingCalls {
+    pub language_server_id: LanguageServerId,
+    pub item: lsp::CallHierarchyItem,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetOutgoingCalls {
+    pub language_server_id: LanguageServerId,
+    pub item: lsp::CallHierarchyItem,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct PrepareTypeHierarchy {
+    pub position: PointUtf16,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSupertypes {
+    pub language_server_id: LanguageServerId,
+    pub item: lsp::TypeHierarchyItem,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSubtypes {
+    pub language_server_id: LanguageServerId,
+    pub item: lsp::TypeHierarchyItem,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GetSemanticTokens {
+    pub language_server_id: LanguageServerId,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GetSemanticTokensDelta {
+    pub language_server_id: LanguageServerId,
+    pub previous_result_id: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GetFoldingRanges;
+
+/// Responds with the selection ranges of every position, from the innermost to the outermost one.
+#[derive(Debug, Clone)]
+pub(crate) struct GetSelectionRanges {
+    pub positions: Vec<Anchor>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GetDocumentLinks;
+
 #[derive(Clone, Debug)]
 pub(crate) struct GetSignatureHelp {
     pub position: PointUtf16,
@@ -1803,6 +1873,1604 @@ impl LspCommand for GetDocumentSymbols {
     }
 }
 
+fn call_hierarchy_supported(capabilities: &AdapterServerCapabilities) -> bool {
+    capabilities
+        .server_capabilities
+        .call_hierarchy_provider
+        .as_ref()
+        .is_some_and(|capability| match capability {
+            lsp::CallHierarchyServerCapability::Simple(supported) => *supported,
+            lsp::CallHierarchyServerCapability::Options(_) => true,
+        })
+}
+
+fn location_from_lsp(buffer: Entity<Buffer>, range: lsp::Range, cx: &AsyncApp) -> Result<Location> {
+    buffer.clone().read_with(cx, |snapshot, _| {
+        let start = snapshot.clip_point_utf16(point_from_lsp(range.start), Bias::Left);
+        let end = snapshot.clip_point_utf16(point_from_lsp(range.end), Bias::Left);
+        Location {
+            buffer,
+            range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+        }
+    })
+}
+
+fn location_to_proto(
+    location: &Location,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::Location {
+    lsp_store
+        .buffer_store()
+        .update(cx, |buffer_store, cx| {
+            buffer_store.create_buffer_for_peer(&location.buffer, peer_id, cx)
+        })
+        .detach_and_log_err(cx);
+    proto::Location {
+        buffer_id: location.buffer.read(cx).remote_id().into(),
+        start: Some(serialize_anchor(&location.range.start)),
+        end: Some(serialize_anchor(&location.range.end)),
+    }
+}
+
+async fn location_from_proto(
+    location: proto::Location,
+    lsp_store: &Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<Location> {
+    let buffer_id = BufferId::new(location.buffer_id)?;
+    let buffer = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.wait_for_remote_buffer(buffer_id, cx)
+        })?
+        .await?;
+    let start = location
+        .start
+        .and_then(deserialize_anchor)
+        .context("missing location start")?;
+    let end = location
+        .end
+        .and_then(deserialize_anchor)
+        .context("missing location end")?;
+    buffer
+        .update(cx, |buffer, _| buffer.wait_for_anchors([start, end]))?
+        .await?;
+    Ok(Location {
+        buffer,
+        range: start..end,
+    })
+}
+
+async fn call_hierarchy_item_from_lsp(
+    item: lsp::CallHierarchyItem,
+    language_server_id: LanguageServerId,
+    lsp_store: &Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<CallHierarchyItem> {
+    let buffer = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.open_local_buffer_via_lsp(item.uri.clone(), language_server_id, cx)
+        })?
+        .await?;
+    Ok(CallHierarchyItem {
+        name: item.name.clone(),
+        kind: item.kind,
+        detail: item.detail.clone(),
+        location: location_from_lsp(buffer, item.selection_range, cx)?,
+        language_server_id,
+        lsp_item: item,
+    })
+}
+
+fn call_hierarchy_item_to_proto(
+    item: CallHierarchyItem,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> proto::CallHierarchyItem {
+    proto::CallHierarchyItem {
+        language_server_id: item.language_server_id.to_proto(),
+        name: item.name,
+        kind: unsafe { mem::transmute::<lsp::SymbolKind, i32>(item.kind) },
+        detail: item.detail,
+        location: Some(location_to_proto(&item.location, lsp_store, peer_id, cx)),
+        lsp_item: serde_json::to_vec(&item.lsp_item).unwrap_or_default(),
+    }
+}
+
+async fn call_hierarchy_item_from_proto(
+    item: proto::CallHierarchyItem,
+    lsp_store: &Entity<LspStore>,
+    cx: &mut AsyncApp,
+) -> Result<CallHierarchyItem> {
+    let location = item
+        .location
+        .context("missing call hierarchy item location")?;
+    Ok(CallHierarchyItem {
+        name: item.name,
+        kind: unsafe { mem::transmute::<i32, lsp::SymbolKind>(item.kind) },
+        detail: item.detail,
+        location: location_from_proto(location, lsp_store, cx).await?,
+        language_server_id: LanguageServerId::from_proto(item.language_server_id),
+        lsp_item: serde_json::from_slice(&item.lsp_item)
+            .context("deserializing call hierarchy item")?,
+    })
+}
+
+/// Resolves calls to and from call hierarchy items, given as the item, the
+/// document the calls are made in and the ranges of the calls in it.
+async fn call_hierarchy_calls_from_lsp(
+    calls: Vec<(lsp::CallHierarchyItem, lsp::Uri, Vec<lsp::Range>)>,
+    language_server_id: LanguageServerId,
+    lsp_store: Entity<LspStore>,
+    mut cx: AsyncApp,
+) -> Result<Vec<CallHierarchyCall>> {
+    let mut result = Vec::with_capacity(calls.len());
+    for (item, ranges_uri, ranges) in calls {
+        let item =
+            call_hierarchy_item_from_lsp(item, language_server_id, &lsp_store, &mut cx).await?;
+        let ranges_buffer = if item.lsp_item.uri == ranges_uri {
+            item.location.buffer.clone()
+        } else {
+            lsp_store
+                .update(&mut cx, |lsp_store, cx| {
+                    lsp_store.open_local_buffer_via_lsp(ranges_uri, language_server_id, cx)
+                })?
+                .await?
+        };
+        let ranges = ranges
+            .into_iter()
+            .map(|range| location_from_lsp(ranges_buffer.clone(), range, &cx))
+            .collect::<Result<Vec<_>>>()?;
+        result.push(CallHierarchyCall { item, ranges });
+    }
+    Ok(result)
+}
+
+fn call_hierarchy_calls_to_proto(
+    calls: Vec<CallHierarchyCall>,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> Vec<proto::CallHierarchyCall> {
+    calls
+        .into_iter()
+        .map(|call| proto::CallHierarchyCall {
+            ranges: call
+                .ranges
+                .iter()
+                .map(|location| location_to_proto(location, lsp_store, peer_id, cx))
+                .collect(),
+            item: Some(call_hierarchy_item_to_proto(
+                call.item, lsp_store, peer_id, cx,
+            )),
+        })
+        .collect()
+}
+
+async fn call_hierarchy_calls_from_proto(
+    calls: Vec<proto::CallHierarchyCall>,
+    lsp_store: Entity<LspStore>,
+    mut cx: AsyncApp,
+) -> Result<Vec<CallHierarchyCall>> {
+    let mut result = Vec::with_capacity(calls.len());
+    for call in calls {
+        let item = call.item.context("missing call hierarchy item")?;
+        let item = call_hierarchy_item_from_proto(item, &lsp_store, &mut cx).await?;
+        let mut ranges = Vec::with_capacity(call.ranges.len());
+        for range in call.ranges {
+            ranges.push(location_from_proto(range, &lsp_store, &mut cx).await?);
+        }
+        result.push(CallHierarchyCall { item, ranges });
+    }
+    Ok(result)
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareCallHierarchy {
+    type Response = Vec<CallHierarchyItem>;
+    type LspRequest = lsp::request::CallHierarchyPrepare;
+    type ProtoRequest = proto::PrepareCallHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare call hierarchy"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        call_hierarchy_supported(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyPrepareParams> {
+        Ok(lsp::CallHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::CallHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyItem>> {
+        let mut result = Vec::new();
+        for item in items.unwrap_or_default() {
+            result.push(call_hierarchy_item_from_lsp(item, server_id, &lsp_store, &mut cx).await?);
+        }
+        Ok(result)
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareCallHierarchy {
+        proto::PrepareCallHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(language::proto::serialize_anchor(
+                &buffer.anchor_before(self.position),
+            )),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareCallHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<CallHierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::PrepareCallHierarchyResponse {
+        proto::PrepareCallHierarchyResponse {
+            items: response
+                .into_iter()
+                .map(|item| call_hierarchy_item_to_proto(item, lsp_store, peer_id, cx))
+                .collect(),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::PrepareCallHierarchyResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyItem>> {
+        let mut items = Vec::with_capacity(message.items.len());
+        for item in message.items {
+            items.push(call_hierarchy_item_from_proto(item, &lsp_store, &mut cx).await?);
+        }
+        Ok(items)
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareCallHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetIncomingCalls {
+    type Response = Vec<CallHierarchyCall>;
+    type LspRequest = lsp::request::CallHierarchyIncomingCalls;
+    type ProtoRequest = proto::GetIncomingCalls;
+
+    fn display_name(&self) -> &str {
+        "Get incoming calls"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        call_hierarchy_supported(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyIncomingCallsParams> {
+        Ok(lsp::CallHierarchyIncomingCallsParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        calls: Option<Vec<lsp::CallHierarchyIncomingCall>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyCall>> {
+        let calls = calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|call| {
+                let uri = call.from.uri.clone();
+                (call.from, uri, call.from_ranges)
+            })
+            .collect();
+        call_hierarchy_calls_from_lsp(calls, server_id, lsp_store, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetIncomingCalls {
+        proto::GetIncomingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            language_server_id: self.language_server_id.to_proto(),
+            lsp_item: serde_json::to_vec(&self.item).unwrap_or_default(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetIncomingCalls,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            language_server_id: LanguageServerId::from_proto(message.language_server_id),
+            item: serde_json::from_slice(&message.lsp_item)
+                .context("deserializing call hierarchy item")?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<CallHierarchyCall>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetIncomingCallsResponse {
+        proto::GetIncomingCallsResponse {
+            calls: call_hierarchy_calls_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetIncomingCallsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyCall>> {
+        call_hierarchy_calls_from_proto(message.calls, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetIncomingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetOutgoingCalls {
+    type Response = Vec<CallHierarchyCall>;
+    type LspRequest = lsp::request::CallHierarchyOutgoingCalls;
+    type ProtoRequest = proto::GetOutgoingCalls;
+
+    fn display_name(&self) -> &str {
+        "Get outgoing calls"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        call_hierarchy_supported(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::CallHierarchyOutgoingCallsParams> {
+        Ok(lsp::CallHierarchyOutgoingCallsParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        calls: Option<Vec<lsp::CallHierarchyOutgoingCall>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyCall>> {
+        // The calls are made from the queried item, so their ranges are in its document.
+        let calls = calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|call| (call.to, self.item.uri.clone(), call.from_ranges))
+            .collect();
+        call_hierarchy_calls_from_lsp(calls, server_id, lsp_store, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetOutgoingCalls {
+        proto::GetOutgoingCalls {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            language_server_id: self.language_server_id.to_proto(),
+            lsp_item: serde_json::to_vec(&self.item).unwrap_or_default(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetOutgoingCalls,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            language_server_id: LanguageServerId::from_proto(message.language_server_id),
+            item: serde_json::from_slice(&message.lsp_item)
+                .context("deserializing call hierarchy item")?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<CallHierarchyCall>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetOutgoingCallsResponse {
+        proto::GetOutgoingCallsResponse {
+            calls: call_hierarchy_calls_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetOutgoingCallsResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<CallHierarchyCall>> {
+        call_hierarchy_calls_from_proto(message.calls, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetOutgoingCalls) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+fn type_hierarchy_supported(capabilities: &AdapterServerCapabilities) -> bool {
+    match capabilities.server_capabilities.type_hierarchy_provider {
+        Some(OneOf::Left(supported)) => supported,
+        Some(OneOf::Right(_)) => true,
+        None => false,
+    }
+}
+
+async fn type_hierarchy_items_from_lsp(
+    items: Option<Vec<lsp::TypeHierarchyItem>>,
+    language_server_id: LanguageServerId,
+    lsp_store: Entity<LspStore>,
+    mut cx: AsyncApp,
+) -> Result<Vec<TypeHierarchyItem>> {
+    let items = items.unwrap_or_default();
+    let mut result = Vec::with_capacity(items.len());
+    for item in items {
+        let buffer = lsp_store
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.open_local_buffer_via_lsp(item.uri.clone(), language_server_id, cx)
+            })?
+            .await?;
+        result.push(TypeHierarchyItem {
+            name: item.name.clone(),
+            kind: item.kind,
+            detail: item.detail.clone(),
+            location: location_from_lsp(buffer, item.selection_range, &cx)?,
+            language_server_id,
+            lsp_item: item,
+        });
+    }
+    Ok(result)
+}
+
+fn type_hierarchy_items_to_proto(
+    items: Vec<TypeHierarchyItem>,
+    lsp_store: &mut LspStore,
+    peer_id: PeerId,
+    cx: &mut App,
+) -> Vec<proto::TypeHierarchyItem> {
+    items
+        .into_iter()
+        .map(|item| proto::TypeHierarchyItem {
+            language_server_id: item.language_server_id.to_proto(),
+            name: item.name,
+            kind: unsafe { mem::transmute::<lsp::SymbolKind, i32>(item.kind) },
+            detail: item.detail,
+            location: Some(location_to_proto(&item.location, lsp_store, peer_id, cx)),
+            lsp_item: serde_json::to_vec(&item.lsp_item).unwrap_or_default(),
+        })
+        .collect()
+}
+
+async fn type_hierarchy_items_from_proto(
+    items: Vec<proto::TypeHierarchyItem>,
+    lsp_store: Entity<LspStore>,
+    mut cx: AsyncApp,
+) -> Result<Vec<TypeHierarchyItem>> {
+    let mut result = Vec::with_capacity(items.len());
+    for item in items {
+        let location = item
+            .location
+            .context("missing type hierarchy item location")?;
+        result.push(TypeHierarchyItem {
+            name: item.name,
+            kind: unsafe { mem::transmute::<i32, lsp::SymbolKind>(item.kind) },
+            detail: item.detail,
+            location: location_from_proto(location, &lsp_store, &mut cx).await?,
+            language_server_id: LanguageServerId::from_proto(item.language_server_id),
+            lsp_item: serde_json::from_slice(&item.lsp_item)
+                .context("deserializing type hierarchy item")?,
+        });
+    }
+    Ok(result)
+}
+
+#[async_trait(?Send)]
+impl LspCommand for PrepareTypeHierarchy {
+    type Response = Vec<TypeHierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchyPrepare;
+    type ProtoRequest = proto::PrepareTypeHierarchy;
+
+    fn display_name(&self) -> &str {
+        "Prepare type hierarchy"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        type_hierarchy_supported(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchyPrepareParams> {
+        Ok(lsp::TypeHierarchyPrepareParams {
+            text_document_position_params: make_lsp_text_document_position(path, self.position)?,
+            work_done_progress_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_lsp(items, server_id, lsp_store, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::PrepareTypeHierarchy {
+        proto::PrepareTypeHierarchy {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            position: Some(language::proto::serialize_anchor(
+                &buffer.anchor_before(self.position),
+            )),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::PrepareTypeHierarchy,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        let position = message
+            .position
+            .and_then(deserialize_anchor)
+            .context("invalid position")?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            position: buffer.read_with(&cx, |buffer, _| position.to_point_utf16(buffer))?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<TypeHierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::PrepareTypeHierarchyResponse {
+        proto::PrepareTypeHierarchyResponse {
+            items: type_hierarchy_items_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::PrepareTypeHierarchyResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_proto(message.items, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::PrepareTypeHierarchy) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSupertypes {
+    type Response = Vec<TypeHierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySupertypes;
+    type ProtoRequest = proto::GetSupertypes;
+
+    fn display_name(&self) -> &str {
+        "Get supertypes"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        type_hierarchy_supported(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySupertypesParams> {
+        Ok(lsp::TypeHierarchySupertypesParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_lsp(items, server_id, lsp_store, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSupertypes {
+        proto::GetSupertypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            language_server_id: self.language_server_id.to_proto(),
+            lsp_item: serde_json::to_vec(&self.item).unwrap_or_default(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSupertypes,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            language_server_id: LanguageServerId::from_proto(message.language_server_id),
+            item: serde_json::from_slice(&message.lsp_item)
+                .context("deserializing type hierarchy item")?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<TypeHierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetSupertypesResponse {
+        proto::GetSupertypesResponse {
+            items: type_hierarchy_items_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSupertypesResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_proto(message.items, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSupertypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSubtypes {
+    type Response = Vec<TypeHierarchyItem>;
+    type LspRequest = lsp::request::TypeHierarchySubtypes;
+    type ProtoRequest = proto::GetSubtypes;
+
+    fn display_name(&self) -> &str {
+        "Get subtypes"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        type_hierarchy_supported(&capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        _: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::TypeHierarchySubtypesParams> {
+        Ok(lsp::TypeHierarchySubtypesParams {
+            item: self.item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        items: Option<Vec<lsp::TypeHierarchyItem>>,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_lsp(items, server_id, lsp_store, cx).await
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSubtypes {
+        proto::GetSubtypes {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            language_server_id: self.language_server_id.to_proto(),
+            lsp_item: serde_json::to_vec(&self.item).unwrap_or_default(),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSubtypes,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Self> {
+        Ok(Self {
+            language_server_id: LanguageServerId::from_proto(message.language_server_id),
+            item: serde_json::from_slice(&message.lsp_item)
+                .context("deserializing type hierarchy item")?,
+        })
+    }
+
+    fn response_to_proto(
+        response: Vec<TypeHierarchyItem>,
+        lsp_store: &mut LspStore,
+        peer_id: PeerId,
+        _: &clock::Global,
+        cx: &mut App,
+    ) -> proto::GetSubtypesResponse {
+        proto::GetSubtypesResponse {
+            items: type_hierarchy_items_to_proto(response, lsp_store, peer_id, cx),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSubtypesResponse,
+        lsp_store: Entity<LspStore>,
+        _: Entity<Buffer>,
+        cx: AsyncApp,
+    ) -> Result<Vec<TypeHierarchyItem>> {
+        type_hierarchy_items_from_proto(message.items, lsp_store, cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSubtypes) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+pub(crate) fn semantic_tokens_full_supported(capabilities: &lsp::ServerCapabilities) -> bool {
+    let Some(options) = semantic_tokens_options(capabilities) else {
+        return false;
+    };
+    match options.full {
+        Some(lsp::SemanticTokensFullOptions::Bool(supported)) => supported,
+        Some(lsp::SemanticTokensFullOptions::Delta { .. }) => true,
+        None => false,
+    }
+}
+
+pub(crate) fn semantic_tokens_delta_supported(capabilities: &lsp::ServerCapabilities) -> bool {
+    semantic_tokens_options(capabilities).is_some_and(|options| {
+        matches!(
+            options.full,
+            Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) })
+        )
+    })
+}
+
+fn semantic_tokens_update_to_proto(
+    update: Option<SemanticTokensUpdate>,
+) -> proto::GetSemanticTokensResponse {
+    let update = update.map(|update| match update {
+        SemanticTokensUpdate::Full { result_id, data } => proto::SemanticTokensUpdate {
+            result_id,
+            variant: Some(proto::semantic_tokens_update::Variant::Full(
+                proto::SemanticTokensFull { data },
+            )),
+        },
+        SemanticTokensUpdate::Delta { result_id, edits } => proto::SemanticTokensUpdate {
+            result_id,
+            variant: Some(proto::semantic_tokens_update::Variant::Delta(
+                proto::SemanticTokensDelta {
+                    edits: edits
+                        .into_iter()
+                        .map(|edit| proto::SemanticTokensEdit {
+                            start: edit.start,
+                            delete_count: edit.delete_count,
+                            data: edit.data,
+                        })
+                        .collect(),
+                },
+            )),
+        },
+    });
+    proto::GetSemanticTokensResponse { update }
+}
+
+fn semantic_tokens_update_from_proto(
+    message: proto::GetSemanticTokensResponse,
+) -> Result<Option<SemanticTokensUpdate>> {
+    let Some(update) = message.update else {
+        return Ok(None);
+    };
+    let update = match update.variant.context("missing semantic tokens")? {
+        proto::semantic_tokens_update::Variant::Full(full) => SemanticTokensUpdate::Full {
+            result_id: update.result_id,
+            data: full.data,
+        },
+        proto::semantic_tokens_update::Variant::Delta(delta) => SemanticTokensUpdate::Delta {
+            result_id: update.result_id,
+            edits: delta
+                .edits
+                .into_iter()
+                .map(|edit| SemanticTokensEdit {
+                    start: edit.start,
+                    delete_count: edit.delete_count,
+                    data: edit.data,
+                })
+                .collect(),
+        },
+    };
+    Ok(Some(update))
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSemanticTokens {
+    type Response = Option<SemanticTokensUpdate>;
+    type LspRequest = lsp::request::SemanticTokensFullRequest;
+    type ProtoRequest = proto::GetSemanticTokens;
+
+    fn display_name(&self) -> &str {
+        "Get semantic tokens"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        semantic_tokens_full_supported(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SemanticTokensParams> {
+        Ok(lsp::SemanticTokensParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<lsp::SemanticTokensResult>,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Option<SemanticTokensUpdate>> {
+        Ok(message.map(|message| match message {
+            lsp::SemanticTokensResult::Tokens(tokens) => SemanticTokensUpdate::Full {
+                result_id: tokens.result_id,
+                data: flatten_lsp_tokens(tokens.data),
+            },
+            lsp::SemanticTokensResult::Partial(partial) => SemanticTokensUpdate::Full {
+                result_id: None,
+                data: flatten_lsp_tokens(partial.data),
+            },
+        }))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSemanticTokens {
+        proto::GetSemanticTokens {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            language_server_id: self.language_server_id.to_proto(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSemanticTokens,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            language_server_id: LanguageServerId::from_proto(message.language_server_id),
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<SemanticTokensUpdate>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSemanticTokensResponse {
+        semantic_tokens_update_to_proto(response)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSemanticTokensResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Option<SemanticTokensUpdate>> {
+        semantic_tokens_update_from_proto(message)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSemanticTokens) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSemanticTokensDelta {
+    type Response = Option<SemanticTokensUpdate>;
+    type LspRequest = lsp::request::SemanticTokensFullDeltaRequest;
+    type ProtoRequest = proto::GetSemanticTokensDelta;
+
+    fn display_name(&self) -> &str {
+        "Get semantic tokens delta"
+    }
+
+    fn language_server_id(&self) -> Option<LanguageServerId> {
+        Some(self.language_server_id)
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        semantic_tokens_delta_supported(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SemanticTokensDeltaParams> {
+        Ok(lsp::SemanticTokensDeltaParams {
+            text_document: make_text_document_identifier(path)?,
+            previous_result_id: self.previous_result_id.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<lsp::SemanticTokensFullDeltaResult>,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: LanguageServerId,
+        _: AsyncApp,
+    ) -> Result<Option<SemanticTokensUpdate>> {
+        let edits_from_lsp = |edits: Vec<lsp::SemanticTokensEdit>| {
+            edits
+                .into_iter()
+                .map(|edit| SemanticTokensEdit {
+                    start: edit.start,
+                    delete_count: edit.delete_count,
+                    data: flatten_lsp_tokens(edit.data.unwrap_or_default()),
+                })
+                .collect()
+        };
+        Ok(message.map(|message| match message {
+            lsp::SemanticTokensFullDeltaResult::Tokens(tokens) => SemanticTokensUpdate::Full {
+                result_id: tokens.result_id,
+                data: flatten_lsp_tokens(tokens.data),
+            },
+            lsp::SemanticTokensFullDeltaResult::TokensDelta(delta) => SemanticTokensUpdate::Delta {
+                result_id: delta.result_id,
+                edits: edits_from_lsp(delta.edits),
+            },
+            lsp::SemanticTokensFullDeltaResult::PartialTokensDelta { edits } => {
+                SemanticTokensUpdate::Delta {
+                    result_id: None,
+                    edits: edits_from_lsp(edits),
+                }
+            }
+        }))
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSemanticTokensDelta {
+        proto::GetSemanticTokensDelta {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            language_server_id: self.language_server_id.to_proto(),
+            previous_result_id: self.previous_result_id.clone(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSemanticTokensDelta,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self {
+            language_server_id: LanguageServerId::from_proto(message.language_server_id),
+            previous_result_id: message.previous_result_id,
+        })
+    }
+
+    fn response_to_proto(
+        response: Option<SemanticTokensUpdate>,
+        _: &mut LspStore,
+        _: PeerId,
+        _: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSemanticTokensResponse {
+        semantic_tokens_update_to_proto(response)
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSemanticTokensResponse,
+        _: Entity<LspStore>,
+        _: Entity<Buffer>,
+        _: AsyncApp,
+    ) -> Result<Option<SemanticTokensUpdate>> {
+        semantic_tokens_update_from_proto(message)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSemanticTokensDelta) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+pub(crate) fn folding_ranges_supported(capabilities: &lsp::ServerCapabilities) -> bool {
+    match capabilities.folding_range_provider {
+        Some(lsp::FoldingRangeProviderCapability::Simple(supported)) => supported,
+        Some(_) => true,
+        None => false,
+    }
+}
+
+pub(crate) fn selection_ranges_supported(capabilities: &lsp::ServerCapabilities) -> bool {
+    match capabilities.selection_range_provider {
+        Some(lsp::SelectionRangeProviderCapability::Simple(supported)) => supported,
+        Some(_) => true,
+        None => false,
+    }
+}
+
+async fn anchor_ranges_from_proto(
+    ranges: Vec<proto::AnchorRange>,
+    buffer: &Entity<Buffer>,
+    cx: &mut AsyncApp,
+) -> Result<Vec<Range<Anchor>>> {
+    let ranges = ranges
+        .into_iter()
+        .map(deserialize_anchor_range)
+        .collect::<Result<Vec<_>>>()?;
+    buffer
+        .update(cx, |buffer, _| {
+            buffer.wait_for_anchors(ranges.iter().flat_map(|range| [range.start, range.end]))
+        })?
+        .await?;
+    Ok(ranges)
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetFoldingRanges {
+    type Response = Vec<Range<Anchor>>;
+    type LspRequest = lsp::request::FoldingRangeRequest;
+    type ProtoRequest = proto::GetFoldingRanges;
+
+    fn display_name(&self) -> &str {
+        "Get folding ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        folding_ranges_supported(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::FoldingRangeParams> {
+        Ok(lsp::FoldingRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::FoldingRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<Range<Anchor>>> {
+        buffer.read_with(&cx, |buffer, _| {
+            let max_row = buffer.max_point().row;
+            // Only whole lines are folded, the same way indentation-based folds are:
+            // from the end of the first line to the end of the last one.
+            let mut row_ranges = message
+                .unwrap_or_default()
+                .into_iter()
+                .map(|range| range.start_line..range.end_line.min(max_row))
+                .filter(|rows| rows.start < rows.end)
+                .collect::<Vec<_>>();
+            row_ranges.sort_by_key(|rows| (rows.start, Reverse(rows.end)));
+            row_ranges.dedup();
+            row_ranges
+                .into_iter()
+                .map(|rows| {
+                    let start = Point::new(rows.start, buffer.line_len(rows.start));
+                    let end = Point::new(rows.end, buffer.line_len(rows.end));
+                    buffer.anchor_before(start)..buffer.anchor_after(end)
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetFoldingRanges {
+        proto::GetFoldingRanges {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetFoldingRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<Range<Anchor>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetFoldingRangesResponse {
+        proto::GetFoldingRangesResponse {
+            ranges: response.into_iter().map(serialize_anchor_range).collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetFoldingRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Range<Anchor>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        anchor_ranges_from_proto(message.ranges, &buffer, &mut cx).await
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetFoldingRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetSelectionRanges {
+    type Response = Vec<Vec<Range<Anchor>>>;
+    type LspRequest = lsp::request::SelectionRangeRequest;
+    type ProtoRequest = proto::GetSelectionRanges;
+
+    fn display_name(&self) -> &str {
+        "Get selection ranges"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        selection_ranges_supported(&capabilities.server_capabilities)
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        buffer: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::SelectionRangeParams> {
+        let snapshot = buffer.snapshot();
+        Ok(lsp::SelectionRangeParams {
+            text_document: make_text_document_identifier(path)?,
+            positions: self
+                .positions
+                .iter()
+                .map(|position| point_to_lsp(position.to_point_utf16(&snapshot)))
+                .collect(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::SelectionRange>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        _: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer.read_with(&cx, |buffer, _| {
+            message
+                .unwrap_or_default()
+                .into_iter()
+                .map(|selection_range| {
+                    let mut ranges = Vec::new();
+                    let mut selection_range = Some(selection_range);
+                    while let Some(lsp::SelectionRange { range, parent }) = selection_range {
+                        let start =
+                            buffer.clip_point_utf16(point_from_lsp(range.start), Bias::Left);
+                        let end = buffer.clip_point_utf16(point_from_lsp(range.end), Bias::Left);
+                        ranges.push(buffer.anchor_before(start)..buffer.anchor_after(end));
+                        selection_range = parent.map(|parent| *parent);
+                    }
+                    ranges
+                })
+                .collect()
+        })
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetSelectionRanges {
+        proto::GetSelectionRanges {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            positions: self.positions.iter().map(serialize_anchor).collect(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetSelectionRanges,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let positions = message
+            .positions
+            .into_iter()
+            .map(|position| deserialize_anchor(position).context("invalid position"))
+            .collect::<Result<Vec<_>>>()?;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors(positions.iter().copied())
+            })?
+            .await?;
+        Ok(Self { positions })
+    }
+
+    fn response_to_proto(
+        response: Vec<Vec<Range<Anchor>>>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetSelectionRangesResponse {
+        proto::GetSelectionRangesResponse {
+            selection_ranges: response
+                .into_iter()
+                .map(|ranges| proto::SelectionRanges {
+                    ranges: ranges.into_iter().map(serialize_anchor_range).collect(),
+                })
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetSelectionRangesResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<Vec<Range<Anchor>>>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        let mut selection_ranges = Vec::with_capacity(message.selection_ranges.len());
+        for ranges in message.selection_ranges {
+            selection_ranges.push(anchor_ranges_from_proto(ranges.ranges, &buffer, &mut cx).await?);
+        }
+        Ok(selection_ranges)
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetSelectionRanges) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
+impl GetDocumentLinks {
+    pub fn can_resolve_links(capabilities: &ServerCapabilities) -> bool {
+        capabilities
+            .document_link_provider
+            .as_ref()
+            .and_then(|options| options.resolve_provider)
+            .unwrap_or(false)
+    }
+
+    pub fn lsp_to_project_link(
+        lsp_link: lsp::DocumentLink,
+        server_id: LanguageServerId,
+        snapshot: &BufferSnapshot,
+    ) -> DocumentLink {
+        let link_range = range_from_lsp(lsp_link.range);
+        let start = snapshot.clip_point_utf16(link_range.start, Bias::Left);
+        let end = snapshot.clip_point_utf16(link_range.end, Bias::Right);
+        DocumentLink {
+            server_id,
+            range: snapshot.anchor_before(start)..snapshot.anchor_after(end),
+            resolved: lsp_link.target.is_some(),
+            lsp_link,
+        }
+    }
+
+    pub fn project_to_proto_link(link: &DocumentLink) -> proto::DocumentLink {
+        proto::DocumentLink {
+            server_id: link.server_id.to_proto(),
+            start: Some(serialize_anchor(&link.range.start)),
+            end: Some(serialize_anchor(&link.range.end)),
+            lsp_link: serde_json::to_vec(&link.lsp_link).unwrap(),
+            resolved: link.resolved,
+        }
+    }
+
+    pub fn proto_to_project_link(link: proto::DocumentLink) -> Result<DocumentLink> {
+        let start = link
+            .start
+            .and_then(deserialize_anchor)
+            .context("invalid document link start")?;
+        let end = link
+            .end
+            .and_then(deserialize_anchor)
+            .context("invalid document link end")?;
+        Ok(DocumentLink {
+            server_id: LanguageServerId::from_proto(link.server_id),
+            range: start..end,
+            lsp_link: serde_json::from_slice(&link.lsp_link)
+                .context("deserializing proto document link")?,
+            resolved: link.resolved,
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl LspCommand for GetDocumentLinks {
+    type Response = Vec<DocumentLink>;
+    type LspRequest = lsp::request::DocumentLinkRequest;
+    type ProtoRequest = proto::GetDocumentLinks;
+
+    fn display_name(&self) -> &str {
+        "Get document links"
+    }
+
+    fn check_capabilities(&self, capabilities: AdapterServerCapabilities) -> bool {
+        capabilities
+            .server_capabilities
+            .document_link_provider
+            .is_some()
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLinkParams> {
+        Ok(lsp::DocumentLinkParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::DocumentLink>>,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        let snapshot = buffer.read_with(&cx, |buffer, _| buffer.snapshot())?;
+        Ok(message
+            .unwrap_or_default()
+            .into_iter()
+            .map(|lsp_link| Self::lsp_to_project_link(lsp_link, server_id, &snapshot))
+            .collect())
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> proto::GetDocumentLinks {
+        proto::GetDocumentLinks {
+            project_id,
+            buffer_id: buffer.remote_id().into(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: proto::GetDocumentLinks,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Vec<DocumentLink>,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetDocumentLinksResponse {
+        proto::GetDocumentLinksResponse {
+            links: response.iter().map(Self::project_to_proto_link).collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetDocumentLinksResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Vec<DocumentLink>> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .links
+            .into_iter()
+            .map(Self::proto_to_project_link)
+            .collect()
+    }
+
+    fn buffer_id_from_proto(message: &proto::GetDocumentLinks) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
 #[async_trait(?Send)]
 impl LspCommand for GetSignatureHelp {
     type Response = Option<SignatureHelp>;
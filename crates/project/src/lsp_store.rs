@@ -17,13 +17,18 @@ pub mod rust_analyzer_ext;
 pub mod vue_language_server_ext;
 
 mod inlay_hint_cache;
+mod semantic_tokens;
 
 use self::inlay_hint_cache::BufferInlayHints;
+use self::semantic_tokens::BufferSemanticTokens;
+pub(crate) use self::semantic_tokens::{
+    SemanticTokensEdit, SemanticTokensUpdate, flatten_lsp_tokens, semantic_tokens_options,
+};
 use crate::{
     CodeAction, ColorPresentation, Completion, CompletionDisplayOptions, CompletionResponse,
-    CompletionSource, CoreCompletion, DocumentColor, Hover, InlayHint, InlayId, LocationLink,
-    LspAction, LspPullDiagnostics, ManifestProvidersStore, Project, ProjectItem, ProjectPath,
-    ProjectTransaction, PulledDiagnostics, ResolveState, Symbol,
+    CompletionSource, CoreCompletion, DocumentColor, DocumentLink, Hover, InlayHint, InlayId,
+    LocationLink, LspAction, LspPullDiagnostics, ManifestProvidersStore, Project, ProjectItem,
+    ProjectPath, ProjectTransaction, PulledDiagnostics, ResolveState, Symbol,
     buffer_store::{BufferStore, BufferStoreEvent},
     environment::ProjectEnvironment,
     lsp_command::{self, *},
@@ -131,6 +136,7 @@ use util::{
 pub use fs::*;
 pub use language::Location;
 pub use lsp_store::inlay_hint_cache::{CacheInlayHints, InvalidationStrategy};
+pub use lsp_store::semantic_tokens::{SemanticToken, SemanticTokens, SemanticTokensTask};
 #[cfg(any(test, feature = "test-support"))]
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use worktree::{
@@ -270,6 +276,8 @@ pub struct LocalLspStore {
         HashMap<LanguageServerId, (LanguageServerName, Arc<LanguageServer>)>,
     prettier_store: Entity<PrettierStore>,
     next_diagnostic_group_id: usize,
+    /// The id that diagnostics from task problem matchers are stored under, once any were reported.
+    task_diagnostics_source: Option<LanguageServerId>,
     diagnostics: HashMap<
         WorktreeId,
         HashMap<
@@ -296,6 +304,14 @@ pub struct LocalLspStore {
 }
 
 impl LocalLspStore {
+    /// Returns the id that diagnostics reported by task problem matchers are stored under.
+    /// It's taken from the language server ids on first use, so no language server shares it.
+    fn task_diagnostics_source(&mut self) -> LanguageServerId {
+        *self
+            .task_diagnostics_source
+            .get_or_insert_with(|| self.languages.next_language_server_id())
+    }
+
     /// Returns the running language server for the given ID. Note if the language server is starting, it will not be returned.
     pub fn running_language_server_for_id(
         &self,
@@ -959,6 +975,29 @@ impl LocalLspStore {
             })
             .detach();
 
+        language_server
+            .on_request::<lsp::request::SemanticTokensRefresh, _, _>({
+                let this = lsp_store.clone();
+                move |(), cx| {
+                    let this = this.clone();
+                    let mut cx = cx.clone();
+                    async move {
+                        this.update(&mut cx, |this, cx| {
+                            this.invalidate_semantic_tokens();
+                            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+                            this.downstream_client.as_ref().map(|(client, project_id)| {
+                                client.send(proto::RefreshSemanticTokens {
+                                    project_id: *project_id,
+                                })
+                            })
+                        })?
+                        .transpose()?;
+                        Ok(())
+                    }
+                }
+            })
+            .detach();
+
         language_server
             .on_request::<lsp::request::WorkspaceDiagnosticRefresh, _, _>({
                 let this = lsp_store.clone();
@@ -3667,6 +3706,7 @@ pub struct LspStore {
         HashMap<WorktreeId, HashMap<Arc<RelPath>, HashMap<LanguageServerId, DiagnosticSummary>>>,
     pub lsp_server_capabilities: HashMap<LanguageServerId, lsp::ServerCapabilities>,
     lsp_data: HashMap<BufferId, BufferLspData>,
+    semantic_tokens: HashMap<BufferId, BufferSemanticTokens>,
     next_hint_id: Arc<AtomicUsize>,
 }
 
@@ -3675,6 +3715,8 @@ pub struct BufferLspData {
     buffer_version: Global,
     document_colors: Option<DocumentColorData>,
     code_lens: Option<CodeLensData>,
+    folding_ranges: Option<FoldingRangeData>,
+    document_links: Option<DocumentLinkData>,
     inlay_hints: BufferInlayHints,
     lsp_requests: HashMap<LspKey, HashMap<LspRequestId, Task<()>>>,
     chunk_lsp_requests: HashMap<LspKey, HashMap<RowChunk, LspRequestId>>,
@@ -3692,6 +3734,8 @@ impl BufferLspData {
             buffer_version: buffer.read(cx).version(),
             document_colors: None,
             code_lens: None,
+            folding_ranges: None,
+            document_links: None,
             inlay_hints: BufferInlayHints::new(buffer, cx),
             lsp_requests: HashMap::default(),
             chunk_lsp_requests: HashMap::default(),
@@ -3708,6 +3752,9 @@ impl BufferLspData {
             code_lens.lens.remove(&for_server);
         }
 
+        self.folding_ranges = None;
+        self.document_links = None;
+
         self.inlay_hints.remove_server_data(for_server);
     }
 
@@ -3725,6 +3772,9 @@ pub struct DocumentColors {
 
 type DocumentColorTask = Shared<Task<std::result::Result<DocumentColors, Arc<anyhow::Error>>>>;
 type CodeLensTask = Shared<Task<std::result::Result<Option<Vec<CodeAction>>, Arc<anyhow::Error>>>>;
+type FoldingRangesTask =
+    Shared<Task<std::result::Result<Arc<[Range<Anchor>]>, Arc<anyhow::Error>>>>;
+type DocumentLinksTask = Shared<Task<std::result::Result<Arc<[DocumentLink]>, Arc<anyhow::Error>>>>;
 
 #[derive(Debug, Default)]
 struct DocumentColorData {
@@ -3739,6 +3789,18 @@ struct CodeLensData {
     update: Option<(Global, CodeLensTask)>,
 }
 
+#[derive(Debug, Default)]
+struct FoldingRangeData {
+    ranges: Option<Arc<[Range<Anchor>]>>,
+    update: Option<(Global, FoldingRangesTask)>,
+}
+
+#[derive(Debug, Default)]
+struct DocumentLinkData {
+    links: Option<Arc<[DocumentLink]>>,
+    update: Option<(Global, DocumentLinksTask)>,
+}
+
 #[derive(Debug)]
 pub enum LspStoreEvent {
     LanguageServerAdded(LanguageServerId, LanguageServerName, Option<WorktreeId>),
@@ -3760,6 +3822,7 @@ pub enum LspStoreEvent {
         request_id: Option<usize>,
     },
     RefreshCodeLens,
+    RefreshSemanticTokens,
     DiagnosticsUpdated {
         server_id: LanguageServerId,
         paths: Vec<ProjectPath>,
@@ -3835,10 +3898,12 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_apply_code_action);
         client.add_entity_request_handler(Self::handle_get_project_symbols);
         client.add_entity_request_handler(Self::handle_resolve_inlay_hint);
+        client.add_entity_request_handler(Self::handle_resolve_document_link);
         client.add_entity_request_handler(Self::handle_get_color_presentation);
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
+        client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
         client.add_entity_request_handler(Self::handle_on_type_formatting);
         client.add_entity_request_handler(Self::handle_apply_additional_edits_for_completion);
         client.add_entity_request_handler(Self::handle_register_buffer_with_language_servers);
@@ -3847,6 +3912,17 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_get_completions);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentHighlights>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentSymbols>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSemanticTokens>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSemanticTokensDelta>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetFoldingRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSelectionRanges>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentLinks>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
@@ -3955,6 +4031,7 @@ impl LspStore {
                 fs,
                 yarn,
                 next_diagnostic_group_id: Default::default(),
+                task_diagnostics_source: None,
                 diagnostics: Default::default(),
                 _subscription: cx.on_app_quit(|this, cx| {
                     this.as_local_mut()
@@ -3984,6 +4061,7 @@ impl LspStore {
             diagnostic_summaries: HashMap::default(),
             lsp_server_capabilities: HashMap::default(),
             lsp_data: HashMap::default(),
+            semantic_tokens: HashMap::default(),
             next_hint_id: Arc::default(),
             active_entry: None,
             _maintain_workspace_config,
@@ -4045,6 +4123,7 @@ impl LspStore {
             lsp_server_capabilities: HashMap::default(),
             next_hint_id: Arc::default(),
             lsp_data: HashMap::default(),
+            semantic_tokens: HashMap::default(),
             active_entry: None,
 
             _maintain_workspace_config,
@@ -4242,6 +4321,7 @@ impl LspStore {
                     };
                     if refcount == 0 {
                         lsp_store.lsp_data.remove(&buffer_id);
+                        lsp_store.semantic_tokens.remove(&buffer_id);
                         let local = lsp_store.as_local_mut().unwrap();
                         local.registered_buffers.remove(&buffer_id);
 
@@ -7476,6 +7556,389 @@ impl LspStore {
         }
     }
 
+    /// Fetches the semantic tokens of the buffer from all language servers that provide them.
+    ///
+    /// Returns `None` if the cached tokens are up to date and match the `known_cache_version`.
+    pub fn semantic_tokens(
+        &mut self,
+        known_cache_version: Option<usize>,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Option<SemanticTokensTask> {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+        let servers = self.semantic_tokens_servers(&buffer, cx);
+
+        let buffer_tokens = self.semantic_tokens.entry(buffer_id).or_default();
+        if buffer_tokens.is_up_to_date(&version_queried_for, &servers) {
+            if Some(buffer_tokens.cache_version) == known_cache_version {
+                return None;
+            }
+            return Some(Task::ready(Ok(buffer_tokens.to_semantic_tokens())).shared());
+        }
+        if let Some((updating_for, running_update)) = &buffer_tokens.update
+            && !version_queried_for.changed_since(updating_for)
+        {
+            return Some(running_update.clone());
+        }
+
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(30))
+                    .await;
+                let (snapshot, legends, requests) = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        let snapshot = buffer.read(cx).snapshot();
+                        let mut legends = HashMap::default();
+                        let mut requests = Vec::with_capacity(servers.len());
+                        for &server_id in &servers {
+                            let Some(capabilities) = lsp_store.server_capabilities(server_id)
+                            else {
+                                continue;
+                            };
+                            let Some(options) = semantic_tokens_options(&capabilities) else {
+                                continue;
+                            };
+                            legends.insert(server_id, options.legend.clone());
+
+                            let previous_result_id = lsp_store
+                                .semantic_tokens
+                                .get(&buffer_id)
+                                .and_then(|buffer_tokens| {
+                                    buffer_tokens.previous_result_id(server_id)
+                                })
+                                .filter(|_| semantic_tokens_delta_supported(&capabilities));
+                            let request = match previous_result_id {
+                                Some(previous_result_id) => lsp_store.request_lsp(
+                                    buffer.clone(),
+                                    LanguageServerToQuery::Other(server_id),
+                                    GetSemanticTokensDelta {
+                                        language_server_id: server_id,
+                                        previous_result_id,
+                                    },
+                                    cx,
+                                ),
+                                None => lsp_store.request_lsp(
+                                    buffer.clone(),
+                                    LanguageServerToQuery::Other(server_id),
+                                    GetSemanticTokens {
+                                        language_server_id: server_id,
+                                    },
+                                    cx,
+                                ),
+                            };
+                            requests.push(async move { (server_id, request.await) });
+                        }
+                        (snapshot, legends, requests)
+                    })
+                    .map_err(Arc::new)?;
+
+                let responses = join_all(requests).await;
+                lsp_store
+                    .update(cx, |lsp_store, _| {
+                        let buffer_tokens = lsp_store.semantic_tokens.entry(buffer_id).or_default();
+                        for (server_id, response) in responses {
+                            match response {
+                                Ok(update) => {
+                                    if let Some(legend) = legends.get(&server_id) {
+                                        buffer_tokens
+                                            .apply_update(server_id, update, legend, &snapshot);
+                                    }
+                                }
+                                Err(e) => log::error!(
+                                    "Failed to fetch semantic tokens from server {server_id}: {e:#}"
+                                ),
+                            }
+                        }
+                        buffer_tokens
+                            .servers
+                            .retain(|server_id, _| servers.contains(server_id));
+                        buffer_tokens.cache_version += 1;
+                        buffer_tokens.update = None;
+                        buffer_tokens.to_semantic_tokens()
+                    })
+                    .map_err(Arc::new)
+            })
+            .shared();
+        self.semantic_tokens.entry(buffer_id).or_default().update =
+            Some((version_queried_for, new_task.clone()));
+        Some(new_task)
+    }
+
+    fn semantic_tokens_servers(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Vec<LanguageServerId> {
+        if self.upstream_client().is_some() {
+            self.all_capable_for_proto_request(
+                buffer,
+                |_, capabilities| semantic_tokens_full_supported(capabilities),
+                cx,
+            )
+        } else {
+            buffer.update(cx, |buffer, cx| {
+                self.language_servers_for_local_buffer(buffer, cx)
+                    .filter(|(_, server)| semantic_tokens_full_supported(&server.capabilities()))
+                    .map(|(_, server)| server.server_id())
+                    .collect()
+            })
+        }
+    }
+
+    fn server_capabilities(&self, server_id: LanguageServerId) -> Option<lsp::ServerCapabilities> {
+        match self.language_server_for_id(server_id) {
+            Some(server) => Some(server.capabilities()),
+            None => self.lsp_server_capabilities.get(&server_id).cloned(),
+        }
+    }
+
+    fn invalidate_semantic_tokens(&mut self) {
+        for buffer_tokens in self.semantic_tokens.values_mut() {
+            buffer_tokens.invalidate();
+        }
+    }
+
+    /// Fetches the folding ranges of the buffer from the first language server that provides them.
+    ///
+    /// Every range spans whole lines, from the end of its first line to the end of its last one.
+    /// Ranges are sorted by their start, larger ranges first.
+    pub fn folding_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> FoldingRangesTask {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+
+        if let Some(lsp_data) = self.current_lsp_data(buffer_id)
+            && let Some(folding_ranges) = &lsp_data.folding_ranges
+        {
+            if let Some(cached_ranges) = &folding_ranges.ranges
+                && !version_queried_for.changed_since(&lsp_data.buffer_version)
+            {
+                return Task::ready(Ok(cached_ranges.clone())).shared();
+            }
+            if let Some((updating_for, running_update)) = &folding_ranges.update
+                && !version_queried_for.changed_since(updating_for)
+            {
+                return running_update.clone();
+            }
+        }
+
+        let folding_lsp_data = self
+            .latest_lsp_data(buffer, cx)
+            .folding_ranges
+            .get_or_insert_default();
+        let buffer = buffer.clone();
+        let query_version_queried_for = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(30))
+                    .await;
+                let request = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        lsp_store.request_lsp(
+                            buffer,
+                            LanguageServerToQuery::FirstCapable,
+                            GetFoldingRanges,
+                            cx,
+                        )
+                    })
+                    .map_err(Arc::new)?;
+                let fetched_ranges = request
+                    .await
+                    .context("fetching folding ranges")
+                    .map(Arc::<[_]>::from);
+
+                lsp_store
+                    .update(cx, |lsp_store, _| {
+                        if let Some(lsp_data) = lsp_store.current_lsp_data(buffer_id)
+                            && let Some(folding_ranges) = lsp_data.folding_ranges.as_mut()
+                        {
+                            folding_ranges.update = None;
+                            if let Ok(fetched_ranges) = &fetched_ranges
+                                && lsp_data.buffer_version == query_version_queried_for
+                            {
+                                folding_ranges.ranges = Some(fetched_ranges.clone());
+                            }
+                        }
+                    })
+                    .map_err(Arc::new)?;
+                fetched_ranges.map_err(Arc::new)
+            })
+            .shared();
+        folding_lsp_data.update = Some((version_queried_for, new_task.clone()));
+        new_task
+    }
+
+    /// Fetches the document links of the buffer from the first language server that provides them.
+    ///
+    /// Links may have no target yet, those need to be resolved with [`Self::resolve_document_link`].
+    pub fn document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> DocumentLinksTask {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+
+        if let Some(lsp_data) = self.current_lsp_data(buffer_id)
+            && let Some(document_links) = &lsp_data.document_links
+        {
+            if let Some(cached_links) = &document_links.links
+                && !version_queried_for.changed_since(&lsp_data.buffer_version)
+            {
+                return Task::ready(Ok(cached_links.clone())).shared();
+            }
+            if let Some((updating_for, running_update)) = &document_links.update
+                && !version_queried_for.changed_since(updating_for)
+            {
+                return running_update.clone();
+            }
+        }
+
+        let links_lsp_data = self
+            .latest_lsp_data(buffer, cx)
+            .document_links
+            .get_or_insert_default();
+        let buffer = buffer.clone();
+        let query_version_queried_for = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                let request = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        lsp_store.request_lsp(
+                            buffer,
+                            LanguageServerToQuery::FirstCapable,
+                            GetDocumentLinks,
+                            cx,
+                        )
+                    })
+                    .map_err(Arc::new)?;
+                let fetched_links = request
+                    .await
+                    .context("fetching document links")
+                    .map(Arc::<[_]>::from);
+
+                lsp_store
+                    .update(cx, |lsp_store, _| {
+                        if let Some(lsp_data) = lsp_store.current_lsp_data(buffer_id)
+                            && let Some(document_links) = lsp_data.document_links.as_mut()
+                        {
+                            document_links.update = None;
+                            if let Ok(fetched_links) = &fetched_links
+                                && lsp_data.buffer_version == query_version_queried_for
+                            {
+                                document_links.links = Some(fetched_links.clone());
+                            }
+                        }
+                    })
+                    .map_err(Arc::new)?;
+                fetched_links.map_err(Arc::new)
+            })
+            .shared();
+        links_lsp_data.update = Some((version_queried_for, new_task.clone()));
+        new_task
+    }
+
+    /// Resolves the target of a document link, replacing the cached link with the resolved one.
+    pub fn resolve_document_link(
+        &mut self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        if link.resolved {
+            return Task::ready(Ok(link));
+        }
+
+        let buffer_id = buffer.read(cx).remote_id();
+        let resolve_task = if let Some((upstream_client, project_id)) = self.upstream_client() {
+            if !self.check_if_capable_for_proto_request(
+                &buffer,
+                GetDocumentLinks::can_resolve_links,
+                cx,
+            ) {
+                return Task::ready(Ok(DocumentLink {
+                    resolved: true,
+                    ..link
+                }));
+            }
+            let request = proto::ResolveDocumentLink {
+                project_id,
+                buffer_id: buffer_id.into(),
+                link: Some(GetDocumentLinks::project_to_proto_link(&link)),
+            };
+            cx.background_spawn(async move {
+                let response = upstream_client
+                    .request(request)
+                    .await
+                    .context("document link proto request")?;
+                response
+                    .link
+                    .context("missing the resolved document link")
+                    .and_then(GetDocumentLinks::proto_to_project_link)
+            })
+        } else {
+            let Some(lang_server) = buffer.update(cx, |buffer, cx| {
+                self.language_server_for_local_buffer(buffer, link.server_id, cx)
+                    .map(|(_, server)| server.clone())
+            }) else {
+                return Task::ready(Ok(link));
+            };
+            if !GetDocumentLinks::can_resolve_links(&lang_server.capabilities()) {
+                return Task::ready(Ok(DocumentLink {
+                    resolved: true,
+                    ..link
+                }));
+            }
+            let buffer_snapshot = buffer.read(cx).snapshot();
+            let server_id = link.server_id;
+            cx.background_spawn(async move {
+                let resolved_link = lang_server
+                    .request::<lsp::request::DocumentLinkResolve>(link.lsp_link)
+                    .await
+                    .into_response()
+                    .context("document link resolve LSP request")?;
+                Ok(DocumentLink {
+                    resolved: true,
+                    ..GetDocumentLinks::lsp_to_project_link(
+                        resolved_link,
+                        server_id,
+                        &buffer_snapshot,
+                    )
+                })
+            })
+        };
+
+        cx.spawn(async move |lsp_store, cx| {
+            let resolved_link = resolve_task.await?;
+            lsp_store.update(cx, |lsp_store, _| {
+                if let Some(lsp_data) = lsp_store.current_lsp_data(buffer_id)
+                    && let Some(document_links) = lsp_data.document_links.as_mut()
+                    && let Some(cached_links) = document_links.links.as_mut()
+                {
+                    *cached_links = cached_links
+                        .iter()
+                        .map(|cached_link| {
+                            if cached_link.server_id == resolved_link.server_id
+                                && cached_link.range == resolved_link.range
+                            {
+                                resolved_link.clone()
+                            } else {
+                                cached_link.clone()
+                            }
+                        })
+                        .collect();
+                }
+            })?;
+            Ok(resolved_link)
+        })
+    }
+
     pub fn signature_help<T: ToPointUtf16>(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -8425,6 +8888,118 @@ impl LspStore {
         Ok(())
     }
 
+    /// Replaces the diagnostics previously reported by the task with the given label with the
+    /// problems its problem matchers found in its latest output. Relative paths are resolved
+    /// against `cwd`, and problems outside of the project's worktrees are dropped.
+    pub fn report_task_problems(
+        &mut self,
+        task_label: &str,
+        cwd: Option<&Path>,
+        problems: Vec<task::TaskProblem>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let local = self
+            .as_local_mut()
+            .context("task problems can only be reported in local projects")?;
+        let server_id = local.task_diagnostics_source();
+        let source = format!("task: {task_label}");
+        let mut diagnostics_by_path = HashMap::<PathBuf, Vec<_>>::default();
+
+        // Clear the diagnostics this task reported before, in case its problems got fixed.
+        for (worktree_id, summaries) in &self.diagnostic_summaries {
+            let Some(worktree) = self
+                .worktree_store
+                .read(cx)
+                .worktree_for_id(*worktree_id, cx)
+            else {
+                continue;
+            };
+            for (path, summaries_by_server_id) in summaries {
+                if summaries_by_server_id.contains_key(&server_id) {
+                    diagnostics_by_path.insert(worktree.read(cx).absolutize(path), Vec::new());
+                }
+            }
+        }
+
+        for problem in problems {
+            let path = Path::new(&problem.path);
+            let abs_path = match cwd {
+                Some(cwd) if path.is_relative() => cwd.join(path),
+                _ => path.to_path_buf(),
+            };
+            if self
+                .worktree_store
+                .read(cx)
+                .find_worktree(&abs_path, cx)
+                .is_none()
+            {
+                continue;
+            }
+
+            let row = problem.line.saturating_sub(1);
+            let start_column = problem.column.map_or(0, |column| column.saturating_sub(1));
+            let end = match problem.end {
+                Some((end_line, Some(end_column))) => {
+                    PointUtf16::new(end_line.saturating_sub(1), end_column.saturating_sub(1))
+                }
+                Some((end_line, None)) => PointUtf16::new(end_line.saturating_sub(1), u32::MAX),
+                None if problem.column.is_some() => PointUtf16::new(row, start_column + 1),
+                None => PointUtf16::new(row, u32::MAX),
+            };
+            let severity = match problem.severity {
+                task::ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                task::ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                task::ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                task::ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+            };
+            let group_id = post_inc(
+                &mut self
+                    .as_local_mut()
+                    .context("task problems can only be reported in local projects")?
+                    .next_diagnostic_group_id,
+            );
+            diagnostics_by_path
+                .entry(abs_path)
+                .or_default()
+                .push(DiagnosticEntry {
+                    range: Unclipped(PointUtf16::new(row, start_column))..Unclipped(end),
+                    diagnostic: Diagnostic {
+                        source: Some(source.clone()),
+                        code: problem.code.map(lsp::NumberOrString::String),
+                        severity,
+                        message: problem.message,
+                        group_id,
+                        is_primary: true,
+                        is_disk_based: true,
+                        source_kind: DiagnosticSourceKind::Other,
+                        ..Diagnostic::default()
+                    },
+                });
+        }
+
+        let updates = diagnostics_by_path
+            .into_iter()
+            .map(
+                |(document_abs_path, diagnostics)| DocumentDiagnosticsUpdate {
+                    diagnostics: DocumentDiagnostics {
+                        diagnostics,
+                        document_abs_path,
+                        version: None,
+                    },
+                    result_id: None,
+                    registration_id: None,
+                    server_id,
+                    disk_based_sources: Cow::Borrowed(&[]),
+                },
+            )
+            .collect();
+        self.merge_diagnostic_entries(
+            updates,
+            |_, diagnostic, _| diagnostic.source.as_ref() != Some(&source),
+            cx,
+        )
+    }
+
     fn update_worktree_diagnostics(
         &mut self,
         worktree_id: WorktreeId,
@@ -8763,14 +9338,13 @@ impl LspStore {
             cx.clone(),
         )
         .await?;
+        let server_to_query = match request.language_server_id() {
+            Some(server_id) => LanguageServerToQuery::Other(server_id),
+            None => LanguageServerToQuery::FirstCapable,
+        };
         let response = this
             .update(&mut cx, |this, cx| {
-                this.request_lsp(
-                    buffer_handle.clone(),
-                    LanguageServerToQuery::FirstCapable,
-                    request,
-                    cx,
-                )
+                this.request_lsp(buffer_handle.clone(), server_to_query, request, cx)
             })?
             .await?;
         this.update(&mut cx, |this, cx| {
@@ -10202,6 +10776,37 @@ impl LspStore {
         })
     }
 
+    async fn handle_resolve_document_link(
+        lsp_store: Entity<Self>,
+        envelope: TypedEnvelope<proto::ResolveDocumentLink>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ResolveDocumentLinkResponse> {
+        let link = GetDocumentLinks::proto_to_project_link(
+            envelope
+                .payload
+                .link
+                .context("missing the document link to resolve")?,
+        )?;
+        let buffer = lsp_store.update(&mut cx, |lsp_store, cx| {
+            let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+            lsp_store.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_anchors([link.range.start, link.range.end])
+            })?
+            .await?;
+        let resolved_link = lsp_store
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.resolve_document_link(link, buffer, cx)
+            })?
+            .await
+            .context("resolving document link")?;
+        Ok(proto::ResolveDocumentLinkResponse {
+            link: Some(GetDocumentLinks::project_to_proto_link(&resolved_link)),
+        })
+    }
+
     async fn handle_refresh_code_lens(
         this: Entity<Self>,
         _: TypedEnvelope<proto::RefreshCodeLens>,
@@ -10213,6 +10818,18 @@ impl LspStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_refresh_semantic_tokens(
+        this: Entity<Self>,
+        _: TypedEnvelope<proto::RefreshSemanticTokens>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |this, cx| {
+            this.invalidate_semantic_tokens();
+            cx.emit(LspStoreEvent::RefreshSemanticTokens);
+        })?;
+        Ok(proto::Ack {})
+    }
+
     async fn handle_open_buffer_for_symbol(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::OpenBufferForSymbol>,
@@ -11948,6 +12565,9 @@ impl LspStore {
         for lsp_data in self.lsp_data.values_mut() {
             lsp_data.remove_server_data(for_server);
         }
+        for semantic_tokens in self.semantic_tokens.values_mut() {
+            semantic_tokens.remove_server_data(for_server);
+        }
         if let Some(local) = self.as_local_mut() {
             local.buffer_pull_diagnostics_result_ids.remove(&for_server);
             local
@@ -12493,6 +13113,71 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::CallHierarchyServerCapability::Simple(value),
+                        OneOf::Right(caps) => caps,
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/prepareTypeHierarchy" => {
+                    let provider = parse_register_capabilities(reg)?;
+                    server.update_capabilities(|capabilities| {
+                        capabilities.type_hierarchy_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/semanticTokens" => {
+                    if let Some(caps) = reg
+                        .register_options
+                        .map(serde_json::from_value::<lsp::SemanticTokensRegistrationOptions>)
+                        .transpose()?
+                    {
+                        server.update_capabilities(|capabilities| {
+                            capabilities.semantic_tokens_provider = Some(
+                                lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(caps),
+                            );
+                        });
+                        notify_server_capabilities_updated(&server, cx);
+                    }
+                }
+                "textDocument/foldingRange" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::FoldingRangeProviderCapability::Simple(value),
+                        OneOf::Right(caps) => caps,
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.folding_range_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/documentLink" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(_) => lsp::DocumentLinkOptions::default(),
+                        OneOf::Right(caps) => caps,
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.document_link_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/selectionRange" => {
+                    let options = parse_register_capabilities(reg)?;
+                    let provider = match options {
+                        OneOf::Left(value) => lsp::SelectionRangeProviderCapability::Simple(value),
+                        OneOf::Right(caps) => caps,
+                    };
+                    server.update_capabilities(|capabilities| {
+                        capabilities.selection_range_provider = Some(provider);
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability registration: {reg:?}"),
             }
         }
@@ -12684,6 +13369,42 @@ impl LspStore {
                     });
                     notify_server_capabilities_updated(&server, cx);
                 }
+                "textDocument/prepareCallHierarchy" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.call_hierarchy_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/prepareTypeHierarchy" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.type_hierarchy_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/semanticTokens" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.semantic_tokens_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/foldingRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.folding_range_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/selectionRange" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.selection_range_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
+                "textDocument/documentLink" => {
+                    server.update_capabilities(|capabilities| {
+                        capabilities.document_link_provider = None;
+                    });
+                    notify_server_capabilities_updated(&server, cx);
+                }
                 _ => log::warn!("unhandled capability unregistration: {unreg:?}"),
             }
         }
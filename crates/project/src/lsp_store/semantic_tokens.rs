@@ -0,0 +1,232 @@
+use std::{ops::Range, sync::Arc};
+
+use clock::Global;
+use collections::HashMap;
+use futures::future::Shared;
+use gpui::Task;
+use language::{BufferSnapshot, PointUtf16, Unclipped};
+use lsp::LanguageServerId;
+use text::{Anchor, Bias};
+
+/// A token reported by a language server, resolved against that server's token legend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub range: Range<Anchor>,
+    pub token_type: Arc<str>,
+    pub token_modifiers: Vec<Arc<str>>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SemanticTokens {
+    pub tokens: HashMap<LanguageServerId, Arc<[SemanticToken]>>,
+    pub cache_version: Option<usize>,
+}
+
+pub type SemanticTokensTask = Shared<Task<std::result::Result<SemanticTokens, Arc<anyhow::Error>>>>;
+
+/// A response to either a full or a delta semantic tokens request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SemanticTokensUpdate {
+    Full {
+        result_id: Option<String>,
+        data: Vec<u32>,
+    },
+    Delta {
+        result_id: Option<String>,
+        edits: Vec<SemanticTokensEdit>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SemanticTokensEdit {
+    pub start: u32,
+    pub delete_count: u32,
+    pub data: Vec<u32>,
+}
+
+/// Semantic tokens of a buffer, per language server.
+///
+/// Unlike the rest of the LSP data, this survives buffer edits: the raw token data
+/// and its result id are needed to request and apply delta updates.
+#[derive(Debug, Default)]
+pub(crate) struct BufferSemanticTokens {
+    pub(super) servers: HashMap<LanguageServerId, ServerSemanticTokens>,
+    pub(super) cache_version: usize,
+    pub(super) update: Option<(Global, SemanticTokensTask)>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ServerSemanticTokens {
+    pub(super) result_id: Option<String>,
+    /// Token data in the LSP relative encoding, five integers per token.
+    pub(super) data: Vec<u32>,
+    pub(super) tokens: Arc<[SemanticToken]>,
+    /// The buffer version the tokens were fetched for, `None` if the server asked to refresh them.
+    pub(super) fetched_for: Option<Global>,
+}
+
+impl BufferSemanticTokens {
+    pub(super) fn is_up_to_date(&self, version: &Global, servers: &[LanguageServerId]) -> bool {
+        servers.len() == self.servers.len()
+            && servers.iter().all(|server_id| {
+                self.servers.get(server_id).is_some_and(|server_tokens| {
+                    server_tokens
+                        .fetched_for
+                        .as_ref()
+                        .is_some_and(|fetched_for| !version.changed_since(fetched_for))
+                })
+            })
+    }
+
+    pub(super) fn invalidate(&mut self) {
+        for server_tokens in self.servers.values_mut() {
+            server_tokens.fetched_for = None;
+        }
+        self.update = None;
+    }
+
+    pub(super) fn remove_server_data(&mut self, for_server: LanguageServerId) {
+        if self.servers.remove(&for_server).is_some() {
+            self.cache_version += 1;
+        }
+        self.update = None;
+    }
+
+    pub(super) fn previous_result_id(&self, server_id: LanguageServerId) -> Option<String> {
+        self.servers.get(&server_id)?.result_id.clone()
+    }
+
+    pub(super) fn apply_update(
+        &mut self,
+        server_id: LanguageServerId,
+        update: Option<SemanticTokensUpdate>,
+        legend: &lsp::SemanticTokensLegend,
+        snapshot: &BufferSnapshot,
+    ) {
+        let (result_id, data) = match update {
+            Some(SemanticTokensUpdate::Full { result_id, data }) => (result_id, data),
+            Some(SemanticTokensUpdate::Delta { result_id, edits }) => {
+                let mut data = self
+                    .servers
+                    .remove(&server_id)
+                    .map(|server_tokens| server_tokens.data)
+                    .unwrap_or_default();
+                apply_edits(&mut data, edits);
+                (result_id, data)
+            }
+            None => (None, Vec::new()),
+        };
+        let tokens = decode_tokens(&data, legend, snapshot).into();
+        self.servers.insert(
+            server_id,
+            ServerSemanticTokens {
+                result_id,
+                data,
+                tokens,
+                fetched_for: Some(snapshot.version().clone()),
+            },
+        );
+    }
+
+    pub(super) fn to_semantic_tokens(&self) -> SemanticTokens {
+        SemanticTokens {
+            tokens: self
+                .servers
+                .iter()
+                .map(|(server_id, server_tokens)| (*server_id, server_tokens.tokens.clone()))
+                .collect(),
+            cache_version: Some(self.cache_version),
+        }
+    }
+}
+
+pub(crate) fn semantic_tokens_options(
+    capabilities: &lsp::ServerCapabilities,
+) -> Option<&lsp::SemanticTokensOptions> {
+    match capabilities.semantic_tokens_provider.as_ref()? {
+        lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(options) => Some(options),
+        lsp::SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(options) => {
+            Some(&options.semantic_tokens_options)
+        }
+    }
+}
+
+pub(crate) fn flatten_lsp_tokens(tokens: Vec<lsp::SemanticToken>) -> Vec<u32> {
+    tokens
+        .into_iter()
+        .flat_map(|token| {
+            [
+                token.delta_line,
+                token.delta_start,
+                token.length,
+                token.token_type,
+                token.token_modifiers_bitset,
+            ]
+        })
+        .collect()
+}
+
+/// Applies the edits of a delta response, which are all relative to the previous data.
+fn apply_edits(data: &mut Vec<u32>, mut edits: Vec<SemanticTokensEdit>) {
+    edits.sort_by_key(|edit| edit.start);
+    for edit in edits.into_iter().rev() {
+        let start = (edit.start as usize).min(data.len());
+        let end = (start + edit.delete_count as usize).min(data.len());
+        data.splice(start..end, edit.data);
+    }
+}
+
+fn decode_tokens(
+    data: &[u32],
+    legend: &lsp::SemanticTokensLegend,
+    snapshot: &BufferSnapshot,
+) -> Vec<SemanticToken> {
+    let token_types = legend
+        .token_types
+        .iter()
+        .map(|token_type| Arc::<str>::from(token_type.as_str()))
+        .collect::<Vec<_>>();
+    let token_modifiers = legend
+        .token_modifiers
+        .iter()
+        .map(|modifier| Arc::<str>::from(modifier.as_str()))
+        .collect::<Vec<_>>();
+
+    let mut tokens = Vec::with_capacity(data.len() / 5);
+    let mut line = 0;
+    let mut start_column = 0;
+    for token in data.chunks_exact(5) {
+        let (delta_line, delta_start, length) = (token[0], token[1], token[2]);
+        let (token_type, modifiers_bitset) = (token[3], token[4]);
+        if delta_line > 0 {
+            line += delta_line;
+            start_column = delta_start;
+        } else {
+            start_column += delta_start;
+        }
+        let Some(token_type) = token_types.get(token_type as usize) else {
+            continue;
+        };
+
+        let start =
+            snapshot.clip_point_utf16(Unclipped(PointUtf16::new(line, start_column)), Bias::Left);
+        let end = snapshot.clip_point_utf16(
+            Unclipped(PointUtf16::new(line, start_column + length)),
+            Bias::Right,
+        );
+        if start == end {
+            continue;
+        }
+        tokens.push(SemanticToken {
+            range: snapshot.anchor_after(start)..snapshot.anchor_before(end),
+            token_type: token_type.clone(),
+            token_modifiers: token_modifiers
+                .iter()
+                .enumerate()
+                .filter(|(ix, _)| *ix < 32 && modifiers_bitset & (1 << ix) != 0)
+                .map(|(_, modifier)| modifier.clone())
+                .collect(),
+        });
+    }
+    tokens
+}
@@ -142,8 +142,8 @@ pub use language::Location;
 #[cfg(any(test, feature = "test-support"))]
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use task_inventory::{
-    BasicContextProvider, ContextProviderWithTasks, DebugScenarioContext, Inventory, TaskContexts,
-    TaskSourceKind,
+    BasicContextProvider, ChainedTask, ContextProviderWithTasks, DebugScenarioContext, Inventory,
+    TaskChain, TaskContexts, TaskSourceKind,
 };
 
 pub use buffer_store::ProjectTransaction;
@@ -152,6 +152,7 @@ pub use lsp_store::{
     LanguageServerPromptRequest, LanguageServerStatus, LanguageServerToQuery, LspStore,
     LspStoreEvent, ProgressToken, SERVER_PROGRESS_THROTTLE_TIMEOUT,
 };
+pub use terminal::TaskActivity;
 pub use toolchain_store::{ToolchainStore, Toolchains};
 const MAX_PROJECT_SEARCH_HISTORY_SIZE: usize = 500;
 
@@ -344,11 +345,13 @@ pub enum Event {
         request_id: Option<usize>,
     },
     RefreshCodeLens,
+    RefreshSemanticTokens,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
     EntryRenamed(ProjectTransaction, ProjectPath, PathBuf),
     AgentLocationChanged,
+    BackgroundTaskActivityChanged,
 }
 
 pub struct AgentLocationChanged;
@@ -714,6 +717,26 @@ impl LspAction {
     }
 }
 
+/// A link to a file or a URL in a buffer, provided by a language server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentLink {
+    /// The id of the language server that produced this link.
+    pub server_id: LanguageServerId,
+    /// The range of the buffer where the link is clickable.
+    pub range: Range<Anchor>,
+    /// The raw link provided by the language server.
+    /// Its target may be missing until the link is resolved.
+    pub lsp_link: lsp::DocumentLink,
+    /// Whether the link was resolved using the language server.
+    pub resolved: bool,
+}
+
+impl DocumentLink {
+    pub fn target(&self) -> Option<&lsp::Uri> {
+        self.lsp_link.target.as_ref()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ResolveState {
     Resolved,
@@ -793,6 +816,43 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A function or method that takes part in a call hierarchy.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The name of the item, where it is defined.
+    pub location: Location,
+    pub language_server_id: LanguageServerId,
+    /// The item as sent by the language server, to pass back when querying its calls.
+    pub lsp_item: lsp::CallHierarchyItem,
+}
+
+/// Calls between a call hierarchy item and one of its callers or callees.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller for incoming calls, the callee for outgoing ones.
+    pub item: CallHierarchyItem,
+    /// Where the calls are made: in the caller for incoming calls, and in the
+    /// queried item for outgoing ones.
+    pub ranges: Vec<Location>,
+}
+
+/// A class, interface, trait or other type that takes part in a type hierarchy.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    /// The name of the item, where it is defined.
+    pub location: Location,
+    pub language_server_id: LanguageServerId,
+    /// The item as sent by the language server, to pass back when querying its
+    /// supertypes or subtypes.
+    pub lsp_item: lsp::TypeHierarchyItem,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -3082,6 +3142,7 @@ impl Project {
                 request_id: *request_id,
             }),
             LspStoreEvent::RefreshCodeLens => cx.emit(Event::RefreshCodeLens),
+            LspStoreEvent::RefreshSemanticTokens => cx.emit(Event::RefreshSemanticTokens),
             LspStoreEvent::LanguageServerPrompt(prompt) => {
                 cx.emit(Event::LanguageServerPrompt(prompt.clone()))
             }
@@ -3776,6 +3837,159 @@ impl Project {
         )
     }
 
+    /// Returns the selection ranges for every position, from the innermost
+    /// to the outermost range containing it.
+    pub fn selection_ranges(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        positions: Vec<Anchor>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<Vec<Range<Anchor>>>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSelectionRanges { positions },
+            cx,
+        )
+    }
+
+    /// Returns the links to files and URLs in the buffer, reported by language servers.
+    pub fn document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Arc<[DocumentLink]>>> {
+        let document_links = self
+            .lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.document_links(buffer, cx));
+        cx.background_spawn(async move {
+            document_links
+                .await
+                .map_err(|e| anyhow!("document links fetch failed: {e:#}"))
+        })
+    }
+
+    pub fn resolve_document_link(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.resolve_document_link(link, buffer.clone(), cx)
+        })
+    }
+
+    /// Returns the call hierarchy items at the given position, usually the
+    /// function or method defined or called there.
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the callers of a call hierarchy item, querying the language
+    /// server that produced it through `buffer`.
+    pub fn incoming_calls(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetIncomingCalls {
+                language_server_id: item.language_server_id,
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Returns the callees of a call hierarchy item, querying the language
+    /// server that produced it through `buffer`.
+    pub fn outgoing_calls(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetOutgoingCalls {
+                language_server_id: item.language_server_id,
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Returns the type hierarchy items at the given position, usually the
+    /// type defined or referenced there.
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the direct supertypes of a type hierarchy item, querying the
+    /// language server that produced it through `buffer`.
+    pub fn supertypes(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetSupertypes {
+                language_server_id: item.language_server_id,
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
+    /// Returns the direct subtypes of a type hierarchy item, querying the
+    /// language server that produced it through `buffer`.
+    pub fn subtypes(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::Other(item.language_server_id),
+            GetSubtypes {
+                language_server_id: item.language_server_id,
+                item: item.lsp_item.clone(),
+            },
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -47,7 +47,10 @@ use std::{
     num::NonZeroU32,
     ops::Range,
     str::FromStr,
-    sync::{Arc, OnceLock},
+    sync::{
+        Arc, OnceLock,
+        atomic::{self, AtomicUsize},
+    },
     task::Poll,
 };
 use sum_tree::SumTree;
@@ -2878,6 +2881,140 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_task_problem_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({ "a.rs": "one two three", "b.rs": "four five" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [Path::new(path!("/dir"))], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store.clone());
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let problem = |path: &str, severity, message: &str| task::TaskProblem {
+        path: path.to_string(),
+        line: 1,
+        column: Some(5),
+        end: Some((1, Some(8))),
+        severity,
+        code: None,
+        message: message.to_string(),
+    };
+    lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.report_task_problems(
+                "cargo check",
+                Some(Path::new(path!("/dir"))),
+                vec![
+                    problem("a.rs", task::ProblemSeverity::Error, "unknown `two`"),
+                    problem("b.rs", task::ProblemSeverity::Warning, "unused `five`"),
+                    problem(
+                        path!("/elsewhere/c.rs"),
+                        task::ProblemSeverity::Error,
+                        "outside of the project",
+                    ),
+                ],
+                cx,
+            )
+        })
+        .unwrap();
+    cx.run_until_parked();
+
+    lsp_store.read_with(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 1,
+            }
+        );
+    });
+    buffer.read_with(cx, |buffer, _| {
+        let diagnostics = buffer
+            .snapshot()
+            .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+            .map(|entry| {
+                (
+                    entry.range,
+                    entry.diagnostic.source.clone(),
+                    entry.diagnostic.message.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [(
+                Point::new(0, 4)..Point::new(0, 7),
+                Some("task: cargo check".to_string()),
+                "unknown `two`".to_string(),
+            )]
+        );
+    });
+
+    // Running the task again replaces the problems it reported before.
+    lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.report_task_problems(
+                "cargo check",
+                Some(Path::new(path!("/dir"))),
+                vec![problem(
+                    "b.rs",
+                    task::ProblemSeverity::Warning,
+                    "unused `five`",
+                )],
+                cx,
+            )
+        })
+        .unwrap();
+    cx.run_until_parked();
+    lsp_store.read_with(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 0,
+                warning_count: 1,
+            }
+        );
+    });
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .count(),
+            0
+        );
+    });
+
+    lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.report_task_problems(
+                "cargo check",
+                Some(Path::new(path!("/dir"))),
+                vec![],
+                cx,
+            )
+        })
+        .unwrap();
+    cx.run_until_parked();
+    lsp_store.read_with(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary::default()
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -3424,6 +3561,672 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() {}",
+            "b.rs": "fn b() { crate::a(); }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+
+    fn lsp_item(name: &str, path: &str) -> lsp::CallHierarchyItem {
+        let range = lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 4));
+        lsp::CallHierarchyItem {
+            name: name.to_string(),
+            kind: lsp::SymbolKind::FUNCTION,
+            tags: None,
+            detail: Some(format!("fn {name}()")),
+            uri: lsp::Uri::from_file_path(path).unwrap(),
+            range,
+            selection_range: range,
+            data: None,
+        }
+    }
+    let call_range = lsp::Range::new(lsp::Position::new(0, 16), lsp::Position::new(0, 17));
+
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>(
+        |params, _| async move {
+            assert_eq!(
+                params.text_document_position_params.position,
+                lsp::Position::new(0, 3)
+            );
+            Ok(Some(vec![lsp_item("a", path!("/dir/a.rs"))]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.name, "a");
+            Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                from: lsp_item("b", path!("/dir/b.rs")),
+                from_ranges: vec![call_range],
+            }]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.item.name, "b");
+            Ok(Some(vec![lsp::CallHierarchyOutgoingCall {
+                to: lsp_item("a", path!("/dir/a.rs")),
+                from_ranges: vec![call_range],
+            }]))
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, 3, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let a = items[0].clone();
+    assert_eq!(a.name, "a");
+    assert_eq!(a.language_server_id, fake_server.server.server_id());
+    assert_eq!(a.location.buffer, buffer);
+
+    let incoming = project
+        .update(cx, |project, cx| project.incoming_calls(&buffer, &a, cx))
+        .await
+        .unwrap();
+    assert_eq!(incoming.len(), 1);
+    let b = incoming[0].item.clone();
+    cx.update(|cx| {
+        let b_buffer = b.location.buffer.read(cx);
+        assert_eq!(
+            b_buffer.file().unwrap().as_local().unwrap().abs_path(cx),
+            Path::new(path!("/dir/b.rs"))
+        );
+        assert_eq!(b.location.range.to_offset(b_buffer), 3..4);
+        // The calls to `a` are made in `b`.
+        let call = &incoming[0].ranges[0];
+        assert_eq!(call.buffer, b.location.buffer);
+        assert_eq!(call.range.to_offset(b_buffer), 16..17);
+    });
+
+    let outgoing = project
+        .update(cx, |project, cx| project.outgoing_calls(&buffer, &b, cx))
+        .await
+        .unwrap();
+    assert_eq!(outgoing.len(), 1);
+    cx.update(|cx| {
+        assert_eq!(outgoing[0].item.name, "a");
+        assert_eq!(outgoing[0].item.location.buffer, buffer);
+        // The calls from `b` are made in `b`, not in the callee.
+        let call = &outgoing[0].ranges[0];
+        assert_eq!(call.buffer, b.location.buffer);
+        assert_eq!(call.range.to_offset(call.buffer.read(cx)), 16..17);
+    });
+}
+
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "trait Shape {}",
+            "b.rs": "struct Circle;",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                type_hierarchy_provider: Some(lsp::OneOf::Left(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+
+    fn lsp_item(name: &str, path: &str) -> lsp::TypeHierarchyItem {
+        let (kind, start) = match name {
+            "Shape" => (lsp::SymbolKind::INTERFACE, 6),
+            _ => (lsp::SymbolKind::STRUCT, 7),
+        };
+        let range = lsp::Range::new(
+            lsp::Position::new(0, start),
+            lsp::Position::new(0, start + name.len() as u32),
+        );
+        lsp::TypeHierarchyItem {
+            name: name.to_string(),
+            kind,
+            tags: None,
+            detail: None,
+            uri: lsp::Uri::from_file_path(path).unwrap(),
+            range,
+            selection_range: range,
+            data: None,
+        }
+    }
+
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+        |params, _| async move {
+            assert_eq!(
+                params.text_document_position_params.position,
+                lsp::Position::new(0, 6)
+            );
+            Ok(Some(vec![lsp_item("Shape", path!("/dir/a.rs"))]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.name, "Shape");
+            Ok(Some(vec![lsp_item("Circle", path!("/dir/b.rs"))]))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+        |params, _| async move {
+            assert_eq!(params.item.name, "Circle");
+            Ok(Some(vec![lsp_item("Shape", path!("/dir/a.rs"))]))
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 6, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let shape = items[0].clone();
+    assert_eq!(shape.name, "Shape");
+    assert_eq!(shape.kind, lsp::SymbolKind::INTERFACE);
+    assert_eq!(shape.language_server_id, fake_server.server.server_id());
+    assert_eq!(shape.location.buffer, buffer);
+
+    let subtypes = project
+        .update(cx, |project, cx| project.subtypes(&buffer, &shape, cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    let circle = subtypes[0].clone();
+    cx.update(|cx| {
+        let circle_buffer = circle.location.buffer.read(cx);
+        assert_eq!(
+            circle_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/b.rs"))
+        );
+        assert_eq!(circle.location.range.to_offset(circle_buffer), 7..13);
+    });
+
+    let supertypes = project
+        .update(cx, |project, cx| project.supertypes(&buffer, &circle, cx))
+        .await
+        .unwrap();
+    assert_eq!(supertypes.len(), 1);
+    assert_eq!(supertypes[0].name, "Shape");
+    assert_eq!(supertypes[0].location.buffer, buffer);
+}
+
+#[gpui::test]
+async fn test_semantic_tokens(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "let mut a = 1;\nlet b = a;",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                semantic_tokens_provider: Some(
+                    lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        lsp::SemanticTokensOptions {
+                            legend: lsp::SemanticTokensLegend {
+                                token_types: vec![lsp::SemanticTokenType::VARIABLE],
+                                token_modifiers: vec![lsp::SemanticTokenModifier::new("mutable")],
+                            },
+                            full: Some(lsp::SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                            ..Default::default()
+                        },
+                    ),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+
+    let variable = |delta_line, delta_start, token_modifiers_bitset| lsp::SemanticToken {
+        delta_line,
+        delta_start,
+        length: 1,
+        token_type: 0,
+        token_modifiers_bitset,
+    };
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullRequest, _, _>(
+        move |_, _| async move {
+            Ok(Some(lsp::SemanticTokensResult::Tokens(
+                lsp::SemanticTokens {
+                    result_id: Some("1".to_string()),
+                    data: vec![variable(0, 8, 1), variable(1, 4, 0), variable(0, 4, 1)],
+                },
+            )))
+        },
+    );
+    fake_server.set_request_handler::<lsp::request::SemanticTokensFullDeltaRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.previous_result_id, "1");
+            // Move the first token down a line and drop the last one.
+            Ok(Some(lsp::SemanticTokensFullDeltaResult::TokensDelta(
+                lsp::SemanticTokensDelta {
+                    result_id: Some("2".to_string()),
+                    edits: vec![
+                        lsp::SemanticTokensEdit {
+                            start: 10,
+                            delete_count: 5,
+                            data: None,
+                        },
+                        lsp::SemanticTokensEdit {
+                            start: 0,
+                            delete_count: 5,
+                            data: Some(vec![variable(1, 8, 1)]),
+                        },
+                    ],
+                },
+            )))
+        },
+    );
+
+    let tokens_as_offsets = |tokens: lsp_store::SemanticTokens, cx: &mut gpui::TestAppContext| {
+        buffer.read_with(cx, |buffer, _| {
+            tokens
+                .tokens
+                .values()
+                .flat_map(|server_tokens| server_tokens.iter())
+                .map(|token| {
+                    (
+                        token.range.to_offset(buffer),
+                        token.token_type.to_string(),
+                        token
+                            .token_modifiers
+                            .iter()
+                            .map(|modifier| modifier.to_string())
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let full_tokens = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.semantic_tokens(None, buffer.clone(), cx)
+        })
+        .unwrap();
+    cx.executor().advance_clock(Duration::from_millis(100));
+    let full_tokens = full_tokens.await.unwrap();
+    let cache_version = full_tokens.cache_version;
+    assert_eq!(
+        tokens_as_offsets(full_tokens, cx),
+        vec![
+            (8..9, "variable".to_string(), vec!["mutable".to_string()]),
+            (19..20, "variable".to_string(), Vec::new()),
+            (23..24, "variable".to_string(), vec!["mutable".to_string()]),
+        ]
+    );
+    assert!(
+        lsp_store
+            .update(cx, |lsp_store, cx| {
+                lsp_store.semantic_tokens(cache_version, buffer.clone(), cx)
+            })
+            .is_none(),
+        "Up to date tokens should not be fetched again"
+    );
+
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+    let delta_tokens = lsp_store
+        .update(cx, |lsp_store, cx| {
+            lsp_store.semantic_tokens(cache_version, buffer.clone(), cx)
+        })
+        .unwrap();
+    cx.executor().advance_clock(Duration::from_millis(100));
+    let delta_tokens = delta_tokens.await.unwrap();
+    assert_ne!(delta_tokens.cache_version, cache_version);
+    assert_eq!(
+        tokens_as_offsets(delta_tokens, cx),
+        vec![
+            (9..10, "variable".to_string(), vec!["mutable".to_string()]),
+            (20..21, "variable".to_string(), Vec::new()),
+        ]
+    );
+}
+
+#[gpui::test]
+async fn test_folding_and_selection_ranges(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn main() {\n    // #region setup\n    let a = 1;\n    // #endregion\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+                selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+
+    let folding_requests = Arc::new(AtomicUsize::new(0));
+    fake_server.set_request_handler::<lsp::request::FoldingRangeRequest, _, _>({
+        let folding_requests = folding_requests.clone();
+        move |_, _| {
+            folding_requests.fetch_add(1, atomic::Ordering::Release);
+            async move {
+                Ok(Some(vec![
+                    lsp::FoldingRange {
+                        start_line: 1,
+                        end_line: 3,
+                        kind: Some(lsp::FoldingRangeKind::Region),
+                        ..Default::default()
+                    },
+                    lsp::FoldingRange {
+                        start_line: 0,
+                        end_line: 3,
+                        ..Default::default()
+                    },
+                    // Single line and out of bounds ranges cannot be folded.
+                    lsp::FoldingRange {
+                        start_line: 2,
+                        end_line: 2,
+                        ..Default::default()
+                    },
+                    lsp::FoldingRange {
+                        start_line: 7,
+                        end_line: 9,
+                        ..Default::default()
+                    },
+                ]))
+            }
+        }
+    });
+
+    let folding_ranges =
+        lsp_store.update(cx, |lsp_store, cx| lsp_store.folding_ranges(&buffer, cx));
+    cx.executor().advance_clock(Duration::from_millis(100));
+    let folding_ranges = folding_ranges.await.unwrap();
+    let expected_folding_ranges = vec![
+        Point::new(0, 11)..Point::new(3, 17),
+        Point::new(1, 20)..Point::new(3, 17),
+    ];
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            folding_ranges
+                .iter()
+                .map(|range| range.to_point(buffer))
+                .collect::<Vec<_>>(),
+            expected_folding_ranges,
+            "Folding ranges should span whole lines, sorted by their start"
+        );
+    });
+    let cached_folding_ranges = lsp_store
+        .update(cx, |lsp_store, cx| lsp_store.folding_ranges(&buffer, cx))
+        .await
+        .unwrap();
+    assert_eq!(cached_folding_ranges, folding_ranges);
+    assert_eq!(
+        folding_requests.load(atomic::Ordering::Acquire),
+        1,
+        "Folding ranges of an unchanged buffer should be cached"
+    );
+
+    fake_server.set_request_handler::<lsp::request::SelectionRangeRequest, _, _>(
+        move |params, _| async move {
+            assert_eq!(params.positions, vec![lsp::Position::new(2, 8)]);
+            Ok(Some(vec![lsp::SelectionRange {
+                range: lsp::Range::new(lsp::Position::new(2, 8), lsp::Position::new(2, 9)),
+                parent: Some(Box::new(lsp::SelectionRange {
+                    range: lsp::Range::new(lsp::Position::new(2, 4), lsp::Position::new(2, 14)),
+                    parent: Some(Box::new(lsp::SelectionRange {
+                        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(4, 1)),
+                        parent: None,
+                    })),
+                })),
+            }]))
+        },
+    );
+    let position = buffer.read_with(cx, |buffer, _| buffer.anchor_before(Point::new(2, 8)));
+    let selection_ranges = project
+        .update(cx, |project, cx| {
+            project.selection_ranges(&buffer, vec![position], cx)
+        })
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            selection_ranges
+                .iter()
+                .map(|ranges| ranges
+                    .iter()
+                    .map(|range| range.to_point(buffer))
+                    .collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![
+                Point::new(2, 8)..Point::new(2, 9),
+                Point::new(2, 4)..Point::new(2, 14),
+                Point::new(0, 0)..Point::new(4, 1),
+            ]],
+            "Selection ranges should go from the innermost to the outermost one"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_document_links(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "use serde;\nmod b;",
+            "b.rs": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+
+    let link_requests = Arc::new(AtomicUsize::new(0));
+    fake_server.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>({
+        let link_requests = link_requests.clone();
+        move |_, _| {
+            link_requests.fetch_add(1, atomic::Ordering::Release);
+            async move {
+                Ok(Some(vec![
+                    lsp::DocumentLink {
+                        range: lsp::Range::new(lsp::Position::new(0, 4), lsp::Position::new(0, 9)),
+                        target: None,
+                        tooltip: None,
+                        data: Some(json!({ "crate": "serde" })),
+                    },
+                    lsp::DocumentLink {
+                        range: lsp::Range::new(lsp::Position::new(1, 4), lsp::Position::new(1, 5)),
+                        target: Some(lsp::Uri::from_file_path(path!("/dir/b.rs")).unwrap()),
+                        tooltip: None,
+                        data: None,
+                    },
+                ]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(
+        move |link, _| async move {
+            assert_eq!(link.data, Some(json!({ "crate": "serde" })));
+            Ok(lsp::DocumentLink {
+                target: Some("https://docs.rs/serde".parse().unwrap()),
+                ..link
+            })
+        },
+    );
+
+    let links = project
+        .update(cx, |project, cx| project.document_links(&buffer, cx))
+        .await
+        .unwrap();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            links
+                .iter()
+                .map(|link| (
+                    link.range.to_point(buffer),
+                    link.target().map(|target| target.to_string()),
+                    link.resolved
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (Point::new(0, 4)..Point::new(0, 9), None, false),
+                (
+                    Point::new(1, 4)..Point::new(1, 5),
+                    Some(
+                        lsp::Uri::from_file_path(path!("/dir/b.rs"))
+                            .unwrap()
+                            .to_string()
+                    ),
+                    true
+                ),
+            ],
+            "Links without a target should be resolved later"
+        );
+    });
+
+    let resolved_link = project
+        .update(cx, |project, cx| {
+            project.resolve_document_link(&buffer, links[0].clone(), cx)
+        })
+        .await
+        .unwrap();
+    assert!(resolved_link.resolved);
+    assert_eq!(
+        resolved_link.target().map(|target| target.to_string()),
+        Some("https://docs.rs/serde".to_string())
+    );
+
+    let cached_links = project
+        .update(cx, |project, cx| project.document_links(&buffer, cx))
+        .await
+        .unwrap();
+    assert_eq!(cached_links[0], resolved_link);
+    assert_eq!(cached_links[1], links[1]);
+    assert_eq!(
+        link_requests.load(atomic::Ordering::Acquire),
+        1,
+        "Document links of an unchanged buffer should be cached, along with their resolved targets"
+    );
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result, bail};
 use collections::{HashMap, HashSet, VecDeque};
 use dap::DapRegistry;
 use gpui::{App, AppContext as _, Context, Entity, SharedString, Task, WeakEntity};
@@ -21,8 +21,8 @@ use lsp::{LanguageServerId, LanguageServerName};
 use paths::{debug_task_file_name, task_file_name};
 use settings::{InvalidSettingsError, parse_json_with_comments};
 use task::{
-    DebugScenario, ResolvedTask, TaskContext, TaskId, TaskTemplate, TaskTemplates, TaskVariables,
-    VariableName,
+    DebugScenario, DependsOrder, ResolvedTask, TaskContext, TaskId, TaskInput, TaskTemplate,
+    TaskTemplates, TaskVariables, VariableName,
 };
 use text::{BufferId, Point, ToPoint};
 use util::{NumericPrefixWithSuffix, ResultExt as _, post_inc, rel_path::RelPath};
@@ -155,6 +155,29 @@ pub enum TaskSourceKind {
     },
 }
 
+/// A task along with the tasks it depends on, resolved recursively with the same [`TaskContext`].
+/// A task that several tasks of the chain depend on is only included once.
+#[derive(Debug, Clone)]
+pub struct TaskChain {
+    /// Every task comes after the tasks it depends on, so the task that started the chain is last.
+    pub tasks: Vec<ChainedTask>,
+}
+
+/// A task of a [`TaskChain`].
+#[derive(Debug, Clone)]
+pub struct ChainedTask {
+    pub source_kind: TaskSourceKind,
+    pub task: ResolvedTask,
+    /// Ids of the tasks from `depends_on`, in the order they are listed.
+    pub dependencies: Vec<TaskId>,
+}
+
+impl ChainedTask {
+    pub fn depends_order(&self) -> DependsOrder {
+        self.task.original_task().depends_order
+    }
+}
+
 /// A collection of task contexts, derived from the current state of the workspace.
 /// Only contains worktrees that are visible and with their root being a directory.
 #[derive(Debug, Default)]
@@ -373,6 +396,64 @@ impl Inventory {
         })
     }
 
+    /// Resolves the tasks the given task depends on, looking them up by label among the tasks
+    /// available in its worktree. Fails if a dependency is missing, can't be resolved, or the
+    /// dependencies form a cycle.
+    pub fn resolve_task_chain(
+        &self,
+        source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        cx: &App,
+    ) -> Task<Result<TaskChain>> {
+        let worktree_id = match &source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => None,
+        };
+        let tasks = self.list_tasks(None, None, worktree_id, cx);
+        cx.background_spawn(async move {
+            let tasks = tasks.await;
+            let mut chain = TaskChain { tasks: Vec::new() };
+            resolve_task_chain(
+                source_kind,
+                resolved_task,
+                &tasks,
+                &mut Vec::new(),
+                &mut chain.tasks,
+            )?;
+            Ok(chain)
+        })
+    }
+
+    /// Collects the inputs that the task and the tasks it depends on use, but have no values for
+    /// in the context given, so that the user is asked for them before any of the tasks is resolved.
+    /// Every input is collected once, by its id. Dependencies that can't be found are skipped,
+    /// [`Inventory::resolve_task_chain`] reports them.
+    pub fn task_chain_inputs(
+        &self,
+        source_kind: &TaskSourceKind,
+        template: TaskTemplate,
+        task_context: TaskContext,
+        cx: &App,
+    ) -> Task<Vec<TaskInput>> {
+        let worktree_id = match source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => None,
+        };
+        let tasks = self.list_tasks(None, None, worktree_id, cx);
+        cx.background_spawn(async move {
+            let tasks = tasks.await;
+            let mut inputs = Vec::new();
+            collect_task_chain_inputs(
+                &template,
+                &task_context,
+                &tasks,
+                &mut Vec::new(),
+                &mut inputs,
+            );
+            inputs
+        })
+    }
+
     /// Pulls its task sources relevant to the worktree and the language given,
     /// returns all task templates with their source kinds, worktree tasks first, language tasks second
     /// and global tasks last. No specific order inside source kinds groups.
@@ -844,6 +925,89 @@ fn task_variables_preference(task: &ResolvedTask) -> Reverse<usize> {
     })
 }
 
+/// Adds the task to the chain after the tasks it depends on, skipping the ones already in it.
+fn resolve_task_chain(
+    source_kind: TaskSourceKind,
+    task: ResolvedTask,
+    available_tasks: &[(TaskSourceKind, TaskTemplate)],
+    labels_in_progress: &mut Vec<String>,
+    chain: &mut Vec<ChainedTask>,
+) -> Result<TaskId> {
+    let label = task.original_task().label.clone();
+    labels_in_progress.push(label.clone());
+    let mut dependencies = Vec::with_capacity(task.original_task().depends_on.len());
+    for dependency_label in &task.original_task().depends_on {
+        if labels_in_progress.contains(dependency_label) {
+            bail!(
+                "Task dependency cycle: {} -> {dependency_label}",
+                labels_in_progress.join(" -> ")
+            );
+        }
+        let (dependency_source_kind, dependency_template) = available_tasks
+            .iter()
+            .find(|(_, template)| &template.label == dependency_label)
+            .with_context(|| {
+                format!("Task `{label}` depends on unknown task `{dependency_label}`")
+            })?;
+        let dependency = dependency_template
+            .resolve_task(&dependency_source_kind.to_id_base(), task.task_context())
+            .with_context(|| format!("Failed to resolve task `{dependency_label}`"))?;
+        let dependency_id = if chain.iter().any(|chained| chained.task.id == dependency.id) {
+            dependency.id
+        } else {
+            resolve_task_chain(
+                dependency_source_kind.clone(),
+                dependency,
+                available_tasks,
+                labels_in_progress,
+                chain,
+            )?
+        };
+        dependencies.push(dependency_id);
+    }
+    labels_in_progress.pop();
+
+    let id = task.id.clone();
+    chain.push(ChainedTask {
+        source_kind,
+        task,
+        dependencies,
+    });
+    Ok(id)
+}
+
+fn collect_task_chain_inputs(
+    template: &TaskTemplate,
+    task_context: &TaskContext,
+    available_tasks: &[(TaskSourceKind, TaskTemplate)],
+    visited_labels: &mut Vec<String>,
+    inputs: &mut Vec<TaskInput>,
+) {
+    visited_labels.push(template.label.clone());
+    for dependency_label in &template.depends_on {
+        if visited_labels.contains(dependency_label) {
+            continue;
+        }
+        if let Some((_, dependency_template)) = available_tasks
+            .iter()
+            .find(|(_, template)| &template.label == dependency_label)
+        {
+            collect_task_chain_inputs(
+                dependency_template,
+                task_context,
+                available_tasks,
+                visited_labels,
+                inputs,
+            );
+        }
+    }
+    for input in template.missing_inputs(task_context) {
+        if inputs.iter().all(|collected| collected.id != input.id) {
+            inputs.push(input.clone());
+        }
+    }
+}
+
 #[cfg(test)]
 mod test_inventory {
     use gpui::{AppContext as _, Entity, Task, TestAppContext};
@@ -1543,6 +1707,139 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolving_task_chains() {
+        let source_kind = TaskSourceKind::AbsPath {
+            id_base: "global tasks.json".into(),
+            abs_path: paths::tasks_file().clone(),
+        };
+        let template = |label: &str, depends_on: &[&str]| TaskTemplate {
+            label: label.to_string(),
+            command: "echo".to_string(),
+            args: vec![label.to_string()],
+            depends_on: depends_on.iter().map(|label| label.to_string()).collect(),
+            ..TaskTemplate::default()
+        };
+        let resolve = |tasks: &[TaskTemplate], label: &str| {
+            let available_tasks = tasks
+                .iter()
+                .map(|template| (source_kind.clone(), template.clone()))
+                .collect::<Vec<_>>();
+            let root = tasks
+                .iter()
+                .find(|template| template.label == label)
+                .unwrap()
+                .resolve_task(&source_kind.to_id_base(), &TaskContext::default())
+                .unwrap();
+            let mut chain = Vec::new();
+            resolve_task_chain(
+                source_kind.clone(),
+                root,
+                &available_tasks,
+                &mut Vec::new(),
+                &mut chain,
+            )
+            .map(|_| chain)
+        };
+        fn labels(chain: &[ChainedTask]) -> Vec<String> {
+            chain
+                .iter()
+                .map(|chained| {
+                    let dependencies = chained
+                        .dependencies
+                        .iter()
+                        .map(|id| {
+                            let dependency =
+                                chain.iter().find(|chained| &chained.task.id == id).unwrap();
+                            dependency.task.resolved_label.as_str()
+                        })
+                        .join(", ");
+                    format!("{}({dependencies})", chained.task.resolved_label)
+                })
+                .collect()
+        }
+
+        // A task may be depended upon several times, as long as there's no cycle,
+        // but it's only included in the chain once.
+        let tasks = [
+            template("deploy", &["build", "test"]),
+            template("test", &["build"]),
+            template("build", &[]),
+        ];
+        let chain = resolve(&tasks, "deploy").unwrap();
+        assert_eq!(
+            labels(&chain),
+            ["build()", "test(build)", "deploy(build, test)"]
+        );
+
+        let tasks = [
+            template("deploy", &["build"]),
+            template("build", &["codegen"]),
+            template("codegen", &["deploy"]),
+        ];
+        assert_eq!(
+            resolve(&tasks, "deploy").unwrap_err().to_string(),
+            "Task dependency cycle: deploy -> build -> codegen -> deploy"
+        );
+
+        let tasks = [template("deploy", &["build"])];
+        assert_eq!(
+            resolve(&tasks, "deploy").unwrap_err().to_string(),
+            "Task `deploy` depends on unknown task `build`"
+        );
+    }
+
+    #[test]
+    fn test_collecting_task_chain_inputs() {
+        let source_kind = TaskSourceKind::UserInput;
+        let input = |id: &str| TaskInput {
+            id: id.to_string(),
+            ..TaskInput::default()
+        };
+        let template =
+            |label: &str, command: &str, depends_on: &[&str], inputs: &[&str]| TaskTemplate {
+                label: label.to_string(),
+                command: command.to_string(),
+                depends_on: depends_on.iter().map(|label| label.to_string()).collect(),
+                inputs: inputs.iter().map(|id| input(id)).collect(),
+                ..TaskTemplate::default()
+            };
+        let tasks = [
+            template(
+                "deploy",
+                "deploy --target $ZED_INPUT_target",
+                &["build", "test"],
+                &["target"],
+            ),
+            template("test", "cargo test", &["build"], &[]),
+            template(
+                "build",
+                "cargo build --profile $ZED_INPUT_profile --target $ZED_INPUT_target",
+                &[],
+                &["profile", "target"],
+            ),
+        ]
+        .map(|template| (source_kind.clone(), template));
+
+        let mut task_context = TaskContext::default();
+        task_context
+            .task_variables
+            .insert(input("profile").variable_name(), "release".to_string());
+        let mut inputs = Vec::new();
+        collect_task_chain_inputs(
+            &tasks[0].1,
+            &task_context,
+            &tasks,
+            &mut Vec::new(),
+            &mut inputs,
+        );
+        assert_eq!(
+            inputs,
+            [input("target")],
+            "inputs with values should be skipped, and shared inputs collected once"
+        );
+    }
+
     fn init_test(_cx: &mut TestAppContext) {
         zlog::init_test();
         TaskStore::init(None);
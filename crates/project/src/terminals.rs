@@ -14,11 +14,12 @@ use std::{
 };
 use task::{Shell, ShellBuilder, ShellKind, SpawnInTerminal};
 use terminal::{
-    TaskState, TaskStatus, Terminal, TerminalBuilder, terminal_settings::TerminalSettings,
+    TaskActivity, TaskState, TaskStatus, Terminal, TerminalBuilder,
+    terminal_settings::TerminalSettings,
 };
 use util::{command::new_std_command, get_default_system_shell, maybe, rel_path::RelPath};
 
-use crate::{Project, ProjectPath};
+use crate::{Event, Project, ProjectPath};
 
 pub struct Terminals {
     pub(crate) local_handles: Vec<WeakEntity<terminal::Terminal>>,
@@ -81,6 +82,7 @@ impl Project {
             spawned_task: spawn_task.clone(),
             status: TaskStatus::Running,
             completion_rx,
+            activity: spawn_task.background.as_ref().map(|_| TaskActivity::Busy),
         });
         let remote_client = self.remote_client.clone();
         let shell = match &remote_client {
@@ -257,6 +259,13 @@ impl Project {
                     .local_handles
                     .push(terminal_handle.downgrade());
 
+                cx.subscribe(&terminal_handle, |_, _, event, cx| {
+                    if let terminal::Event::TaskActivityChanged = event {
+                        cx.emit(Event::BackgroundTaskActivityChanged);
+                    }
+                })
+                .detach();
+
                 let id = terminal_handle.entity_id();
                 cx.observe_release(&terminal_handle, move |project, _terminal, cx| {
                     let handles = &mut project.terminals.local_handles;
@@ -536,6 +545,22 @@ impl Project {
         &self.terminals.local_handles
     }
 
+    /// Tasks running in the background in local terminals, along with what they are doing.
+    pub fn background_tasks<'a>(
+        &'a self,
+        cx: &'a App,
+    ) -> impl Iterator<Item = (&'a SpawnInTerminal, TaskActivity)> + 'a {
+        self.terminals.local_handles.iter().filter_map(|terminal| {
+            let task = terminal.upgrade()?.read(cx).task()?;
+            let activity = task.activity?;
+            if task.status == TaskStatus::Running {
+                Some((&task.spawned_task, activity))
+            } else {
+                None
+            }
+        })
+    }
+
     fn resolve_directory_environment(
         &self,
         shell: &str,
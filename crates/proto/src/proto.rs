@@ -97,6 +97,27 @@ messages!(
     (GetDocumentHighlightsResponse, Background),
     (GetDocumentSymbols, Background),
     (GetDocumentSymbolsResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
+    (GetSemanticTokens, Background),
+    (GetSemanticTokensDelta, Background),
+    (GetSemanticTokensResponse, Background),
+    (GetFoldingRanges, Background),
+    (GetFoldingRangesResponse, Background),
+    (GetSelectionRanges, Background),
+    (GetSelectionRangesResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
     (GetHover, Background),
     (GetHoverResponse, Background),
     (GetNotifications, Foreground),
@@ -220,11 +241,14 @@ messages!(
     (ResolveCompletionDocumentationResponse, Background),
     (ResolveInlayHint, Background),
     (ResolveInlayHintResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
     (GetDocumentColor, Background),
     (GetDocumentColorResponse, Background),
     (GetColorPresentation, Background),
     (GetColorPresentationResponse, Background),
     (RefreshCodeLens, Background),
+    (RefreshSemanticTokens, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
     (RespondToChannelInvite, Foreground),
@@ -307,6 +331,30 @@ messages!(
     (AskPassResponse, Background),
     (GitCreateRemote, Background),
     (GitRemoveRemote, Background),
+    (GitGetRebaseEntries, Background),
+    (GitRebaseEntriesResponse, Background),
+    (GitRebase, Background),
+    (GitRebaseCommand, Background),
+    (GitGetRebaseStatus, Background),
+    (GitRebaseStatusResponse, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitApplyCommitResponse, Background),
+    (GitGetTags, Background),
+    (GitTagsResponse, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitPushTag, Background),
+    (GitCommitLog, Background),
+    (GitCommitLogResponse, Background),
+    (GitLoadConflictStages, Background),
+    (GitConflictStagesResponse, Background),
+    (GitSubmoduleCommand, Background),
+    (GitBisectStart, Background),
+    (GitBisectMark, Background),
+    (GitBisectReset, Background),
+    (GitGetBisectStatus, Background),
+    (GitBisectStatusResponse, Background),
     (GitCreateBranch, Background),
     (GitChangeBranch, Background),
     (GitRenameBranch, Background),
@@ -379,6 +427,17 @@ request_messages!(
     (GetImplementation, GetImplementationResponse),
     (GetDocumentHighlights, GetDocumentHighlightsResponse),
     (GetDocumentSymbols, GetDocumentSymbolsResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
+    (GetSemanticTokens, GetSemanticTokensResponse),
+    (GetSemanticTokensDelta, GetSemanticTokensResponse),
+    (GetFoldingRanges, GetFoldingRangesResponse),
+    (GetSelectionRanges, GetSelectionRangesResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
     (GetHover, GetHoverResponse),
     (GetNotifications, GetNotificationsResponse),
     (GetProjectSymbols, GetProjectSymbolsResponse),
@@ -417,6 +476,7 @@ request_messages!(
     (PrepareRename, PrepareRenameResponse),
     (RefreshInlayHints, Ack),
     (RefreshCodeLens, Ack),
+    (RefreshSemanticTokens, Ack),
     (RejoinChannelBuffers, RejoinChannelBuffersResponse),
     (RejoinRoom, RejoinRoomResponse),
     (ReloadBuffers, ReloadBuffersResponse),
@@ -433,6 +493,7 @@ request_messages!(
         ResolveCompletionDocumentationResponse
     ),
     (ResolveInlayHint, ResolveInlayHintResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
     (GetDocumentColor, GetDocumentColorResponse),
     (GetColorPresentation, GetColorPresentationResponse),
     (RespondToChannelInvite, Ack),
@@ -508,6 +569,23 @@ request_messages!(
     (AskPassRequest, AskPassResponse),
     (GitCreateRemote, Ack),
     (GitRemoveRemote, Ack),
+    (GitGetRebaseEntries, GitRebaseEntriesResponse),
+    (GitRebase, Ack),
+    (GitRebaseCommand, Ack),
+    (GitGetRebaseStatus, GitRebaseStatusResponse),
+    (GitCherryPick, GitApplyCommitResponse),
+    (GitRevert, GitApplyCommitResponse),
+    (GitGetTags, GitTagsResponse),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitPushTag, RemoteMessageResponse),
+    (GitCommitLog, GitCommitLogResponse),
+    (GitLoadConflictStages, GitConflictStagesResponse),
+    (GitSubmoduleCommand, RemoteMessageResponse),
+    (GitBisectStart, Ack),
+    (GitBisectMark, Ack),
+    (GitBisectReset, Ack),
+    (GitGetBisectStatus, GitBisectStatusResponse),
     (GitCreateBranch, Ack),
     (GitChangeBranch, Ack),
     (GitRenameBranch, Ack),
@@ -579,6 +657,17 @@ entity_messages!(
     GetImplementation,
     GetDocumentHighlights,
     GetDocumentSymbols,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
+    GetSemanticTokens,
+    GetSemanticTokensDelta,
+    GetFoldingRanges,
+    GetSelectionRanges,
+    GetDocumentLinks,
     GetHover,
     GetProjectSymbols,
     GetReferences,
@@ -606,11 +695,13 @@ entity_messages!(
     PrepareRename,
     RefreshInlayHints,
     RefreshCodeLens,
+    RefreshSemanticTokens,
     ReloadBuffers,
     RemoveProjectCollaborator,
     RenameProjectEntry,
     ResolveCompletionDocumentation,
     ResolveInlayHint,
+    ResolveDocumentLink,
     SaveBuffer,
     Stage,
     StartLanguageServer,
@@ -682,6 +773,23 @@ entity_messages!(
     GitCreateBranch,
     GitCreateRemote,
     GitRemoveRemote,
+    GitGetRebaseEntries,
+    GitRebase,
+    GitRebaseCommand,
+    GitGetRebaseStatus,
+    GitCherryPick,
+    GitRevert,
+    GitGetTags,
+    GitCreateTag,
+    GitDeleteTag,
+    GitPushTag,
+    GitCommitLog,
+    GitLoadConflictStages,
+    GitSubmoduleCommand,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitGetBisectStatus,
     CheckForPushedCommits,
     GitDiff,
     GitInit,
@@ -6,7 +6,7 @@ use futures::{
     future::Shared,
     stream,
 };
-use gpui::{App, Entity, Task, Window};
+use gpui::{App, Context, Entity, Task, Window};
 use language::LanguageName;
 pub use native_kernel::*;
 
@@ -156,6 +156,12 @@ pub fn python_env_kernel_specifications(
     }
 }
 
+/// Receives the messages of a running kernel, such as a REPL session of an editor or a notebook.
+pub trait KernelSession: Sized + 'static {
+    fn route(&mut self, message: &JupyterMessage, window: &mut Window, cx: &mut Context<Self>);
+    fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>);
+}
+
 pub trait RunningKernel: Send + Debug {
     fn request_tx(&self) -> mpsc::Sender<JupyterMessage>;
     fn working_directory(&self) -> &PathBuf;
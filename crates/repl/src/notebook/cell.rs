@@ -1,14 +1,17 @@
 #![allow(unused, dead_code)]
 use std::sync::Arc;
 
-use editor::{Editor, EditorMode, MultiBuffer};
+use editor::{Editor, EditorEvent, EditorMode, MultiBuffer};
 use futures::future::Shared;
 use gpui::{
-    App, Entity, Hsla, RetainAllImageCache, Task, TextStyleRefinement, image_cache, prelude::*,
+    App, Entity, EventEmitter, Hsla, RetainAllImageCache, Subscription, Task, TextStyleRefinement,
+    image_cache, prelude::*,
 };
+use jupyter_protocol::Stdio;
 use language::{Buffer, Language, LanguageRegistry};
 use markdown_preview::{markdown_parser::parse_markdown, markdown_renderer::render_markdown_block};
 use nbformat::v4::{CellId, CellMetadata, CellType};
+use runtimelib::{ExecutionState, JupyterMessageContent, MimeBundle};
 use settings::Settings as _;
 use theme::ThemeSettings;
 use ui::{IconButtonShape, prelude::*};
@@ -85,6 +88,14 @@ pub enum Cell {
     Raw(Entity<RawCell>),
 }
 
+/// Events of a cell the notebook reacts to.
+pub enum CellEvent {
+    /// The source of the cell was edited.
+    Edited,
+    /// The cell asked to be run.
+    Run,
+}
+
 fn convert_outputs(
     outputs: &Vec<nbformat::v4::Output>,
     window: &mut Window,
@@ -92,24 +103,76 @@ fn convert_outputs(
 ) -> Vec<Output> {
     outputs
         .iter()
-        .map(|output| match output {
-            nbformat::v4::Output::Stream { text, .. } => Output::Stream {
-                content: cx.new(|cx| TerminalOutput::from(&text.0, window, cx)),
+        .map(|output| Output::from_notebook(output, window, cx))
+        .collect()
+}
+
+/// Splits a cell's source into lines the way nbformat stores it, keeping the line endings.
+fn source_lines(source: &str) -> Vec<String> {
+    source
+        .split_inclusive('\n')
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// Creates the editor of a cell's source, highlighted with `language` once it is loaded.
+fn cell_editor<C: EventEmitter<CellEvent> + RenderableCell>(
+    source: &str,
+    language: impl Future<Output = Option<Arc<Language>>> + 'static,
+    window: &mut Window,
+    cx: &mut Context<C>,
+) -> (Entity<Editor>, Task<()>, Subscription) {
+    let buffer = cx.new(|cx| Buffer::local(source, cx));
+    let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+
+    let editor = cx.new(|cx| {
+        let mut editor = Editor::new(
+            EditorMode::AutoHeight {
+                min_lines: 1,
+                max_lines: Some(1024),
             },
-            nbformat::v4::Output::DisplayData(display_data) => {
-                Output::new(&display_data.data, None, window, cx)
-            }
-            nbformat::v4::Output::ExecuteResult(execute_result) => {
-                Output::new(&execute_result.data, None, window, cx)
+            multi_buffer,
+            None,
+            window,
+            cx,
+        );
+
+        let theme = ThemeSettings::get_global(cx);
+
+        let refinement = TextStyleRefinement {
+            font_family: Some(theme.buffer_font.family.clone()),
+            font_size: Some(theme.buffer_font_size(cx).into()),
+            color: Some(cx.theme().colors().editor_foreground),
+            background_color: Some(gpui::transparent_black()),
+            ..Default::default()
+        };
+
+        editor.set_show_gutter(false, cx);
+        editor.set_text_style_refinement(refinement);
+        editor
+    });
+
+    let language_task = cx.spawn_in(window, async move |_, cx| {
+        let language = language.await;
+
+        buffer.update(cx, |buffer, cx| {
+            buffer.set_language(language.clone(), cx);
+        });
+    });
+
+    let subscription = cx.subscribe_in(
+        &editor,
+        window,
+        |cell, editor, event: &EditorEvent, window, cx| {
+            if let EditorEvent::BufferEdited = event {
+                let source = editor.read(cx).text(cx);
+                cell.set_source(source, window, cx);
+                cx.emit(CellEvent::Edited);
             }
-            nbformat::v4::Output::Error(error) => Output::ErrorOutput(ErrorView {
-                ename: error.ename.clone(),
-                evalue: error.evalue.clone(),
-                traceback: cx
-                    .new(|cx| TerminalOutput::from(&error.traceback.join("\n"), window, cx)),
-            }),
-        })
-        .collect()
+        },
+    );
+
+    (editor, language_task, subscription)
 }
 
 impl Cell {
@@ -125,40 +188,36 @@ impl Cell {
                 id,
                 metadata,
                 source,
-                ..
+                attachments,
             } => {
                 let source = source.join("");
 
                 let entity = cx.new(|cx| {
-                    let markdown_parsing_task = {
-                        let languages = languages.clone();
-                        let source = source.clone();
-
-                        cx.spawn_in(window, async move |this, cx| {
-                            let parsed_markdown = cx
-                                .background_spawn(async move {
-                                    parse_markdown(&source, None, Some(languages)).await
-                                })
-                                .await;
-
-                            this.update(cx, |cell: &mut MarkdownCell, _| {
-                                cell.parsed_markdown = Some(parsed_markdown);
-                            })
-                            .log_err();
-                        })
-                    };
+                    let markdown_language = languages.language_for_name("Markdown");
+                    let (editor, language_task, editor_subscription) = cell_editor(
+                        &source,
+                        async move { markdown_language.await.ok() },
+                        window,
+                        cx,
+                    );
 
-                    MarkdownCell {
-                        markdown_parsing_task,
+                    let mut cell = MarkdownCell {
+                        markdown_parsing_task: Task::ready(()),
                         image_cache: RetainAllImageCache::new(cx),
                         languages: languages.clone(),
                         id: id.clone(),
                         metadata: metadata.clone(),
+                        attachments: attachments.clone(),
                         source: source.clone(),
+                        editor,
                         parsed_markdown: None,
                         selected: false,
                         cell_position: None,
-                    }
+                        language_task,
+                        _editor_subscription: editor_subscription,
+                    };
+                    cell.parse_markdown(window, cx);
+                    cell
                 });
 
                 Cell::Markdown(entity)
@@ -170,75 +229,148 @@ impl Cell {
                 source,
                 outputs,
             } => Cell::Code(cx.new(|cx| {
-                let text = source.join("");
-
-                let buffer = cx.new(|cx| Buffer::local(text.clone(), cx));
-                let multi_buffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
-
-                let editor_view = cx.new(|cx| {
-                    let mut editor = Editor::new(
-                        EditorMode::AutoHeight {
-                            min_lines: 1,
-                            max_lines: Some(1024),
-                        },
-                        multi_buffer,
-                        None,
-                        window,
-                        cx,
-                    );
-
-                    let theme = ThemeSettings::get_global(cx);
-
-                    let refinement = TextStyleRefinement {
-                        font_family: Some(theme.buffer_font.family.clone()),
-                        font_size: Some(theme.buffer_font_size(cx).into()),
-                        color: Some(cx.theme().colors().editor_foreground),
-                        background_color: Some(gpui::transparent_black()),
-                        ..Default::default()
-                    };
-
-                    editor.set_text(text, window, cx);
-                    editor.set_show_gutter(false, cx);
-                    editor.set_text_style_refinement(refinement);
-
-                    // editor.set_read_only(true);
-                    editor
-                });
-
-                let buffer = buffer.clone();
-                let language_task = cx.spawn_in(window, async move |this, cx| {
-                    let language = notebook_language.await;
-
-                    buffer.update(cx, |buffer, cx| {
-                        buffer.set_language(language.clone(), cx);
-                    });
-                });
+                let source = source.join("");
+                let (editor, language_task, editor_subscription) =
+                    cell_editor(&source, notebook_language, window, cx);
 
                 CodeCell {
                     id: id.clone(),
                     metadata: metadata.clone(),
                     execution_count: *execution_count,
-                    source: source.join(""),
-                    editor: editor_view,
+                    source,
+                    editor,
                     outputs: convert_outputs(outputs, window, cx),
+                    notebook_outputs: outputs.clone(),
+                    clear_on_next_output: false,
+                    executing: false,
                     selected: false,
                     language_task,
                     cell_position: None,
+                    _editor_subscription: editor_subscription,
                 }
             })),
             nbformat::v4::Cell::Raw {
                 id,
                 metadata,
                 source,
-            } => Cell::Raw(cx.new(|_| RawCell {
-                id: id.clone(),
-                metadata: metadata.clone(),
-                source: source.join(""),
-                selected: false,
-                cell_position: None,
+            } => Cell::Raw(cx.new(|cx| {
+                let source = source.join("");
+                let (editor, language_task, editor_subscription) =
+                    cell_editor(&source, async { None }, window, cx);
+
+                RawCell {
+                    id: id.clone(),
+                    metadata: metadata.clone(),
+                    source,
+                    editor,
+                    selected: false,
+                    cell_position: None,
+                    language_task,
+                    _editor_subscription: editor_subscription,
+                }
             })),
         }
     }
+
+    pub fn id(&self, cx: &App) -> CellId {
+        match self {
+            Cell::Code(cell) => cell.read(cx).id().clone(),
+            Cell::Markdown(cell) => cell.read(cx).id().clone(),
+            Cell::Raw(cell) => cell.read(cx).id().clone(),
+        }
+    }
+
+    pub fn cell_type(&self, cx: &App) -> CellType {
+        match self {
+            Cell::Code(cell) => cell.read(cx).cell_type(),
+            Cell::Markdown(cell) => cell.read(cx).cell_type(),
+            Cell::Raw(cell) => cell.read(cx).cell_type(),
+        }
+    }
+
+    pub fn editor(&self, cx: &App) -> Entity<Editor> {
+        match self {
+            Cell::Code(cell) => cell.read(cx).editor.clone(),
+            Cell::Markdown(cell) => cell.read(cx).editor.clone(),
+            Cell::Raw(cell) => cell.read(cx).editor.clone(),
+        }
+    }
+
+    /// The cell in nbformat v4, with the metadata it was loaded with.
+    pub fn to_nbformat(&self, cx: &App) -> nbformat::v4::Cell {
+        match self {
+            Cell::Code(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Code {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    execution_count: cell.execution_count,
+                    source: source_lines(&cell.source),
+                    outputs: cell.notebook_outputs.clone(),
+                }
+            }
+            Cell::Markdown(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Markdown {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    source: source_lines(&cell.source),
+                    attachments: cell.attachments.clone(),
+                }
+            }
+            Cell::Raw(cell) => {
+                let cell = cell.read(cx);
+                nbformat::v4::Cell::Raw {
+                    id: cell.id.clone(),
+                    metadata: cell.metadata.clone(),
+                    source: source_lines(&cell.source),
+                }
+            }
+        }
+    }
+
+    /// The cell converted to another type, keeping its id, metadata and source.
+    pub fn with_cell_type(&self, cell_type: CellType, cx: &App) -> nbformat::v4::Cell {
+        let (id, metadata, source) = match self.to_nbformat(cx) {
+            nbformat::v4::Cell::Code {
+                id,
+                metadata,
+                source,
+                ..
+            }
+            | nbformat::v4::Cell::Markdown {
+                id,
+                metadata,
+                source,
+                ..
+            }
+            | nbformat::v4::Cell::Raw {
+                id,
+                metadata,
+                source,
+            } => (id, metadata, source),
+        };
+        match cell_type {
+            CellType::Code => nbformat::v4::Cell::Code {
+                id,
+                metadata,
+                execution_count: None,
+                source,
+                outputs: Vec::new(),
+            },
+            CellType::Markdown => nbformat::v4::Cell::Markdown {
+                id,
+                metadata,
+                source,
+                attachments: None,
+            },
+            CellType::Raw => nbformat::v4::Cell::Raw {
+                id,
+                metadata,
+                source,
+            },
+        }
+    }
 }
 
 pub trait RenderableCell: Render {
@@ -248,6 +380,7 @@ pub trait RenderableCell: Render {
     fn cell_type(&self) -> CellType;
     fn metadata(&self) -> &CellMetadata;
     fn source(&self) -> &String;
+    fn set_source(&mut self, source: String, window: &mut Window, cx: &mut Context<Self>);
     fn selected(&self) -> bool;
     fn set_selected(&mut self, selected: bool) -> &mut Self;
     fn selected_bg_color(&self, window: &mut Window, cx: &mut Context<Self>) -> Hsla {
@@ -335,15 +468,47 @@ pub trait RunnableCell: RenderableCell {
 pub struct MarkdownCell {
     id: CellId,
     metadata: CellMetadata,
+    attachments: Option<serde_json::Value>,
     image_cache: Entity<RetainAllImageCache>,
     source: String,
+    editor: Entity<Editor>,
     parsed_markdown: Option<markdown_preview::markdown_elements::ParsedMarkdown>,
     markdown_parsing_task: Task<()>,
     selected: bool,
     cell_position: Option<CellPosition>,
     languages: Arc<LanguageRegistry>,
+    language_task: Task<()>,
+    _editor_subscription: Subscription,
 }
 
+impl MarkdownCell {
+    fn parse_markdown(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let languages = self.languages.clone();
+        let source = self.source.clone();
+
+        self.markdown_parsing_task = cx.spawn_in(window, async move |this, cx| {
+            let parsed_markdown = cx
+                .background_spawn(
+                    async move { parse_markdown(&source, None, Some(languages)).await },
+                )
+                .await;
+
+            this.update(cx, |cell: &mut MarkdownCell, cx| {
+                cell.parsed_markdown = Some(parsed_markdown);
+                cx.notify();
+            })
+            .log_err();
+        });
+    }
+
+    /// Markdown cells are edited while selected, and rendered otherwise.
+    fn is_editing(&self) -> bool {
+        self.selected || self.source.trim().is_empty()
+    }
+}
+
+impl EventEmitter<CellEvent> for MarkdownCell {}
+
 impl RenderableCell for MarkdownCell {
     const CELL_TYPE: CellType = CellType::Markdown;
 
@@ -363,6 +528,11 @@ impl RenderableCell for MarkdownCell {
         &self.source
     }
 
+    fn set_source(&mut self, source: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.source = source;
+        self.parse_markdown(window, cx);
+    }
+
     fn selected(&self) -> bool {
         self.selected
     }
@@ -388,13 +558,50 @@ impl RenderableCell for MarkdownCell {
 
 impl Render for MarkdownCell {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let Some(parsed) = self.parsed_markdown.as_ref() else {
-            return div();
+        let content = if self.is_editing() {
+            div()
+                .py_1p5()
+                .w_full()
+                .child(
+                    div()
+                        .flex()
+                        .size_full()
+                        .flex_1()
+                        .py_3()
+                        .px_5()
+                        .rounded_lg()
+                        .border_1()
+                        .border_color(cx.theme().colors().border)
+                        .bg(cx.theme().colors().editor_background)
+                        .child(div().w_full().child(self.editor.clone())),
+                )
+                .into_any_element()
+        } else {
+            let mut markdown_render_context =
+                markdown_preview::markdown_renderer::RenderContext::new(None, window, cx);
+            let blocks = self
+                .parsed_markdown
+                .as_ref()
+                .map(|parsed| parsed.children.as_slice())
+                .unwrap_or_default();
+
+            v_flex()
+                .image_cache(self.image_cache.clone())
+                .size_full()
+                .flex_1()
+                .p_3()
+                .font_ui(cx)
+                .text_size(TextSize::Default.rems(cx))
+                .children(blocks.iter().map(|child| {
+                    div().relative().child(
+                        div()
+                            .relative()
+                            .child(render_markdown_block(child, &mut markdown_render_context)),
+                    )
+                }))
+                .into_any_element()
         };
 
-        let mut markdown_render_context =
-            markdown_preview::markdown_renderer::RenderContext::new(None, window, cx);
-
         v_flex()
             .size_full()
             // TODO: Move base cell render into trait impl so we don't have to repeat this
@@ -408,20 +615,7 @@ impl Render for MarkdownCell {
                     .gap(DynamicSpacing::Base08.rems(cx))
                     .bg(self.selected_bg_color(window, cx))
                     .child(self.gutter(window, cx))
-                    .child(
-                        v_flex()
-                            .image_cache(self.image_cache.clone())
-                            .size_full()
-                            .flex_1()
-                            .p_3()
-                            .font_ui(cx)
-                            .text_size(TextSize::Default.rems(cx))
-                            .children(parsed.children.iter().map(|child| {
-                                div().relative().child(div().relative().child(
-                                    render_markdown_block(child, &mut markdown_render_context),
-                                ))
-                            })),
-                    ),
+                    .child(content),
             )
             // TODO: Move base cell render into trait impl so we don't have to repeat this
             .children(self.cell_position_spacer(false, window, cx))
@@ -435,21 +629,193 @@ pub struct CodeCell {
     source: String,
     editor: Entity<editor::Editor>,
     outputs: Vec<Output>,
+    /// The outputs as saved to the notebook, one for each of `outputs`.
+    notebook_outputs: Vec<nbformat::v4::Output>,
+    clear_on_next_output: bool,
+    executing: bool,
     selected: bool,
     cell_position: Option<CellPosition>,
     language_task: Task<()>,
+    _editor_subscription: Subscription,
 }
 
+impl EventEmitter<CellEvent> for CodeCell {}
+
 impl CodeCell {
-    pub fn is_dirty(&self, cx: &App) -> bool {
-        self.editor.read(cx).buffer().read(cx).is_dirty(cx)
-    }
     pub fn has_outputs(&self) -> bool {
         !self.outputs.is_empty()
     }
 
     pub fn clear_outputs(&mut self) {
         self.outputs.clear();
+        self.notebook_outputs.clear();
+        self.execution_count = None;
+        self.clear_on_next_output = false;
+    }
+
+    pub fn is_executing(&self) -> bool {
+        self.executing
+    }
+
+    pub fn start_execution(&mut self, cx: &mut Context<Self>) {
+        self.clear_outputs();
+        self.executing = true;
+        cx.notify();
+    }
+
+    pub fn fail_execution(&mut self, message: String, cx: &mut Context<Self>) {
+        self.executing = false;
+        self.outputs.push(Output::Message(message));
+        cx.notify();
+    }
+
+    /// Accept a Jupyter message sent in reply to the execution of this cell.
+    pub fn push_message(
+        &mut self,
+        message: &JupyterMessageContent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match message {
+            JupyterMessageContent::ExecuteInput(input) => {
+                self.execution_count = Some(input.execution_count.0 as i32);
+            }
+            JupyterMessageContent::ExecuteResult(result) => {
+                self.execution_count = Some(result.execution_count.0 as i32);
+                let output = Output::new(
+                    &result.data,
+                    result.transient.as_ref().and_then(|t| t.display_id.clone()),
+                    window,
+                    cx,
+                );
+                self.push_output(
+                    output,
+                    nbformat::v4::Output::ExecuteResult(nbformat::v4::ExecuteResult {
+                        execution_count: result.execution_count,
+                        data: result.data.clone(),
+                        metadata: result.metadata.clone(),
+                    }),
+                );
+            }
+            JupyterMessageContent::DisplayData(result) => {
+                let output = Output::new(
+                    &result.data,
+                    result.transient.as_ref().and_then(|t| t.display_id.clone()),
+                    window,
+                    cx,
+                );
+                self.push_output(
+                    output,
+                    nbformat::v4::Output::DisplayData(nbformat::v4::DisplayData {
+                        data: result.data.clone(),
+                        metadata: result.metadata.clone(),
+                    }),
+                );
+            }
+            JupyterMessageContent::StreamContent(result) => {
+                self.push_stream(&result.name, &result.text, window, cx);
+            }
+            JupyterMessageContent::ErrorOutput(result) => {
+                let traceback =
+                    cx.new(|cx| TerminalOutput::from(&result.traceback.join("\n"), window, cx));
+                self.push_output(
+                    Output::ErrorOutput(ErrorView {
+                        ename: result.ename.clone(),
+                        evalue: result.evalue.clone(),
+                        traceback,
+                    }),
+                    nbformat::v4::Output::Error(nbformat::v4::ErrorOutput {
+                        ename: result.ename.clone(),
+                        evalue: result.evalue.clone(),
+                        traceback: result.traceback.clone(),
+                    }),
+                );
+            }
+            JupyterMessageContent::ClearOutput(options) => {
+                if options.wait {
+                    self.clear_on_next_output = true;
+                } else {
+                    self.outputs.clear();
+                    self.notebook_outputs.clear();
+                }
+            }
+            JupyterMessageContent::Status(status) => match status.execution_state {
+                ExecutionState::Busy => self.executing = true,
+                ExecutionState::Idle => self.executing = false,
+                _ => return,
+            },
+            _ => return,
+        }
+        cx.notify();
+    }
+
+    pub fn update_display_data(
+        &mut self,
+        data: &MimeBundle,
+        display_id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut any = false;
+        for (output, notebook_output) in self.outputs.iter_mut().zip(&mut self.notebook_outputs) {
+            if output.display_id().as_deref() != Some(display_id) {
+                continue;
+            }
+            *output = Output::new(data, Some(display_id.to_owned()), window, cx);
+            match notebook_output {
+                nbformat::v4::Output::DisplayData(display_data) => display_data.data = data.clone(),
+                nbformat::v4::Output::ExecuteResult(result) => result.data = data.clone(),
+                _ => {}
+            }
+            any = true;
+        }
+
+        if any {
+            cx.notify();
+        }
+    }
+
+    fn push_output(&mut self, output: Output, notebook_output: nbformat::v4::Output) {
+        if self.clear_on_next_output {
+            self.outputs.clear();
+            self.notebook_outputs.clear();
+            self.clear_on_next_output = false;
+        }
+        self.outputs.push(output);
+        self.notebook_outputs.push(notebook_output);
+    }
+
+    fn push_stream(&mut self, name: &Stdio, text: &str, window: &mut Window, cx: &mut App) {
+        let name = match name {
+            Stdio::Stdout => "stdout",
+            Stdio::Stderr => "stderr",
+        };
+
+        // Consecutive output of the same stream is combined, handling colors, carriage returns, etc
+        if !self.clear_on_next_output
+            && let Some(nbformat::v4::Output::Stream {
+                name: last_name,
+                text: last_text,
+            }) = self.notebook_outputs.last_mut()
+            && last_name == name
+            && let Some(Output::Stream { content }) = self.outputs.last()
+        {
+            last_text.0.push_str(text);
+            content.update(cx, |content, cx| {
+                content.append_text(text, cx);
+            });
+            return;
+        }
+
+        self.push_output(
+            Output::Stream {
+                content: cx.new(|cx| TerminalOutput::from(text, window, cx)),
+            },
+            nbformat::v4::Output::Stream {
+                name: name.to_string(),
+                text: nbformat::v4::MultilineString(text.to_string()),
+            },
+        );
     }
 
     fn output_control(&self) -> Option<CellControlType> {
@@ -521,15 +887,18 @@ impl RenderableCell for CodeCell {
         &self.source
     }
 
+    fn set_source(&mut self, source: String, _window: &mut Window, _cx: &mut Context<Self>) {
+        self.source = source;
+    }
+
     fn control(&self, window: &mut Window, cx: &mut Context<Self>) -> Option<CellControl> {
         let cell_control = if self.has_outputs() {
             CellControl::new("rerun-cell", CellControlType::RerunCell)
         } else {
             CellControl::new("run-cell", CellControlType::RunCell)
-                .on_click(cx.listener(move |this, _, window, cx| this.run(window, cx)))
         };
 
-        Some(cell_control)
+        Some(cell_control.on_click(cx.listener(move |this, _, window, cx| this.run(window, cx))))
     }
 
     fn selected(&self) -> bool {
@@ -552,8 +921,8 @@ impl RenderableCell for CodeCell {
 }
 
 impl RunnableCell for CodeCell {
-    fn run(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Running code cell: {}", self.id);
+    fn run(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(CellEvent::Run);
     }
 
     fn execution_count(&self) -> Option<i32> {
@@ -642,6 +1011,12 @@ impl Render for CodeCell {
                                             Output::Table { content, .. } => {
                                                 Some(content.clone().into_any_element())
                                             }
+                                            Output::Svg { content, .. } => {
+                                                Some(content.clone().into_any_element())
+                                            }
+                                            Output::Json { content, .. } => {
+                                                Some(content.clone().into_any_element())
+                                            }
                                             Output::ErrorOutput(error_view) => {
                                                 error_view.render(window, cx)
                                             }
@@ -672,10 +1047,15 @@ pub struct RawCell {
     id: CellId,
     metadata: CellMetadata,
     source: String,
+    editor: Entity<Editor>,
     selected: bool,
     cell_position: Option<CellPosition>,
+    language_task: Task<()>,
+    _editor_subscription: Subscription,
 }
 
+impl EventEmitter<CellEvent> for RawCell {}
+
 impl RenderableCell for RawCell {
     const CELL_TYPE: CellType = CellType::Raw;
 
@@ -695,6 +1075,10 @@ impl RenderableCell for RawCell {
         &self.source
     }
 
+    fn set_source(&mut self, source: String, _window: &mut Window, _cx: &mut Context<Self>) {
+        self.source = source;
+    }
+
     fn selected(&self) -> bool {
         self.selected
     }
@@ -735,9 +1119,7 @@ impl Render for RawCell {
                             .size_full()
                             .flex_1()
                             .p_3()
-                            .font_ui(cx)
-                            .text_size(TextSize::Default.rems(cx))
-                            .child(self.source.clone()),
+                            .child(div().w_full().child(self.editor.clone())),
                     ),
             )
             // TODO: Move base cell render into trait impl so we don't have to repeat this
@@ -1,35 +1,37 @@
-#![allow(unused, dead_code)]
+use std::env::temp_dir;
 use std::future::Future;
+use std::path::Path;
 use std::{path::PathBuf, sync::Arc};
 
-use anyhow::{Context as _, Result};
-use client::proto::ViewId;
+use anyhow::{Context as _, Result, anyhow};
 use collections::HashMap;
 use feature_flags::{FeatureFlagAppExt as _, NotebookFeatureFlag};
 use futures::FutureExt;
 use futures::future::Shared;
 use gpui::{
-    AnyElement, App, Entity, EventEmitter, FocusHandle, Focusable, ListScrollEvent, ListState,
-    Point, Task, actions, list, prelude::*,
+    AnyElement, App, Entity, EventEmitter, FocusHandle, Focusable, ListState, MouseButton, Point,
+    Subscription, Task, actions, list, prelude::*,
 };
 use language::{Language, LanguageRegistry};
-use project::{Project, ProjectEntryId, ProjectPath};
+use project::{Fs, Project, ProjectEntryId, ProjectPath};
+use runtimelib::{ExecuteRequest, ExecutionState, JupyterMessage, JupyterMessageContent};
 use ui::{Tooltip, prelude::*};
+use uuid::Uuid;
 use workspace::item::{ItemEvent, SaveOptions, TabContentParams};
 use workspace::searchable::SearchableItemHandle;
-use workspace::{Item, ItemHandle, Pane, ProjectItem, ToolbarItemLocation};
-use workspace::{ToolbarItemEvent, ToolbarItemView};
+use workspace::{Item, Pane, ProjectItem};
 
-use super::{Cell, CellPosition, RenderableCell};
+use super::{Cell, CellEvent, CellPosition, RenderableCell};
+use crate::kernels::{
+    Kernel, KernelSession, KernelSpecification, NativeRunningKernel, RemoteRunningKernel,
+};
+use crate::repl_store::ReplStore;
 
-use nbformat::v4::CellId;
-use nbformat::v4::Metadata as NotebookMetadata;
+use nbformat::v4::{CellId, CellMetadata, CellType};
 
 actions!(
     notebook,
     [
-        /// Opens a Jupyter notebook file.
-        OpenNotebook,
         /// Runs all cells in the notebook.
         RunAll,
         /// Clears all cell outputs.
@@ -42,13 +44,20 @@ actions!(
         AddMarkdownBlock,
         /// Adds a new code cell.
         AddCodeBlock,
+        /// Runs the current cell.
+        RunCell,
+        /// Deletes the current cell.
+        DeleteCell,
+        /// Changes the current cell into a code cell.
+        ChangeToCodeCell,
+        /// Changes the current cell into a markdown cell.
+        ChangeToMarkdownCell,
+        /// Changes the current cell into a raw cell.
+        ChangeToRawCell,
     ]
 );
 
-pub(crate) const MAX_TEXT_BLOCK_WIDTH: f32 = 9999.0;
-pub(crate) const SMALL_SPACING_SIZE: f32 = 8.0;
 pub(crate) const MEDIUM_SPACING_SIZE: f32 = 12.0;
-pub(crate) const LARGE_SPACING_SIZE: f32 = 16.0;
 pub(crate) const GUTTER_WIDTH: f32 = 19.0;
 pub(crate) const CODE_BLOCK_INSET: f32 = MEDIUM_SPACING_SIZE;
 pub(crate) const CONTROL_SIZE: f32 = 20.0;
@@ -77,13 +86,22 @@ pub struct NotebookEditor {
 
     focus_handle: FocusHandle,
     notebook_item: Entity<NotebookItem>,
+    notebook_language: Shared<Task<Option<Arc<Language>>>>,
 
-    remote_id: Option<ViewId>,
     cell_list: ListState,
 
     selected_cell_index: usize,
     cell_order: Vec<CellId>,
     cell_map: HashMap<CellId, Cell>,
+    cell_subscriptions: HashMap<CellId, Subscription>,
+    dirty: bool,
+
+    kernel: Kernel,
+    kernel_specification: Option<KernelSpecification>,
+    /// Execute requests waiting for the kernel to start.
+    queued_messages: Vec<JupyterMessage>,
+    /// The cells being run, by the id of their execute request.
+    executions: HashMap<String, CellId>,
 }
 
 impl NotebookEditor {
@@ -103,45 +121,147 @@ impl NotebookEditor {
             .spawn_in(window, async move |_, _| notebook_language.await)
             .shared();
 
-        let mut cell_order = vec![]; // Vec<CellId>
-        let mut cell_map = HashMap::default(); // HashMap<CellId, Cell>
-
-        for (index, cell) in notebook_item
-            .read(cx)
-            .notebook
-            .clone()
-            .cells
-            .iter()
-            .enumerate()
+        if language_name
+            .as_deref()
+            .is_some_and(|name| name.eq_ignore_ascii_case("python"))
         {
-            let cell_id = cell.id();
-            cell_order.push(cell_id.clone());
-            cell_map.insert(
-                cell_id.clone(),
-                Cell::load(cell, &languages, notebook_language.clone(), window, cx),
-            );
+            let worktree_id = notebook_item.read(cx).project_path.worktree_id;
+            ReplStore::global(cx).update(cx, |store, cx| {
+                store
+                    .refresh_python_kernelspecs(worktree_id, &project, cx)
+                    .detach_and_log_err(cx);
+            });
         }
 
-        let notebook_handle = cx.entity().downgrade();
-        let cell_count = cell_order.len();
+        let cell_list = ListState::new(0, gpui::ListAlignment::Top, px(1000.));
 
-        let this = cx.entity();
-        let cell_list = ListState::new(cell_count, gpui::ListAlignment::Top, px(1000.));
-
-        Self {
+        let mut this = Self {
             project,
             languages: languages.clone(),
             focus_handle,
             notebook_item,
-            remote_id: None,
+            notebook_language,
             cell_list,
             selected_cell_index: 0,
-            cell_order: cell_order.clone(),
-            cell_map: cell_map.clone(),
+            cell_order: Vec::new(),
+            cell_map: HashMap::default(),
+            cell_subscriptions: HashMap::default(),
+            dirty: false,
+            kernel: Kernel::Shutdown,
+            kernel_specification: None,
+            queued_messages: Vec::new(),
+            executions: HashMap::default(),
+        };
+        this.load_cells(window, cx);
+        this
+    }
+
+    /// Replaces the cells with those of the notebook item.
+    fn load_cells(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.cell_order.clear();
+        self.cell_map.clear();
+        self.cell_subscriptions.clear();
+        self.executions.clear();
+
+        let cells = self.notebook_item.read(cx).notebook.cells.clone();
+        for cell in &cells {
+            let cell = Cell::load(
+                cell,
+                &self.languages,
+                self.notebook_language.clone(),
+                window,
+                cx,
+            );
+            let cell_id = cell.id(cx);
+            self.cell_subscriptions
+                .insert(cell_id.clone(), self.subscribe_to_cell(&cell, window, cx));
+            self.cell_order.push(cell_id.clone());
+            self.cell_map.insert(cell_id, cell);
+        }
+
+        self.cell_list.reset(self.cell_order.len());
+        self.selected_cell_index = self
+            .selected_cell_index
+            .min(self.cell_order.len().saturating_sub(1));
+        cx.notify();
+    }
+
+    fn subscribe_to_cell(
+        &self,
+        cell: &Cell,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Subscription {
+        let cell_id = cell.id(cx);
+        let on_event = move |this: &mut Self,
+                             event: &CellEvent,
+                             window: &mut Window,
+                             cx: &mut Context<Self>| {
+            match event {
+                CellEvent::Edited => this.mark_dirty(cx),
+                CellEvent::Run => this.execute_cell(cell_id.clone(), window, cx),
+            }
+        };
+        match cell {
+            Cell::Code(cell) => cx.subscribe_in(cell, window, move |this, _, event, window, cx| {
+                on_event(this, event, window, cx)
+            }),
+            Cell::Markdown(cell) => {
+                cx.subscribe_in(cell, window, move |this, _, event, window, cx| {
+                    on_event(this, event, window, cx)
+                })
+            }
+            Cell::Raw(cell) => cx.subscribe_in(cell, window, move |this, _, event, window, cx| {
+                on_event(this, event, window, cx)
+            }),
         }
     }
 
-    fn has_outputs(&self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+    fn mark_dirty(&mut self, cx: &mut Context<Self>) {
+        if !self.dirty {
+            self.dirty = true;
+            cx.emit(ItemEvent::UpdateTab);
+        }
+        cx.notify();
+    }
+
+    /// The notebook as it is currently edited, with the metadata it was loaded with.
+    fn to_notebook(&self, cx: &App) -> nbformat::v4::Notebook {
+        let notebook = &self.notebook_item.read(cx).notebook;
+        nbformat::v4::Notebook {
+            metadata: notebook.metadata.clone(),
+            nbformat: notebook.nbformat,
+            nbformat_minor: notebook.nbformat_minor,
+            cells: self
+                .cell_order
+                .iter()
+                .filter_map(|cell_id| self.cell_map.get(cell_id))
+                .map(|cell| cell.to_nbformat(cx))
+                .collect(),
+        }
+    }
+
+    fn write_notebook(
+        &mut self,
+        abs_path: PathBuf,
+        fs: Arc<dyn Fs>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let notebook = self.to_notebook(cx);
+        cx.spawn(async move |this, cx| {
+            let content = nbformat::serialize_notebook(&nbformat::Notebook::V4(notebook.clone()))
+                .context("serializing the notebook")?;
+            fs.atomic_write(abs_path, content).await?;
+            this.update(cx, |this, cx| {
+                this.notebook_item
+                    .update(cx, |item, _| item.notebook = notebook);
+                this.dirty = false;
+                cx.emit(ItemEvent::UpdateTab);
+            })
+        })
+    }
+
+    fn has_outputs(&self, cx: &App) -> bool {
         self.cell_map.values().any(|cell| {
             if let Cell::Code(code_cell) = cell {
                 code_cell.read(cx).has_outputs()
@@ -151,7 +271,7 @@ impl NotebookEditor {
         })
     }
 
-    fn clear_outputs(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+    fn clear_outputs(&mut self, cx: &mut Context<Self>) {
         for cell in self.cell_map.values() {
             if let Cell::Code(code_cell) = cell {
                 code_cell.update(cx, |cell, _cx| {
@@ -159,30 +279,276 @@ impl NotebookEditor {
                 });
             }
         }
+        self.mark_dirty(cx);
     }
 
     fn run_cells(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Cells would all run here, if that was implemented!");
+        for cell_id in self.cell_order.clone() {
+            self.execute_cell(cell_id, window, cx);
+        }
     }
 
-    fn open_notebook(&mut self, _: &OpenNotebook, _window: &mut Window, _cx: &mut Context<Self>) {
-        println!("Open notebook triggered");
+    fn run_selected_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(cell_id) = self.cell_order.get(self.selected_cell_index).cloned() {
+            self.execute_cell(cell_id, window, cx);
+        }
+    }
+
+    fn execute_cell(&mut self, cell_id: CellId, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(Cell::Code(cell)) = self.cell_map.get(&cell_id).cloned() else {
+            return;
+        };
+        let code = cell.read(cx).source().clone();
+        if code.trim().is_empty() {
+            return;
+        }
+
+        if matches!(self.kernel, Kernel::Shutdown | Kernel::ErroredLaunch(_)) {
+            self.start_kernel(window, cx);
+        }
+        if let Kernel::ErroredLaunch(error) = &self.kernel {
+            let error = format!("Kernel error: {error}");
+            cell.update(cx, |cell, cx| cell.fail_execution(error, cx));
+            return;
+        }
+
+        cell.update(cx, |cell, cx| cell.start_execution(cx));
+        let message: JupyterMessage = ExecuteRequest {
+            code,
+            ..ExecuteRequest::default()
+        }
+        .into();
+        self.executions
+            .insert(message.header.msg_id.clone(), cell_id);
+        self.send(message);
+        self.mark_dirty(cx);
+    }
+
+    fn send(&mut self, message: JupyterMessage) {
+        match &mut self.kernel {
+            Kernel::RunningKernel(kernel) => {
+                kernel.request_tx().try_send(message).ok();
+            }
+            Kernel::StartingKernel(_) => self.queued_messages.push(message),
+            _ => {}
+        }
+    }
+
+    /// Picks the kernel named in the notebook's metadata, or else one for its language.
+    fn kernel_specification(&self, cx: &App) -> Option<KernelSpecification> {
+        let notebook_item = self.notebook_item.read(cx);
+        let kernel_name = notebook_item
+            .notebook
+            .metadata
+            .kernelspec
+            .as_ref()
+            .map(|spec| spec.name.to_lowercase());
+        let language = notebook_item
+            .language_name()
+            .map(|language| language.to_lowercase());
+
+        let store = ReplStore::global(cx).read(cx);
+        let specifications = store
+            .kernel_specifications_for_worktree(notebook_item.project_path.worktree_id)
+            .collect::<Vec<_>>();
+        specifications
+            .iter()
+            .find(|spec| Some(spec.name().to_lowercase()) == kernel_name)
+            .or_else(|| {
+                specifications
+                    .iter()
+                    .find(|spec| Some(spec.language().to_lowercase()) == language)
+            })
+            .map(|spec| (*spec).clone())
+    }
+
+    fn start_kernel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(kernel_specification) = self.kernel_specification(cx) else {
+            self.kernel = Kernel::ErroredLaunch("no kernel found for this notebook".to_string());
+            cx.notify();
+            return;
+        };
+
+        let working_directory = self
+            .notebook_item
+            .read(cx)
+            .path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(temp_dir);
+        let fs = self.project.read(cx).fs().clone();
+        let notebook = cx.entity();
+
+        let kernel = match kernel_specification.clone() {
+            KernelSpecification::Jupyter(kernel_specification)
+            | KernelSpecification::PythonEnv(kernel_specification) => NativeRunningKernel::new(
+                kernel_specification,
+                cx.entity_id(),
+                working_directory,
+                fs,
+                notebook,
+                window,
+                cx,
+            ),
+            KernelSpecification::Remote(remote_kernel_specification) => RemoteRunningKernel::new(
+                remote_kernel_specification,
+                working_directory,
+                notebook,
+                window,
+                cx,
+            ),
+        };
+
+        let pending_kernel = cx
+            .spawn(async move |this, cx| {
+                let kernel = kernel.await;
+                this.update(cx, |this, cx| match kernel {
+                    Ok(kernel) => {
+                        this.kernel = Kernel::RunningKernel(kernel);
+                        for message in std::mem::take(&mut this.queued_messages) {
+                            this.send(message);
+                        }
+                        cx.notify();
+                    }
+                    Err(error) => this.kernel_errored(error.to_string(), cx),
+                })
+                .ok();
+            })
+            .shared();
+
+        self.kernel = Kernel::StartingKernel(pending_kernel);
+        self.kernel_specification = Some(kernel_specification);
+        cx.notify();
     }
 
     fn move_cell_up(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell up triggered");
+        let index = self.selected_cell_index;
+        if index == 0 || index >= self.cell_count() {
+            return;
+        }
+        self.cell_order.swap(index - 1, index);
+        self.cell_list.splice(index - 1..index + 1, 2);
+        self.set_selected_index(index - 1, true, window, cx);
+        self.mark_dirty(cx);
     }
 
     fn move_cell_down(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Move cell down triggered");
+        let index = self.selected_cell_index;
+        if index + 1 >= self.cell_count() {
+            return;
+        }
+        self.cell_order.swap(index, index + 1);
+        self.cell_list.splice(index..index + 2, 2);
+        self.set_selected_index(index + 1, true, window, cx);
+        self.mark_dirty(cx);
     }
 
     fn add_markdown_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Add markdown block triggered");
+        self.add_cell(CellType::Markdown, window, cx);
     }
 
     fn add_code_block(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        println!("Add code block triggered");
+        self.add_cell(CellType::Code, window, cx);
+    }
+
+    /// Adds an empty cell below the current one and focuses it.
+    fn add_cell(&mut self, cell_type: CellType, window: &mut Window, cx: &mut Context<Self>) {
+        let id = CellId::from(Uuid::new_v4());
+        let metadata = CellMetadata::default();
+        let cell = match cell_type {
+            CellType::Code => nbformat::v4::Cell::Code {
+                id,
+                metadata,
+                execution_count: None,
+                source: Vec::new(),
+                outputs: Vec::new(),
+            },
+            CellType::Markdown => nbformat::v4::Cell::Markdown {
+                id,
+                metadata,
+                source: Vec::new(),
+                attachments: None,
+            },
+            CellType::Raw => nbformat::v4::Cell::Raw {
+                id,
+                metadata,
+                source: Vec::new(),
+            },
+        };
+        let cell = Cell::load(
+            &cell,
+            &self.languages,
+            self.notebook_language.clone(),
+            window,
+            cx,
+        );
+
+        let index = if self.cell_order.is_empty() {
+            0
+        } else {
+            self.selected_cell_index + 1
+        };
+        let cell_id = cell.id(cx);
+        self.cell_subscriptions
+            .insert(cell_id.clone(), self.subscribe_to_cell(&cell, window, cx));
+        self.cell_order.insert(index, cell_id.clone());
+        self.cell_list.splice(index..index, 1);
+        window.focus(&cell.editor(cx).focus_handle(cx));
+        self.cell_map.insert(cell_id, cell);
+        self.set_selected_index(index, true, window, cx);
+        self.mark_dirty(cx);
+    }
+
+    fn delete_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let index = self.selected_cell_index;
+        if index >= self.cell_count() {
+            return;
+        }
+        let cell_id = self.cell_order.remove(index);
+        self.cell_map.remove(&cell_id);
+        self.cell_subscriptions.remove(&cell_id);
+        self.executions
+            .retain(|_, executed_cell_id| *executed_cell_id != cell_id);
+        self.cell_list.splice(index..index + 1, 0);
+        let index = index.min(self.cell_order.len().saturating_sub(1));
+        self.set_selected_index(index, true, window, cx);
+        window.focus(&self.focus_handle);
+        self.mark_dirty(cx);
+    }
+
+    fn change_cell_type(
+        &mut self,
+        cell_type: CellType,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let index = self.selected_cell_index;
+        let Some(cell) = self
+            .cell_order
+            .get(index)
+            .and_then(|cell_id| self.cell_map.get(cell_id))
+        else {
+            return;
+        };
+        if cell.cell_type(cx) == cell_type {
+            return;
+        }
+
+        let cell = Cell::load(
+            &cell.with_cell_type(cell_type, cx),
+            &self.languages,
+            self.notebook_language.clone(),
+            window,
+            cx,
+        );
+        let cell_id = cell.id(cx);
+        self.executions
+            .retain(|_, executed_cell_id| *executed_cell_id != cell_id);
+        self.cell_subscriptions
+            .insert(cell_id.clone(), self.subscribe_to_cell(&cell, window, cx));
+        self.cell_map.insert(cell_id, cell);
+        self.cell_list.splice(index..index + 1, 1);
+        self.mark_dirty(cx);
     }
 
     fn cell_count(&self) -> usize {
@@ -275,7 +641,7 @@ impl NotebookEditor {
         self.cell_list.scroll_to_reveal_item(index);
     }
 
-    fn button_group(window: &mut Window, cx: &mut Context<Self>) -> Div {
+    fn button_group(cx: &mut Context<Self>) -> Div {
         v_flex()
             .gap(DynamicSpacing::Base04.rems(cx))
             .items_center()
@@ -303,7 +669,7 @@ impl NotebookEditor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
-        let has_outputs = self.has_outputs(window, cx);
+        let has_outputs = self.has_outputs(cx);
 
         v_flex()
             .max_w(px(CONTROL_SIZE + 4.0))
@@ -317,7 +683,7 @@ impl NotebookEditor {
                 v_flex()
                     .gap(DynamicSpacing::Base08.rems(cx))
                     .child(
-                        Self::button_group(window, cx)
+                        Self::button_group(cx)
                             .child(
                                 Self::render_notebook_control(
                                     "run-all-cells",
@@ -325,7 +691,7 @@ impl NotebookEditor {
                                     window,
                                     cx,
                                 )
-                                .tooltip(move |window, cx| {
+                                .tooltip(move |_, cx| {
                                     Tooltip::for_action("Execute all cells", &RunAll, cx)
                                 })
                                 .on_click(|_, window, cx| {
@@ -340,7 +706,7 @@ impl NotebookEditor {
                                     cx,
                                 )
                                 .disabled(!has_outputs)
-                                .tooltip(move |window, cx| {
+                                .tooltip(move |_, cx| {
                                     Tooltip::for_action("Clear all outputs", &ClearOutputs, cx)
                                 })
                                 .on_click(|_, window, cx| {
@@ -349,7 +715,7 @@ impl NotebookEditor {
                             ),
                     )
                     .child(
-                        Self::button_group(window, cx)
+                        Self::button_group(cx)
                             .child(
                                 Self::render_notebook_control(
                                     "move-cell-up",
@@ -357,7 +723,7 @@ impl NotebookEditor {
                                     window,
                                     cx,
                                 )
-                                .tooltip(move |window, cx| {
+                                .tooltip(move |_, cx| {
                                     Tooltip::for_action("Move cell up", &MoveCellUp, cx)
                                 })
                                 .on_click(|_, window, cx| {
@@ -371,16 +737,31 @@ impl NotebookEditor {
                                     window,
                                     cx,
                                 )
-                                .tooltip(move |window, cx| {
+                                .tooltip(move |_, cx| {
                                     Tooltip::for_action("Move cell down", &MoveCellDown, cx)
                                 })
                                 .on_click(|_, window, cx| {
                                     window.dispatch_action(Box::new(MoveCellDown), cx);
                                 }),
+                            )
+                            .child(
+                                Self::render_notebook_control(
+                                    "delete-cell",
+                                    IconName::Trash,
+                                    window,
+                                    cx,
+                                )
+                                .disabled(self.cell_count() == 0)
+                                .tooltip(move |_, cx| {
+                                    Tooltip::for_action("Delete cell", &DeleteCell, cx)
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(Box::new(DeleteCell), cx);
+                                }),
                             ),
                     )
                     .child(
-                        Self::button_group(window, cx)
+                        Self::button_group(cx)
                             .child(
                                 Self::render_notebook_control(
                                     "new-markdown-cell",
@@ -388,7 +769,7 @@ impl NotebookEditor {
                                     window,
                                     cx,
                                 )
-                                .tooltip(move |window, cx| {
+                                .tooltip(move |_, cx| {
                                     Tooltip::for_action("Add markdown block", &AddMarkdownBlock, cx)
                                 })
                                 .on_click(|_, window, cx| {
@@ -402,7 +783,7 @@ impl NotebookEditor {
                                     window,
                                     cx,
                                 )
-                                .tooltip(move |window, cx| {
+                                .tooltip(move |_, cx| {
                                     Tooltip::for_action("Add code block", &AddCodeBlock, cx)
                                 })
                                 .on_click(|_, window, cx| {
@@ -422,12 +803,26 @@ impl NotebookEditor {
                         cx,
                     ))
                     .child(
-                        Self::button_group(window, cx)
-                            .child(IconButton::new("repl", IconName::ReplNeutral)),
+                        Self::button_group(cx).child(
+                            IconButton::new("repl", IconName::ReplNeutral)
+                                .tooltip(Tooltip::text(self.kernel_status_text())),
+                        ),
                     ),
             )
     }
 
+    fn kernel_status_text(&self) -> String {
+        let name = self
+            .kernel_specification
+            .as_ref()
+            .map(|spec| spec.name().to_string())
+            .unwrap_or_else(|| "Kernel".to_string());
+        match &self.kernel {
+            Kernel::ErroredLaunch(error) => format!("{name}: {error}"),
+            kernel => format!("{name}: {}", kernel.status().to_string()),
+        }
+    }
+
     fn cell_position(&self, index: usize) -> CellPosition {
         match index {
             0 => CellPosition::First,
@@ -436,13 +831,7 @@ impl NotebookEditor {
         }
     }
 
-    fn render_cell(
-        &self,
-        index: usize,
-        cell: &Cell,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) -> impl IntoElement {
+    fn render_cell(&self, index: usize, cell: &Cell, cx: &mut Context<Self>) -> impl IntoElement {
         let cell_position = self.cell_position(index);
 
         let is_selected = index == self.selected_cell_index;
@@ -478,12 +867,7 @@ impl Render for NotebookEditor {
         div()
             .key_context("notebook")
             .track_focus(&self.focus_handle)
-            .on_action(cx.listener(|this, &OpenNotebook, window, cx| {
-                this.open_notebook(&OpenNotebook, window, cx)
-            }))
-            .on_action(
-                cx.listener(|this, &ClearOutputs, window, cx| this.clear_outputs(window, cx)),
-            )
+            .on_action(cx.listener(|this, &ClearOutputs, _, cx| this.clear_outputs(cx)))
             .on_action(cx.listener(|this, &RunAll, window, cx| this.run_cells(window, cx)))
             .on_action(cx.listener(|this, &MoveCellUp, window, cx| this.move_cell_up(window, cx)))
             .on_action(
@@ -495,6 +879,17 @@ impl Render for NotebookEditor {
             .on_action(
                 cx.listener(|this, &AddCodeBlock, window, cx| this.add_code_block(window, cx)),
             )
+            .on_action(cx.listener(|this, &RunCell, window, cx| this.run_selected_cell(window, cx)))
+            .on_action(cx.listener(|this, &DeleteCell, window, cx| this.delete_cell(window, cx)))
+            .on_action(cx.listener(|this, &ChangeToCodeCell, window, cx| {
+                this.change_cell_type(CellType::Code, window, cx)
+            }))
+            .on_action(cx.listener(|this, &ChangeToMarkdownCell, window, cx| {
+                this.change_cell_type(CellType::Markdown, window, cx)
+            }))
+            .on_action(cx.listener(|this, &ChangeToRawCell, window, cx| {
+                this.change_cell_type(CellType::Raw, window, cx)
+            }))
             .on_action(cx.listener(Self::select_next))
             .on_action(cx.listener(Self::select_previous))
             .on_action(cx.listener(Self::select_first))
@@ -514,12 +909,21 @@ impl Render for NotebookEditor {
                     .overflow_y_scroll()
                     .child(list(
                         self.cell_list.clone(),
-                        cx.processor(|this, ix, window, cx| {
+                        cx.processor(|this, ix, _, cx| {
                             this.cell_order
                                 .get(ix)
                                 .and_then(|cell_id| this.cell_map.get(cell_id))
                                 .map(|cell| {
-                                    this.render_cell(ix, cell, window, cx).into_any_element()
+                                    div()
+                                        .on_mouse_down(
+                                            MouseButton::Left,
+                                            cx.listener(move |this, _, window, cx| {
+                                                this.set_selected_index(ix, false, window, cx);
+                                                cx.notify();
+                                            }),
+                                        )
+                                        .child(this.render_cell(ix, cell, cx))
+                                        .into_any_element()
                                 })
                                 .unwrap_or_else(|| div().into_any())
                         }),
@@ -565,23 +969,7 @@ impl project::ProjectItem for NotebookItem {
                     .with_context(|| format!("finding the absolute path of {path:?}"))?;
 
                 // todo: watch for changes to the file
-                let file_content = fs.load(abs_path.as_path()).await?;
-                let notebook = nbformat::parse_notebook(&file_content);
-
-                let notebook = match notebook {
-                    Ok(nbformat::Notebook::V4(notebook)) => notebook,
-                    // 4.1 - 4.4 are converted to 4.5
-                    Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
-                        // TODO: Decide if we want to mutate the notebook by including Cell IDs
-                        // and any other conversions
-
-                        nbformat::upgrade_legacy_notebook(legacy_notebook)?
-                    }
-                    // Bad notebooks and notebooks v4.0 and below are not supported
-                    Err(e) => {
-                        anyhow::bail!("Failed to parse notebook: {:?}", e);
-                    }
-                };
+                let notebook = load_notebook(fs.as_ref(), &abs_path).await?;
 
                 let id = project
                     .update(cx, |project, cx| {
@@ -615,6 +1003,26 @@ impl project::ProjectItem for NotebookItem {
     }
 }
 
+async fn load_notebook(fs: &dyn Fs, abs_path: &Path) -> Result<nbformat::v4::Notebook> {
+    let file_content = fs.load(abs_path).await?;
+    let notebook = nbformat::parse_notebook(&file_content);
+
+    match notebook {
+        Ok(nbformat::Notebook::V4(notebook)) => Ok(notebook),
+        // 4.1 - 4.4 are converted to 4.5
+        Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
+            // TODO: Decide if we want to mutate the notebook by including Cell IDs
+            // and any other conversions
+
+            Ok(nbformat::upgrade_legacy_notebook(legacy_notebook)?)
+        }
+        // Bad notebooks and notebooks v4.0 and below are not supported
+        Err(e) => {
+            anyhow::bail!("Failed to parse notebook: {:?}", e);
+        }
+    }
+}
+
 impl NotebookItem {
     pub fn language_name(&self) -> Option<String> {
         self.notebook
@@ -644,7 +1052,74 @@ impl NotebookItem {
     }
 }
 
-impl EventEmitter<()> for NotebookEditor {}
+impl EventEmitter<ItemEvent> for NotebookEditor {}
+
+impl KernelSession for NotebookEditor {
+    fn route(&mut self, message: &JupyterMessage, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(parent_message_id) = message
+            .parent_header
+            .as_ref()
+            .map(|header| header.msg_id.clone())
+        else {
+            return;
+        };
+
+        match &message.content {
+            JupyterMessageContent::Status(status) => {
+                self.kernel.set_execution_state(&status.execution_state);
+                cx.notify();
+            }
+            JupyterMessageContent::KernelInfoReply(reply) => {
+                self.kernel.set_kernel_info(reply);
+                cx.notify();
+            }
+            JupyterMessageContent::UpdateDisplayData(update) => {
+                let Some(display_id) = update.transient.display_id.as_deref() else {
+                    return;
+                };
+                for cell in self.cell_map.values() {
+                    if let Cell::Code(cell) = cell {
+                        cell.update(cx, |cell, cx| {
+                            cell.update_display_data(&update.data, display_id, window, cx);
+                        });
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        let Some(Cell::Code(cell)) = self
+            .executions
+            .get(&parent_message_id)
+            .and_then(|cell_id| self.cell_map.get(cell_id))
+        else {
+            return;
+        };
+        cell.update(cx, |cell, cx| {
+            cell.push_message(&message.content, window, cx)
+        });
+
+        if let JupyterMessageContent::Status(status) = &message.content
+            && matches!(status.execution_state, ExecutionState::Idle)
+        {
+            self.executions.remove(&parent_message_id);
+        }
+    }
+
+    fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
+        self.kernel = Kernel::ErroredLaunch(error_message.clone());
+        self.queued_messages.clear();
+        for (_, cell_id) in self.executions.drain() {
+            if let Some(Cell::Code(cell)) = self.cell_map.get(&cell_id) {
+                cell.update(cx, |cell, cx| {
+                    cell.fail_execution(format!("Kernel error: {error_message}"), cx)
+                });
+            }
+        }
+        cx.notify();
+    }
+}
 
 // pub struct NotebookControls {
 //     pane_focused: bool,
@@ -692,7 +1167,11 @@ impl EventEmitter<()> for NotebookEditor {}
 // }
 
 impl Item for NotebookEditor {
-    type Event = ();
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
 
     fn can_split(&self) -> bool {
         true
@@ -724,7 +1203,7 @@ impl Item for NotebookEditor {
         f(self.notebook_item.entity_id(), self.notebook_item.read(cx))
     }
 
-    fn tab_content(&self, params: TabContentParams, window: &Window, cx: &App) -> AnyElement {
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
         Label::new(self.tab_content_text(params.detail.unwrap_or(0), cx))
             .single_line()
             .color(params.text_color())
@@ -769,49 +1248,67 @@ impl Item for NotebookEditor {
         // TODO
     }
 
-    // TODO
     fn can_save(&self, _cx: &App) -> bool {
-        false
+        true
     }
-    // TODO
+
     fn save(
         &mut self,
         _options: SaveOptions,
-        _project: Entity<Project>,
+        project: Entity<Project>,
         _window: &mut Window,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("save() must be implemented if can_save() returns true")
+        let abs_path = self.notebook_item.read(cx).path.clone();
+        let fs = project.read(cx).fs().clone();
+        self.write_notebook(abs_path, fs, cx)
     }
 
-    // TODO
     fn save_as(
         &mut self,
-        _project: Entity<Project>,
-        _path: ProjectPath,
+        project: Entity<Project>,
+        path: ProjectPath,
         _window: &mut Window,
-        _cx: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("save_as() must be implemented if can_save() returns true")
+        let Some(abs_path) = project.read(cx).absolute_path(&path, cx) else {
+            return Task::ready(Err(anyhow!("finding the absolute path of {path:?}")));
+        };
+        let fs = project.read(cx).fs().clone();
+        let write = self.write_notebook(abs_path.clone(), fs, cx);
+        let notebook_item = self.notebook_item.clone();
+        cx.spawn(async move |this, cx| {
+            write.await?;
+            notebook_item.update(cx, |item, _| {
+                item.path = abs_path;
+                item.project_path = path;
+            })?;
+            this.update(cx, |_, cx| cx.emit(ItemEvent::UpdateTab))
+        })
     }
-    // TODO
+
     fn reload(
         &mut self,
-        _project: Entity<Project>,
-        _window: &mut Window,
-        _cx: &mut Context<Self>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        unimplemented!("reload() must be implemented if can_save() returns true")
+        let abs_path = self.notebook_item.read(cx).path.clone();
+        let fs = project.read(cx).fs().clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let notebook = load_notebook(fs.as_ref(), &abs_path).await?;
+            this.update_in(cx, |this, window, cx| {
+                this.notebook_item
+                    .update(cx, |item, _| item.notebook = notebook);
+                this.load_cells(window, cx);
+                this.dirty = false;
+                cx.emit(ItemEvent::UpdateTab);
+            })
+        })
     }
 
-    fn is_dirty(&self, cx: &App) -> bool {
-        self.cell_map.values().any(|cell| {
-            if let Cell::Code(code_cell) = cell {
-                code_cell.read(cx).is_dirty(cx)
-            } else {
-                false
-            }
-        })
+    fn is_dirty(&self, _cx: &App) -> bool {
+        self.dirty
     }
 }
 
@@ -834,3 +1331,217 @@ impl ProjectItem for NotebookEditor {
         Self::new(project, item, window, cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::{path, rel_path::rel_path};
+
+    const NOTEBOOK: &str = r##"{
+        "metadata": {
+            "kernelspec": {"name": "deno", "display_name": "Deno", "language": "typescript"},
+            "language_info": {"name": "typescript"},
+            "custom": {"key": "value"}
+        },
+        "nbformat": 4,
+        "nbformat_minor": 5,
+        "cells": [
+            {
+                "cell_type": "markdown",
+                "id": "intro",
+                "metadata": {"tags": ["header"]},
+                "source": ["# Title\n", "Text"]
+            },
+            {
+                "cell_type": "code",
+                "id": "setup",
+                "metadata": {"collapsed": true, "custom": {"key": "value"}},
+                "execution_count": 1,
+                "source": ["let x = 1;"],
+                "outputs": []
+            }
+        ]
+    }"##;
+
+    async fn open_notebook(
+        cx: &mut TestAppContext,
+    ) -> (Entity<NotebookEditor>, Arc<FakeFs>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({ "notebook.ipynb": NOTEBOOK }))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let project_path = ProjectPath {
+            worktree_id,
+            path: rel_path("notebook.ipynb").into(),
+        };
+        let notebook_item = cx
+            .update(|cx| {
+                <NotebookItem as project::ProjectItem>::try_open(&project, &project_path, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        let (notebook, cx) = cx
+            .add_window_view(|window, cx| NotebookEditor::new(project, notebook_item, window, cx));
+        (notebook, fs, cx)
+    }
+
+    fn cells(
+        notebook: &Entity<NotebookEditor>,
+        cx: &mut VisualTestContext,
+    ) -> Vec<(&'static str, String)> {
+        notebook.read_with(cx, |notebook, cx| {
+            notebook
+                .to_notebook(cx)
+                .cells
+                .into_iter()
+                .map(|cell| match cell {
+                    nbformat::v4::Cell::Code { source, .. } => ("code", source.concat()),
+                    nbformat::v4::Cell::Markdown { source, .. } => ("markdown", source.concat()),
+                    nbformat::v4::Cell::Raw { source, .. } => ("raw", source.concat()),
+                })
+                .collect()
+        })
+    }
+
+    fn parse_notebook(content: &str) -> nbformat::v4::Notebook {
+        match nbformat::parse_notebook(content).unwrap() {
+            nbformat::Notebook::V4(notebook) => notebook,
+            _ => panic!("expected an nbformat v4 notebook"),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_editing_cells(cx: &mut TestAppContext) {
+        let (notebook, _, cx) = open_notebook(cx).await;
+        let title = ("markdown", "# Title\nText".to_string());
+        let code = |source: &str| ("code", source.to_string());
+        assert_eq!(cells(&notebook, cx), [title.clone(), code("let x = 1;")]);
+        assert!(!notebook.read_with(cx, |notebook, cx| notebook.is_dirty(cx)));
+
+        notebook.update_in(cx, |notebook, window, cx| {
+            notebook.add_code_block(window, cx);
+            notebook.add_markdown_block(window, cx);
+        });
+        assert_eq!(
+            cells(&notebook, cx),
+            [
+                title.clone(),
+                code(""),
+                ("markdown", String::new()),
+                code("let x = 1;")
+            ],
+            "cells should be added below the selected one"
+        );
+        assert!(notebook.read_with(cx, |notebook, cx| notebook.is_dirty(cx)));
+
+        notebook.update_in(cx, |notebook, window, cx| notebook.delete_cell(window, cx));
+        assert_eq!(
+            cells(&notebook, cx),
+            [title.clone(), code(""), code("let x = 1;")]
+        );
+        assert_eq!(
+            notebook.read_with(cx, |notebook, _| notebook.selected_index()),
+            2
+        );
+
+        notebook.update_in(cx, |notebook, window, cx| {
+            notebook.change_cell_type(CellType::Raw, window, cx);
+            notebook.move_cell_up(window, cx);
+        });
+        assert_eq!(
+            cells(&notebook, cx),
+            [title.clone(), ("raw", "let x = 1;".to_string()), code("")]
+        );
+        let raw_cell =
+            notebook.read_with(cx, |notebook, cx| notebook.to_notebook(cx).cells[1].clone());
+        assert_eq!(
+            serde_json::to_value(&raw_cell).unwrap()["id"],
+            "setup",
+            "changing the type of a cell should keep its id"
+        );
+
+        notebook.update_in(cx, |notebook, window, cx| {
+            notebook.move_cell_up(window, cx);
+            notebook.move_cell_up(window, cx);
+        });
+        assert_eq!(
+            cells(&notebook, cx),
+            [("raw", "let x = 1;".to_string()), title.clone(), code("")],
+            "the first cell should not move up"
+        );
+
+        notebook.update_in(cx, |notebook, window, cx| {
+            notebook.move_cell_down(window, cx)
+        });
+        assert_eq!(
+            cells(&notebook, cx),
+            [title, ("raw", "let x = 1;".to_string()), code("")]
+        );
+        assert_eq!(
+            notebook.read_with(cx, |notebook, _| notebook.selected_index()),
+            1
+        );
+    }
+
+    #[gpui::test]
+    async fn test_saving_keeps_notebook_and_cell_metadata(cx: &mut TestAppContext) {
+        let (notebook, fs, cx) = open_notebook(cx).await;
+        notebook.update_in(cx, |notebook, window, cx| {
+            notebook.add_code_block(window, cx)
+        });
+        let project = notebook.read_with(cx, |notebook, _| notebook.project.clone());
+        notebook
+            .update_in(cx, |notebook, window, cx| {
+                notebook.save(SaveOptions::default(), project, window, cx)
+            })
+            .await
+            .unwrap();
+        assert!(!notebook.read_with(cx, |notebook, cx| notebook.is_dirty(cx)));
+
+        let content = fs
+            .load(path!("/project/notebook.ipynb").as_ref())
+            .await
+            .unwrap();
+        let saved = parse_notebook(&content);
+        let original = parse_notebook(NOTEBOOK);
+        assert_eq!(
+            (saved.nbformat, saved.nbformat_minor),
+            (original.nbformat, original.nbformat_minor)
+        );
+        assert_eq!(
+            serde_json::to_value(&saved.metadata).unwrap(),
+            serde_json::to_value(&original.metadata).unwrap()
+        );
+        assert_eq!(saved.cells.len(), 3);
+        assert_eq!(
+            serde_json::to_value(&saved.cells[0]).unwrap(),
+            serde_json::to_value(&original.cells[0]).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&saved.cells[2]).unwrap(),
+            serde_json::to_value(&original.cells[1]).unwrap()
+        );
+
+        let content = serde_json::from_str::<serde_json::Value>(&content).unwrap();
+        assert_eq!(content["metadata"]["custom"], json!({"key": "value"}));
+        assert_eq!(content["cells"][0]["metadata"]["tags"], json!(["header"]));
+        assert_eq!(
+            content["cells"][2]["metadata"]["custom"],
+            json!({"key": "value"})
+        );
+    }
+}
@@ -13,8 +13,9 @@
 //!
 //! The module supports several output types, including:
 //! - Plain text
-//! - Markdown
-//! - Images (PNG and JPEG)
+//! - Markdown, HTML and LaTeX
+//! - Images (PNG, JPEG, GIF and SVG)
+//! - JSON, as a collapsible tree
 //! - Tables
 //! - Error messages
 //!
@@ -35,6 +36,7 @@
 
 use editor::{Editor, MultiBuffer};
 use gpui::{AnyElement, ClipboardItem, Entity, Render, WeakEntity};
+use jupyter_protocol::Stdio;
 use language::Buffer;
 use runtimelib::{ExecutionState, JupyterMessageContent, MimeBundle, MimeType};
 use ui::{
@@ -45,9 +47,15 @@ use ui::{
 mod image;
 use image::ImageView;
 
+mod json;
+use json::JsonView;
+
 mod markdown;
 use markdown::MarkdownView;
 
+mod svg;
+use svg::SvgView;
+
 mod table;
 use table::TableView;
 
@@ -61,10 +69,14 @@ use workspace::Workspace;
 /// When deciding what to render from a collection of mediatypes, we need to rank them in order of importance
 fn rank_mime_type(mimetype: &MimeType) -> usize {
     match mimetype {
-        MimeType::DataTable(_) => 6,
-        MimeType::Png(_) => 4,
-        MimeType::Jpeg(_) => 3,
-        MimeType::Markdown(_) => 2,
+        MimeType::DataTable(_) => 9,
+        MimeType::Png(_) => 8,
+        MimeType::Jpeg(_) | MimeType::Gif(_) => 7,
+        MimeType::Svg(_) => 6,
+        MimeType::Html(_) => 5,
+        MimeType::Latex(_) => 4,
+        MimeType::Markdown(_) => 3,
+        MimeType::Json(_) | MimeType::GeoJson(_) => 2,
         MimeType::Plain(_) => 1,
         // All other media types are not supported in Zed at this time
         _ => 0,
@@ -124,6 +136,14 @@ pub enum Output {
         content: Entity<MarkdownView>,
         display_id: Option<String>,
     },
+    Svg {
+        content: Entity<SvgView>,
+        display_id: Option<String>,
+    },
+    Json {
+        content: Entity<JsonView>,
+        display_id: Option<String>,
+    },
     ClearOutputWaitMarker,
 }
 
@@ -213,6 +233,8 @@ impl Output {
             Self::Image { content, .. } => Some(content.clone().into_any_element()),
             Self::Message(message) => Some(div().child(message.clone()).into_any_element()),
             Self::Table { content, .. } => Some(content.clone().into_any_element()),
+            Self::Svg { content, .. } => Some(content.clone().into_any_element()),
+            Self::Json { content, .. } => Some(content.clone().into_any_element()),
             Self::ErrorOutput(error_view) => error_view.render(window, cx),
             Self::ClearOutputWaitMarker => None,
         };
@@ -323,6 +345,12 @@ impl Output {
                 Self::Table { content, .. } => {
                     Self::render_output_controls(content.clone(), workspace, window, cx)
                 }
+                Self::Svg { content, .. } => {
+                    Self::render_output_controls(content.clone(), workspace, window, cx)
+                }
+                Self::Json { content, .. } => {
+                    Self::render_output_controls(content.clone(), workspace, window, cx)
+                }
                 Self::ClearOutputWaitMarker => None,
             })
     }
@@ -336,6 +364,8 @@ impl Output {
             Output::Message(_) => None,
             Output::Table { display_id, .. } => display_id.clone(),
             Output::Markdown { display_id, .. } => display_id.clone(),
+            Output::Svg { display_id, .. } => display_id.clone(),
+            Output::Json { display_id, .. } => display_id.clone(),
             Output::ClearOutputWaitMarker => None,
         }
     }
@@ -358,19 +388,88 @@ impl Output {
                     display_id,
                 }
             }
-            Some(MimeType::Png(data)) | Some(MimeType::Jpeg(data)) => match ImageView::from(data) {
-                Ok(view) => Output::Image {
-                    content: cx.new(|_| view),
+            Some(MimeType::Html(html)) => {
+                let content = cx.new(|cx| MarkdownView::from_html(html.clone(), cx));
+                Output::Markdown {
+                    content,
+                    display_id,
+                }
+            }
+            Some(MimeType::Latex(latex)) => {
+                let content = cx.new(|cx| MarkdownView::from_latex(latex.clone(), cx));
+                Output::Markdown {
+                    content,
                     display_id,
-                },
-                Err(error) => Output::Message(format!("Failed to load image: {}", error)),
+                }
+            }
+            Some(MimeType::Png(data)) | Some(MimeType::Jpeg(data)) | Some(MimeType::Gif(data)) => {
+                match ImageView::from(data) {
+                    Ok(view) => Output::Image {
+                        content: cx.new(|_| view),
+                        display_id,
+                    },
+                    Err(error) => Output::Message(format!("Failed to load image: {}", error)),
+                }
+            }
+            Some(MimeType::Svg(source)) => Output::Svg {
+                content: cx.new(|cx| SvgView::from(source.clone(), window, cx)),
+                display_id,
             },
             Some(MimeType::DataTable(data)) => Output::Table {
                 content: cx.new(|cx| TableView::new(data, window, cx)),
                 display_id,
             },
-            // Any other media types are not supported
-            _ => Output::Message("Unsupported media type".to_string()),
+            Some(MimeType::Json(value)) | Some(MimeType::GeoJson(value)) => Output::Json {
+                content: cx.new(|_| JsonView::new(value.clone(), None)),
+                display_id,
+            },
+            _ => Self::fallback(data, display_id, cx),
+        }
+    }
+
+    /// Shows an output stored in notebook format.
+    pub fn from_notebook(output: &nbformat::v4::Output, window: &mut Window, cx: &mut App) -> Self {
+        match output {
+            nbformat::v4::Output::Stream { text, .. } => Output::Stream {
+                content: cx.new(|cx| TerminalOutput::from(&text.0, window, cx)),
+            },
+            nbformat::v4::Output::DisplayData(display_data) => {
+                Output::new(&display_data.data, None, window, cx)
+            }
+            nbformat::v4::Output::ExecuteResult(execute_result) => {
+                Output::new(&execute_result.data, None, window, cx)
+            }
+            nbformat::v4::Output::Error(error) => Output::ErrorOutput(ErrorView {
+                ename: error.ename.clone(),
+                evalue: error.evalue.clone(),
+                traceback: cx
+                    .new(|cx| TerminalOutput::from(&error.traceback.join("\n"), window, cx)),
+            }),
+        }
+    }
+
+    /// Charts and widgets can't be drawn in Zed yet, so show the spec they were
+    /// built from instead of dropping the output altogether.
+    fn fallback(data: &MimeBundle, display_id: Option<String>, cx: &mut App) -> Self {
+        let spec = data.content.iter().find_map(|mimetype| match mimetype {
+            MimeType::VegaLiteV4(spec) | MimeType::VegaLiteV5(spec) => {
+                Some(("Vega-Lite chart", spec))
+            }
+            MimeType::VegaV5(spec) => Some(("Vega chart", spec)),
+            MimeType::Plotly(spec) => Some(("Plotly chart", spec)),
+            MimeType::WidgetView(spec) => Some(("Jupyter widget", spec)),
+            _ => None,
+        });
+
+        match spec {
+            Some((kind, spec)) => {
+                let title = format!("{kind} (not supported yet, showing its specification)");
+                Output::Json {
+                    content: cx.new(|_| JsonView::new(spec.clone(), Some(title.into()))),
+                    display_id,
+                }
+            }
+            None => Output::Message("Unsupported media type".to_string()),
         }
     }
 }
@@ -387,6 +486,8 @@ pub enum ExecutionStatus {
     Shutdown,
     KernelErrored(String),
     Restarting,
+    /// The outputs were restored from a previous session and may be out of date.
+    Stale,
 }
 
 /// An ExecutionView shows the outputs of an execution.
@@ -396,6 +497,8 @@ pub struct ExecutionView {
     #[allow(unused)]
     workspace: WeakEntity<Workspace>,
     pub outputs: Vec<Output>,
+    /// The outputs in notebook format, with their display ids, so they can be persisted.
+    notebook_outputs: Vec<(Option<String>, nbformat::v4::Output)>,
     pub status: ExecutionStatus,
 }
 
@@ -408,10 +511,39 @@ impl ExecutionView {
         Self {
             workspace,
             outputs: Default::default(),
+            notebook_outputs: Default::default(),
             status,
         }
     }
 
+    /// Shows outputs persisted by a previous session, marked as stale.
+    pub fn restored(
+        notebook_outputs: Vec<nbformat::v4::Output>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            workspace,
+            outputs: notebook_outputs
+                .iter()
+                .map(|output| Output::from_notebook(output, window, cx))
+                .collect(),
+            notebook_outputs: notebook_outputs
+                .into_iter()
+                .map(|output| (None, output))
+                .collect(),
+            status: ExecutionStatus::Stale,
+        }
+    }
+
+    pub fn notebook_outputs(&self) -> Vec<nbformat::v4::Output> {
+        self.notebook_outputs
+            .iter()
+            .map(|(_, output)| output.clone())
+            .collect()
+    }
+
     /// Accept a Jupyter message belonging to this execution
     pub fn push_message(
         &mut self,
@@ -419,6 +551,7 @@ impl ExecutionView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        let notebook_output = notebook_output(message);
         let output: Output = match message {
             JupyterMessageContent::ExecuteResult(result) => Output::new(
                 &result.data,
@@ -437,6 +570,11 @@ impl ExecutionView {
                 if let Some(new_terminal) = self.apply_terminal_text(&result.text, window, cx) {
                     new_terminal
                 } else {
+                    if let Some((_, nbformat::v4::Output::Stream { text, .. })) =
+                        self.notebook_outputs.last_mut()
+                    {
+                        text.0.push_str(&result.text);
+                    }
                     return;
                 }
             }
@@ -455,6 +593,13 @@ impl ExecutionView {
                     if let runtimelib::Payload::Page { data, .. } = payload {
                         let output = Output::new(data, None, window, cx);
                         self.outputs.push(output);
+                        self.notebook_outputs.push((
+                            None,
+                            nbformat::v4::Output::DisplayData(nbformat::v4::DisplayData {
+                                data: data.clone(),
+                                metadata: Default::default(),
+                            }),
+                        ));
                     }
                 }
                 cx.notify();
@@ -463,6 +608,7 @@ impl ExecutionView {
             JupyterMessageContent::ClearOutput(options) => {
                 if !options.wait {
                     self.outputs.clear();
+                    self.notebook_outputs.clear();
                     cx.notify();
                     return;
                 }
@@ -497,10 +643,23 @@ impl ExecutionView {
             && let Output::ClearOutputWaitMarker = output
         {
             self.outputs.clear();
+            self.notebook_outputs.clear();
         }
 
         self.outputs.push(output);
+        self.notebook_outputs.extend(notebook_output);
+
+        cx.notify();
+    }
 
+    /// Show a MIME bundle that didn't come from an execution, such as an inspection result.
+    pub fn push_data(&mut self, data: &MimeBundle, window: &mut Window, cx: &mut Context<Self>) {
+        self.outputs.push(Output::new(data, None, window, cx));
+        cx.notify();
+    }
+
+    pub fn push_text(&mut self, text: impl Into<String>, cx: &mut Context<Self>) {
+        self.outputs.push(Output::Message(text.into()));
         cx.notify();
     }
 
@@ -522,6 +681,17 @@ impl ExecutionView {
             }
         });
 
+        for (other_display_id, notebook_output) in &mut self.notebook_outputs {
+            if other_display_id.as_deref() != Some(display_id) {
+                continue;
+            }
+            match notebook_output {
+                nbformat::v4::Output::DisplayData(display_data) => display_data.data = data.clone(),
+                nbformat::v4::Output::ExecuteResult(result) => result.data = data.clone(),
+                _ => {}
+            }
+        }
+
         if any {
             cx.notify();
         }
@@ -553,6 +723,49 @@ impl ExecutionView {
     }
 }
 
+/// The notebook format of a message that produces an output, along with its display id.
+fn notebook_output(
+    message: &JupyterMessageContent,
+) -> Option<(Option<String>, nbformat::v4::Output)> {
+    match message {
+        JupyterMessageContent::ExecuteResult(result) => Some((
+            result.transient.as_ref().and_then(|t| t.display_id.clone()),
+            nbformat::v4::Output::ExecuteResult(nbformat::v4::ExecuteResult {
+                execution_count: result.execution_count,
+                data: result.data.clone(),
+                metadata: result.metadata.clone(),
+            }),
+        )),
+        JupyterMessageContent::DisplayData(result) => Some((
+            result.transient.as_ref().and_then(|t| t.display_id.clone()),
+            nbformat::v4::Output::DisplayData(nbformat::v4::DisplayData {
+                data: result.data.clone(),
+                metadata: result.metadata.clone(),
+            }),
+        )),
+        JupyterMessageContent::StreamContent(result) => Some((
+            None,
+            nbformat::v4::Output::Stream {
+                name: match result.name {
+                    Stdio::Stdout => "stdout",
+                    Stdio::Stderr => "stderr",
+                }
+                .to_string(),
+                text: nbformat::v4::MultilineString(result.text.clone()),
+            },
+        )),
+        JupyterMessageContent::ErrorOutput(result) => Some((
+            None,
+            nbformat::v4::Output::Error(nbformat::v4::ErrorOutput {
+                ename: result.ename.clone(),
+                evalue: result.evalue.clone(),
+                traceback: result.traceback.clone(),
+            }),
+        )),
+        _ => None,
+    }
+}
+
 impl Render for ExecutionView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let status = match &self.status {
@@ -590,6 +803,10 @@ impl Render for ExecutionView {
             ExecutionStatus::KernelErrored(error) => Label::new(format!("Kernel error: {}", error))
                 .color(Color::Error)
                 .into_any_element(),
+            ExecutionStatus::Stale => Label::new("Output from a previous session")
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element(),
         };
 
         if self.outputs.is_empty() {
@@ -610,8 +827,82 @@ impl Render for ExecutionView {
             .children(match self.status {
                 ExecutionStatus::Executing => vec![status],
                 ExecutionStatus::Queued => vec![status],
+                ExecutionStatus::Stale => vec![status],
                 _ => vec![],
             })
             .into_any_element()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::App;
+    use serde_json::json;
+
+    use super::*;
+
+    fn mime_bundle(data: serde_json::Value) -> MimeBundle {
+        serde_json::from_value(data).unwrap()
+    }
+
+    #[test]
+    fn test_rank_mime_type() {
+        let richest = |data| {
+            mime_bundle(data)
+                .richest(rank_mime_type)
+                .cloned()
+                .expect("a media type should be picked")
+        };
+        let plain = "<IPython.core.display.HTML object>";
+
+        assert!(matches!(
+            richest(json!({"text/plain": plain, "text/html": "<b>1</b>"})),
+            MimeType::Html(_)
+        ));
+        assert!(matches!(
+            richest(json!({
+                "text/plain": plain,
+                "text/html": "<svg></svg>",
+                "image/svg+xml": "<svg></svg>",
+            })),
+            MimeType::Svg(_)
+        ));
+        assert!(matches!(
+            richest(json!({
+                "text/plain": plain,
+                "text/markdown": "$x^2$",
+                "text/latex": "$x^2$",
+            })),
+            MimeType::Latex(_)
+        ));
+        assert!(matches!(
+            richest(json!({"text/latex": "$x^2$", "text/html": "<i>x</i>"})),
+            MimeType::Html(_)
+        ));
+        assert!(matches!(
+            richest(json!({"text/plain": plain, "application/json": {"a": 1}})),
+            MimeType::Json(_)
+        ));
+        assert!(matches!(
+            richest(json!({"text/plain": plain, "application/x-unknown": "data"})),
+            MimeType::Plain(_)
+        ));
+    }
+
+    #[gpui::test]
+    fn test_unsupported_outputs_fall_back(cx: &mut App) {
+        let chart = mime_bundle(json!({
+            "application/vnd.vegalite.v5+json": {"mark": "bar"},
+        }));
+        assert!(
+            matches!(Output::fallback(&chart, None, cx), Output::Json { .. }),
+            "a chart should be shown as its specification"
+        );
+
+        let unknown = mime_bundle(json!({"application/x-unknown": "data"}));
+        match Output::fallback(&unknown, None, cx) {
+            Output::Message(message) => assert_eq!(message, "Unsupported media type"),
+            _ => panic!("an unknown media type should be reported as unsupported"),
+        }
+    }
+}
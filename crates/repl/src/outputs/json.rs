@@ -0,0 +1,224 @@
+use collections::HashSet;
+use gpui::{AnyElement, App, ClipboardItem, Entity, SharedString, Window};
+use language::Buffer;
+use serde_json::Value;
+use ui::{Disclosure, IntoElement, Styled, div, prelude::*, v_flex};
+
+use crate::outputs::OutputContent;
+
+/// The number of entries of an object or array shown before the rest are elided.
+const MAX_ENTRIES: usize = 200;
+
+/// JsonView renders JSON output as a tree whose objects and arrays can be collapsed.
+pub struct JsonView {
+    value: Value,
+    title: Option<SharedString>,
+    /// Paths of the expanded objects and arrays, as JSON pointers.
+    expanded: HashSet<String>,
+}
+
+impl JsonView {
+    pub fn new(value: Value, title: Option<SharedString>) -> Self {
+        // Some kernels send JSON serialized into a string, which is shown as is if it doesn't parse.
+        let value = match value {
+            Value::String(text) => match serde_json::from_str(&text) {
+                Ok(parsed @ (Value::Object(_) | Value::Array(_))) => parsed,
+                _ => Value::String(text),
+            },
+            value => value,
+        };
+        let mut expanded = HashSet::default();
+        expanded.insert(String::new());
+        Self {
+            value,
+            title,
+            expanded,
+        }
+    }
+
+    fn pretty_text(&self) -> String {
+        serde_json::to_string_pretty(&self.value).unwrap_or_else(|_| self.value.to_string())
+    }
+
+    fn render_value(
+        &self,
+        key: Option<String>,
+        value: &Value,
+        path: String,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let key_label = key.map(|key| Label::new(format!("{key}:")).color(Color::Muted));
+
+        let entries: Vec<(String, &Value)> = match value {
+            Value::Object(object) => object
+                .iter()
+                .map(|(key, value)| (key.clone(), value))
+                .collect(),
+            Value::Array(array) => array
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (index.to_string(), value))
+                .collect(),
+            leaf => {
+                let (text, color) = match leaf {
+                    Value::String(string) => (format!("{string:?}"), Color::Created),
+                    Value::Null => ("null".to_string(), Color::Muted),
+                    other => (other.to_string(), Color::Accent),
+                };
+                return h_flex()
+                    .gap_1()
+                    .pl_5()
+                    .children(key_label)
+                    .child(Label::new(text).color(color))
+                    .into_any_element();
+            }
+        };
+
+        let is_expanded = self.expanded.contains(&path);
+        let summary = match value {
+            Value::Object(_) => format!("{{{} keys}}", entries.len()),
+            _ => format!("[{} items]", entries.len()),
+        };
+        let elided = entries.len().saturating_sub(MAX_ENTRIES);
+
+        v_flex()
+            .child(
+                h_flex()
+                    .id(SharedString::from(format!("json-output-{path}")))
+                    .gap_1()
+                    .cursor_pointer()
+                    .child(
+                        Disclosure::new(
+                            SharedString::from(format!("json-output-toggle-{path}")),
+                            is_expanded,
+                        )
+                        .on_click(cx.listener({
+                            let path = path.clone();
+                            move |this, _, _, cx| this.toggle(&path, cx)
+                        })),
+                    )
+                    .children(key_label)
+                    .child(Label::new(summary).color(Color::Muted))
+                    .on_click(cx.listener({
+                        let path = path.clone();
+                        move |this, _, _, cx| this.toggle(&path, cx)
+                    })),
+            )
+            .when(is_expanded, |this| {
+                this.child(
+                    v_flex()
+                        .pl_4()
+                        .children(entries.into_iter().take(MAX_ENTRIES).map(|(key, value)| {
+                            let path = format!("{path}/{}", escape_pointer_token(&key));
+                            self.render_value(Some(key), value, path, cx)
+                        }))
+                        .when(elided > 0, |this| {
+                            this.child(
+                                div().pl_5().child(
+                                    Label::new(format!("… {elided} more")).color(Color::Muted),
+                                ),
+                            )
+                        }),
+                )
+            })
+            .into_any_element()
+    }
+
+    fn toggle(&mut self, path: &str, cx: &mut Context<Self>) {
+        if !self.expanded.remove(path) {
+            self.expanded.insert(path.to_string());
+        }
+        cx.notify();
+    }
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+impl Render for JsonView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let value = self.value.clone();
+        v_flex()
+            .py_1()
+            .font_buffer(cx)
+            .text_buffer(cx)
+            .children(
+                self.title
+                    .clone()
+                    .map(|title| Label::new(title).color(Color::Muted)),
+            )
+            .child(self.render_value(None, &value, String::new(), cx))
+    }
+}
+
+impl OutputContent for JsonView {
+    fn clipboard_content(&self, _window: &Window, _cx: &App) -> Option<ClipboardItem> {
+        Some(ClipboardItem::new_string(self.pretty_text()))
+    }
+
+    fn has_clipboard_content(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+
+    fn has_buffer_content(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+
+    fn buffer_content(&mut self, _: &mut Window, cx: &mut App) -> Option<Entity<Buffer>> {
+        let buffer = cx.new(|cx| {
+            let mut buffer = Buffer::local(self.pretty_text(), cx)
+                .with_language(language::PLAIN_TEXT.clone(), cx);
+            buffer.set_capability(language::Capability::ReadOnly, cx);
+            buffer
+        });
+        Some(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::AppContext as _;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_json_serialized_into_a_string() {
+        let view = JsonView::new(json!(r#"{"a": [1, 2]}"#), None);
+        assert_eq!(view.value, json!({"a": [1, 2]}));
+        assert_eq!(view.pretty_text(), "{\n  \"a\": [\n    1,\n    2\n  ]\n}");
+
+        let malformed = r#"{"a": [1, 2"#;
+        let view = JsonView::new(json!(malformed), None);
+        assert_eq!(
+            view.value,
+            json!(malformed),
+            "malformed JSON should be shown as is"
+        );
+
+        let view = JsonView::new(json!("42"), None);
+        assert_eq!(
+            view.value,
+            json!("42"),
+            "only objects and arrays should be parsed"
+        );
+    }
+
+    #[gpui::test]
+    fn test_toggling_entries(cx: &mut App) {
+        let view = cx.new(|_| JsonView::new(json!({"a/b": {"c~d": 1}}), None));
+        let path = format!("/{}", escape_pointer_token("a/b"));
+        assert_eq!(path, "/a~1b");
+        assert_eq!(escape_pointer_token("c~d"), "c~0d");
+
+        view.update(cx, |view, cx| {
+            assert!(view.expanded.contains(""), "the root should start expanded");
+            view.toggle(&path, cx);
+            assert!(view.expanded.contains(&path));
+            view.toggle("", cx);
+            view.toggle(&path, cx);
+            assert!(view.expanded.is_empty());
+        });
+    }
+}
@@ -1,7 +1,10 @@
+use std::{cell::RefCell, rc::Rc};
+
 use anyhow::Result;
 use gpui::{
     App, ClipboardItem, Context, Entity, RetainAllImageCache, Task, Window, div, prelude::*,
 };
+use html_to_markdown::{TagHandler, convert_html_to_markdown, markdown};
 use language::Buffer;
 use markdown_preview::{
     markdown_elements::ParsedMarkdown, markdown_parser::parse_markdown,
@@ -24,6 +27,31 @@ impl MarkdownView {
             let text = text.clone();
             cx.background_spawn(async move { parse_markdown(&text.clone(), None, None).await })
         };
+        Self::parsing(text, parsed, cx)
+    }
+
+    /// Renders HTML converted to Markdown, copying the original HTML.
+    pub fn from_html(html: String, cx: &mut Context<Self>) -> Self {
+        let parsed = {
+            let html = html.clone();
+            cx.background_spawn(async move {
+                let markdown = html_to_markdown(&html);
+                parse_markdown(&markdown, None, None).await
+            })
+        };
+        Self::parsing(html, parsed, cx)
+    }
+
+    /// Renders LaTeX as its source, since there is no TeX renderer.
+    pub fn from_latex(latex: String, cx: &mut Context<Self>) -> Self {
+        let parsed = {
+            let markdown = format!("```latex\n{}\n```", latex.trim());
+            cx.background_spawn(async move { parse_markdown(&markdown, None, None).await })
+        };
+        Self::parsing(latex, parsed, cx)
+    }
+
+    fn parsing(raw_text: String, parsed: Task<ParsedMarkdown>, cx: &mut Context<Self>) -> Self {
         let task = cx.spawn(async move |markdown_view, cx| {
             let content = parsed.await;
 
@@ -35,7 +63,7 @@ impl MarkdownView {
         });
 
         Self {
-            raw_text: text,
+            raw_text,
             image_cache: RetainAllImageCache::new(cx),
             contents: None,
             parsing_markdown_task: Some(task),
@@ -43,6 +71,25 @@ impl MarkdownView {
     }
 }
 
+fn html_to_markdown(html: &str) -> String {
+    let mut handlers: Vec<TagHandler> = vec![
+        Rc::new(RefCell::new(markdown::WebpageChromeRemover)),
+        Rc::new(RefCell::new(markdown::ParagraphHandler)),
+        Rc::new(RefCell::new(markdown::HeadingHandler)),
+        Rc::new(RefCell::new(markdown::ListHandler)),
+        Rc::new(RefCell::new(markdown::TableHandler::new())),
+        Rc::new(RefCell::new(markdown::StyledTextHandler)),
+        Rc::new(RefCell::new(markdown::CodeHandler)),
+    ];
+    match convert_html_to_markdown(html.as_bytes(), &mut handlers) {
+        Ok(markdown) => markdown,
+        Err(error) => {
+            log::warn!("failed to convert HTML output to Markdown: {error:#}");
+            format!("```html\n{}\n```", html.trim())
+        }
+    }
+}
+
 impl OutputContent for MarkdownView {
     fn clipboard_content(&self, _window: &Window, _cx: &App) -> Option<ClipboardItem> {
         Some(ClipboardItem::new_string(self.raw_text.clone()))
@@ -91,3 +138,34 @@ impl Render for MarkdownView {
             .into_any_element()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_markdown() {
+        let markdown = html_to_markdown(
+            "<h1>Results</h1><p>Some <strong>bold</strong> text</p><ul><li>first</li></ul>\
+             <table><thead><tr><th>a</th><th>b</th></tr></thead>\
+             <tbody><tr><td>1</td><td>2</td></tr></tbody></table>",
+        );
+        for expected in [
+            "# Results",
+            "Some **bold** text",
+            "- first",
+            "| a | b |",
+            "| --- | --- |",
+            "| 1 | 2 |",
+        ] {
+            assert!(
+                markdown.contains(expected),
+                "{expected:?} is missing from {markdown:?}"
+            );
+        }
+        assert!(
+            !markdown.contains('<'),
+            "no tags should be left in {markdown:?}"
+        );
+    }
+}
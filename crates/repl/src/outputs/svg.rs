@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use gpui::{App, ClipboardItem, RenderImage, SharedString, Task, Window, img};
+use ui::{IntoElement, Styled, div, prelude::*};
+
+use crate::outputs::OutputContent;
+
+/// SvgView renders an SVG output with the same renderer as the SVG preview.
+pub struct SvgView {
+    source: String,
+    image: Option<Result<Arc<RenderImage>, SharedString>>,
+    _render_task: Task<()>,
+}
+
+impl SvgView {
+    pub fn from(source: String, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        const SCALE_FACTOR: f32 = 1.0;
+
+        let renderer = cx.svg_renderer();
+        let bytes = source.clone().into_bytes();
+        let background_task =
+            cx.background_spawn(
+                async move { renderer.render_single_frame(&bytes, SCALE_FACTOR, true) },
+            );
+
+        let render_task = cx.spawn_in(window, async move |this, cx| {
+            let result = background_task.await;
+
+            this.update(cx, |view, cx| {
+                view.image = Some(result.map_err(|e| e.to_string().into()));
+                cx.notify();
+            })
+            .ok();
+        });
+
+        Self {
+            source,
+            image: None,
+            _render_task: render_task,
+        }
+    }
+}
+
+impl Render for SvgView {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        match self.image.clone() {
+            Some(Ok(image)) => {
+                let size = image.size(0);
+                div()
+                    .w(px(size.width.0 as f32))
+                    .h(px(size.height.0 as f32))
+                    .max_w_full()
+                    .child(img(image).size_full())
+                    .into_any_element()
+            }
+            Some(Err(error)) => Label::new(format!("Failed to render SVG: {error}"))
+                .color(Color::Error)
+                .into_any_element(),
+            None => div().into_any_element(),
+        }
+    }
+}
+
+impl OutputContent for SvgView {
+    fn clipboard_content(&self, _window: &Window, _cx: &App) -> Option<ClipboardItem> {
+        Some(ClipboardItem::new_string(self.source.clone()))
+    }
+
+    fn has_clipboard_content(&self, _window: &Window, _cx: &App) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::TestAppContext;
+    use settings::SettingsStore;
+
+    use super::*;
+
+    fn render_svg(source: &str, cx: &mut TestAppContext) -> Result<Arc<RenderImage>, SharedString> {
+        let (view, cx) =
+            cx.add_window_view(|window, cx| SvgView::from(source.to_string(), window, cx));
+        cx.run_until_parked();
+        view.read_with(cx, |view, _| view.image.clone())
+            .expect("the SVG should be rendered")
+    }
+
+    #[gpui::test]
+    async fn test_rendering_svg(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+
+        let image = render_svg(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="20"><rect width="10" height="20"/></svg>"#,
+            cx,
+        );
+        assert!(image.is_ok(), "a valid SVG should render");
+
+        let error = render_svg("<svg><rect", cx);
+        assert!(error.is_err(), "a malformed SVG should fail to render");
+    }
+}
@@ -0,0 +1,161 @@
+use std::{ops::Range, path::Path, sync::Arc};
+
+use anyhow::Result;
+use db::{
+    sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
+    sqlez_macros::sql,
+};
+use language::Point;
+use util::ResultExt as _;
+
+/// The outputs of a code range that was run in a REPL session.
+#[derive(Clone)]
+pub(crate) struct SerializedOutputBlock {
+    pub(crate) code_range: Range<Point>,
+    /// The code that produced the outputs, to tell whether the file changed since.
+    pub(crate) code: String,
+    pub(crate) outputs: Vec<nbformat::v4::Output>,
+}
+
+pub struct ReplDb(ThreadSafeConnection);
+
+impl Domain for ReplDb {
+    const NAME: &str = stringify!(ReplDb);
+
+    const MIGRATIONS: &[&str] = &[sql!(
+        CREATE TABLE repl_outputs (
+            path BLOB NOT NULL,
+            start_row INTEGER NOT NULL,
+            start_column INTEGER NOT NULL,
+            end_row INTEGER NOT NULL,
+            end_column INTEGER NOT NULL,
+            code TEXT NOT NULL,
+            outputs TEXT NOT NULL,
+            PRIMARY KEY(path, start_row, start_column)
+        ) STRICT;
+    )];
+}
+
+db::static_connection!(REPL_DB, ReplDb, []);
+
+impl ReplDb {
+    /// Replaces the outputs stored for the file at `path`.
+    pub(crate) async fn save_outputs(
+        &self,
+        path: Arc<Path>,
+        blocks: Vec<SerializedOutputBlock>,
+    ) -> Result<()> {
+        log::debug!("Saving {} REPL outputs for {path:?}", blocks.len());
+        self.write(move |conn| {
+            conn.with_savepoint("save_repl_outputs", || {
+                conn.exec_bound(sql!(
+                    DELETE FROM repl_outputs WHERE path = ?
+                ))?(path.clone())?;
+
+                let mut insert = conn.exec_bound(sql!(
+                    INSERT OR REPLACE INTO repl_outputs
+                        (path, start_row, start_column, end_row, end_column, code, outputs)
+                    VALUES
+                        (?, ?, ?, ?, ?, ?, ?)
+                ))?;
+                for block in blocks {
+                    let outputs = serde_json::to_string(&block.outputs)?;
+                    insert((
+                        path.clone(),
+                        block.code_range.start.row,
+                        block.code_range.start.column,
+                        block.code_range.end.row,
+                        block.code_range.end.column,
+                        block.code,
+                        outputs,
+                    ))?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    pub(crate) fn get_outputs(&self, path: Arc<Path>) -> Result<Vec<SerializedOutputBlock>> {
+        let rows: Vec<(u32, u32, u32, u32, String, String)> = self.select_bound(sql!(
+            SELECT start_row, start_column, end_row, end_column, code, outputs
+            FROM repl_outputs
+            WHERE path = ?
+            ORDER BY start_row, start_column
+        ))?(path)?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(
+                |(start_row, start_column, end_row, end_column, code, outputs)| {
+                    Some(SerializedOutputBlock {
+                        code_range: Point::new(start_row, start_column)
+                            ..Point::new(end_row, end_column),
+                        code,
+                        outputs: serde_json::from_str(&outputs).log_err()?,
+                    })
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summarize(
+        blocks: Vec<SerializedOutputBlock>,
+    ) -> Vec<(Range<Point>, String, serde_json::Value)> {
+        blocks
+            .into_iter()
+            .map(|block| {
+                let outputs = serde_json::to_value(&block.outputs).unwrap();
+                (block.code_range, block.code, outputs)
+            })
+            .collect()
+    }
+
+    #[gpui::test]
+    async fn test_save_and_get_outputs() {
+        let db = ReplDb::open_test_db("test_save_and_get_outputs").await;
+        let path: Arc<Path> = Path::new("/project/main.py").into();
+
+        let blocks = vec![
+            SerializedOutputBlock {
+                code_range: Point::new(0, 0)..Point::new(0, 9),
+                code: "print(42)".into(),
+                outputs: vec![nbformat::v4::Output::Stream {
+                    name: "stdout".into(),
+                    text: nbformat::v4::MultilineString("42\n".into()),
+                }],
+            },
+            SerializedOutputBlock {
+                code_range: Point::new(2, 0)..Point::new(2, 5),
+                code: "1 / 0".into(),
+                outputs: vec![nbformat::v4::Output::Error(nbformat::v4::ErrorOutput {
+                    ename: "ZeroDivisionError".into(),
+                    evalue: "division by zero".into(),
+                    traceback: Vec::new(),
+                })],
+            },
+        ];
+        db.save_outputs(path.clone(), blocks.clone()).await.unwrap();
+        assert_eq!(
+            summarize(db.get_outputs(path.clone()).unwrap()),
+            summarize(blocks.clone())
+        );
+
+        // Saving again replaces every block of the file
+        db.save_outputs(path.clone(), blocks[1..].to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            summarize(db.get_outputs(path.clone()).unwrap()),
+            summarize(blocks[1..].to_vec())
+        );
+
+        db.save_outputs(path.clone(), Vec::new()).await.unwrap();
+        assert!(db.get_outputs(path).unwrap().is_empty());
+    }
+}
@@ -3,11 +3,13 @@ mod jupyter_settings;
 pub mod kernels;
 pub mod notebook;
 mod outputs;
+mod persistence;
 mod repl_editor;
 mod repl_sessions_ui;
 mod repl_settings;
 mod repl_store;
 mod session;
+mod variable_explorer;
 
 use std::{sync::Arc, time::Duration};
 
@@ -20,11 +22,12 @@ pub use crate::jupyter_settings::JupyterSettings;
 pub use crate::kernels::{Kernel, KernelSpecification, KernelStatus};
 pub use crate::repl_editor::*;
 pub use crate::repl_sessions_ui::{
-    ClearOutputs, Interrupt, ReplSessionsPage, Restart, Run, Sessions, Shutdown,
+    ClearOutputs, Interrupt, ReplSessionsPage, Restart, Run, Sessions, Shutdown, Variables,
 };
 pub use crate::repl_settings::ReplSettings;
 use crate::repl_store::ReplStore;
 pub use crate::session::Session;
+pub use crate::variable_explorer::{KernelVariable, VariableExplorer};
 
 pub const KERNEL_DOCS_URL: &str = "https://zed.dev/docs/repl#changing-kernels";
 
@@ -11,6 +11,7 @@ use workspace::{Workspace, item::Item};
 
 use crate::jupyter_settings::JupyterSettings;
 use crate::repl_store::ReplStore;
+use crate::variable_explorer::VariableExplorer;
 
 actions!(
     repl,
@@ -23,6 +24,8 @@ actions!(
         ClearOutputs,
         /// Opens the REPL sessions panel.
         Sessions,
+        /// Opens the variables of the active REPL session.
+        Variables,
         /// Interrupts the currently running kernel.
         Interrupt,
         /// Shuts down the current kernel.
@@ -58,6 +61,27 @@ pub fn init(cx: &mut App) {
                 }
             });
 
+            workspace.register_action(|workspace, _: &Variables, window, cx| {
+                let existing = workspace
+                    .active_pane()
+                    .read(cx)
+                    .items()
+                    .find_map(|item| item.downcast::<VariableExplorer>());
+
+                if let Some(existing) = existing {
+                    workspace.activate_item(&existing, true, true, window, cx);
+                } else {
+                    let variable_explorer = VariableExplorer::new(window, cx);
+                    workspace.add_item_to_active_pane(
+                        Box::new(variable_explorer),
+                        None,
+                        true,
+                        window,
+                        cx,
+                    )
+                }
+            });
+
             workspace.register_action(|_workspace, _: &RefreshKernelspecs, _, cx| {
                 let store = ReplStore::global(cx);
                 store.update(cx, |store, cx| {
@@ -101,6 +125,10 @@ pub fn init(cx: &mut App) {
 
                 let editor_handle = cx.entity().downgrade();
 
+                if JupyterSettings::enabled(cx) {
+                    crate::session::restore_outputs(editor_handle.clone(), window, cx);
+                }
+
                 if let Some(language) = language
                     && language.name() == "Python".into()
                     && let (Some(project_path), Some(project)) = (project_path, project)
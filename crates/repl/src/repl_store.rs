@@ -12,6 +12,7 @@ use settings::{Settings, SettingsStore};
 use crate::kernels::{
     list_remote_kernelspecs, local_kernel_specifications, python_env_kernel_specifications,
 };
+use crate::session::EditorBlock;
 use crate::{JupyterSettings, KernelSpecification, Session};
 
 struct GlobalReplStore(Entity<ReplStore>);
@@ -22,6 +23,8 @@ pub struct ReplStore {
     fs: Arc<dyn Fs>,
     enabled: bool,
     sessions: HashMap<EntityId, Entity<Session>>,
+    /// Outputs restored from a previous session, by editor, until a session adopts them.
+    restored_blocks: HashMap<EntityId, HashMap<String, EditorBlock>>,
     kernel_specifications: Vec<KernelSpecification>,
     selected_kernel_for_worktree: HashMap<WorktreeId, KernelSpecification>,
     kernel_specifications_for_worktree: HashMap<WorktreeId, Vec<KernelSpecification>>,
@@ -54,6 +57,7 @@ impl ReplStore {
             fs,
             enabled: JupyterSettings::enabled(cx),
             sessions: HashMap::default(),
+            restored_blocks: HashMap::default(),
             kernel_specifications: Vec::new(),
             _subscriptions: subscriptions,
             kernel_specifications_for_worktree: HashMap::default(),
@@ -272,12 +276,50 @@ impl ReplStore {
         self.sessions.get(&entity_id)
     }
 
-    pub fn insert_session(&mut self, entity_id: EntityId, session: Entity<Session>) {
+    pub fn insert_session(
+        &mut self,
+        entity_id: EntityId,
+        session: Entity<Session>,
+        cx: &mut Context<Self>,
+    ) {
         self.sessions.insert(entity_id, session);
+        cx.notify();
     }
 
-    pub fn remove_session(&mut self, entity_id: EntityId) {
+    pub fn remove_session(&mut self, entity_id: EntityId, cx: &mut Context<Self>) {
         self.sessions.remove(&entity_id);
+        cx.notify();
+    }
+
+    pub(crate) fn insert_restored_blocks(
+        &mut self,
+        entity_id: EntityId,
+        blocks: HashMap<String, EditorBlock>,
+    ) {
+        self.restored_blocks.insert(entity_id, blocks);
+    }
+
+    pub(crate) fn take_restored_blocks(
+        &mut self,
+        entity_id: EntityId,
+    ) -> HashMap<String, EditorBlock> {
+        self.restored_blocks.remove(&entity_id).unwrap_or_default()
+    }
+
+    pub(crate) fn remove_restored_block(&mut self, entity_id: EntityId, key: &str) {
+        if let Some(blocks) = self.restored_blocks.get_mut(&entity_id) {
+            blocks.remove(key);
+        }
+    }
+
+    pub(crate) fn restored_blocks(
+        &self,
+        entity_id: EntityId,
+    ) -> impl Iterator<Item = &EditorBlock> {
+        self.restored_blocks
+            .get(&entity_id)
+            .into_iter()
+            .flat_map(|blocks| blocks.values())
     }
 
     #[cfg(test)]
@@ -1,9 +1,12 @@
 use crate::components::KernelListItem;
 use crate::kernels::RemoteRunningKernel;
+use crate::persistence::{REPL_DB, SerializedOutputBlock};
+use crate::repl_store::ReplStore;
 use crate::setup_editor_session_actions;
+use crate::variable_explorer::{self, KernelVariable};
 use crate::{
     KernelStatus,
-    kernels::{Kernel, KernelSpecification, NativeRunningKernel},
+    kernels::{Kernel, KernelSession, KernelSpecification, NativeRunningKernel},
     outputs::{ExecutionStatus, ExecutionView},
 };
 use anyhow::Context as _;
@@ -21,16 +24,18 @@ use futures::FutureExt as _;
 use gpui::{
     Context, Entity, EventEmitter, Render, Subscription, Task, WeakEntity, Window, div, prelude::*,
 };
+use jupyter_protocol::Stdio;
 use language::Point;
 use project::Fs;
 use runtimelib::{
-    ExecuteRequest, ExecutionState, InterruptRequest, JupyterMessage, JupyterMessageContent,
-    ShutdownRequest,
+    ExecuteRequest, ExecutionState, InspectRequest, InterruptRequest, JupyterMessage,
+    JupyterMessageContent, ShutdownRequest,
 };
-use std::{env::temp_dir, ops::Range, sync::Arc, time::Duration};
+use std::{env::temp_dir, ops::Range, path::Path, sync::Arc, time::Duration};
 use theme::ActiveTheme;
 use ui::{IconButtonShape, Tooltip, prelude::*};
 use util::ResultExt as _;
+use workspace::Workspace;
 
 pub struct Session {
     fs: Arc<dyn Fs>,
@@ -38,10 +43,21 @@ pub struct Session {
     pub kernel: Kernel,
     blocks: HashMap<String, EditorBlock>,
     pub kernel_specification: KernelSpecification,
+    variables: Vec<KernelVariable>,
+    watching_variables: bool,
+    variables_request: Option<VariablesRequest>,
+    inspection: Option<(SharedString, Entity<ExecutionView>)>,
+    inspect_request_id: Option<String>,
     _buffer_subscription: Subscription,
 }
 
-struct EditorBlock {
+/// A run of the variables helper, whose standard output is collected until the kernel is idle.
+struct VariablesRequest {
+    msg_id: String,
+    stdout: String,
+}
+
+pub(crate) struct EditorBlock {
     code_range: Range<Anchor>,
     invalidation_anchor: Anchor,
     block_id: CustomBlockId,
@@ -55,14 +71,14 @@ impl EditorBlock {
     fn new(
         editor: WeakEntity<Editor>,
         code_range: Range<Anchor>,
-        status: ExecutionStatus,
         on_close: CloseBlockFn,
-        cx: &mut Context<Session>,
+        cx: &mut App,
+        execution_view: impl FnOnce(WeakEntity<Workspace>, &mut App) -> Entity<ExecutionView>,
     ) -> anyhow::Result<Self> {
         let editor = editor.upgrade().context("editor is not open")?;
         let workspace = editor.read(cx).workspace().context("workspace dropped")?;
 
-        let execution_view = cx.new(|cx| ExecutionView::new(status, workspace.downgrade(), cx));
+        let execution_view = execution_view(workspace.downgrade(), cx);
 
         let (block_id, invalidation_anchor) = editor.update(cx, |editor, cx| {
             let buffer = editor.buffer().clone();
@@ -215,12 +231,22 @@ impl Session {
             })
             .ok();
 
+        // Outputs restored when the editor was opened now belong to this session
+        let blocks = ReplStore::global(cx).update(cx, |store, _| {
+            store.take_restored_blocks(editor.entity_id())
+        });
+
         let mut session = Self {
             fs,
             editor,
             kernel: Kernel::StartingKernel(Task::ready(()).shared()),
-            blocks: HashMap::default(),
+            blocks,
             kernel_specification,
+            variables: Vec::new(),
+            watching_variables: false,
+            variables_request: None,
+            inspection: None,
+            inspect_request_id: None,
             _buffer_subscription: subscription,
         };
 
@@ -237,6 +263,12 @@ impl Session {
             .and_then(|editor| editor.read(cx).working_directory(cx))
             .unwrap_or_else(temp_dir);
 
+        // A new kernel starts with an empty namespace
+        self.variables.clear();
+        self.variables_request = None;
+        self.inspection = None;
+        self.inspect_request_id = None;
+
         telemetry::event!(
             "Kernel Status Changed",
             kernel_language,
@@ -291,26 +323,6 @@ impl Session {
         cx.notify();
     }
 
-    pub fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
-        self.kernel(Kernel::ErroredLaunch(error_message.clone()), cx);
-
-        self.blocks.values().for_each(|block| {
-            block.execution_view.update(cx, |execution_view, cx| {
-                match execution_view.status {
-                    ExecutionStatus::Finished => {
-                        // Do nothing when the output was good
-                    }
-                    _ => {
-                        // All other cases, set the status to errored
-                        execution_view.status =
-                            ExecutionStatus::KernelErrored(error_message.clone())
-                    }
-                }
-                cx.notify();
-            });
-        });
-    }
-
     fn on_buffer_event(
         &mut self,
         buffer: Entity<MultiBuffer>,
@@ -337,6 +349,7 @@ impl Session {
                         editor.remove_blocks(blocks_to_remove, None, cx);
                     })
                     .ok();
+                self.save_outputs(cx);
                 cx.notify();
             }
         }
@@ -361,6 +374,21 @@ impl Session {
             .ok();
 
         self.blocks.clear();
+        self.save_outputs(cx);
+    }
+
+    /// Persists the outputs of every block, so they can be restored when the file is reopened.
+    fn save_outputs(&self, cx: &mut Context<Self>) {
+        let Some(editor) = self.editor.upgrade() else {
+            return;
+        };
+        let Some(path) = editor_abs_path(&editor, cx) else {
+            return;
+        };
+
+        let blocks = serialize_blocks(self.blocks.values(), &editor, cx);
+        cx.background_spawn(async move { REPL_DB.save_outputs(path, blocks).await })
+            .detach_and_log_err(cx);
     }
 
     pub fn execute(
@@ -424,6 +452,7 @@ impl Session {
                 if let Some(session) = session_view.upgrade() {
                     session.update(cx, |session, cx| {
                         session.blocks.remove(&parent_message_id);
+                        session.save_outputs(cx);
                         cx.notify();
                     });
                 }
@@ -438,9 +467,13 @@ impl Session {
             },
         );
 
-        let Ok(editor_block) =
-            EditorBlock::new(self.editor.clone(), anchor_range, status, on_close, cx)
-        else {
+        let Ok(editor_block) = EditorBlock::new(
+            self.editor.clone(),
+            anchor_range,
+            on_close,
+            cx,
+            |workspace, cx| cx.new(|cx| ExecutionView::new(status, workspace, cx)),
+        ) else {
             return;
         };
 
@@ -487,58 +520,116 @@ impl Session {
         }
     }
 
-    pub fn route(&mut self, message: &JupyterMessage, window: &mut Window, cx: &mut Context<Self>) {
-        let parent_message_id = match message.parent_header.as_ref() {
-            Some(header) => &header.msg_id,
-            None => return,
-        };
+    pub fn interrupt(&mut self, cx: &mut Context<Self>) {
+        match &mut self.kernel {
+            Kernel::RunningKernel(_kernel) => {
+                self.send(InterruptRequest {}.into(), cx).ok();
+            }
+            Kernel::StartingKernel(_task) => {
+                // NOTE: If we switch to a literal queue instead of chaining on to the task, clear all queued executions
+            }
+            _ => {}
+        }
+    }
 
-        match &message.content {
-            JupyterMessageContent::Status(status) => {
-                self.kernel.set_execution_state(&status.execution_state);
+    pub fn variables(&self) -> &[KernelVariable] {
+        &self.variables
+    }
 
-                telemetry::event!(
-                    "Kernel Status Changed",
-                    kernel_language = self.kernel_specification.language(),
-                    kernel_status = KernelStatus::from(&self.kernel).to_string(),
-                    repl_session_id = cx.entity_id().to_string(),
-                );
+    pub fn inspection(&self) -> Option<&(SharedString, Entity<ExecutionView>)> {
+        self.inspection.as_ref()
+    }
 
-                cx.notify();
-            }
-            JupyterMessageContent::KernelInfoReply(reply) => {
-                self.kernel.set_kernel_info(reply);
-                cx.notify();
-            }
-            JupyterMessageContent::UpdateDisplayData(update) => {
-                let display_id = if let Some(display_id) = update.transient.display_id.clone() {
-                    display_id
-                } else {
-                    return;
-                };
+    pub fn supports_variables(&self) -> bool {
+        variable_explorer::variables_code(&self.kernel_specification.language()).is_some()
+    }
 
-                self.blocks.iter_mut().for_each(|(_, block)| {
-                    block.execution_view.update(cx, |execution_view, cx| {
-                        execution_view.update_display_data(&update.data, &display_id, window, cx);
-                    });
-                });
-                return;
-            }
-            _ => {}
+    /// Keeps the variables up to date after every execution from now on.
+    pub fn watch_variables(&mut self, cx: &mut Context<Self>) {
+        if !self.watching_variables {
+            self.watching_variables = true;
+            self.refresh_variables(cx);
         }
+    }
 
-        if let Some(block) = self.blocks.get_mut(parent_message_id) {
-            block.handle_message(message, window, cx);
+    pub fn refresh_variables(&mut self, cx: &mut Context<Self>) {
+        let Kernel::RunningKernel(_) = &self.kernel else {
+            return;
+        };
+        let Some(code) = variable_explorer::variables_code(&self.kernel_specification.language())
+        else {
+            return;
+        };
+
+        // Silent requests that don't stop on errors leave the user's queued executions alone,
+        // even if the helper fails.
+        let message: JupyterMessage = ExecuteRequest {
+            code: code.to_string(),
+            silent: true,
+            store_history: false,
+            stop_on_error: false,
+            ..ExecuteRequest::default()
         }
+        .into();
+        // A request that is still running is superseded, and its output ignored
+        self.variables_request = Some(VariablesRequest {
+            msg_id: message.header.msg_id.clone(),
+            stdout: String::new(),
+        });
+        self.send(message, cx).ok();
     }
 
-    pub fn interrupt(&mut self, cx: &mut Context<Self>) {
-        match &mut self.kernel {
-            Kernel::RunningKernel(_kernel) => {
-                self.send(InterruptRequest {}.into(), cx).ok();
+    pub fn inspect_variable(
+        &mut self,
+        name: SharedString,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) {
+        let Kernel::RunningKernel(_) = &self.kernel else {
+            return;
+        };
+
+        let message: JupyterMessage = InspectRequest {
+            code: name.to_string(),
+            cursor_pos: name.len(),
+            detail_level: Some(0),
+        }
+        .into();
+        let execution_view =
+            cx.new(|cx| ExecutionView::new(ExecutionStatus::Executing, workspace, cx));
+        self.inspect_request_id = Some(message.header.msg_id.clone());
+        self.inspection = Some((name, execution_view));
+        self.send(message, cx).ok();
+        cx.notify();
+    }
+
+    fn handle_variables_message(&mut self, message: &JupyterMessage, cx: &mut Context<Self>) {
+        let Some(request) = self.variables_request.as_mut() else {
+            return;
+        };
+
+        match &message.content {
+            JupyterMessageContent::StreamContent(stream)
+                if matches!(stream.name, Stdio::Stdout) =>
+            {
+                request.stdout.push_str(&stream.text);
             }
-            Kernel::StartingKernel(_task) => {
-                // NOTE: If we switch to a literal queue instead of chaining on to the task, clear all queued executions
+            JupyterMessageContent::ErrorOutput(error) => {
+                log::warn!(
+                    "listing kernel variables failed: {}: {}",
+                    error.ename,
+                    error.evalue
+                );
+            }
+            JupyterMessageContent::Status(status)
+                if matches!(status.execution_state, ExecutionState::Idle) =>
+            {
+                let stdout = std::mem::take(&mut request.stdout);
+                self.variables_request = None;
+                if let Some(variables) = variable_explorer::parse_variables(&stdout).log_err() {
+                    self.variables = variables;
+                    cx.notify();
+                }
             }
             _ => {}
         }
@@ -639,6 +730,147 @@ impl Session {
     }
 }
 
+fn editor_abs_path(editor: &Entity<Editor>, cx: &App) -> Option<Arc<Path>> {
+    let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+    let abs_path = buffer.read(cx).file()?.as_local()?.abs_path(cx);
+    Some(abs_path.into())
+}
+
+fn serialize_blocks<'a>(
+    blocks: impl IntoIterator<Item = &'a EditorBlock>,
+    editor: &Entity<Editor>,
+    cx: &App,
+) -> Vec<SerializedOutputBlock> {
+    let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+    blocks
+        .into_iter()
+        .filter_map(|block| {
+            let outputs = block.execution_view.read(cx).notebook_outputs();
+            if outputs.is_empty() {
+                return None;
+            }
+            let code_range = block.code_range.to_point(&snapshot);
+            Some(SerializedOutputBlock {
+                code: snapshot.text_for_range(code_range.clone()).collect(),
+                code_range,
+                outputs,
+            })
+        })
+        .collect()
+}
+
+/// Shows the outputs persisted for the file of `editor` as stale blocks, until a
+/// session for the editor adopts them.
+pub(crate) fn restore_outputs(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+    window
+        .spawn(cx, async move |cx| {
+            let path = cx.update(|_, cx| {
+                editor
+                    .upgrade()
+                    .and_then(|editor| editor_abs_path(&editor, cx))
+            })?;
+            let Some(path) = path else {
+                return anyhow::Ok(());
+            };
+
+            let serialized_blocks = cx
+                .background_spawn(async move { REPL_DB.get_outputs(path) })
+                .await?;
+            if serialized_blocks.is_empty() {
+                return anyhow::Ok(());
+            }
+
+            cx.update(|window, cx| {
+                let Some(editor_entity) = editor.upgrade() else {
+                    return;
+                };
+                let snapshot = editor_entity.read(cx).buffer().read(cx).snapshot(cx);
+                let mut blocks = HashMap::default();
+                for serialized_block in serialized_blocks {
+                    let code_range = serialized_block.code_range;
+                    if code_range.end > snapshot.max_point()
+                        || snapshot
+                            .text_for_range(code_range.clone())
+                            .collect::<String>()
+                            != serialized_block.code
+                    {
+                        // The file changed since, so the outputs no longer belong to this code
+                        continue;
+                    }
+
+                    let key = uuid::Uuid::new_v4().to_string();
+                    let anchor_range = snapshot.anchor_before(code_range.start)
+                        ..snapshot.anchor_after(code_range.end);
+                    let on_close = close_restored_block(editor.clone(), key.clone());
+                    let outputs = serialized_block.outputs;
+                    if let Some(block) = EditorBlock::new(
+                        editor.clone(),
+                        anchor_range,
+                        on_close,
+                        cx,
+                        |workspace, cx| {
+                            cx.new(|cx| ExecutionView::restored(outputs, workspace, window, cx))
+                        },
+                    )
+                    .log_err()
+                    {
+                        blocks.insert(key, block);
+                    }
+                }
+
+                let entity_id = editor_entity.entity_id();
+                ReplStore::global(cx).update(cx, |store, _| {
+                    store.insert_restored_blocks(entity_id, blocks);
+                });
+                cx.observe_release(&editor_entity, move |_, cx| {
+                    ReplStore::global(cx).update(cx, |store, _| {
+                        store.take_restored_blocks(entity_id);
+                    });
+                })
+                .detach();
+            })
+        })
+        .detach_and_log_err(cx);
+}
+
+fn close_restored_block(editor: WeakEntity<Editor>, key: String) -> CloseBlockFn {
+    Arc::new(
+        move |block_id: CustomBlockId, _: &mut Window, cx: &mut App| {
+            let Some(editor) = editor.upgrade() else {
+                return;
+            };
+
+            let store = ReplStore::global(cx);
+            if let Some(session) = store.read(cx).get_session(editor.entity_id()).cloned() {
+                session.update(cx, |session, cx| {
+                    session.blocks.remove(&key);
+                    session.save_outputs(cx);
+                    cx.notify();
+                });
+            } else {
+                store.update(cx, |store, _| {
+                    store.remove_restored_block(editor.entity_id(), &key);
+                });
+                if let Some(path) = editor_abs_path(&editor, cx) {
+                    let blocks = serialize_blocks(
+                        store.read(cx).restored_blocks(editor.entity_id()),
+                        &editor,
+                        cx,
+                    );
+                    cx.background_spawn(async move { REPL_DB.save_outputs(path, blocks).await })
+                        .detach_and_log_err(cx);
+                }
+            }
+
+            editor.update(cx, |editor, cx| {
+                let mut block_ids = HashSet::default();
+                block_ids.insert(block_id);
+                editor.remove_blocks(block_ids, None, cx);
+            });
+        },
+    )
+}
+
 pub enum SessionEvent {
     Shutdown(WeakEntity<Editor>),
 }
@@ -700,3 +932,110 @@ impl Render for Session {
             .buttons(interrupt_button)
     }
 }
+
+impl KernelSession for Session {
+    fn route(&mut self, message: &JupyterMessage, window: &mut Window, cx: &mut Context<Self>) {
+        let parent_message_id = match message.parent_header.as_ref() {
+            Some(header) => &header.msg_id,
+            None => return,
+        };
+
+        // Listing variables happens behind the user's back, so it doesn't affect the kernel status
+        if self
+            .variables_request
+            .as_ref()
+            .is_some_and(|request| &request.msg_id == parent_message_id)
+        {
+            self.handle_variables_message(message, cx);
+            return;
+        }
+
+        match &message.content {
+            JupyterMessageContent::Status(status) => {
+                self.kernel.set_execution_state(&status.execution_state);
+
+                if self.watching_variables
+                    && matches!(status.execution_state, ExecutionState::Idle)
+                    && self.blocks.contains_key(parent_message_id)
+                {
+                    self.refresh_variables(cx);
+                }
+
+                telemetry::event!(
+                    "Kernel Status Changed",
+                    kernel_language = self.kernel_specification.language(),
+                    kernel_status = KernelStatus::from(&self.kernel).to_string(),
+                    repl_session_id = cx.entity_id().to_string(),
+                );
+
+                cx.notify();
+            }
+            JupyterMessageContent::KernelInfoReply(reply) => {
+                self.kernel.set_kernel_info(reply);
+                cx.notify();
+            }
+            JupyterMessageContent::InspectReply(reply)
+                if self.inspect_request_id.as_ref() == Some(parent_message_id) =>
+            {
+                self.inspect_request_id = None;
+                if let Some((_, execution_view)) = &self.inspection {
+                    execution_view.update(cx, |execution_view, cx| {
+                        if reply.found {
+                            execution_view.push_data(&reply.data, window, cx);
+                        } else {
+                            execution_view.push_text("No details available", cx);
+                        }
+                        execution_view.status = ExecutionStatus::Finished;
+                    });
+                }
+                cx.notify();
+                return;
+            }
+            JupyterMessageContent::UpdateDisplayData(update) => {
+                let display_id = if let Some(display_id) = update.transient.display_id.clone() {
+                    display_id
+                } else {
+                    return;
+                };
+
+                self.blocks.iter_mut().for_each(|(_, block)| {
+                    block.execution_view.update(cx, |execution_view, cx| {
+                        execution_view.update_display_data(&update.data, &display_id, window, cx);
+                    });
+                });
+                return;
+            }
+            _ => {}
+        }
+
+        if let Some(block) = self.blocks.get_mut(parent_message_id) {
+            block.handle_message(message, window, cx);
+
+            if let JupyterMessageContent::Status(status) = &message.content
+                && matches!(status.execution_state, ExecutionState::Idle)
+            {
+                self.save_outputs(cx);
+            }
+        }
+    }
+
+    fn kernel_errored(&mut self, error_message: String, cx: &mut Context<Self>) {
+        self.kernel(Kernel::ErroredLaunch(error_message.clone()), cx);
+
+        self.blocks.values().for_each(|block| {
+            block.execution_view.update(cx, |execution_view, cx| {
+                match execution_view.status {
+                    ExecutionStatus::Finished | ExecutionStatus::Stale => {
+                        // Do nothing when the output was good
+                    }
+                    _ => {
+                        // All other cases, set the status to errored
+                        execution_view.status =
+                            ExecutionStatus::KernelErrored(error_message.clone())
+                    }
+                }
+                cx.notify();
+            });
+        });
+    }
+}
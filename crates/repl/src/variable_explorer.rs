@@ -0,0 +1,342 @@
+//! Lists the variables defined in a running kernel.
+//!
+//! The variables are listed by running a small language-specific helper in the
+//! kernel, which prints them as JSON. Details of a single variable come from the
+//! kernel itself through an `inspect_request`.
+
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use gpui::{
+    App, Entity, EventEmitter, FocusHandle, Focusable, Subscription, WeakEntity, prelude::*,
+};
+use serde::Deserialize;
+use ui::{Tooltip, prelude::*};
+use workspace::item::ItemEvent;
+use workspace::{Workspace, item::Item};
+
+use crate::Session;
+use crate::repl_store::ReplStore;
+
+/// A variable defined in a kernel, as reported by its variables helper.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KernelVariable {
+    pub name: SharedString,
+    #[serde(rename = "type")]
+    pub type_name: SharedString,
+    pub shape: Option<SharedString>,
+    pub value: SharedString,
+}
+
+/// The code that prints the variables of a kernel for the given language, if it's supported.
+pub(crate) fn variables_code(language: &str) -> Option<&'static str> {
+    match language.to_lowercase().as_str() {
+        "python" => Some(include_str!("variable_explorer/variables.py")),
+        "r" => Some(include_str!("variable_explorer/variables.R")),
+        "julia" => Some(include_str!("variable_explorer/variables.jl")),
+        _ => None,
+    }
+}
+
+/// Parses what the variables helper printed. Output printed by the user's own
+/// startup code can precede it, so only the last line is considered.
+pub(crate) fn parse_variables(stdout: &str) -> Result<Vec<KernelVariable>> {
+    let line = stdout
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .context("the variables helper printed nothing")?;
+    serde_json::from_str(line).context("parsing the variables printed by the kernel")
+}
+
+/// A tab that shows the variables of the REPL session of the most recently active editor.
+pub struct VariableExplorer {
+    workspace: WeakEntity<Workspace>,
+    editor: Option<WeakEntity<Editor>>,
+    session: Option<Entity<Session>>,
+    focus_handle: FocusHandle,
+    _session_subscription: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl VariableExplorer {
+    pub fn new(window: &mut Window, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let workspace = cx.entity();
+        let editor = workspace
+            .read(cx)
+            .active_item(cx)
+            .and_then(|item| item.act_as::<Editor>(cx))
+            .map(|editor| editor.downgrade());
+
+        cx.new(|cx| {
+            let subscriptions = vec![
+                cx.subscribe_in(&workspace, window, |this, workspace, event, _, cx| {
+                    if let workspace::Event::ActiveItemChanged = event
+                        && let Some(editor) = workspace
+                            .read(cx)
+                            .active_item(cx)
+                            .and_then(|item| item.act_as::<Editor>(cx))
+                    {
+                        this.editor = Some(editor.downgrade());
+                        this.sync_session(cx);
+                    }
+                }),
+                cx.observe(&ReplStore::global(cx), |this, _, cx| this.sync_session(cx)),
+            ];
+
+            let mut this = Self {
+                workspace: workspace.downgrade(),
+                editor,
+                session: None,
+                focus_handle: cx.focus_handle(),
+                _session_subscription: None,
+                _subscriptions: subscriptions,
+            };
+            this.sync_session(cx);
+            this
+        })
+    }
+
+    fn sync_session(&mut self, cx: &mut Context<Self>) {
+        let session = self.editor.as_ref().and_then(|editor| {
+            ReplStore::global(cx)
+                .read(cx)
+                .get_session(editor.entity_id())
+                .cloned()
+        });
+        if session == self.session {
+            return;
+        }
+
+        self._session_subscription = session
+            .as_ref()
+            .map(|session| cx.observe(session, |_, _, cx| cx.notify()));
+        if let Some(session) = &session {
+            session.update(cx, |session, cx| session.watch_variables(cx));
+        }
+        self.session = session;
+        cx.notify();
+    }
+
+    fn render_variable(
+        &self,
+        index: usize,
+        variable: &KernelVariable,
+        selected: bool,
+        session: &Entity<Session>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let name = variable.name.clone();
+        let session = session.clone();
+        let workspace = self.workspace.clone();
+
+        h_flex()
+            .id(("variable", index))
+            .w_full()
+            .px_2()
+            .py_0p5()
+            .gap_4()
+            .rounded_sm()
+            .cursor_pointer()
+            .when(selected, |this| {
+                this.bg(cx.theme().colors().element_selected)
+            })
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .child(
+                div()
+                    .w_1_5()
+                    .child(Label::new(variable.name.clone()).truncate()),
+            )
+            .child(
+                div().w_1_6().child(
+                    Label::new(variable.type_name.clone())
+                        .color(Color::Muted)
+                        .truncate(),
+                ),
+            )
+            .child(
+                div().w_1_6().child(
+                    Label::new(variable.shape.clone().unwrap_or_default())
+                        .color(Color::Muted)
+                        .truncate(),
+                ),
+            )
+            .child(
+                div().flex_1().overflow_hidden().child(
+                    Label::new(variable.value.clone())
+                        .buffer_font(cx)
+                        .truncate(),
+                ),
+            )
+            .on_click(cx.listener(move |_, _, _, cx| {
+                session.update(cx, |session, cx| {
+                    session.inspect_variable(name.clone(), workspace.clone(), cx);
+                });
+            }))
+    }
+}
+
+impl EventEmitter<ItemEvent> for VariableExplorer {}
+
+impl Focusable for VariableExplorer {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for VariableExplorer {
+    type Event = ItemEvent;
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Variables".into()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(workspace::item::ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for VariableExplorer {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let container = v_flex()
+            .track_focus(&self.focus_handle)
+            .id("variable-explorer")
+            .p_4()
+            .gap_2()
+            .size_full()
+            .overflow_y_scroll();
+
+        let Some(session) = self.session.clone() else {
+            return container
+                .child(Label::new("Variables").size(LabelSize::Large))
+                .child(Label::new(
+                    "Run code in an editor with the 'repl::Run' command to see the variables of its kernel.",
+                ));
+        };
+
+        let session_ref = session.read(cx);
+        let kernel_name = session_ref.kernel_specification.name();
+        let header = h_flex()
+            .w_full()
+            .justify_between()
+            .child(Label::new(format!("Variables ({kernel_name})")).size(LabelSize::Large))
+            .child(
+                IconButton::new("refresh-variables", IconName::RotateCw)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Refresh Variables"))
+                    .on_click({
+                        let session = session.clone();
+                        move |_, _, cx| {
+                            session.update(cx, |session, cx| session.refresh_variables(cx));
+                        }
+                    }),
+            );
+
+        if !session_ref.supports_variables() {
+            let language = session_ref.kernel_specification.language();
+            return container.child(header).child(Label::new(format!(
+                "Listing variables isn't supported for {language} kernels."
+            )));
+        }
+
+        let variables = session_ref.variables().to_vec();
+        let inspection = session_ref.inspection().cloned();
+        if variables.is_empty() {
+            return container
+                .child(header)
+                .child(Label::new("No variables defined").color(Color::Muted));
+        }
+
+        let selected = inspection.as_ref().map(|(name, _)| name.clone());
+        let rows = variables
+            .iter()
+            .enumerate()
+            .map(|(index, variable)| {
+                let is_selected = selected.as_ref() == Some(&variable.name);
+                self.render_variable(index, variable, is_selected, &session, cx)
+                    .into_any_element()
+            })
+            .collect::<Vec<_>>();
+
+        container
+            .child(header)
+            .child(
+                h_flex()
+                    .w_full()
+                    .px_2()
+                    .gap_4()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(div().w_1_5().child(Label::new("Name").color(Color::Muted)))
+                    .child(div().w_1_6().child(Label::new("Type").color(Color::Muted)))
+                    .child(div().w_1_6().child(Label::new("Shape").color(Color::Muted)))
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(Label::new("Value").color(Color::Muted)),
+                    ),
+            )
+            .child(v_flex().w_full().children(rows))
+            .children(inspection.map(|(name, execution_view)| {
+                v_flex()
+                    .w_full()
+                    .pt_2()
+                    .gap_1()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(name).buffer_font(cx))
+                    .child(execution_view)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_variables() {
+        let stdout = concat!(
+            "loading startup script\n",
+            r#"[{"name": "df", "type": "DataFrame", "shape": "3 × 2", "value": "a b"},"#,
+            r#" {"name": "x", "type": "int", "shape": null, "value": "1"}]"#,
+            "\n\n",
+        );
+        let variables = parse_variables(stdout).unwrap();
+        assert_eq!(
+            variables,
+            vec![
+                KernelVariable {
+                    name: "df".into(),
+                    type_name: "DataFrame".into(),
+                    shape: Some("3 × 2".into()),
+                    value: "a b".into(),
+                },
+                KernelVariable {
+                    name: "x".into(),
+                    type_name: "int".into(),
+                    shape: None,
+                    value: "1".into(),
+                },
+            ]
+        );
+
+        assert!(parse_variables("").is_err());
+        assert!(parse_variables("NameError: name 'x' is not defined\n").is_err());
+    }
+
+    #[test]
+    fn test_variables_code() {
+        assert!(variables_code("python").is_some());
+        assert!(variables_code("R").is_some());
+        assert!(variables_code("julia").is_some());
+        assert!(variables_code("typescript").is_none());
+    }
+}
@@ -0,0 +1,26 @@
+let
+    json_string(text) = "\"" * escape_string(filter(!iscntrl, text), '"') * "\""
+
+    entries = String[]
+    for name in names(Main; all = true)
+        text = string(name)
+        (startswith(text, "#") || startswith(text, "_")) && continue
+        name in (:Base, :Core, :Main, :ans, :eval, :include) && continue
+        isdefined(Main, name) || continue
+        value = getfield(Main, name)
+        (value isa Module || value isa Function || value isa Type) && continue
+        shape = if value isa AbstractArray
+            json_string(join(size(value), " × "))
+        elseif value isa Union{AbstractDict,AbstractSet,Tuple}
+            json_string(string(length(value)))
+        else
+            "null"
+        end
+        preview = first(replace(repr(value), r"\s+" => " "), 200)
+        push!(
+            entries,
+            "{\"name\":$(json_string(text)),\"type\":$(json_string(string(typeof(value)))),\"shape\":$shape,\"value\":$(json_string(preview))}",
+        )
+    end
+    println("[", join(entries, ","), "]")
+end
@@ -145,6 +145,9 @@ pub struct SettingsContent {
 
     pub title_bar: Option<TitleBarSettingsContent>,
 
+    /// Configuration for the Type Hierarchy Panel
+    pub type_hierarchy_panel: Option<PanelSettingsContent>,
+
     /// Whether or not to enable Vim mode.
     ///
     /// Default: false
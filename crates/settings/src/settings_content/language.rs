@@ -439,6 +439,32 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: false
     pub colorize_brackets: Option<bool>,
+    /// Whether to request semantic tokens from language servers and use them
+    /// to highlight the code on top of the tree-sitter highlights.
+    ///
+    /// Default: true
+    pub semantic_tokens: Option<bool>,
+    /// Maps semantic token types reported by language servers to the syntax
+    /// highlight names of the theme.
+    ///
+    /// Keys are either a token type (e.g. `"function"`) or a token type with a
+    /// modifier (e.g. `"variable.mutable"`), the latter taking precedence.
+    /// An empty value keeps the tree-sitter highlight for that token.
+    ///
+    /// Default: see the default settings
+    pub semantic_token_highlights: Option<HashMap<String, String>>,
+    /// Whether to use the folding ranges reported by language servers to fold code,
+    /// instead of the indentation.
+    ///
+    /// Buffers whose language servers report no folding ranges keep using the indentation.
+    ///
+    /// Default: false
+    pub lsp_folding_ranges: Option<bool>,
+    /// Whether to use the selection ranges reported by language servers to expand
+    /// the selection with `editor: select larger syntax node`, instead of the syntax tree.
+    ///
+    /// Default: false
+    pub lsp_selection_ranges: Option<bool>,
 }
 
 /// Controls how whitespace should be displayedin the editor.
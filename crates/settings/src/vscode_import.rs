@@ -212,6 +212,7 @@ impl VsCodeSettings {
             terminal: self.terminal_settings_content(),
             theme: Box::new(self.theme_settings_content()),
             title_bar: None,
+            type_hierarchy_panel: None,
             vim: None,
             vim_mode: None,
             workspace: self.workspace_settings_content(),
@@ -451,6 +452,14 @@ impl VsCodeSettings {
             remove_trailing_whitespace_on_save: self.read_bool("editor.trimAutoWhitespace"),
             show_completion_documentation: None,
             colorize_brackets: self.read_bool("editor.bracketPairColorization.enabled"),
+            semantic_tokens: self.read_bool("editor.semanticHighlighting.enabled"),
+            lsp_folding_ranges: self.read_enum("editor.foldingStrategy", |s| match s {
+                "auto" => Some(true),
+                "indentation" => Some(false),
+                _ => None,
+            }),
+            lsp_selection_ranges: None,
+            semantic_token_highlights: None,
             show_completions_on_input: self.read_bool("editor.suggestOnTriggerCharacters"),
             show_edit_predictions: self.read_bool("editor.inlineSuggest.enabled"),
             show_whitespaces: self.read_enum("editor.renderWhitespace", |s| {
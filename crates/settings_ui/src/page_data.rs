@@ -4482,6 +4482,69 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     metadata: None,
                     files: USER,
                 }),
+                SettingsPageItem::SectionHeader("Type Hierarchy Panel"),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Type Hierarchy Panel Button",
+                    description: "Show the type hierarchy panel button in the status bar.",
+                    field: Box::new(SettingField {
+                        json_path: Some("type_hierarchy_panel.button"),
+                        pick: |settings_content| {
+                            settings_content
+                                .type_hierarchy_panel
+                                .as_ref()?
+                                .button
+                                .as_ref()
+                        },
+                        write: |settings_content, value| {
+                            settings_content
+                                .type_hierarchy_panel
+                                .get_or_insert_default()
+                                .button = value;
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Type Hierarchy Panel Dock",
+                    description: "Where to dock the type hierarchy panel.",
+                    field: Box::new(SettingField {
+                        json_path: Some("type_hierarchy_panel.dock"),
+                        pick: |settings_content| {
+                            settings_content.type_hierarchy_panel.as_ref()?.dock.as_ref()
+                        },
+                        write: |settings_content, value| {
+                            settings_content
+                                .type_hierarchy_panel
+                                .get_or_insert_default()
+                                .dock = value;
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Type Hierarchy Panel Default Width",
+                    description: "Default width of the type hierarchy panel in pixels.",
+                    field: Box::new(SettingField {
+                        json_path: Some("type_hierarchy_panel.default_width"),
+                        pick: |settings_content| {
+                            settings_content
+                                .type_hierarchy_panel
+                                .as_ref()?
+                                .default_width
+                                .as_ref()
+                        },
+                        write: |settings_content, value| {
+                            settings_content
+                                .type_hierarchy_panel
+                                .get_or_insert_default()
+                                .default_width = value;
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
                 SettingsPageItem::SectionHeader("Agent Panel"),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Agent Panel Button",
@@ -7159,6 +7222,85 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | PROJECT,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Semantic Tokens",
+            description: "Whether to highlight code with semantic tokens from language servers.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).semantic_tokens"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.semantic_tokens.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.semantic_tokens = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Semantic Token Highlights",
+            description: "Which theme syntax highlight to use for each semantic token type.",
+            field: Box::new(
+                SettingField {
+                    json_path: Some("languages.$(language).semantic_token_highlights"),
+                    pick: |settings_content| {
+                        language_settings_field(settings_content, |language| {
+                            language.semantic_token_highlights.as_ref()
+                        })
+                    },
+                    write: |settings_content, value| {
+                        language_settings_field_mut(settings_content, value, |language, value| {
+                            language.semantic_token_highlights = value;
+                        })
+                    },
+                }
+                .unimplemented(),
+            ),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "LSP Folding Ranges",
+            description: "Whether to fold code using folding ranges from language servers instead of indentation.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).lsp_folding_ranges"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.lsp_folding_ranges.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.lsp_folding_ranges = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "LSP Selection Ranges",
+            description: "Whether to expand selections using selection ranges from language servers instead of the syntax tree.",
+            field: Box::new(SettingField {
+                json_path: Some("languages.$(language).lsp_selection_ranges"),
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        language.lsp_selection_ranges.as_ref()
+                    })
+                },
+                write: |settings_content, value| {
+                    language_settings_field_mut(settings_content, value, |language, value| {
+                        language.lsp_selection_ranges = value;
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | PROJECT,
+        }),
     ]);
 
     if current_language().is_none() {
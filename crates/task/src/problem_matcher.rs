@@ -0,0 +1,438 @@
+use anyhow::Context as _;
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Extracts problems (errors, warnings, etc.) from the output of a task.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcher {
+    /// One of the built-in matchers.
+    Preset(ProblemMatcherPreset),
+    /// A matcher defined by regular expressions.
+    Custom(CustomProblemMatcher),
+}
+
+/// Built-in problem matchers for common tools.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemMatcherPreset {
+    /// Errors and warnings of `rustc`, also printed by `cargo build`, `cargo check` and `cargo clippy`.
+    #[serde(alias = "cargo")]
+    Rustc,
+    /// Errors and warnings of the TypeScript compiler.
+    Tsc,
+    /// Errors, warnings and notes of GCC and Clang.
+    Gcc,
+    /// Problems reported by ESLint with its default `stylish` formatter.
+    Eslint,
+}
+
+/// A problem matcher defined by one or more regular expressions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CustomProblemMatcher {
+    /// Patterns that have to match consecutive lines of the output to produce a problem.
+    /// Each of them captures some of the problem's fields.
+    pub pattern: Vec<ProblemPattern>,
+    /// Severity of the problems whose patterns don't capture one.
+    #[serde(default)]
+    pub severity: ProblemSeverity,
+}
+
+/// A regular expression matching a line of a task's output, along with the capture groups
+/// that hold the fields of a problem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    /// The regular expression to match the line with.
+    pub regexp: String,
+    /// Capture group of the file path, absolute or relative to the task's working directory.
+    #[serde(default)]
+    pub file: Option<usize>,
+    /// Capture group of the one-based line number.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// Capture group of the one-based column number.
+    #[serde(default)]
+    pub column: Option<usize>,
+    /// Capture group of the one-based line number the problem ends at.
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    /// Capture group of the one-based column number the problem ends at.
+    #[serde(default)]
+    pub end_column: Option<usize>,
+    /// Capture group of the severity, such as `error` or `warning`.
+    #[serde(default)]
+    pub severity: Option<usize>,
+    /// Capture group of the problem's code.
+    #[serde(default)]
+    pub code: Option<usize>,
+    /// Capture group of the problem's message.
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// Whether the last pattern may match several lines in a row, each producing a problem.
+    #[serde(default, rename = "loop")]
+    pub repeat: bool,
+}
+
+/// How severe a problem found in a task's output is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl ProblemSeverity {
+    fn parse(severity: &str) -> Option<Self> {
+        let severity = severity.to_lowercase();
+        if severity.starts_with("err") || severity.starts_with("fatal") {
+            Some(Self::Error)
+        } else if severity.starts_with("warn") {
+            Some(Self::Warning)
+        } else if severity.starts_with("info") || severity.starts_with("note") {
+            Some(Self::Info)
+        } else if severity.starts_with("hint") || severity.starts_with("help") {
+            Some(Self::Hint)
+        } else {
+            None
+        }
+    }
+}
+
+/// A problem found in a task's output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskProblem {
+    /// Path to the file, as printed by the task.
+    pub path: String,
+    /// One-based line number.
+    pub line: u32,
+    /// One-based column number, if the task printed one.
+    pub column: Option<u32>,
+    /// One-based line and column numbers of the end of the problem, if the task printed them.
+    pub end: Option<(u32, Option<u32>)>,
+    pub severity: ProblemSeverity,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl ProblemMatcher {
+    /// Returns all problems this matcher finds in the output given.
+    pub fn match_output(&self, output: &str) -> anyhow::Result<Vec<TaskProblem>> {
+        let matcher = match self {
+            Self::Preset(preset) => preset.matcher(),
+            Self::Custom(matcher) => matcher.clone(),
+        };
+        let patterns = matcher
+            .pattern
+            .iter()
+            .map(|pattern| {
+                Regex::new(&pattern.regexp)
+                    .with_context(|| format!("invalid problem pattern `{}`", pattern.regexp))
+                    .map(|regex| (regex, pattern))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if patterns.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let lines = output.lines().collect::<Vec<_>>();
+        let mut problems = Vec::new();
+        let mut ix = 0;
+        while ix < lines.len() {
+            match match_problems_at(&patterns, &lines, ix, matcher.severity) {
+                Some((next_ix, matched)) => {
+                    problems.extend(matched);
+                    ix = next_ix;
+                }
+                None => ix += 1,
+            }
+        }
+        Ok(problems)
+    }
+}
+
+impl ProblemMatcherPreset {
+    /// The regular expressions behind a preset.
+    pub fn matcher(&self) -> CustomProblemMatcher {
+        let pattern = match self {
+            // error[E0308]: mismatched types
+            //   --> src/main.rs:4:18
+            Self::Rustc => vec![
+                ProblemPattern {
+                    regexp: r"^(warning|warn|error)(?:\[(\S*?)\])?: (.*)$".to_string(),
+                    severity: Some(1),
+                    code: Some(2),
+                    message: Some(3),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regexp: r"^\s*-->\s+(.*?):(\d+):(\d+)\s*$".to_string(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    ..ProblemPattern::default()
+                },
+            ],
+            // src/index.ts(4,7): error TS2322: Type 'string' is not assignable to type 'number'.
+            // src/index.ts:4:7 - error TS2322: Type 'string' is not assignable to type 'number'.
+            Self::Tsc => vec![ProblemPattern {
+                regexp: r"^([^\s].*?)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+(TS\d+)\s*:\s*(.*)$"
+                    .to_string(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                code: Some(5),
+                message: Some(6),
+                ..ProblemPattern::default()
+            }],
+            // src/main.c:4:7: error: expected ';' before 'return'
+            Self::Gcc => vec![ProblemPattern {
+                regexp: r"^(.*?):(\d+):(\d*):?\s+(?:fatal\s+)?(warning|error|note):\s+(.*)$"
+                    .to_string(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                ..ProblemPattern::default()
+            }],
+            // /project/src/index.js
+            //   4:7  error  'foo' is assigned a value but never used  no-unused-vars
+            Self::Eslint => vec![
+                ProblemPattern {
+                    regexp: r"^([^\s].*)$".to_string(),
+                    file: Some(1),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regexp: r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.*?)(?:\s\s+(\S+))?$"
+                        .to_string(),
+                    line: Some(1),
+                    column: Some(2),
+                    severity: Some(3),
+                    message: Some(4),
+                    code: Some(5),
+                    repeat: true,
+                    ..ProblemPattern::default()
+                },
+            ],
+        };
+        CustomProblemMatcher {
+            pattern,
+            severity: ProblemSeverity::Error,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct ProblemFields {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<ProblemSeverity>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl ProblemFields {
+    fn capture(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let text = |group: Option<usize>| {
+            group
+                .and_then(|group| captures.get(group))
+                .map(|capture| capture.as_str().trim())
+                .filter(|text| !text.is_empty())
+        };
+        let number = |group: Option<usize>| text(group).and_then(|text| text.parse().ok());
+
+        if let Some(file) = text(pattern.file) {
+            self.file = Some(file.to_string());
+        }
+        self.line = number(pattern.line).or(self.line);
+        self.column = number(pattern.column).or(self.column);
+        self.end_line = number(pattern.end_line).or(self.end_line);
+        self.end_column = number(pattern.end_column).or(self.end_column);
+        if let Some(severity) = text(pattern.severity).and_then(ProblemSeverity::parse) {
+            self.severity = Some(severity);
+        }
+        if let Some(code) = text(pattern.code) {
+            self.code = Some(code.to_string());
+        }
+        if let Some(message) = text(pattern.message) {
+            self.message = Some(message.to_string());
+        }
+    }
+
+    fn into_problem(self, default_severity: ProblemSeverity) -> Option<TaskProblem> {
+        Some(TaskProblem {
+            path: self.file?,
+            line: self.line?,
+            column: self.column,
+            end: self.end_line.map(|line| (line, self.end_column)),
+            severity: self.severity.unwrap_or(default_severity),
+            code: self.code,
+            message: self.message?,
+        })
+    }
+}
+
+/// Tries to match all patterns against the lines starting at `ix`, returning the index of the
+/// first line after the match along with the problems found.
+fn match_problems_at(
+    patterns: &[(Regex, &ProblemPattern)],
+    lines: &[&str],
+    ix: usize,
+    default_severity: ProblemSeverity,
+) -> Option<(usize, Vec<TaskProblem>)> {
+    let mut fields = ProblemFields::default();
+    let mut line_ix = ix;
+    for (pattern_ix, (regex, pattern)) in patterns.iter().enumerate() {
+        let captures = regex.captures(lines.get(line_ix)?)?;
+        line_ix += 1;
+
+        let is_last = pattern_ix + 1 == patterns.len();
+        if is_last && pattern.repeat {
+            let mut problems = Vec::new();
+            let mut captures = captures;
+            loop {
+                let mut problem_fields = fields.clone();
+                problem_fields.capture(pattern, &captures);
+                problems.extend(problem_fields.into_problem(default_severity));
+                match lines.get(line_ix).and_then(|line| regex.captures(line)) {
+                    Some(next_captures) => {
+                        captures = next_captures;
+                        line_ix += 1;
+                    }
+                    None => break,
+                }
+            }
+            return Some((line_ix, problems));
+        }
+        fields.capture(pattern, &captures);
+    }
+    let problem = fields.into_problem(default_severity)?;
+    Some((line_ix, vec![problem]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rustc_problem_matcher() {
+        let output = r#"
+   Compiling example v0.1.0 (/example)
+error[E0308]: mismatched types
+ --> src/main.rs:2:18
+  |
+2 |     let x: i32 = "one";
+  |            ---   ^^^^^ expected `i32`, found `&str`
+
+warning: unused variable: `y`
+  --> src/lib.rs:10:9
+   |
+warning: `example` (bin "example") generated 1 warning
+"#;
+        let problems = ProblemMatcher::Preset(ProblemMatcherPreset::Rustc)
+            .match_output(output)
+            .unwrap();
+        assert_eq!(
+            problems,
+            vec![
+                TaskProblem {
+                    path: "src/main.rs".to_string(),
+                    line: 2,
+                    column: Some(18),
+                    end: None,
+                    severity: ProblemSeverity::Error,
+                    code: Some("E0308".to_string()),
+                    message: "mismatched types".to_string(),
+                },
+                TaskProblem {
+                    path: "src/lib.rs".to_string(),
+                    line: 10,
+                    column: Some(9),
+                    end: None,
+                    severity: ProblemSeverity::Warning,
+                    code: None,
+                    message: "unused variable: `y`".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_looping_problem_matcher() {
+        let output = "
+/project/src/index.js
+  4:7   error    'foo' is assigned a value but never used  no-unused-vars
+  12:1  warning  Unexpected console statement              no-console
+
+✖ 2 problems (1 error, 1 warning)
+";
+        let problems = ProblemMatcher::Preset(ProblemMatcherPreset::Eslint)
+            .match_output(output)
+            .unwrap();
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.path.as_str(),
+                    problem.line,
+                    problem.severity,
+                    problem.code.as_deref()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "/project/src/index.js",
+                    4,
+                    ProblemSeverity::Error,
+                    Some("no-unused-vars")
+                ),
+                (
+                    "/project/src/index.js",
+                    12,
+                    ProblemSeverity::Warning,
+                    Some("no-console")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_problem_matcher() {
+        let matcher: ProblemMatcher = serde_json::from_value(serde_json::json!({
+            "pattern": [{
+                "regexp": "^(.*):(\\d+): (.*)$",
+                "file": 1,
+                "line": 2,
+                "message": 3
+            }],
+            "severity": "warning"
+        }))
+        .unwrap();
+        let problems = matcher
+            .match_output("lint.py:7: line too long\nall done")
+            .unwrap();
+        assert_eq!(
+            problems,
+            vec![TaskProblem {
+                path: "lint.py".to_string(),
+                line: 7,
+                column: None,
+                end: None,
+                severity: ProblemSeverity::Warning,
+                code: None,
+                message: "line too long".to_string(),
+            }]
+        );
+    }
+}
@@ -2,6 +2,7 @@
 
 mod adapter_schema;
 mod debug_format;
+mod problem_matcher;
 mod serde_helpers;
 pub mod static_source;
 mod task_template;
@@ -21,9 +22,14 @@ pub use debug_format::{
     AttachRequest, BuildTaskDefinition, DebugRequest, DebugScenario, DebugTaskFile, LaunchRequest,
     Request, TcpArgumentsTemplate, ZedDebugConfig,
 };
+pub use problem_matcher::{
+    CustomProblemMatcher, ProblemMatcher, ProblemMatcherPreset, ProblemPattern, ProblemSeverity,
+    TaskProblem,
+};
 pub use task_template::{
-    DebugArgsRequest, HideStrategy, RevealStrategy, TaskTemplate, TaskTemplates,
-    substitute_variables_in_map, substitute_variables_in_str,
+    BackgroundPatterns, DebugArgsRequest, DependsOrder, HideStrategy, RevealStrategy, TaskInput,
+    TaskInputKind, TaskTemplate, TaskTemplates, substitute_variables_in_map,
+    substitute_variables_in_str,
 };
 pub use util::shell::{Shell, ShellKind};
 pub use util::shell_builder::ShellBuilder;
@@ -74,6 +80,13 @@ pub struct SpawnInTerminal {
     pub show_command: bool,
     /// Whether to show the rerun button in the terminal tab.
     pub show_rerun: bool,
+    /// Problem matchers to run over the task's output once it finishes.
+    pub problem_matcher: Vec<ProblemMatcher>,
+    /// Output patterns telling when a task running in the background is busy or idle.
+    pub background: Option<BackgroundPatterns>,
+    /// Whether the task is spawned as a dependency of another task, in which case a running
+    /// background task is waited for instead of restarted.
+    pub is_dependency: bool,
 }
 
 impl SpawnInTerminal {
@@ -117,6 +130,8 @@ pub struct ResolvedTask {
     pub id: TaskId,
     /// A template the task got resolved from.
     original_task: TaskTemplate,
+    /// A context the task got resolved with, reused to resolve the tasks it depends on.
+    task_context: TaskContext,
     /// Full, unshortened label of the task after all resolutions are made.
     pub resolved_label: String,
     /// Variables that were substituted during the task template resolution.
@@ -132,6 +147,11 @@ impl ResolvedTask {
         &self.original_task
     }
 
+    /// A context the task was resolved with.
+    pub fn task_context(&self) -> &TaskContext {
+        &self.task_context
+    }
+
     /// Variables that were substituted during the task template resolution.
     pub fn substituted_variables(&self) -> &HashSet<VariableName> {
         &self.substituted_variables
@@ -141,6 +161,12 @@ impl ResolvedTask {
     pub fn display_label(&self) -> &str {
         self.resolved.label.as_str()
     }
+
+    /// Inputs the task uses, but that have no value in its context yet.
+    /// Such tasks are only resolved to be listed, see [`TaskTemplate::missing_inputs`].
+    pub fn missing_inputs(&self) -> Vec<&TaskInput> {
+        self.original_task.missing_inputs(&self.task_context)
+    }
 }
 
 /// Variables, available for use in [`TaskContext`] when a Zed's [`TaskTemplate`] gets resolved into a [`ResolvedTask`].
@@ -176,6 +202,9 @@ pub enum VariableName {
     /// Open a Picker to select a process ID to use in place
     /// Can only be used to debug configurations
     PickProcessId,
+    /// A value the user provides when the task is spawned, defined by one of the task's [`TaskInput`]s.
+    /// Will be printed with `INPUT_` prefix.
+    Input(Cow<'static, str>),
     /// Custom variable, provided by the plugin or other external source.
     /// Will be printed with `CUSTOM_` prefix to avoid potential conflicts with other variables.
     Custom(Cow<'static, str>),
@@ -215,6 +244,10 @@ impl FromStr for VariableName {
                     without_prefix.strip_prefix(ZED_CUSTOM_VARIABLE_NAME_PREFIX)
                 {
                     Self::Custom(Cow::Owned(custom_name.to_owned()))
+                } else if let Some(input_id) =
+                    without_prefix.strip_prefix(ZED_INPUT_VARIABLE_NAME_PREFIX)
+                {
+                    Self::Input(Cow::Owned(input_id.to_owned()))
                 } else {
                     return Err(());
                 }
@@ -227,6 +260,7 @@ impl FromStr for VariableName {
 /// A prefix that all [`VariableName`] variants are prefixed with when used in environment variables and similar template contexts.
 pub const ZED_VARIABLE_NAME_PREFIX: &str = "ZED_";
 const ZED_CUSTOM_VARIABLE_NAME_PREFIX: &str = "CUSTOM_";
+const ZED_INPUT_VARIABLE_NAME_PREFIX: &str = "INPUT_";
 
 impl std::fmt::Display for VariableName {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -248,6 +282,10 @@ impl std::fmt::Display for VariableName {
                 f,
                 "{ZED_VARIABLE_NAME_PREFIX}{ZED_CUSTOM_VARIABLE_NAME_PREFIX}{s}"
             ),
+            Self::Input(id) => write!(
+                f,
+                "{ZED_VARIABLE_NAME_PREFIX}{ZED_INPUT_VARIABLE_NAME_PREFIX}{id}"
+            ),
         }
     }
 }
@@ -402,6 +440,12 @@ impl EnvVariableReplacer {
                 if let Some(replacement_command) = self.commands.get(command_name) {
                     return Some(format!("${{{replacement_command}}}"));
                 }
+            } else if left == "input" && !right.is_empty() {
+                let input_id = right[1..].to_owned();
+                return Some(format!(
+                    "${{{}}}",
+                    VariableName::Input(Cow::Owned(input_id))
+                ));
             }
 
             let (variable_name, default) = (left, right);
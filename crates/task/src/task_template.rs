@@ -3,14 +3,14 @@ use collections::{HashMap, HashSet};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::{borrow::Cow, path::PathBuf};
 use util::schemars::{AllowTrailingCommas, DefaultDenyUnknownFields};
 use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
-    AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
+    AttachRequest, ProblemMatcher, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext,
+    TaskId, VariableName, ZED_VARIABLE_NAME_PREFIX, serde_helpers::non_empty_string_vec,
 };
 
 /// A template definition of a Zed task to run.
@@ -72,6 +72,29 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks that have to finish successfully before this one starts.
+    /// A task with dependencies may omit its `command` to only run them.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// How the tasks in `depends_on` are run:
+    /// * `parallel` — all at once (default)
+    /// * `sequential` — one after another, in the order they are listed, stopping at the first failure
+    #[serde(default)]
+    pub depends_order: DependsOrder,
+    /// Problem matchers that turn the task's output into diagnostics once it finishes:
+    /// either one of the presets (`rustc`, `tsc`, `gcc`, `eslint`), or a custom matcher
+    /// with regular expressions that capture the file, line, column, severity and message.
+    #[serde(default)]
+    pub problem_matcher: Vec<ProblemMatcher>,
+    /// Marks a task that keeps running in the background, such as a file watcher or a dev server,
+    /// with the output patterns that tell when it starts and finishes working.
+    /// Tasks depending on it and debug scenarios building with it start once it is done working,
+    /// instead of waiting for it to exit.
+    #[serde(default)]
+    pub background: Option<BackgroundPatterns>,
+    /// Values the user is asked for when the task is spawned, available in the task as `$ZED_INPUT_<id>`.
+    #[serde(default)]
+    pub inputs: Vec<TaskInput>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -109,6 +132,79 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// How the dependencies of a task are run.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Run all dependencies at once.
+    #[default]
+    Parallel,
+    /// Run dependencies one after another, in the order they are listed.
+    Sequential,
+}
+
+/// Output patterns of a task running in the background.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BackgroundPatterns {
+    /// A regular expression matching the output line that the task prints when it starts working,
+    /// e.g. after a file change. The task is busy until its `ends_pattern` is printed.
+    pub begins_pattern: String,
+    /// A regular expression matching the output line that the task prints when it is done working,
+    /// e.g. once a build finishes or a server starts listening. The task is idle and ready afterwards.
+    pub ends_pattern: String,
+}
+
+/// A value the user is asked for when a task using it is spawned.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TaskInput {
+    /// The identifier of the input, the task gets its value as `$ZED_INPUT_<id>`.
+    pub id: String,
+    /// How the value is provided:
+    /// * `prompt_string` — the user types it in (default)
+    /// * `pick_string` — the user picks one of `options`
+    /// * `command` — the user picks one of the lines `command` prints
+    #[serde(default, rename = "type")]
+    pub kind: TaskInputKind,
+    /// What the value is for, shown when asking for it.
+    #[serde(default)]
+    pub description: String,
+    /// The value suggested to the user.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// The values to pick from, for `pick_string` inputs.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// The command whose output lines are the values to pick from, for `command` inputs.
+    /// It runs in the task's working directory.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments of `command`.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// How the value of a [`TaskInput`] is provided.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskInputKind {
+    /// The user types the value in.
+    #[default]
+    PromptString,
+    /// The user picks one of the given options.
+    PickString,
+    /// The user picks one of the lines printed by a command.
+    Command,
+}
+
+impl TaskInput {
+    /// The variable holding the value of this input.
+    pub fn variable_name(&self) -> VariableName {
+        VariableName::Input(Cow::Owned(self.id.clone()))
+    }
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -127,6 +223,28 @@ impl TaskTemplates {
 }
 
 impl TaskTemplate {
+    /// Inputs the task uses, but that have no value in the context given.
+    /// The user has to provide them before the task is resolved to be spawned.
+    pub fn missing_inputs(&self, cx: &TaskContext) -> Vec<&TaskInput> {
+        self.inputs
+            .iter()
+            .filter(|input| {
+                let variable = input.variable_name();
+                cx.task_variables.get(&variable).is_none() && self.uses_variable(&variable)
+            })
+            .collect()
+    }
+
+    fn uses_variable(&self, variable: &VariableName) -> bool {
+        let name = variable.to_string();
+        [&self.label, &self.command]
+            .into_iter()
+            .chain(&self.args)
+            .chain(self.cwd.as_ref())
+            .chain(self.env.values())
+            .any(|text| references_variable(text, &name))
+    }
+
     /// Replaces all `VariableName` task variables in the task template string fields.
     /// If any replacement fails or the new string substitutions still have [`ZED_VARIABLE_NAME_PREFIX`],
     /// `None` is returned.
@@ -134,13 +252,15 @@ impl TaskTemplate {
     /// Every [`ResolvedTask`] gets a [`TaskId`], based on the `id_base` (to avoid collision with various task sources),
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
-        if self.label.trim().is_empty() || self.command.trim().is_empty() {
+        if self.label.trim().is_empty()
+            || (self.command.trim().is_empty() && self.depends_on.is_empty())
+        {
             return None;
         }
 
         let mut variable_names = HashMap::default();
         let mut substituted_variables = HashSet::default();
-        let task_variables = cx
+        let mut task_variables = cx
             .task_variables
             .0
             .iter()
@@ -152,6 +272,20 @@ impl TaskTemplate {
                 (key_string, value.as_str())
             })
             .collect::<HashMap<_, _>>();
+        // Inputs the user has not provided yet are left as they are, so that the task can be listed.
+        // It has to be resolved with their values to be spawned, see `TaskTemplate::missing_inputs`.
+        let input_placeholders = self
+            .inputs
+            .iter()
+            .map(TaskInput::variable_name)
+            .filter(|variable| cx.task_variables.get(variable).is_none())
+            .map(|variable| (variable.template_value(), variable))
+            .collect::<Vec<_>>();
+        for (placeholder, variable) in &input_placeholders {
+            let key_string = variable.to_string();
+            variable_names.insert(key_string.clone(), variable.clone());
+            task_variables.insert(key_string, placeholder.as_str());
+        }
         let truncated_variables = truncate_variables(&task_variables);
         let cwd = match self.cwd.as_deref() {
             Some(cwd) => {
@@ -246,6 +380,7 @@ impl TaskTemplate {
             id: id.clone(),
             substituted_variables,
             original_task: self.clone(),
+            task_context: cx.clone(),
             resolved_label: full_label.clone(),
             resolved: SpawnInTerminal {
                 id,
@@ -260,7 +395,7 @@ impl TaskTemplate {
                         command_label
                     },
                 ),
-                command: Some(command),
+                command: (!command.trim().is_empty()).then_some(command),
                 args: args_with_substitutions,
                 env,
                 use_new_terminal: self.use_new_terminal,
@@ -272,6 +407,9 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matcher: self.problem_matcher.clone(),
+                background: self.background.clone(),
+                is_dependency: false,
             },
         })
     }
@@ -279,6 +417,17 @@ impl TaskTemplate {
 
 const MAX_DISPLAY_VARIABLE_LENGTH: usize = 15;
 
+/// Whether the text references the variable, as `$NAME` or `${NAME}`, possibly with a default value.
+fn references_variable(text: &str, name: &str) -> bool {
+    text.match_indices('$').any(|(ix, _)| {
+        let reference = &text[ix + 1..];
+        let reference = reference.strip_prefix('{').unwrap_or(reference);
+        reference
+            .strip_prefix(name)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_'))
+    })
+}
+
 fn truncate_variables(task_variables: &HashMap<String, &str>) -> HashMap<String, String> {
     task_variables
         .iter()
@@ -470,6 +619,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_templates_with_dependencies_only() {
+        let compound_task = TaskTemplate {
+            label: "build all".to_string(),
+            depends_on: vec!["build server".to_string(), "build client".to_string()],
+            ..TaskTemplate::default()
+        };
+        let resolved_task = compound_task
+            .resolve_task(TEST_ID_BASE, &TaskContext::default())
+            .expect("should resolve a task that only runs its dependencies");
+        assert_eq!(resolved_task.resolved.command, None);
+        assert_eq!(
+            resolved_task.original_task().depends_on,
+            compound_task.depends_on
+        );
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
@@ -735,6 +901,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolving_task_inputs() {
+        let task = TaskTemplate {
+            label: "deploy to $ZED_INPUT_target".into(),
+            command: "deploy".into(),
+            args: vec!["--target".into(), "$ZED_INPUT_target".into()],
+            inputs: vec![
+                TaskInput {
+                    id: "target".into(),
+                    kind: TaskInputKind::PickString,
+                    options: vec!["staging".into(), "production".into()],
+                    ..TaskInput::default()
+                },
+                TaskInput {
+                    id: "unused".into(),
+                    ..TaskInput::default()
+                },
+                TaskInput {
+                    id: "region".into(),
+                    ..TaskInput::default()
+                },
+            ],
+            env: HashMap::from_iter([("REGION".into(), "${ZED_INPUT_region}".into())]),
+            ..TaskTemplate::default()
+        };
+        assert_eq!(
+            task.missing_inputs(&TaskContext::default()),
+            vec![&task.inputs[0], &task.inputs[2]]
+        );
+        let mut task_context = TaskContext::default();
+        task_context
+            .task_variables
+            .insert(task.inputs[2].variable_name(), "us".into());
+        assert_eq!(task.missing_inputs(&task_context), vec![&task.inputs[0]]);
+        let task_with_similar_variable = TaskTemplate {
+            label: "deploy to $ZED_INPUT_targets".into(),
+            command: "echo $ZED_INPUT_targets".into(),
+            args: vec!["--target".into(), "${ZED_INPUT_targets}".into()],
+            env: HashMap::default(),
+            ..task.clone()
+        };
+        assert_eq!(
+            task_with_similar_variable.missing_inputs(&task_context),
+            Vec::<&TaskInput>::new(),
+            "only variables named exactly after the input should be considered its uses"
+        );
+
+        let unresolved_task = task
+            .resolve_task(TEST_ID_BASE, &task_context)
+            .expect("should resolve a task to list before its inputs are provided");
+        assert_eq!(
+            unresolved_task.resolved_label,
+            "deploy to $ZED_INPUT_target"
+        );
+        assert_eq!(unresolved_task.missing_inputs(), vec![&task.inputs[0]]);
+
+        task_context
+            .task_variables
+            .insert(task.inputs[0].variable_name(), "staging".into());
+        let resolved_task = task.resolve_task(TEST_ID_BASE, &task_context).unwrap();
+        assert_eq!(resolved_task.resolved_label, "deploy to staging");
+        assert_eq!(
+            resolved_task.resolved.args,
+            vec!["--target".to_string(), "staging".to_string()]
+        );
+        assert!(resolved_task.missing_inputs().is_empty());
+    }
+
     #[test]
     fn test_symbol_dependent_tasks() {
         let task_with_all_properties = TaskTemplate {
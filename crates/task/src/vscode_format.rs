@@ -3,7 +3,11 @@ use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    BackgroundPatterns, CustomProblemMatcher, DependsOrder, EnvVariableReplacer, ProblemMatcher,
+    ProblemMatcherPreset, ProblemPattern, ProblemSeverity, TaskInput, TaskInputKind, TaskTemplate,
+    TaskTemplates, VariableName,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -42,23 +46,100 @@ enum Command {
 }
 
 impl VsCodeTaskDefinition {
-    fn into_zed_format(
-        self,
-        replacer: &EnvVariableReplacer,
-    ) -> anyhow::Result<Option<TaskTemplate>> {
-        if self.other_attributes.contains_key("dependsOn") {
+    /// `dependsOn` holds either a single task label or a list of them.
+    fn depends_on(&self) -> anyhow::Result<Vec<String>> {
+        match self.other_attributes.get("dependsOn") {
+            None => Ok(Vec::new()),
+            Some(serde_json_lenient::Value::String(label)) => Ok(vec![label.clone()]),
+            Some(serde_json_lenient::Value::Array(labels)) => labels
+                .iter()
+                .map(|label| match label {
+                    serde_json_lenient::Value::String(label) => Ok(label.clone()),
+                    _ => bail!(
+                        "Unsupported `dependsOn` entry in task `{}`, only task labels are supported",
+                        self.label
+                    ),
+                })
+                .collect(),
+            Some(_) => bail!("Invalid `dependsOn` value in task `{}`", self.label),
+        }
+    }
+
+    /// `problemMatcher` holds either a single matcher or a list of them, where each matcher is
+    /// either the name of a predefined one or an object with its patterns.
+    fn problem_matchers(&self) -> Vec<&serde_json_lenient::Value> {
+        match self.other_attributes.get("problemMatcher") {
+            None => Vec::new(),
+            Some(serde_json_lenient::Value::Array(matchers)) => matchers.iter().collect(),
+            Some(matcher) => vec![matcher],
+        }
+    }
+
+    fn problem_matcher(&self) -> Vec<ProblemMatcher> {
+        self.problem_matchers()
+            .into_iter()
+            .filter_map(|matcher| {
+                let problem_matcher = problem_matcher_from_vscode(matcher);
+                if problem_matcher.is_none() {
+                    log::warn!(
+                        "Skipping unsupported problem matcher {matcher} of task `{}`",
+                        self.label
+                    );
+                }
+                problem_matcher
+            })
+            .collect()
+    }
+
+    /// Background tasks (`isBackground`) get the patterns telling when they are busy from their
+    /// problem matcher, either its `background` property or the predefined watch matchers.
+    fn background(&self) -> Option<BackgroundPatterns> {
+        let is_background = self
+            .other_attributes
+            .get("isBackground")
+            .and_then(|is_background| is_background.as_bool())
+            .unwrap_or(false);
+        if !is_background {
+            return None;
+        }
+        let background = self
+            .problem_matchers()
+            .into_iter()
+            .find_map(background_patterns_from_vscode);
+        if background.is_none() {
             log::warn!(
-                "Skipping deserializing of a task `{}` with the unsupported `dependsOn` key",
+                "Background task `{}` has no problem matcher with background patterns, it will be treated as a regular task",
                 self.label
             );
-            return Ok(None);
         }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
-        // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
+        background
+    }
+
+    fn into_zed_format(
+        self,
+        replacer: &EnvVariableReplacer,
+    ) -> anyhow::Result<Option<TaskTemplate>> {
+        let depends_on = self.depends_on()?;
+        let depends_order = match self
+            .other_attributes
+            .get("dependsOrder")
+            .and_then(|order| order.as_str())
+        {
+            Some("sequence") => DependsOrder::Sequential,
+            _ => DependsOrder::Parallel,
+        };
+        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option).
+        // Such tasks only run their dependencies.
         let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
+            if depends_on.is_empty() {
+                bail!("Missing `type` field in task");
+            }
+            return Ok(Some(TaskTemplate {
+                label: self.label,
+                depends_on,
+                depends_order,
+                ..TaskTemplate::default()
+            }));
         };
 
         let (command, args) = match command {
@@ -69,10 +150,16 @@ impl VsCodeTaskDefinition {
         // Per VSC docs, only `command`, `args` and `options` support variable substitution.
         let command = replacer.replace(&command);
         let args = args.into_iter().map(|arg| replacer.replace(&arg)).collect();
+        let problem_matcher = self.problem_matcher();
+        let background = self.background();
         let mut template = TaskTemplate {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order,
+            problem_matcher,
+            background,
             ..TaskTemplate::default()
         };
         if let Some(options) = self.options {
@@ -83,10 +170,218 @@ impl VsCodeTaskDefinition {
     }
 }
 
+fn problem_matcher_preset(name: &str) -> Option<ProblemMatcherPreset> {
+    match name.strip_prefix('$')? {
+        "rustc" | "rustc-watch" => Some(ProblemMatcherPreset::Rustc),
+        "tsc" | "tsc-watch" => Some(ProblemMatcherPreset::Tsc),
+        "gcc" => Some(ProblemMatcherPreset::Gcc),
+        "eslint-stylish" => Some(ProblemMatcherPreset::Eslint),
+        _ => None,
+    }
+}
+
+const TSC_WATCH_BEGINS_PATTERN: &str = r"(?:Starting compilation in watch mode|File change detected\. Starting incremental compilation)\.\.\.";
+const TSC_WATCH_ENDS_PATTERN: &str =
+    r"(?:Compilation complete\.|Found \d+ errors?\.) Watching for file changes\.";
+const CARGO_WATCH_BEGINS_PATTERN: &str = r"^\[Running\b";
+const CARGO_WATCH_ENDS_PATTERN: &str = r"^\[Finished running\b";
+
+fn background_patterns_from_vscode(
+    matcher: &serde_json_lenient::Value,
+) -> Option<BackgroundPatterns> {
+    use serde_json_lenient::Value;
+
+    let name = match matcher {
+        Value::String(name) => name.as_str(),
+        Value::Object(matcher) => {
+            if let Some(background) = matcher.get("background") {
+                // Patterns are either regular expressions or objects with a `regexp` property.
+                let pattern = |key: &str| match background.get(key)? {
+                    Value::String(pattern) => Some(pattern.clone()),
+                    Value::Object(pattern) => Some(pattern.get("regexp")?.as_str()?.to_string()),
+                    _ => None,
+                };
+                return Some(BackgroundPatterns {
+                    begins_pattern: pattern("beginsPattern")?,
+                    ends_pattern: pattern("endsPattern")?,
+                });
+            }
+            matcher.get("base")?.as_str()?
+        }
+        _ => return None,
+    };
+    let (begins_pattern, ends_pattern) = match name {
+        "$tsc-watch" => (TSC_WATCH_BEGINS_PATTERN, TSC_WATCH_ENDS_PATTERN),
+        "$rustc-watch" => (CARGO_WATCH_BEGINS_PATTERN, CARGO_WATCH_ENDS_PATTERN),
+        _ => return None,
+    };
+    Some(BackgroundPatterns {
+        begins_pattern: begins_pattern.to_string(),
+        ends_pattern: ends_pattern.to_string(),
+    })
+}
+
+fn problem_matcher_from_vscode(matcher: &serde_json_lenient::Value) -> Option<ProblemMatcher> {
+    use serde_json_lenient::Value;
+
+    let matcher = match matcher {
+        Value::String(name) => return problem_matcher_preset(name).map(ProblemMatcher::Preset),
+        Value::Object(matcher) => matcher,
+        _ => return None,
+    };
+    let pattern = match matcher.get("pattern") {
+        // Matchers may only adjust a predefined one, e.g. to change where files are looked up.
+        None => {
+            let base = matcher.get("base")?.as_str()?;
+            return problem_matcher_preset(base).map(ProblemMatcher::Preset);
+        }
+        Some(Value::String(name)) => {
+            return problem_matcher_preset(name).map(ProblemMatcher::Preset);
+        }
+        Some(Value::Array(patterns)) => patterns
+            .iter()
+            .map(problem_pattern_from_vscode)
+            .collect::<Option<Vec<_>>>()?,
+        Some(pattern) => vec![problem_pattern_from_vscode(pattern)?],
+    };
+    let severity = match matcher
+        .get("severity")
+        .and_then(|severity| severity.as_str())
+    {
+        Some("warning") => ProblemSeverity::Warning,
+        Some("info") => ProblemSeverity::Info,
+        Some("hint") => ProblemSeverity::Hint,
+        _ => ProblemSeverity::Error,
+    };
+    Some(ProblemMatcher::Custom(CustomProblemMatcher {
+        pattern,
+        severity,
+    }))
+}
+
+fn problem_pattern_from_vscode(pattern: &serde_json_lenient::Value) -> Option<ProblemPattern> {
+    let pattern = pattern.as_object()?;
+    let group = |key: &str| {
+        pattern
+            .get(key)
+            .and_then(|group| group.as_u64())
+            .map(|group| group as usize)
+    };
+    Some(ProblemPattern {
+        regexp: pattern.get("regexp")?.as_str()?.to_string(),
+        file: group("file"),
+        line: group("line"),
+        column: group("column"),
+        end_line: group("endLine"),
+        end_column: group("endColumn"),
+        severity: group("severity"),
+        code: group("code"),
+        message: group("message"),
+        repeat: pattern
+            .get("loop")
+            .and_then(|repeat| repeat.as_bool())
+            .unwrap_or(false),
+    })
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum VsCodeInput {
+    PromptString {
+        id: String,
+        #[serde(default)]
+        description: String,
+        default: Option<String>,
+    },
+    PickString {
+        id: String,
+        #[serde(default)]
+        description: String,
+        options: Vec<VsCodePickStringOption>,
+        default: Option<String>,
+    },
+    Command {
+        id: String,
+        command: String,
+        #[serde(default)]
+        args: serde_json_lenient::Value,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum VsCodePickStringOption {
+    Value(String),
+    Labeled { value: String },
+}
+
+impl VsCodeInput {
+    fn into_zed_format(self) -> anyhow::Result<TaskInput> {
+        Ok(match self {
+            Self::PromptString {
+                id,
+                description,
+                default,
+            } => TaskInput {
+                id,
+                kind: TaskInputKind::PromptString,
+                description,
+                default,
+                ..TaskInput::default()
+            },
+            Self::PickString {
+                id,
+                description,
+                options,
+                default,
+            } => TaskInput {
+                id,
+                kind: TaskInputKind::PickString,
+                description,
+                default,
+                options: options
+                    .into_iter()
+                    .map(|option| match option {
+                        VsCodePickStringOption::Value(value)
+                        | VsCodePickStringOption::Labeled { value } => value,
+                    })
+                    .collect(),
+                ..TaskInput::default()
+            },
+            // Code runs editor commands here; the one of the popular "Tasks Shell Input" extension
+            // runs a shell command, same as Zed's command inputs.
+            Self::Command { id, command, args } => {
+                let shell_command = args.get("command").and_then(|command| command.as_str());
+                match shell_command {
+                    Some(shell_command) if command == "shellCommand.execute" => TaskInput {
+                        id,
+                        kind: TaskInputKind::Command,
+                        command: Some(shell_command.to_owned()),
+                        ..TaskInput::default()
+                    },
+                    _ => bail!("Unsupported command `{command}` of input `{id}`"),
+                }
+            }
+        })
+    }
+}
+
+/// Whether any of the template's fields that support variables reference the variable.
+fn uses_variable(template: &TaskTemplate, variable: &str) -> bool {
+    [&template.label, &template.command]
+        .into_iter()
+        .chain(&template.args)
+        .chain(&template.cwd)
+        .chain(template.env.values())
+        .any(|value| value.contains(variable))
+}
+
 /// [`VsCodeTaskFile`] is a superset of Code's task definition format.
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct VsCodeTaskFile {
     tasks: Vec<VsCodeTaskDefinition>,
+    #[serde(default)]
+    inputs: Vec<VsCodeInput>,
 }
 
 impl TryFrom<VsCodeTaskFile> for TaskTemplates {
@@ -105,6 +400,12 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
                 VariableName::SelectedText.to_string(),
             ),
         ]));
+        // Code defines inputs once for all tasks, Zed templates list the ones they use.
+        let inputs = value
+            .inputs
+            .into_iter()
+            .filter_map(|input| input.into_zed_format().log_err())
+            .collect::<Vec<_>>();
         let templates = value
             .tasks
             .into_iter()
@@ -114,6 +415,17 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
                     .log_err()
                     .flatten()
             })
+            .map(|mut template| {
+                template.inputs = inputs
+                    .iter()
+                    .filter(|input| {
+                        let variable = format!("${{{}}}", input.variable_name());
+                        uses_variable(&template, &variable)
+                    })
+                    .cloned()
+                    .collect();
+                template
+            })
             .collect();
         Ok(Self(templates))
     }
@@ -124,11 +436,12 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
-        TaskTemplate, TaskTemplates, VsCodeTaskFile,
+        BackgroundPatterns, DependsOrder, ProblemMatcher, ProblemMatcherPreset, TaskInput,
+        TaskInputKind, TaskTemplate, TaskTemplates, VsCodeTaskFile,
         vscode_format::{Command, VsCodeTaskDefinition},
     };
 
-    use super::EnvVariableReplacer;
+    use super::{EnvVariableReplacer, TSC_WATCH_BEGINS_PATTERN, TSC_WATCH_ENDS_PATTERN};
 
     fn compare_without_other_attributes(lhs: VsCodeTaskDefinition, rhs: VsCodeTaskDefinition) {
         assert_eq!(
@@ -227,6 +540,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
             TaskTemplate {
@@ -238,18 +552,25 @@ mod tests {
                     "${ZED_WORKTREE_ROOT}/src".to_string(),
                     "--watch".to_string(),
                 ],
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
+                background: Some(BackgroundPatterns {
+                    begins_pattern: TSC_WATCH_BEGINS_PATTERN.to_string(),
+                    ends_pattern: TSC_WATCH_ENDS_PATTERN.to_string(),
+                }),
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
         ];
@@ -330,28 +651,109 @@ mod tests {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
+                background: Some(BackgroundPatterns {
+                    begins_pattern: TSC_WATCH_BEGINS_PATTERN.to_string(),
+                    ends_pattern: TSC_WATCH_ENDS_PATTERN.to_string(),
+                }),
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Rustc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Rustc)],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matcher: vec![ProblemMatcher::Preset(ProblemMatcherPreset::Tsc)],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                depends_order: DependsOrder::Parallel,
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                depends_order: DependsOrder::Parallel,
+                ..Default::default()
+            },
+        ];
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(tasks.0, expected);
+    }
+
+    #[test]
+    fn can_import_inputs() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "version": "2.0.0",
+                "tasks": [
+                    {
+                        "label": "deploy",
+                        "type": "shell",
+                        "command": "./deploy.sh ${input:target}"
+                    },
+                    {
+                        "label": "build",
+                        "type": "shell",
+                        "command": "make"
+                    }
+                ],
+                "inputs": [
+                    {
+                        "id": "target",
+                        "type": "pickString",
+                        "description": "Where to deploy",
+                        "options": ["staging", { "label": "Production", "value": "production" }],
+                        "default": "staging"
+                    },
+                    {
+                        "id": "message",
+                        "type": "promptString"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let expected = vec![
+            TaskTemplate {
+                label: "deploy".to_string(),
+                command: "./deploy.sh ${ZED_INPUT_target}".to_string(),
+                inputs: vec![TaskInput {
+                    id: "target".to_string(),
+                    kind: TaskInputKind::PickString,
+                    description: "Where to deploy".to_string(),
+                    default: Some("staging".to_string()),
+                    options: vec!["staging".to_string(), "production".to_string()],
+                    ..TaskInput::default()
+                }],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "build".to_string(),
+                command: "make".to_string(),
                 ..Default::default()
             },
         ];
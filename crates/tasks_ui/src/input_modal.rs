@@ -0,0 +1,330 @@
+use std::{cmp, path::PathBuf, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use futures::channel::oneshot;
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
+    IntoElement, ParentElement, Render, SharedString, Styled, Subscription, Task, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use task::{Shell, ShellBuilder, TaskContext, TaskInput, TaskInputKind};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, TaskInputProvider, Workspace};
+
+/// Asks for task input values with a picker modal per input.
+pub(crate) struct TaskInputModals;
+
+impl TaskInputProvider for TaskInputModals {
+    fn request_inputs(
+        &self,
+        inputs: Vec<TaskInput>,
+        task_context: &TaskContext,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<Option<Vec<String>>>> {
+        let cwd = task_context.cwd.clone();
+        let env = task_context.project_env.clone();
+        let is_local = workspace.project().read(cx).is_local();
+        cx.spawn_in(window, async move |workspace, cx| {
+            let mut values = Vec::with_capacity(inputs.len());
+            for input in inputs {
+                let options = match input.kind {
+                    TaskInputKind::PromptString => Vec::new(),
+                    TaskInputKind::PickString => input.options.clone(),
+                    TaskInputKind::Command => {
+                        anyhow::ensure!(
+                            is_local,
+                            "Command inputs are not supported in remote projects"
+                        );
+                        command_input_options(&input, cwd.clone(), &env).await?
+                    }
+                };
+
+                let (value_tx, value_rx) = oneshot::channel();
+                workspace.update_in(cx, |workspace, window, cx| {
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        TaskInputModal::new(input, options, value_tx, window, cx)
+                    })
+                })?;
+                let Ok(value) = value_rx.await else {
+                    return Ok(None);
+                };
+                values.push(value);
+            }
+            Ok(Some(values))
+        })
+    }
+}
+
+/// Runs the input's command and returns its non-empty output lines.
+async fn command_input_options(
+    input: &TaskInput,
+    cwd: Option<PathBuf>,
+    env: &HashMap<String, String>,
+) -> Result<Vec<String>> {
+    let command = input
+        .command
+        .clone()
+        .with_context(|| format!("Input `{}` has no command to run", input.id))?;
+    let (program, args) =
+        ShellBuilder::new(&Shell::System, cfg!(windows)).build(Some(command.clone()), &input.args);
+    let mut process = util::command::new_smol_command(program);
+    process.args(args).envs(env);
+    if let Some(cwd) = cwd {
+        process.current_dir(cwd);
+    }
+    let output = process
+        .output()
+        .await
+        .with_context(|| format!("running `{command}` for input `{}`", input.id))?;
+    anyhow::ensure!(
+        output.status.success(),
+        "`{command}` for input `{}` failed: {}",
+        input.id,
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
+pub(crate) struct TaskInputModal {
+    picker: Entity<Picker<TaskInputDelegate>>,
+    _subscription: Subscription,
+}
+
+impl TaskInputModal {
+    fn new(
+        input: TaskInput,
+        options: Vec<String>,
+        tx: oneshot::Sender<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = TaskInputDelegate::new(input, options, tx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent));
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for TaskInputModal {}
+impl EventEmitter<DismissEvent> for TaskInputModal {}
+
+impl Focusable for TaskInputModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for TaskInputModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TaskInputModal")
+            .w(rems(34.))
+            .child(self.picker.clone())
+            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.cancel(&Default::default(), window, cx);
+                })
+            }))
+    }
+}
+
+pub(crate) struct TaskInputDelegate {
+    input: TaskInput,
+    options: Vec<String>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    query: String,
+    tx: Option<oneshot::Sender<String>>,
+}
+
+impl TaskInputDelegate {
+    fn new(input: TaskInput, options: Vec<String>, tx: oneshot::Sender<String>) -> Self {
+        Self {
+            input,
+            options,
+            matches: Vec::new(),
+            selected_index: 0,
+            query: String::new(),
+            tx: Some(tx),
+        }
+    }
+
+    fn is_prompt(&self) -> bool {
+        self.input.kind == TaskInputKind::PromptString
+    }
+
+    /// The value a prompt input gets when confirmed: what the user typed, or the default otherwise.
+    fn prompt_value(&self) -> Option<String> {
+        if self.query.is_empty() {
+            self.input.default.clone()
+        } else {
+            Some(self.query.clone())
+        }
+    }
+}
+
+impl PickerDelegate for TaskInputDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        let description = if self.input.description.is_empty() {
+            self.input.id.as_str()
+        } else {
+            self.input.description.as_str()
+        };
+        match &self.input.default {
+            Some(default) if self.is_prompt() => format!("{description} ({default})").into(),
+            _ => description.into(),
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        if self.is_prompt() {
+            Some("Type a value".into())
+        } else {
+            Some("No options".into())
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        if self.is_prompt() {
+            usize::from(self.prompt_value().is_some())
+        } else {
+            self.matches.len()
+        }
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        self.query = query.clone();
+        if self.is_prompt() {
+            self.selected_index = 0;
+            return Task::ready(());
+        }
+
+        let candidates = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(ix, option)| StringMatchCandidate::new(ix, option))
+            .collect::<Vec<_>>();
+        let default = self.input.default.clone();
+        cx.spawn_in(window, async move |picker, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    10000,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    delegate.selected_index = if query.is_empty() {
+                        matches
+                            .iter()
+                            .position(|hit| Some(&hit.string) == default.as_ref())
+                            .unwrap_or(0)
+                    } else {
+                        cmp::min(delegate.selected_index, matches.len().saturating_sub(1))
+                    };
+                    delegate.matches = matches;
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let value = if self.is_prompt() {
+            self.prompt_value()
+        } else {
+            self.matches
+                .get(self.selected_index)
+                .map(|hit| hit.string.clone())
+        };
+        let Some(value) = value else {
+            return;
+        };
+        self.tx.take().map(|tx| tx.send(value));
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm_input(&mut self, _: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if !self.is_prompt() {
+            return;
+        }
+        self.tx.take().map(|tx| tx.send(self.query.clone()));
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let label = if self.is_prompt() {
+            HighlightedLabel::new(self.prompt_value()?, Vec::new())
+        } else {
+            let hit = self.matches.get(ix)?;
+            HighlightedLabel::new(hit.string.clone(), hit.positions.clone())
+        };
+        Some(
+            ListItem::new(SharedString::from(format!("task-input-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(label),
+        )
+    }
+}
@@ -10,8 +10,8 @@ use gpui::{
 };
 use itertools::Itertools;
 use picker::{Picker, PickerDelegate, highlighted_match_with_paths::HighlightedMatch};
-use project::{TaskSourceKind, task_store::TaskStore};
-use task::{DebugScenario, ResolvedTask, RevealTarget, TaskContext, TaskTemplate};
+use project::{TaskActivity, TaskSourceKind, task_store::TaskStore};
+use task::{DebugScenario, DependsOrder, ResolvedTask, RevealTarget, TaskContext, TaskTemplate};
 use ui::{
     ActiveTheme, Clickable, FluentBuilder as _, IconButtonShape, IconWithIndicator, Indicator,
     IntoElement, KeyBinding, ListItem, ListItemSpacing, RenderOnce, Toggleable, Tooltip, div,
@@ -455,7 +455,9 @@ impl PickerDelegate for TasksModalDelegate {
                 String::new()
             };
 
-        if resolved_task.resolved.command_label != resolved_task.resolved_label {
+        if !resolved_task.resolved.command_label.is_empty()
+            && resolved_task.resolved.command_label != resolved_task.resolved_label
+        {
             if !tooltip_label_text.trim().is_empty() {
                 tooltip_label_text.push('\n');
             }
@@ -474,6 +476,37 @@ impl PickerDelegate for TasksModalDelegate {
                     .as_str(),
             );
         }
+        if !template.depends_on.is_empty() {
+            if !tooltip_label_text.trim().is_empty() {
+                tooltip_label_text.push('\n');
+            }
+            let order = match template.depends_order {
+                DependsOrder::Parallel => "in parallel",
+                DependsOrder::Sequential => "in sequence",
+            };
+            tooltip_label_text.push_str(&format!(
+                "Runs after {} ({order})",
+                template.depends_on.join(", ")
+            ));
+        }
+        let background_activity = self.workspace.upgrade().and_then(|workspace| {
+            workspace
+                .read(cx)
+                .project()
+                .read(cx)
+                .background_tasks(cx)
+                .find(|(spawned_task, _)| spawned_task.id == resolved_task.id)
+                .map(|(_, activity)| activity)
+        });
+        if let Some(activity) = background_activity {
+            if !tooltip_label_text.trim().is_empty() {
+                tooltip_label_text.push('\n');
+            }
+            tooltip_label_text.push_str(match activity {
+                TaskActivity::Busy => "Running in the background, busy",
+                TaskActivity::Idle => "Running in the background, ready",
+            });
+        }
         let tooltip_label = if tooltip_label_text.trim().is_empty() {
             None
         } else {
@@ -532,6 +565,19 @@ impl PickerDelegate for TasksModalDelegate {
                 .end_slot::<AnyElement>(
                     h_flex()
                         .gap_1()
+                        .when_some(background_activity, |this, activity| {
+                            this.child(Indicator::dot().color(match activity {
+                                TaskActivity::Busy => Color::Warning,
+                                TaskActivity::Idle => Color::Success,
+                            }))
+                        })
+                        .when(!template.depends_on.is_empty(), |this| {
+                            this.child(
+                                Icon::new(IconName::ListTree)
+                                    .color(Color::Muted)
+                                    .size(IconSize::Small),
+                            )
+                        })
                         .child(Label::new(truncate_and_trailoff(
                             &template
                                 .tags
@@ -7,6 +7,7 @@ use project::{Location, TaskContexts, TaskSourceKind, Worktree};
 use task::{RevealTarget, TaskContext, TaskId, TaskTemplate, TaskVariables, VariableName};
 use workspace::Workspace;
 
+mod input_modal;
 mod modal;
 
 pub use modal::{Rerun, ShowAttachModal, Spawn, TaskOverrides, TasksModal};
@@ -14,6 +15,7 @@ pub use modal::{Rerun, ShowAttachModal, Spawn, TaskOverrides, TasksModal};
 pub fn init(cx: &mut App) {
     cx.observe_new(
         |workspace: &mut Workspace, _: Option<&mut Window>, _: &mut Context<Workspace>| {
+            workspace.set_task_input_provider(input_modal::TaskInputModals);
             workspace
                 .register_action(spawn_task_or_modal)
                 .register_action(move |workspace, action: &modal::Rerun, window, cx| {
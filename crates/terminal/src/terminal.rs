@@ -30,7 +30,10 @@ use log::trace;
 
 use futures::{
     FutureExt,
-    channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+    channel::{
+        mpsc::{UnboundedReceiver, UnboundedSender, unbounded},
+        oneshot,
+    },
 };
 
 use itertools::Itertools as _;
@@ -45,12 +48,12 @@ use pty_info::{ProcessIdGetter, PtyProcessInfo};
 use serde::{Deserialize, Serialize};
 use settings::Settings;
 use smol::channel::{Receiver, Sender};
-use task::{HideStrategy, Shell, SpawnInTerminal};
+use task::{BackgroundPatterns, HideStrategy, Shell, SpawnInTerminal};
 use terminal_hyperlinks::RegexSearches;
 use terminal_settings::{AlternateScroll, CursorShape, TerminalSettings};
 use theme::{ActiveTheme, Theme};
 use urlencoding;
-use util::truncate_and_trailoff;
+use util::{ResultExt as _, truncate_and_trailoff};
 
 use std::{
     borrow::Cow,
@@ -60,7 +63,7 @@ use std::{
     path::PathBuf,
     process::ExitStatus,
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant},
 };
 use thiserror::Error;
 
@@ -125,6 +128,8 @@ pub enum Event {
     SelectionsChanged,
     NewNavigationTarget(Option<MaybeNavigationTarget>),
     Open(MaybeNavigationTarget),
+    /// A task running in the background became busy or idle, or exited.
+    TaskActivityChanged,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -360,6 +365,9 @@ impl TerminalBuilder {
 
         let terminal = Terminal {
             task: None,
+            background_matcher: None,
+            task_ready_txs: Vec::new(),
+            task_activity_update: None,
             terminal_type: TerminalType::DisplayOnly,
             completion_tx: None,
             term,
@@ -577,8 +585,14 @@ impl TerminalBuilder {
             let _io_thread = event_loop.spawn(); // DANGER
 
             let no_task = task.is_none();
+            let background_matcher = task.as_ref().and_then(|task| {
+                BackgroundMatcher::new(task.spawned_task.background.as_ref()?).log_err()
+            });
             let terminal = Terminal {
                 task,
+                background_matcher,
+                task_ready_txs: Vec::new(),
+                task_activity_update: None,
                 terminal_type: TerminalType::Pty {
                     pty_tx: Notifier(pty_tx),
                     info: pty_info,
@@ -829,6 +843,9 @@ pub struct Terminal {
     selection_phase: SelectionPhase,
     hyperlink_regex_searches: RegexSearches,
     task: Option<TaskState>,
+    background_matcher: Option<BackgroundMatcher>,
+    task_ready_txs: Vec<oneshot::Sender<()>>,
+    task_activity_update: Option<Task<()>>,
     vi_mode_enabled: bool,
     is_remote_terminal: bool,
     last_mouse_move_time: Instant,
@@ -857,6 +874,68 @@ pub struct TaskState {
     pub status: TaskStatus,
     pub completion_rx: Receiver<Option<ExitStatus>>,
     pub spawned_task: SpawnInTerminal,
+    /// What the task is doing, if it runs in the background.
+    pub activity: Option<TaskActivity>,
+}
+
+/// What a task running in the background is doing, according to its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskActivity {
+    /// The task is working, e.g. rebuilding after a file change.
+    Busy,
+    /// The task is done working and ready, e.g. a dev server that started listening.
+    Idle,
+}
+
+/// How many of the last output lines are matched against the patterns of a background task.
+const BACKGROUND_TASK_LINES_TO_MATCH: usize = 50;
+/// How long output is collected before it's matched against the patterns of a background task,
+/// so that a burst of output is only matched once.
+const BACKGROUND_TASK_MATCH_DELAY: Duration = Duration::from_millis(100);
+
+/// Matches the output of a background task against its patterns.
+pub struct BackgroundMatcher {
+    begins: fancy_regex::Regex,
+    ends: fancy_regex::Regex,
+}
+
+impl BackgroundMatcher {
+    pub fn new(patterns: &BackgroundPatterns) -> Result<Self> {
+        Ok(Self {
+            begins: fancy_regex::Regex::new(&patterns.begins_pattern)
+                .with_context(|| format!("invalid begins pattern {:?}", patterns.begins_pattern))?,
+            ends: fancy_regex::Regex::new(&patterns.ends_pattern)
+                .with_context(|| format!("invalid ends pattern {:?}", patterns.ends_pattern))?,
+        })
+    }
+
+    /// Determines the activity from the latest line that matches either of the patterns.
+    fn activity(&self, lines: &[String]) -> Option<TaskActivity> {
+        lines.iter().rev().find_map(|line| {
+            if self.ends.is_match(line).unwrap_or(false) {
+                Some(TaskActivity::Idle)
+            } else if self.begins.is_match(line).unwrap_or(false) {
+                Some(TaskActivity::Busy)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Drops the output up to the end of the last match of the begins pattern, which belongs to
+    /// earlier runs of the task.
+    pub fn latest_run_output<'a>(&self, output: &'a str) -> &'a str {
+        let mut latest_run_start = 0;
+        let mut line_start = 0;
+        for line in output.split_inclusive('\n') {
+            let text = line.trim_end_matches(['\r', '\n']);
+            if let Ok(Some(begin)) = self.begins.find(text) {
+                latest_run_start = line_start + begin.end();
+            }
+            line_start += line.len();
+        }
+        &output[latest_run_start..]
+    }
 }
 
 /// A status of the current terminal tab's task.
@@ -941,6 +1020,7 @@ impl Terminal {
             }
             AlacTermEvent::Wakeup => {
                 cx.emit(Event::Wakeup);
+                self.schedule_task_activity_update(cx);
 
                 if let TerminalType::Pty { info, .. } = &mut self.terminal_type {
                     if info.has_changed() {
@@ -2097,6 +2177,76 @@ impl Terminal {
         Task::ready(None)
     }
 
+    /// Waits for the task to get ready: tasks running in the background are ready once they
+    /// become idle, and resolve with a successful status then, other tasks once they finish.
+    pub fn wait_for_ready_task(&mut self, cx: &App) -> Task<Option<ExitStatus>> {
+        let Some(task) = self.task() else {
+            return Task::ready(None);
+        };
+        match task.activity {
+            Some(TaskActivity::Idle) => Task::ready(Some(ExitStatus::default())),
+            Some(TaskActivity::Busy) if task.status == TaskStatus::Running => {
+                let (ready_tx, ready_rx) = oneshot::channel();
+                self.task_ready_txs.push(ready_tx);
+                let completion = self.wait_for_completed_task(cx);
+                cx.spawn(async move |_| {
+                    futures::select_biased! {
+                        ready = ready_rx.fuse() => ready.ok().map(|()| ExitStatus::default()),
+                        status = completion.fuse() => status,
+                    }
+                })
+            }
+            _ => self.wait_for_completed_task(cx),
+        }
+    }
+
+    fn schedule_task_activity_update(&mut self, cx: &mut Context<Self>) {
+        if self.background_matcher.is_none() || self.task_activity_update.is_some() {
+            return;
+        }
+        self.task_activity_update = Some(cx.spawn(async move |terminal, cx| {
+            cx.background_executor()
+                .timer(BACKGROUND_TASK_MATCH_DELAY)
+                .await;
+            terminal
+                .update(cx, |terminal, cx| {
+                    terminal.task_activity_update = None;
+                    terminal.update_task_activity(cx);
+                })
+                .ok();
+        }));
+    }
+
+    fn update_task_activity(&mut self, cx: &mut Context<Self>) {
+        let Some(background_matcher) = &self.background_matcher else {
+            return;
+        };
+        if self
+            .task
+            .as_ref()
+            .is_none_or(|task| task.status != TaskStatus::Running)
+        {
+            return;
+        }
+        let lines = self.last_n_non_empty_lines(BACKGROUND_TASK_LINES_TO_MATCH);
+        let Some(activity) = background_matcher.activity(&lines) else {
+            return;
+        };
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+        if task.activity == Some(activity) {
+            return;
+        }
+        task.activity = Some(activity);
+        if activity == TaskActivity::Idle {
+            for ready_tx in self.task_ready_txs.drain(..) {
+                ready_tx.send(()).ok();
+            }
+        }
+        cx.emit(Event::TaskActivityChanged);
+    }
+
     fn register_task_finished(&mut self, error_code: Option<i32>, cx: &mut Context<Terminal>) {
         let e: Option<ExitStatus> = error_code.map(|code| {
             #[cfg(unix)]
@@ -2135,6 +2285,9 @@ impl Terminal {
                 task.status.register_terminal_exit();
             }
         };
+        if self.background_matcher.is_some() {
+            cx.emit(Event::TaskActivityChanged);
+        }
 
         let (finished_successfully, task_line, command_line) = task_summary(task, error_code);
         let mut lines_to_show = Vec::new();
@@ -2386,7 +2539,7 @@ pub fn rgba_color(r: u8, g: u8, b: u8) -> Hsla {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::{cell::RefCell, rc::Rc, time::Duration};
 
     use super::*;
     use crate::{
@@ -2666,6 +2819,123 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_background_task_activity() {
+        let matcher = BackgroundMatcher::new(&BackgroundPatterns {
+            begins_pattern: r"^\[Running".to_string(),
+            ends_pattern: r"^\[Finished running".to_string(),
+        })
+        .unwrap();
+        let lines = |lines: &[&str]| {
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matcher.activity(&lines(&["$ cargo watch"])), None);
+        assert_eq!(
+            matcher.activity(&lines(&["[Running 'cargo check']", "Checking crate"])),
+            Some(TaskActivity::Busy)
+        );
+        assert_eq!(
+            matcher.activity(&lines(&[
+                "[Running 'cargo check']",
+                "Finished dev profile",
+                "[Finished running. Exit status: 0]",
+            ])),
+            Some(TaskActivity::Idle)
+        );
+        assert_eq!(
+            matcher.activity(&lines(&[
+                "[Finished running. Exit status: 0]",
+                "[Running 'cargo check']",
+            ])),
+            Some(TaskActivity::Busy)
+        );
+
+        let output =
+            "[Running 'cargo check']\nerror: first\n[Running 'cargo check']\nerror: second\n";
+        assert_eq!(
+            matcher.latest_run_output(output),
+            " 'cargo check']\nerror: second\n"
+        );
+        assert_eq!(matcher.latest_run_output("error: only"), "error: only");
+
+        let lookaround = BackgroundMatcher::new(&BackgroundPatterns {
+            begins_pattern: r"^(?=\[Running)".to_string(),
+            ends_pattern: r"^\[Finished(?! with errors)".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            lookaround.latest_run_output("old\n[Running]\nnew\n"),
+            "[Running]\nnew\n"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_background_task_gets_ready_while_running(cx: &mut TestAppContext) {
+        let patterns = BackgroundPatterns {
+            begins_pattern: r"^\[Running".to_string(),
+            ends_pattern: r"^\[Finished running".to_string(),
+        };
+        let terminal = cx.new(|cx| {
+            let mut terminal = TerminalBuilder::new_display_only(
+                CursorShape::default(),
+                AlternateScroll::On,
+                None,
+                0,
+            )
+            .unwrap()
+            .subscribe(cx);
+            let (completion_tx, completion_rx) = smol::channel::unbounded();
+            terminal.completion_tx = Some(completion_tx);
+            terminal.background_matcher = BackgroundMatcher::new(&patterns).ok();
+            terminal.task = Some(TaskState {
+                status: TaskStatus::Running,
+                completion_rx,
+                spawned_task: SpawnInTerminal {
+                    background: Some(patterns.clone()),
+                    ..SpawnInTerminal::default()
+                },
+                activity: None,
+            });
+            terminal
+        });
+        let write_output = |output: &[u8], cx: &mut TestAppContext| {
+            terminal.update(cx, |terminal, cx| {
+                terminal.write_output(output, cx);
+                terminal.process_event(AlacTermEvent::Wakeup, cx);
+            });
+            cx.executor().advance_clock(BACKGROUND_TASK_MATCH_DELAY);
+            cx.run_until_parked();
+        };
+
+        write_output(b"[Running 'cargo check']\n", cx);
+        assert_eq!(
+            terminal.read_with(cx, |terminal, _| terminal.task().unwrap().activity),
+            Some(TaskActivity::Busy)
+        );
+        let ready = Rc::new(RefCell::new(None));
+        let wait_for_ready = terminal.update(cx, |terminal, cx| terminal.wait_for_ready_task(cx));
+        cx.spawn({
+            let ready = ready.clone();
+            |_| async move { *ready.borrow_mut() = Some(wait_for_ready.await) }
+        })
+        .detach();
+
+        write_output(b"Checking crate\n", cx);
+        assert_eq!(*ready.borrow(), None, "a busy task is not ready");
+
+        write_output(b"[Finished running. Exit status: 0]\n", cx);
+        assert_eq!(*ready.borrow(), Some(Some(ExitStatus::default())));
+        terminal.read_with(cx, |terminal, _| {
+            let task = terminal.task().unwrap();
+            assert_eq!(task.activity, Some(TaskActivity::Idle));
+            assert_eq!(task.status, TaskStatus::Running);
+        });
+    }
+
     #[test]
     fn test_mouse_to_cell_clamp() {
         let mut rng = rand::rng();
@@ -11,16 +11,18 @@ use collections::HashMap;
 use db::kvp::KEY_VALUE_STORE;
 use futures::{channel::oneshot, future::join_all};
 use gpui::{
-    Action, AnyView, App, AsyncApp, AsyncWindowContext, Context, Corner, Entity, EventEmitter,
-    ExternalPaths, FocusHandle, Focusable, IntoElement, ParentElement, Pixels, Render, Styled,
-    Task, WeakEntity, Window, actions,
+    Action, AnyView, App, AsyncApp, AsyncWindowContext, Context, Corner, Entity, EntityId,
+    EventEmitter, ExternalPaths, FocusHandle, Focusable, IntoElement, ParentElement, Pixels,
+    Render, Styled, Subscription, Task, WeakEntity, Window, actions,
 };
 use itertools::Itertools;
 use project::{Fs, Project, ProjectEntryId};
 use search::{BufferSearchBar, buffer_search::DivRegistrar};
 use settings::{Settings, TerminalDockPosition};
 use task::{RevealStrategy, RevealTarget, Shell, ShellBuilder, SpawnInTerminal, TaskId};
-use terminal::{Terminal, terminal_settings::TerminalSettings};
+use terminal::{
+    BackgroundMatcher, TaskActivity, TaskStatus, Terminal, terminal_settings::TerminalSettings,
+};
 use ui::{
     ButtonLike, Clickable, ContextMenu, FluentBuilder, PopoverMenu, SplitButton, Toggleable,
     Tooltip, prelude::*,
@@ -37,7 +39,7 @@ use workspace::{
     move_active_item, move_item, pane,
 };
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context as _, Result, anyhow};
 use zed_actions::assistant::InlineAssist;
 
 const TERMINAL_PANEL_KEY: &str = "TerminalPanel";
@@ -84,6 +86,8 @@ pub struct TerminalPanel {
     pending_serialization: Task<Option<()>>,
     pending_terminals_to_add: usize,
     deferred_tasks: HashMap<TaskId, Task<()>>,
+    /// Subscriptions reporting the problems of background tasks, by the terminal they run in.
+    background_task_reports: HashMap<EntityId, [Subscription; 2]>,
     assistant_enabled: bool,
     assistant_tab_bar_button: Option<AnyView>,
     active: bool,
@@ -104,6 +108,7 @@ impl TerminalPanel {
             height: None,
             pending_terminals_to_add: 0,
             deferred_tasks: HashMap::default(),
+            background_task_reports: HashMap::default(),
             assistant_enabled: false,
             assistant_tab_bar_button: None,
             active: false,
@@ -568,6 +573,19 @@ impl TerminalPanel {
             return self.spawn_in_new_terminal(task, window, cx);
         };
 
+        // Tasks running in the background are reused while they run, so that tasks depending
+        // on them don't restart them. Rerunning them directly still restarts them.
+        if task.is_dependency && task.background.is_some() {
+            let existing_terminal = existing.2.read(cx).terminal();
+            if existing_terminal
+                .read(cx)
+                .task()
+                .is_some_and(|task| task.status == TaskStatus::Running)
+            {
+                return Task::ready(Ok(existing_terminal.downgrade()));
+            }
+        }
+
         let (existing_item_index, task_pane, existing_terminal) = existing;
         if task.allow_concurrent_runs {
             return self.replace_terminal(
@@ -609,6 +627,57 @@ impl TerminalPanel {
         cx.spawn(async move |_, _| rx.await?)
     }
 
+    /// Reports the problems of a task running in the background each time it is done working,
+    /// until it finishes or its terminal is closed.
+    fn report_background_task_problems(
+        &mut self,
+        task: &SpawnInTerminal,
+        terminal: &WeakEntity<Terminal>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(terminal) = terminal.upgrade() else {
+            return;
+        };
+        let task = task.clone();
+        let terminal_id = terminal.entity_id();
+        let activity_subscription = cx.subscribe_in(
+            &terminal,
+            window,
+            move |terminal_panel, terminal, event, window, cx| {
+                if event != &terminal::Event::TaskActivityChanged {
+                    return;
+                }
+                let Some(terminal_task) = terminal.read(cx).task() else {
+                    return;
+                };
+                if terminal_task.status != TaskStatus::Running {
+                    terminal_panel
+                        .background_task_reports
+                        .remove(&terminal.entity_id());
+                    return;
+                }
+                if terminal_task.activity != Some(TaskActivity::Idle) {
+                    return;
+                }
+                let task = task.clone();
+                let terminal = terminal.downgrade();
+                cx.spawn_in(window, async move |terminal_panel, cx| {
+                    let terminal_panel = terminal_panel
+                        .upgrade()
+                        .context("terminal panel was dropped")?;
+                    report_task_problems(&terminal_panel, &task, &terminal, cx).await
+                })
+                .detach_and_log_err(cx);
+            },
+        );
+        let release_subscription = cx.observe_release(&terminal, move |terminal_panel, _, _| {
+            terminal_panel.background_task_reports.remove(&terminal_id);
+        });
+        self.background_task_reports
+            .insert(terminal_id, [activity_subscription, release_subscription]);
+    }
+
     fn spawn_in_new_terminal(
         &mut self,
         spawn_task: SpawnInTerminal,
@@ -1713,11 +1782,31 @@ impl workspace::TerminalProvider for TerminalProvider {
                 .ok()?
                 .await;
             match terminal {
+                Ok(terminal) if task.background.is_some() => {
+                    if !task.problem_matcher.is_empty() {
+                        terminal_panel
+                            .update_in(cx, |terminal_panel, window, cx| {
+                                terminal_panel
+                                    .report_background_task_problems(&task, &terminal, window, cx)
+                            })
+                            .ok()?;
+                    }
+                    let ready_status = terminal
+                        .update(cx, |terminal, cx| terminal.wait_for_ready_task(cx))
+                        .ok()?
+                        .await?;
+                    Some(Ok(ready_status))
+                }
                 Ok(terminal) => {
                     let exit_status = terminal
                         .read_with(cx, |terminal, cx| terminal.wait_for_completed_task(cx))
                         .ok()?
                         .await?;
+                    if !task.problem_matcher.is_empty() {
+                        report_task_problems(&terminal_panel, &task, &terminal, cx)
+                            .await
+                            .log_err();
+                    }
                     Some(Ok(exit_status))
                 }
                 Err(e) => Some(Err(e)),
@@ -1726,6 +1815,48 @@ impl workspace::TerminalProvider for TerminalProvider {
     }
 }
 
+/// Runs the task's problem matchers over its terminal output and reports the problems found
+/// as diagnostics of the project.
+async fn report_task_problems(
+    terminal_panel: &Entity<TerminalPanel>,
+    task: &SpawnInTerminal,
+    terminal: &WeakEntity<Terminal>,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let output = terminal.read_with(cx, |terminal, _| terminal.get_content())?;
+    let problem_matchers = task.problem_matcher.clone();
+    let background = task.background.clone();
+    let problems = cx
+        .background_spawn(async move {
+            // Tasks running in the background keep the output of their earlier runs around.
+            let output = match background {
+                Some(background) => BackgroundMatcher::new(&background)?
+                    .latest_run_output(&output)
+                    .to_string(),
+                None => output,
+            };
+            let mut problems = Vec::new();
+            for problem_matcher in &problem_matchers {
+                problems.extend(problem_matcher.match_output(&output)?);
+            }
+            anyhow::Ok(problems)
+        })
+        .await?;
+    let project = terminal_panel
+        .read_with(cx, |terminal_panel, cx| {
+            terminal_panel
+                .workspace
+                .upgrade()
+                .map(|workspace| workspace.read(cx).project().clone())
+        })?
+        .context("workspace was dropped")?;
+    project.update(cx, |project, cx| {
+        project.lsp_store().update(cx, |lsp_store, cx| {
+            lsp_store.report_task_problems(&task.full_label, task.cwd.as_deref(), problems, cx)
+        })
+    })?
+}
+
 struct InlineAssistTabBarButton {
     focus_handle: FocusHandle,
 }
@@ -18,8 +18,8 @@ use schemars::JsonSchema;
 use task::TaskId;
 use terminal::{
     Clear, Copy, Event, HoveredWord, MaybeNavigationTarget, Paste, ScrollLineDown, ScrollLineUp,
-    ScrollPageDown, ScrollPageUp, ScrollToBottom, ScrollToTop, ShowCharacterPalette, TaskState,
-    TaskStatus, Terminal, TerminalBounds, ToggleViMode,
+    ScrollPageDown, ScrollPageUp, ScrollToBottom, ScrollToTop, ShowCharacterPalette, TaskActivity,
+    TaskState, TaskStatus, Terminal, TerminalBounds, ToggleViMode,
     alacritty_terminal::{
         index::Point,
         term::{TermMode, point_to_viewport, search::RegexSearch},
@@ -888,7 +888,7 @@ fn subscribe_for_terminal_events(
                     }
                 }
 
-                Event::TitleChanged => {
+                Event::TitleChanged | Event::TaskActivityChanged => {
                     cx.emit(ItemEvent::UpdateTab);
                 }
 
@@ -1164,6 +1164,16 @@ impl Item for TerminalView {
 
         let (icon, icon_color, rerun_button) = match terminal.task() {
             Some(terminal_task) => match &terminal_task.status {
+                TaskStatus::Running if terminal_task.activity == Some(TaskActivity::Busy) => (
+                    IconName::ArrowCircle,
+                    Color::Muted,
+                    TerminalView::rerun_button(terminal_task),
+                ),
+                TaskStatus::Running if terminal_task.activity == Some(TaskActivity::Idle) => (
+                    IconName::PlayFilled,
+                    Color::Success,
+                    TerminalView::rerun_button(terminal_task),
+                ),
                 TaskStatus::Running => (
                     IconName::PlayFilled,
                     Color::Disabled,
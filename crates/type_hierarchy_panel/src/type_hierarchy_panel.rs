@@ -0,0 +1,849 @@
+//! A panel that shows the supertypes or subtypes of a type as a tree, using
+//! the type hierarchy requests of the language server.
+//!
+//! Types are only requested when an entry is expanded, and selecting an entry
+//! opens its definition.
+
+mod type_hierarchy_panel_settings;
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Result;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    Action, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    ScrollStrategy, SharedString, Task, UniformListScrollHandle, WeakEntity, Window, actions,
+    uniform_list,
+};
+use language::{Buffer, ToPoint as _};
+use project::{Fs, Project, TypeHierarchyItem};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Toast, Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::NotificationId,
+};
+
+pub use type_hierarchy_panel_settings::TypeHierarchyPanelSettings;
+
+const TYPE_HIERARCHY_PANEL_KEY: &str = "TypeHierarchyPanel";
+
+actions!(
+    type_hierarchy_panel,
+    [
+        /// Toggles focus on the type hierarchy panel.
+        ToggleFocus,
+        /// Shows the supertypes of the type under the cursor in the type hierarchy panel.
+        ShowSupertypes,
+        /// Shows the subtypes of the type under the cursor in the type hierarchy panel.
+        ShowSubtypes,
+        /// Switches the type hierarchy between supertypes and subtypes.
+        ToggleDirection,
+        /// Expands the selected entry, loading its types.
+        ExpandSelectedEntry,
+        /// Collapses the selected entry.
+        CollapseSelectedEntry,
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<TypeHierarchyPanel>(window, cx);
+            })
+            .register_action(|workspace, _: &ShowSupertypes, window, cx| {
+                show_type_hierarchy(workspace, TypeHierarchyDirection::Supertypes, window, cx);
+            })
+            .register_action(|workspace, _: &ShowSubtypes, window, cx| {
+                show_type_hierarchy(workspace, TypeHierarchyDirection::Subtypes, window, cx);
+            });
+    })
+    .detach();
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypeHierarchyDirection {
+    /// The types that the types extend or implement.
+    Supertypes,
+    /// The types that extend or implement the types.
+    #[default]
+    Subtypes,
+}
+
+/// Shows the type hierarchy of the type under the newest cursor of the active
+/// editor in the type hierarchy panel.
+fn show_type_hierarchy(
+    workspace: &mut Workspace,
+    direction: TypeHierarchyDirection,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let editor = editor.read(cx);
+    let head = editor.selections.newest_anchor().head();
+    let Some((buffer, position)) = editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    else {
+        return;
+    };
+
+    let items = workspace.project().update(cx, |project, cx| {
+        project.prepare_type_hierarchy(&buffer, position, cx)
+    });
+    cx.spawn_in(window, async move |workspace, cx| {
+        let items = items.await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            if items.is_empty() {
+                struct NoTypeHierarchy;
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<NoTypeHierarchy>(),
+                        "No type hierarchy found at the cursor",
+                    )
+                    .autohide(),
+                    cx,
+                );
+                return;
+            }
+
+            if let Some(panel) = workspace.focus_panel::<TypeHierarchyPanel>(window, cx) {
+                panel.update(cx, |panel, cx| {
+                    panel.set_hierarchy(buffer, items, direction, cx);
+                });
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+enum Children {
+    Unloaded,
+    Loading { _task: Task<()> },
+    Loaded(Vec<usize>),
+    Failed(SharedString),
+}
+
+struct TypeEntry {
+    item: TypeHierarchyItem,
+    parent: Option<usize>,
+    depth: usize,
+    expanded: bool,
+    children: Children,
+}
+
+/// The types a hierarchy was requested for.
+struct Hierarchy {
+    /// The buffer the hierarchy was requested in, to route the requests for
+    /// types to the language server that produced the items.
+    buffer: Entity<Buffer>,
+    roots: Vec<TypeHierarchyItem>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedTypeHierarchyPanel {
+    width: Option<Pixels>,
+    direction: Option<TypeHierarchyDirection>,
+}
+
+pub struct TypeHierarchyPanel {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    width: Option<Pixels>,
+    pending_serialization: Task<Option<()>>,
+    hierarchy: Option<Hierarchy>,
+    direction: TypeHierarchyDirection,
+    entries: Vec<TypeEntry>,
+    visible_entries: Vec<usize>,
+    selected_entry: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+}
+
+impl TypeHierarchyPanel {
+    pub fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let fs = workspace.app_state().fs.clone();
+        let workspace = workspace.weak_handle();
+        cx.new(|cx| Self {
+            workspace,
+            project,
+            fs,
+            width: None,
+            pending_serialization: Task::ready(None),
+            hierarchy: None,
+            direction: TypeHierarchyDirection::default(),
+            entries: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_entry: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+        })
+    }
+
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = if let Some(panel) = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(TYPE_HIERARCHY_PANEL_KEY) })
+                .await
+                .log_err()
+                .flatten()
+            {
+                Some(serde_json::from_str::<SerializedTypeHierarchyPanel>(
+                    &panel,
+                )?)
+            } else {
+                None
+            };
+
+            workspace.update(cx, |workspace, cx| {
+                let panel = Self::new(workspace, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|w| w.round());
+                        panel.direction = serialized_panel.direction.unwrap_or_default();
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        let direction = Some(self.direction);
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        TYPE_HIERARCHY_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedTypeHierarchyPanel { width, direction })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    pub fn direction(&self) -> TypeHierarchyDirection {
+        self.direction
+    }
+
+    /// Replaces the shown hierarchy with the one of the given types.
+    pub fn set_hierarchy(
+        &mut self,
+        buffer: Entity<Buffer>,
+        roots: Vec<TypeHierarchyItem>,
+        direction: TypeHierarchyDirection,
+        cx: &mut Context<Self>,
+    ) {
+        self.hierarchy = Some(Hierarchy { buffer, roots });
+        if self.direction != direction {
+            self.direction = direction;
+            self.serialize(cx);
+        }
+        self.reset_entries(cx);
+    }
+
+    fn toggle_direction(&mut self, _: &ToggleDirection, _: &mut Window, cx: &mut Context<Self>) {
+        self.direction = match self.direction {
+            TypeHierarchyDirection::Supertypes => TypeHierarchyDirection::Subtypes,
+            TypeHierarchyDirection::Subtypes => TypeHierarchyDirection::Supertypes,
+        };
+        self.serialize(cx);
+        self.reset_entries(cx);
+    }
+
+    /// Rebuilds the tree from its roots, loading their types right away.
+    fn reset_entries(&mut self, cx: &mut Context<Self>) {
+        self.entries = self
+            .hierarchy
+            .iter()
+            .flat_map(|hierarchy| &hierarchy.roots)
+            .map(|item| TypeEntry {
+                item: item.clone(),
+                parent: None,
+                depth: 0,
+                expanded: false,
+                children: Children::Unloaded,
+            })
+            .collect();
+        self.selected_entry = (!self.entries.is_empty()).then_some(0);
+        for ix in 0..self.entries.len() {
+            self.expand_entry(ix, cx);
+        }
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn update_visible_entries(&mut self) {
+        fn push_visible(entries: &[TypeEntry], ix: usize, visible: &mut Vec<usize>) {
+            visible.push(ix);
+            let entry = &entries[ix];
+            if let (true, Children::Loaded(children)) = (entry.expanded, &entry.children) {
+                for &child in children {
+                    push_visible(entries, child, visible);
+                }
+            }
+        }
+
+        self.visible_entries.clear();
+        for ix in 0..self.entries.len() {
+            if self.entries[ix].parent.is_none() {
+                push_visible(&self.entries, ix, &mut self.visible_entries);
+            }
+        }
+    }
+
+    fn expand_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(hierarchy) = &self.hierarchy else {
+            return;
+        };
+        let entry = &mut self.entries[ix];
+        entry.expanded = true;
+        if let Children::Unloaded | Children::Failed(_) = entry.children {
+            let item = entry.item.clone();
+            let types = self.project.update(cx, |project, cx| match self.direction {
+                TypeHierarchyDirection::Supertypes => {
+                    project.supertypes(&hierarchy.buffer, &item, cx)
+                }
+                TypeHierarchyDirection::Subtypes => project.subtypes(&hierarchy.buffer, &item, cx),
+            });
+            let task = cx.spawn(async move |this, cx| {
+                let types = types.await;
+                this.update(cx, |this, cx| this.set_types(ix, types, cx))
+                    .ok();
+            });
+            self.entries[ix].children = Children::Loading { _task: task };
+        }
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn set_types(
+        &mut self,
+        ix: usize,
+        types: Result<Vec<TypeHierarchyItem>>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(parent) = self.entries.get(ix) else {
+            return;
+        };
+        let depth = parent.depth + 1;
+        let children = match types {
+            Ok(types) => {
+                let first_child = self.entries.len();
+                self.entries.extend(types.into_iter().map(|item| TypeEntry {
+                    item,
+                    parent: Some(ix),
+                    depth,
+                    expanded: false,
+                    children: Children::Unloaded,
+                }));
+                Children::Loaded((first_child..self.entries.len()).collect())
+            }
+            Err(error) => {
+                log::error!("failed to load type hierarchy: {error:#}");
+                Children::Failed(format!("{error:#}").into())
+            }
+        };
+        self.entries[ix].children = children;
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn collapse_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.entries[ix].expanded = false;
+        self.update_visible_entries();
+        cx.notify();
+    }
+
+    fn toggle_expanded(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.entries[ix].expanded {
+            self.collapse_entry(ix, cx);
+        } else {
+            self.expand_entry(ix, cx);
+        }
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.selected_entry else {
+            return;
+        };
+        if !self.entries[ix].expanded {
+            self.expand_entry(ix, cx);
+        } else if let Children::Loaded(children) = &self.entries[ix].children
+            && let Some(&child) = children.first()
+        {
+            self.select_entry(child, cx);
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.selected_entry else {
+            return;
+        };
+        if self.entries[ix].expanded {
+            self.collapse_entry(ix, cx);
+        } else if let Some(parent) = self.entries[ix].parent {
+            self.select_entry(parent, cx);
+        }
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        if let Some(visible_ix) = self.visible_entries.iter().position(|&entry| entry == ix) {
+            self.scroll_handle
+                .scroll_to_item(visible_ix, ScrollStrategy::Center);
+        }
+        cx.notify();
+    }
+
+    fn selected_visible_ix(&self) -> Option<usize> {
+        let selected = self.selected_entry?;
+        self.visible_entries.iter().position(|&ix| ix == selected)
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next = match self.selected_visible_ix() {
+            Some(visible_ix) => visible_ix + 1,
+            None => 0,
+        };
+        if let Some(&ix) = self.visible_entries.get(next) {
+            self.select_entry(ix, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous = match self.selected_visible_ix() {
+            Some(visible_ix) => visible_ix.saturating_sub(1),
+            None => 0,
+        };
+        if let Some(&ix) = self.visible_entries.get(previous) {
+            self.select_entry(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.open_entry(ix, true, window, cx);
+        }
+    }
+
+    /// Opens the definition of an entry in the active pane.
+    fn open_entry(&mut self, ix: usize, focus: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let location = self.entries[ix].item.location.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let pane = workspace.active_pane().clone();
+                let editor = workspace.open_project_item::<Editor>(
+                    pane,
+                    location.buffer,
+                    true,
+                    focus,
+                    true,
+                    true,
+                    window,
+                    cx,
+                );
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(
+                        SelectionEffects::scroll(Autoscroll::center()),
+                        window,
+                        cx,
+                        |s| s.select_anchor_ranges([location.range]),
+                    );
+                });
+            })
+            .ok();
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry = &self.entries[ix];
+        let buffer = entry.item.location.buffer.read(cx);
+        let file_name = buffer
+            .file()
+            .map(|file| file.file_name(cx).to_string())
+            .unwrap_or_default();
+        let row = entry.item.location.range.start.to_point(buffer).row + 1;
+        let is_leaf = matches!(&entry.children, Children::Loaded(children) if children.is_empty());
+
+        ListItem::new(("type-hierarchy-entry", ix))
+            .spacing(ListItemSpacing::Sparse)
+            .indent_level(entry.depth)
+            .indent_step_size(px(12.))
+            .toggle((!is_leaf).then_some(entry.expanded))
+            .on_toggle(cx.listener(move |this, _, _, cx| this.toggle_expanded(ix, cx)))
+            .toggle_state(self.selected_entry == Some(ix))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .overflow_hidden()
+                    .child(Label::new(entry.item.name.clone()))
+                    .children(entry.item.detail.clone().map(|detail| {
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate()
+                    }))
+                    .child(
+                        Label::new(format!("{file_name}:{row}"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .end_slot::<AnyElement>(match &entry.children {
+                Children::Loading { .. } => Some(
+                    Label::new("Loading…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .into_any_element(),
+                ),
+                Children::Failed(error) => Some(
+                    div()
+                        .id(("type-hierarchy-error", ix))
+                        .tooltip(Tooltip::text(error.clone()))
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Error),
+                        )
+                        .into_any_element(),
+                ),
+                Children::Unloaded | Children::Loaded(_) => None,
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.select_entry(ix, cx);
+                this.open_entry(ix, false, window, cx);
+            }))
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (title, toggle_tooltip) = match self.direction {
+            TypeHierarchyDirection::Supertypes => ("Supertypes", "Show Subtypes"),
+            TypeHierarchyDirection::Subtypes => ("Subtypes", "Show Supertypes"),
+        };
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(title).color(Color::Muted))
+            .child(
+                IconButton::new("toggle-type-hierarchy-direction", IconName::ArrowRightLeft)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::for_action_title(toggle_tooltip, &ToggleDirection))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_direction(&ToggleDirection, window, cx)
+                    })),
+            )
+    }
+
+    #[cfg(test)]
+    fn visible_entries(&self) -> Vec<(usize, String)> {
+        self.visible_entries
+            .iter()
+            .map(|&ix| (self.entries[ix].depth, self.entries[ix].item.name.clone()))
+            .collect()
+    }
+}
+
+impl Focusable for TypeHierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for TypeHierarchyPanel {}
+
+impl Panel for TypeHierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "TypeHierarchyPanel"
+    }
+
+    fn panel_key() -> &'static str {
+        TYPE_HIERARCHY_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        TypeHierarchyPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.type_hierarchy_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| TypeHierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        TypeHierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTree)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
+        Some("Type Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+}
+
+impl Render for TypeHierarchyPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("TypeHierarchyPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::toggle_direction))
+            .size_full()
+            .child(self.render_header(cx))
+            .map(|this| {
+                if self.hierarchy.is_none() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new(
+                                "Show the supertypes or subtypes of a type from the editor.",
+                            )
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            "type-hierarchy",
+                            self.visible_entries.len(),
+                            cx.processor(|this, range: Range<usize>, _window, cx| {
+                                range
+                                    .map(|visible_ix| {
+                                        let ix = this.visible_entries[visible_ix];
+                                        this.render_entry(ix, cx).into_any_element()
+                                    })
+                                    .collect()
+                            }),
+                        )
+                        .track_scroll(&self.scroll_handle)
+                        .p_1()
+                        .flex_1(),
+                    )
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt as _;
+    use gpui::{TestAppContext, VisualTestContext};
+    use language::FakeLspAdapter;
+    use project::FakeFs;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use util::{path, rel_path::rel_path};
+    use workspace::AppState;
+
+    #[gpui::test]
+    async fn test_type_hierarchy_panel(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "a.rs": "trait A {}\ntrait B: A {}\ntrait C: B {}\n",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+        language_registry.add(language::rust_lang());
+        let mut fake_servers = language_registry.register_fake_lsp(
+            "Rust",
+            FakeLspAdapter {
+                capabilities: lsp::ServerCapabilities {
+                    type_hierarchy_provider: Some(lsp::OneOf::Left(true)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = TypeHierarchyPanel::new(workspace, cx);
+            workspace.add_panel(panel.clone(), window, cx);
+            panel
+        });
+        let worktree_id = project.update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("a.rs")), None, true, window, cx)
+            })
+            .await
+            .unwrap();
+        let fake_server = fake_servers.next().await.unwrap();
+
+        // Each trait is on its own row, and extends the one on the previous row.
+        fn lsp_item(row: u32) -> lsp::TypeHierarchyItem {
+            let range = lsp::Range::new(lsp::Position::new(row, 6), lsp::Position::new(row, 7));
+            lsp::TypeHierarchyItem {
+                name: ["A", "B", "C"][row as usize].to_string(),
+                kind: lsp::SymbolKind::INTERFACE,
+                tags: None,
+                detail: None,
+                uri: lsp::Uri::from_file_path(path!("/dir/a.rs")).unwrap(),
+                range,
+                selection_range: range,
+                data: None,
+            }
+        }
+
+        fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>(
+            |_, _| async move { Ok(Some(vec![lsp_item(0)])) },
+        );
+        let subtypes_requests = Arc::new(AtomicUsize::new(0));
+        fake_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>({
+            let subtypes_requests = subtypes_requests.clone();
+            move |params, _| {
+                subtypes_requests.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    let row = params.item.range.start.line;
+                    Ok(Some(
+                        (row < 2).then(|| lsp_item(row + 1)).into_iter().collect(),
+                    ))
+                }
+            }
+        });
+        fake_server.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>(
+            |_, _| async move { Ok(Some(Vec::new())) },
+        );
+
+        cx.dispatch_action(ShowSubtypes);
+        cx.run_until_parked();
+
+        // The subtypes of the root are loaded right away, but not theirs.
+        assert_eq!(
+            visible_entries(&panel, cx),
+            [(0, "A".to_string()), (1, "B".to_string())]
+        );
+        assert_eq!(subtypes_requests.load(Ordering::SeqCst), 1);
+
+        cx.dispatch_action(menu::SelectNext);
+        cx.dispatch_action(ExpandSelectedEntry);
+        cx.run_until_parked();
+        assert_eq!(
+            visible_entries(&panel, cx),
+            [
+                (0, "A".to_string()),
+                (1, "B".to_string()),
+                (2, "C".to_string())
+            ]
+        );
+        assert_eq!(subtypes_requests.load(Ordering::SeqCst), 2);
+
+        cx.dispatch_action(CollapseSelectedEntry);
+        assert_eq!(
+            visible_entries(&panel, cx),
+            [(0, "A".to_string()), (1, "B".to_string())]
+        );
+
+        // Opening an entry selects its name in the editor.
+        cx.dispatch_action(menu::Confirm);
+        cx.run_until_parked();
+        let selection = workspace.update(cx, |workspace, cx| {
+            let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+            editor.update(cx, |editor, cx| {
+                editor
+                    .selections
+                    .newest::<language::Point>(&editor.display_snapshot(cx))
+                    .range()
+            })
+        });
+        assert_eq!(
+            selection,
+            language::Point::new(1, 6)..language::Point::new(1, 7)
+        );
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.toggle_direction(&ToggleDirection, window, cx)
+        });
+        cx.run_until_parked();
+        panel.read_with(cx, |panel, _| {
+            assert_eq!(panel.direction(), TypeHierarchyDirection::Supertypes);
+        });
+        assert_eq!(visible_entries(&panel, cx), [(0, "A".to_string())]);
+    }
+
+    fn visible_entries(
+        panel: &Entity<TypeHierarchyPanel>,
+        cx: &mut VisualTestContext,
+    ) -> Vec<(usize, String)> {
+        panel.read_with(cx, |panel, _| panel.visible_entries())
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            crate::init(cx);
+            editor::init(cx);
+            state
+        })
+    }
+}
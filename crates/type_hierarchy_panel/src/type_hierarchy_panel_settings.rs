@@ -0,0 +1,23 @@
+use gpui::Pixels;
+use settings::{RegisterSetting, Settings};
+use ui::px;
+use workspace::dock::DockPosition;
+
+#[derive(Debug, RegisterSetting)]
+pub struct TypeHierarchyPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+}
+
+impl Settings for TypeHierarchyPanelSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let panel = content.type_hierarchy_panel.as_ref().unwrap();
+
+        Self {
+            button: panel.button.unwrap(),
+            dock: panel.dock.unwrap().into(),
+            default_width: panel.default_width.map(px).unwrap(),
+        }
+    }
+}
@@ -41,6 +41,7 @@ use zed_actions::{OpenDocs, RevealTarget};
 
 use crate::{
     ToggleMarksView, ToggleRegistersView, Vim,
+    helix::{HelixShellBehavior, HelixShellExec},
     motion::{EndOfDocument, Motion, MotionKind, StartOfDocument},
     normal::{
         JoinLines,
@@ -48,6 +49,7 @@ use crate::{
     },
     object::Object,
     state::{Mark, Mode},
+    vimrc::{self, Vimrc},
     visual::VisualDeleteLine,
 };
 
@@ -1605,6 +1607,18 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
         VimCommand::str(("map", ""), "vim::OpenDefaultKeymap"),
         VimCommand::new(("h", "elp"), OpenDocs),
     ]
+    .into_iter()
+    .chain(HelixShellBehavior::ALL.into_iter().map(|behavior| {
+        VimCommand::new(
+            (behavior.command_name(), ""),
+            HelixShellExec {
+                command: String::new(),
+                behavior,
+            },
+        )
+        .args(move |_, command| Some(HelixShellExec { command, behavior }.boxed_clone()))
+    }))
+    .collect()
 }
 
 struct VimCommands(Vec<VimCommand>);
@@ -1653,7 +1667,16 @@ fn wrap_count(action: Box<dyn Action>, range: &CommandRange) -> Option<Box<dyn A
 }
 
 pub fn command_interceptor(
+    input: &str,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut App,
+) -> Task<CommandInterceptResult> {
+    intercept_command(input, true, workspace, cx)
+}
+
+fn intercept_command(
     mut input: &str,
+    expand_user_commands: bool,
     workspace: WeakEntity<Workspace>,
     cx: &mut App,
 ) -> Task<CommandInterceptResult> {
@@ -1666,6 +1689,22 @@ pub fn command_interceptor(
     let has_trailing_space = query.ends_with(" ");
     let mut query = query.as_str().trim();
 
+    // User commands are expanded once, so that commands expanding into themselves can't recurse.
+    if let Some(expanded) = Vimrc::expand_user_command(query, cx).filter(|_| expand_user_commands) {
+        let string = input.to_string();
+        let positions = generate_positions(&string, &(range_prefix.clone() + query));
+        let task = intercept_command(&(range_prefix + &expanded), false, workspace, cx);
+        return cx.spawn(async move |_| {
+            let mut result = task.await;
+            result.results.truncate(1);
+            for item in &mut result.results {
+                item.string = string.clone();
+                item.positions = positions.clone();
+            }
+            result
+        });
+    }
+
     let on_matching_lines = (query.starts_with('g') || query.starts_with('v'))
         .then(|| {
             let (pattern, range, search, invert) = OnMatchingLines::parse(query, &range)?;
@@ -1690,6 +1729,8 @@ pub fn command_interceptor(
             }
             .boxed_clone(),
         )
+    } else if let Some(action) = vimrc::command_action(query) {
+        Some(action)
     } else if query.starts_with("se ") || query.starts_with("set ") {
         let (prefix, option) = query.split_once(' ').unwrap();
         let mut commands = VimOption::possible_commands(option);
@@ -2107,7 +2148,7 @@ impl Vim {
         }
     }
 
-    fn prepare_shell_command(
+    pub(crate) fn prepare_shell_command(
         &mut self,
         command: &str,
         _: &mut Window,
@@ -2292,6 +2333,9 @@ impl ShellExec {
                     show_summary: false,
                     show_command: false,
                     show_rerun: false,
+                    problem_matcher: Vec::new(),
+                    background: None,
+                    is_dependency: false,
                 };
 
                 let task_status = workspace.spawn_in_terminal(spawn_in_terminal, window, cx);
@@ -3,6 +3,8 @@ mod duplicate;
 mod object;
 mod paste;
 mod select;
+mod selections;
+mod shell;
 
 use editor::display_map::DisplaySnapshot;
 use editor::{
@@ -18,8 +20,10 @@ use text::{Bias, SelectionGoal};
 use workspace::searchable::FilteredSearchRange;
 use workspace::searchable::{self, Direction};
 
+pub use shell::{HelixShellBehavior, HelixShellExec};
+
 use crate::motion::{self, MotionKind};
-use crate::state::SearchState;
+use crate::state::{HelixRegexOperation, SearchState};
 use crate::{
     Vim,
     motion::{Motion, right},
@@ -56,6 +60,32 @@ actions!(
         HelixSelectNext,
         /// Delete the selection and enter edit mode, without yanking the selection.
         HelixSelectPrevious,
+        /// Split each selection on the matches of a given pattern.
+        HelixSplitSelections,
+        /// Split each selection into its lines, without the line breaks.
+        HelixSplitSelectionsOnNewline,
+        /// Keep only the selections that match a given pattern.
+        HelixKeepSelections,
+        /// Remove the selections that match a given pattern.
+        HelixRemoveSelections,
+        /// Align the selections by padding them with spaces.
+        HelixAlignSelections,
+        /// Make the previous selection the newest one.
+        HelixRotateSelectionsBackward,
+        /// Make the next selection the newest one.
+        HelixRotateSelectionsForward,
+        /// Move the contents of each selection to the previous selection.
+        HelixRotateSelectionContentsBackward,
+        /// Move the contents of each selection to the next selection.
+        HelixRotateSelectionContentsForward,
+        /// Pipe each selection through a shell command, replacing it with the output.
+        HelixPipe,
+        /// Pipe each selection into a shell command, ignoring the output.
+        HelixPipeTo,
+        /// Insert the output of a shell command before each selection.
+        HelixInsertOutput,
+        /// Append the output of a shell command after each selection.
+        HelixAppendOutput,
     ]
 );
 
@@ -80,6 +110,68 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::helix_substitute_no_yank);
     Vim::action(editor, cx, Vim::helix_select_next);
     Vim::action(editor, cx, Vim::helix_select_previous);
+    Vim::action(editor, cx, |vim, _: &HelixSplitSelections, window, cx| {
+        vim.helix_prompt_regex(HelixRegexOperation::Split, window, cx);
+    });
+    Vim::action(editor, cx, |vim, _: &HelixKeepSelections, window, cx| {
+        vim.helix_prompt_regex(HelixRegexOperation::Keep, window, cx);
+    });
+    Vim::action(editor, cx, |vim, _: &HelixRemoveSelections, window, cx| {
+        vim.helix_prompt_regex(HelixRegexOperation::Remove, window, cx);
+    });
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixSplitSelectionsOnNewline, window, cx| {
+            vim.helix_split_selections_on_newline(window, cx);
+        },
+    );
+    Vim::action(editor, cx, |vim, _: &HelixAlignSelections, window, cx| {
+        vim.helix_align_selections(window, cx);
+    });
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixRotateSelectionsBackward, window, cx| {
+            vim.helix_rotate_selections(false, window, cx);
+        },
+    );
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixRotateSelectionsForward, window, cx| {
+            vim.helix_rotate_selections(true, window, cx);
+        },
+    );
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixRotateSelectionContentsBackward, window, cx| {
+            vim.helix_rotate_selection_contents(false, window, cx);
+        },
+    );
+    Vim::action(
+        editor,
+        cx,
+        |vim, _: &HelixRotateSelectionContentsForward, window, cx| {
+            vim.helix_rotate_selection_contents(true, window, cx);
+        },
+    );
+    Vim::action(editor, cx, |vim, _: &HelixPipe, window, cx| {
+        vim.helix_shell_prompt(HelixShellBehavior::Replace, window, cx);
+    });
+    Vim::action(editor, cx, |vim, _: &HelixPipeTo, window, cx| {
+        vim.helix_shell_prompt(HelixShellBehavior::Ignore, window, cx);
+    });
+    Vim::action(editor, cx, |vim, _: &HelixInsertOutput, window, cx| {
+        vim.helix_shell_prompt(HelixShellBehavior::Insert, window, cx);
+    });
+    Vim::action(editor, cx, |vim, _: &HelixAppendOutput, window, cx| {
+        vim.helix_shell_prompt(HelixShellBehavior::Append, window, cx);
+    });
+    Vim::action(editor, cx, |vim, action: &HelixShellExec, window, cx| {
+        action.run(vim, window, cx)
+    });
 }
 
 impl Vim {
@@ -510,6 +602,17 @@ impl Vim {
         _: &HelixSelectRegex,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.helix_prompt_regex(HelixRegexOperation::Select, window, cx);
+    }
+
+    /// Opens the search bar to read a regex that is applied to the current selections
+    /// with `operation` once the search is submitted.
+    fn helix_prompt_regex(
+        &mut self,
+        operation: HelixRegexOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         Vim::take_forced_motion(cx);
         let Some(pane) = self.pane(window, cx) else {
@@ -537,7 +640,8 @@ impl Vim {
                         Some(FilteredSearchRange::Selection),
                         window,
                         cx,
-                    ) {
+                    ) && operation == HelixRegexOperation::Select
+                    {
                         cx.spawn_in(window, async move |search_bar, cx| {
                             if search.await.is_ok() {
                                 search_bar.update_in(cx, |search_bar, window, cx| {
@@ -555,7 +659,7 @@ impl Vim {
                         prior_selections,
                         prior_operator: self.operator_stack.last().cloned(),
                         prior_mode: self.mode,
-                        helix_select: true,
+                        helix_regex: Some(operation),
                     }
                 });
             }
@@ -1666,4 +1770,99 @@ mod test {
             Mode::HelixNormal,
         );
     }
+
+    #[gpui::test]
+    async fn test_helix_split_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«one, two,threeˇ» four", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-s , space *");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("«oneˇ», «twoˇ»,«threeˇ» four", Mode::HelixNormal);
+
+        cx.set_state(
+            indoc! {"
+                «one
+                two
+                ˇ»three"},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("alt-s");
+        cx.assert_state(
+            indoc! {"
+                «oneˇ»
+                «twoˇ»
+                three"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_helix_keep_and_remove_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("shift-k t");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("one «twoˇ» «threeˇ»", Mode::HelixNormal);
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-shift-k t");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("«oneˇ» two three", Mode::HelixNormal);
+
+        // Removing every selection leaves them untouched.
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-shift-k .");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        cx.assert_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_helix_align_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state(
+            indoc! {"
+                a «=ˇ» 1 «#ˇ» a
+                bbb «=ˇ» 2 «#ˇ» b
+                cc «=ˇ» 333 «#ˇ» c"},
+            Mode::HelixNormal,
+        );
+        cx.simulate_keystrokes("&");
+        cx.assert_state(
+            indoc! {"
+                a   «=ˇ» 1   «#ˇ» a
+                bbb «=ˇ» 2   «#ˇ» b
+                cc  «=ˇ» 333 «#ˇ» c"},
+            Mode::HelixNormal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_helix_rotate_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«aˇ» «bˇ» «cˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes(") ,");
+        cx.assert_state("«aˇ» b c", Mode::HelixNormal);
+
+        cx.set_state("«aˇ» «bˇ» «cˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("( ,");
+        cx.assert_state("a «bˇ» c", Mode::HelixNormal);
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-)");
+        cx.assert_state("«threeˇ» «oneˇ» «twoˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("alt-(");
+        cx.assert_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+    }
 }
@@ -0,0 +1,333 @@
+use std::ops::Range;
+
+use editor::{MultiBufferOffset, MultiBufferSnapshot, ToOffset};
+use gpui::Context;
+use language::Point;
+use regex::{Regex, RegexBuilder};
+use search::{BufferSearchBar, SearchOptions};
+use ui::Window;
+
+use crate::{Vim, state::HelixRegexOperation};
+
+impl Vim {
+    /// Applies the regex typed into the search bar to the selections that were active
+    /// when the search bar was opened.
+    pub(crate) fn helix_regex_submit(
+        &mut self,
+        operation: HelixRegexOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(pane) = self.pane(window, cx) else {
+            return;
+        };
+        let regex = pane.update(cx, |pane, cx| {
+            let search_bar = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>()?;
+            search_bar.update(cx, |search_bar, cx| {
+                let query = search_bar.query(cx);
+                let case_sensitive = search_bar.has_search_option(SearchOptions::CASE_SENSITIVE);
+                search_bar.dismiss(&Default::default(), window, cx);
+                if query.is_empty() {
+                    return None;
+                }
+                RegexBuilder::new(&query)
+                    .case_insensitive(!case_sensitive)
+                    .build()
+                    .ok()
+            })
+        });
+        let prior_selections = std::mem::take(&mut self.search.prior_selections);
+        self.search.helix_regex = None;
+
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let ranges = prior_selections
+                .iter()
+                .map(|range| range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot))
+                .collect::<Vec<_>>();
+            let ranges = match regex {
+                Some(regex) => match operation {
+                    HelixRegexOperation::Select => return,
+                    HelixRegexOperation::Split => split_ranges(&snapshot, ranges, &regex),
+                    HelixRegexOperation::Keep => keep_ranges(&snapshot, ranges, &regex, true),
+                    HelixRegexOperation::Remove => keep_ranges(&snapshot, ranges, &regex, false),
+                },
+                None => ranges,
+            };
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(ranges);
+            });
+        });
+    }
+
+    /// Splits every selection into one selection per line, dropping the line breaks.
+    pub(super) fn helix_split_selections_on_newline(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let newline = Regex::new(r"\r?\n").expect("newline regex is valid");
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let ranges = editor
+                .selections
+                .all::<MultiBufferOffset>(&editor.display_snapshot(cx))
+                .into_iter()
+                .map(|selection| selection.tail()..selection.head())
+                .collect();
+            let ranges = split_ranges(&snapshot, ranges, &newline);
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(ranges);
+            });
+        });
+    }
+
+    /// Inserts spaces before the selections so that the selections sharing a column
+    /// index on their lines start at the same column.
+    /// Selections spanning multiple lines can't be aligned.
+    pub(super) fn helix_align_selections(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let selections = editor.selections.all::<Point>(&editor.display_snapshot(cx));
+            if selections
+                .iter()
+                .any(|selection| selection.start.row != selection.end.row)
+            {
+                return;
+            }
+
+            let starts = selections
+                .iter()
+                .map(|selection| {
+                    let line_start = Point::new(selection.start.row, 0);
+                    let column = snapshot
+                        .text_for_range(line_start..selection.start)
+                        .flat_map(str::chars)
+                        .count();
+                    (selection.start.row, column)
+                })
+                .collect::<Vec<_>>();
+            let padding = alignment_padding(&starts);
+            if padding.iter().all(|padding| *padding == 0) {
+                return;
+            }
+
+            let mut edits = Vec::new();
+            let mut new_selections = Vec::with_capacity(selections.len());
+            let mut row_shift = 0;
+            let mut last_row = None;
+            for (mut selection, padding) in selections.into_iter().zip(padding) {
+                if last_row != Some(selection.start.row) {
+                    row_shift = 0;
+                    last_row = Some(selection.start.row);
+                }
+                if padding > 0 {
+                    edits.push((selection.start..selection.start, " ".repeat(padding)));
+                }
+                row_shift += padding as u32;
+                selection.start.column += row_shift;
+                selection.end.column += row_shift;
+                new_selections.push(selection);
+            }
+
+            editor.transact(window, cx, |editor, window, cx| {
+                editor.edit(edits, cx);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select(new_selections);
+                });
+            });
+        });
+    }
+
+    /// Makes the selection `count` places after (or before) the newest one the newest.
+    pub(super) fn helix_rotate_selections(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let count = Vim::take_count(cx).unwrap_or(1);
+        self.update_editor(cx, |_, editor, cx| {
+            let mut selections = editor
+                .selections
+                .all::<MultiBufferOffset>(&editor.display_snapshot(cx));
+            let len = selections.len();
+            if len < 2 {
+                return;
+            }
+            let newest_id = editor.selections.newest_anchor().id;
+            let Some(newest) = selections
+                .iter()
+                .position(|selection| selection.id == newest_id)
+            else {
+                return;
+            };
+            let target = if forward {
+                (newest + count) % len
+            } else {
+                (newest + len - count % len) % len
+            };
+            editor.change_selections(Default::default(), window, cx, |s| {
+                selections[target].id = s.new_selection_id();
+                s.select(selections);
+            });
+        });
+    }
+
+    /// Moves the text of every selection `count` selections forward (or backward),
+    /// wrapping around at the ends.
+    pub(super) fn helix_rotate_selection_contents(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let count = Vim::take_count(cx).unwrap_or(1);
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let selections = editor
+                .selections
+                .all::<MultiBufferOffset>(&editor.display_snapshot(cx));
+            let len = selections.len();
+            if len < 2 {
+                return;
+            }
+            let mut contents = selections
+                .iter()
+                .map(|selection| {
+                    snapshot
+                        .text_for_range(selection.range())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            if forward {
+                contents.rotate_right(count % len);
+            } else {
+                contents.rotate_left(count % len);
+            }
+
+            let mut edits = Vec::with_capacity(len);
+            let mut new_selections = Vec::with_capacity(len);
+            let mut delta = 0isize;
+            for (mut selection, text) in selections.into_iter().zip(contents) {
+                let old_len = selection.end.0 - selection.start.0;
+                edits.push((selection.range(), text.clone()));
+                selection.start = selection.start + delta;
+                selection.end = selection.start + text.len();
+                delta += text.len() as isize - old_len as isize;
+                new_selections.push(selection);
+            }
+
+            editor.transact(window, cx, |editor, window, cx| {
+                editor.edit(edits, cx);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select(new_selections);
+                });
+            });
+        });
+    }
+}
+
+/// Splits each (possibly reversed) range on the matches of `regex`.
+/// Empty ranges and empty matches are left alone.
+fn split_ranges(
+    snapshot: &MultiBufferSnapshot,
+    ranges: Vec<Range<MultiBufferOffset>>,
+    regex: &Regex,
+) -> Vec<Range<MultiBufferOffset>> {
+    let mut result = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        let reversed = range.start > range.end;
+        let (start, end) = if reversed {
+            (range.end, range.start)
+        } else {
+            (range.start, range.end)
+        };
+        if start == end {
+            result.push(range);
+            continue;
+        }
+
+        let text = snapshot.text_for_range(start..end).collect::<String>();
+        let mut piece_start = start;
+        let mut push_piece = |piece: Range<MultiBufferOffset>| {
+            if reversed {
+                result.push(piece.end..piece.start);
+            } else {
+                result.push(piece);
+            }
+        };
+        for found in regex.find_iter(&text) {
+            if found.is_empty() {
+                continue;
+            }
+            push_piece(piece_start..start + found.start());
+            piece_start = start + found.end();
+        }
+        if piece_start < end {
+            push_piece(piece_start..end);
+        }
+    }
+    result
+}
+
+/// Keeps the ranges whose text matches `regex` (or doesn't, when `keep_matching` is false).
+/// If no range would remain, all of them are kept.
+fn keep_ranges(
+    snapshot: &MultiBufferSnapshot,
+    ranges: Vec<Range<MultiBufferOffset>>,
+    regex: &Regex,
+    keep_matching: bool,
+) -> Vec<Range<MultiBufferOffset>> {
+    let kept = ranges
+        .iter()
+        .filter(|range| {
+            let range = if range.start > range.end {
+                range.end..range.start
+            } else {
+                (*range).clone()
+            };
+            let text = snapshot.text_for_range(range).collect::<String>();
+            regex.is_match(&text) == keep_matching
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if kept.is_empty() { ranges } else { kept }
+}
+
+/// Given the `(row, column)` start of every selection in order, returns the number of
+/// spaces to insert before each selection so that the n-th selections of all lines line up.
+fn alignment_padding(starts: &[(u32, usize)]) -> Vec<usize> {
+    // Indices into `starts`, grouped by line.
+    let mut lines: Vec<Vec<usize>> = Vec::new();
+    let mut last_row = None;
+    for (ix, (row, _)) in starts.iter().enumerate() {
+        if last_row == Some(*row) {
+            lines.last_mut().unwrap().push(ix);
+        } else {
+            lines.push(vec![ix]);
+            last_row = Some(*row);
+        }
+    }
+
+    let mut padding = vec![0; starts.len()];
+    let mut line_shifts = vec![0; lines.len()];
+    let columns = lines.iter().map(Vec::len).max().unwrap_or(0);
+    for column in 0..columns {
+        let target = lines
+            .iter()
+            .zip(&line_shifts)
+            .filter_map(|(line, shift)| Some(starts[*line.get(column)?].1 + shift))
+            .max()
+            .unwrap_or(0);
+        for (line, shift) in lines.iter().zip(line_shifts.iter_mut()) {
+            let Some(&ix) = line.get(column) else {
+                continue;
+            };
+            let delta = target - (starts[ix].1 + *shift);
+            padding[ix] = delta;
+            *shift += delta;
+        }
+    }
+    padding
+}
@@ -0,0 +1,299 @@
+use std::{ops::Range, process::Stdio};
+
+use anyhow::{Context as _, Result, bail};
+use editor::{Anchor, MultiBufferOffset};
+use futures::AsyncWriteExt as _;
+use gpui::{Action, Context, Window};
+use util::ResultExt as _;
+use workspace::notifications::NotifyResultExt as _;
+
+use crate::Vim;
+
+/// What to do with the output of a shell command that is run on the selections.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HelixShellBehavior {
+    /// Pipe each selection into the command and replace it with the output.
+    Replace,
+    /// Pipe each selection into the command and discard the output.
+    Ignore,
+    /// Run the command once and insert its output before each selection.
+    Insert,
+    /// Run the command once and insert its output after each selection.
+    Append,
+}
+
+impl HelixShellBehavior {
+    pub const ALL: [HelixShellBehavior; 4] = [
+        HelixShellBehavior::Replace,
+        HelixShellBehavior::Ignore,
+        HelixShellBehavior::Insert,
+        HelixShellBehavior::Append,
+    ];
+
+    /// The name of the command that runs a shell command with this behavior.
+    pub fn command_name(&self) -> &'static str {
+        match self {
+            HelixShellBehavior::Replace => "pipe",
+            HelixShellBehavior::Ignore => "pipe-to",
+            HelixShellBehavior::Insert => "insert-output",
+            HelixShellBehavior::Append => "append-output",
+        }
+    }
+
+    fn pipes_selections(&self) -> bool {
+        matches!(
+            self,
+            HelixShellBehavior::Replace | HelixShellBehavior::Ignore
+        )
+    }
+}
+
+/// Runs a shell command on every selection.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub struct HelixShellExec {
+    pub(crate) command: String,
+    pub(crate) behavior: HelixShellBehavior,
+}
+
+impl Vim {
+    /// Opens the command palette to read the shell command to run with `behavior`.
+    pub(super) fn helix_shell_prompt(
+        &mut self,
+        behavior: HelixShellBehavior,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace(window) else {
+            return;
+        };
+        workspace.update(cx, |workspace, cx| {
+            command_palette::CommandPalette::toggle(
+                workspace,
+                &format!("{} ", behavior.command_name()),
+                window,
+                cx,
+            );
+        })
+    }
+}
+
+/// For every selection, the range the output of a shell command goes into and the text that is
+/// piped into the command.
+type ShellTargets = Vec<(Range<Anchor>, String)>;
+
+impl HelixShellExec {
+    pub fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        if self.command.trim().is_empty() {
+            return;
+        }
+        let Some(workspace) = vim.workspace(window) else {
+            return;
+        };
+        let project = workspace.read(cx).project().clone();
+        let command = vim.prepare_shell_command(&self.command, window, cx);
+        let behavior = self.behavior;
+        let Some(targets) = vim.helix_shell_targets(behavior, cx) else {
+            return;
+        };
+
+        let runs = if behavior.pipes_selections() {
+            targets.len()
+        } else {
+            1
+        };
+        let processes = (0..runs)
+            .map(|_| project.update(cx, |project, cx| project.exec_in_shell(command.clone(), cx)))
+            .collect::<Vec<_>>();
+
+        let command = self.command.clone();
+        let task = cx.spawn_in(window, async move |vim, cx| {
+            let outputs = async {
+                let mut outputs = Vec::with_capacity(processes.len());
+                for (process_task, (_, input)) in processes.into_iter().zip(&targets) {
+                    let mut process = process_task.await?;
+                    process.stdout(Stdio::piped());
+                    process.stderr(Stdio::piped());
+                    if behavior.pipes_selections() {
+                        process.stdin(Stdio::piped());
+                    } else {
+                        process.stdin(Stdio::null());
+                    }
+
+                    let mut running = process
+                        .spawn()
+                        .with_context(|| format!("Failed to run `{command}`"))?;
+                    if let Some(mut stdin) = running.stdin.take() {
+                        let input = input.clone();
+                        cx.background_spawn(async move {
+                            stdin.write_all(input.as_bytes()).await.log_err();
+                            stdin.flush().await.log_err();
+                        })
+                        .detach();
+                    }
+
+                    let output = cx.background_spawn(running.output()).await?;
+                    if !output.status.success() {
+                        bail!(
+                            "`{command}` failed ({}): {}",
+                            output.status,
+                            String::from_utf8_lossy(&output.stderr).trim()
+                        );
+                    }
+                    outputs.push(String::from_utf8_lossy(&output.stdout).into_owned());
+                }
+                anyhow::Ok(outputs)
+            }
+            .await;
+
+            vim.update_in(cx, |vim, window, cx| {
+                vim.finish_helix_shell(behavior, targets, outputs, window, cx);
+                vim.cancel_running_command(window, cx);
+            })
+            .log_err();
+        });
+        vim.running_command.replace(task);
+    }
+}
+
+impl Vim {
+    fn helix_shell_targets(
+        &mut self,
+        behavior: HelixShellBehavior,
+        cx: &mut Context<Self>,
+    ) -> Option<ShellTargets> {
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            editor
+                .selections
+                .all::<MultiBufferOffset>(&editor.display_snapshot(cx))
+                .into_iter()
+                .map(|selection| {
+                    let (start, end) = match behavior {
+                        HelixShellBehavior::Replace | HelixShellBehavior::Ignore => {
+                            (selection.start, selection.end)
+                        }
+                        HelixShellBehavior::Insert => (selection.start, selection.start),
+                        HelixShellBehavior::Append => (selection.end, selection.end),
+                    };
+                    let input = snapshot
+                        .text_for_range(selection.range())
+                        .collect::<String>();
+                    (
+                        snapshot.anchor_before(start)..snapshot.anchor_after(end),
+                        input,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    /// Puts the outputs of a shell command into the editor, or shows why the command failed.
+    fn finish_helix_shell(
+        &mut self,
+        behavior: HelixShellBehavior,
+        targets: ShellTargets,
+        outputs: Result<Vec<String>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let outputs = match outputs {
+            Ok(outputs) => outputs,
+            Err(error) => {
+                if let Some(workspace) = self.workspace(window) {
+                    workspace.update(cx, |workspace, cx| {
+                        Err::<(), _>(error).notify_err(workspace, cx);
+                    });
+                }
+                return;
+            }
+        };
+        if outputs.is_empty() || behavior == HelixShellBehavior::Ignore {
+            return;
+        }
+        self.update_editor(cx, |_, editor, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                let edits = targets
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, (range, input))| {
+                        let output = &outputs[ix.min(outputs.len() - 1)];
+                        (range.clone(), trim_output(output, input).to_string())
+                    })
+                    .collect::<Vec<_>>();
+                editor.edit(edits, cx);
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_anchor_ranges(targets.into_iter().map(|(range, _)| range));
+                });
+            })
+        });
+    }
+}
+
+/// Drops the trailing line break most commands print, unless the selection ended with one too.
+fn trim_output<'a>(output: &'a str, input: &str) -> &'a str {
+    if input.ends_with('\n') {
+        return output;
+    }
+    output
+        .strip_suffix("\r\n")
+        .or_else(|| output.strip_suffix('\n'))
+        .unwrap_or(output)
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::anyhow;
+
+    use super::HelixShellBehavior;
+    use crate::{VimAddon, state::Mode, test::VimTestContext};
+
+    #[gpui::test]
+    async fn test_pipe(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+        let vim =
+            cx.update_editor(|editor, _, _| editor.addon::<VimAddon>().unwrap().entity.clone());
+
+        cx.set_state("«oneˇ» and «twoˇ»", Mode::HelixNormal);
+        cx.update_entity(vim.clone(), |vim, window, cx| {
+            let targets = vim
+                .helix_shell_targets(HelixShellBehavior::Replace, cx)
+                .unwrap();
+            assert_eq!(
+                targets
+                    .iter()
+                    .map(|(_, input)| input.as_str())
+                    .collect::<Vec<_>>(),
+                ["one", "two"]
+            );
+            vim.finish_helix_shell(
+                HelixShellBehavior::Replace,
+                targets,
+                Ok(vec!["ONE\n".to_string(), "TWO\n".to_string()]),
+                window,
+                cx,
+            );
+        });
+        cx.assert_state("«ONEˇ» and «TWOˇ»", Mode::HelixNormal);
+
+        cx.update_entity(vim, |vim, window, cx| {
+            let targets = vim
+                .helix_shell_targets(HelixShellBehavior::Replace, cx)
+                .unwrap();
+            vim.finish_helix_shell(
+                HelixShellBehavior::Replace,
+                targets,
+                Err(anyhow!("`false` failed (exit status: 1)")),
+                window,
+                cx,
+            );
+        });
+        cx.assert_state("«ONEˇ» and «TWOˇ»", Mode::HelixNormal);
+        assert_eq!(
+            cx.workspace(|workspace, _, _| workspace.notification_ids().len()),
+            1,
+            "A failed command should be reported to the user"
+        );
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     Vim,
     command::CommandRange,
     motion::Motion,
-    state::{Mode, SearchState},
+    state::{HelixRegexOperation, Mode, SearchState},
 };
 
 /// Moves to the next search match.
@@ -195,7 +195,7 @@ impl Vim {
                         prior_selections,
                         prior_operator: self.operator_stack.last().cloned(),
                         prior_mode,
-                        helix_select: false,
+                        helix_regex: None,
                     }
                 });
             }
@@ -212,6 +212,14 @@ impl Vim {
     }
 
     pub fn search_submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(operation) = self
+            .search
+            .helix_regex
+            .filter(|operation| *operation != HelixRegexOperation::Select)
+        {
+            self.helix_regex_submit(operation, window, cx);
+            return;
+        }
         self.store_visual_marks(window, cx);
         let Some(pane) = self.pane(window, cx) else {
             return;
@@ -219,7 +227,7 @@ impl Vim {
         let new_selections = self.editor_selections(window, cx);
         let result = pane.update(cx, |pane, cx| {
             let search_bar = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>()?;
-            if self.search.helix_select {
+            if self.search.helix_regex == Some(HelixRegexOperation::Select) {
                 search_bar.update(cx, |search_bar, cx| {
                     search_bar.select_all_matches(&Default::default(), window, cx)
                 });
@@ -209,6 +209,8 @@ pub struct VimGlobals {
 
     pub dot_recording: bool,
     pub dot_replaying: bool,
+    /// The number of `:noremap` mappings whose keystrokes are being replayed.
+    pub replaying_noremap: usize,
 
     /// pre_count is the number before an operator is specified (3 in 3d2d)
     pub pre_count: Option<usize>,
@@ -990,7 +992,20 @@ pub struct SearchState {
     pub prior_selections: Vec<Range<Anchor>>,
     pub prior_operator: Option<Operator>,
     pub prior_mode: Mode,
-    pub helix_select: bool,
+    pub helix_regex: Option<HelixRegexOperation>,
+}
+
+/// What a helix command that prompts for a regex does with the selections once it's submitted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HelixRegexOperation {
+    /// Select all matches within the selections.
+    Select,
+    /// Split the selections on matches.
+    Split,
+    /// Keep only the selections that contain a match.
+    Keep,
+    /// Remove the selections that contain a match.
+    Remove,
 }
 
 impl Operator {
@@ -17,6 +17,7 @@ mod replace;
 mod rewrap;
 mod state;
 mod surrounds;
+mod vimrc;
 mod visual;
 
 use crate::normal::paste::Paste as VimPaste;
@@ -52,6 +53,7 @@ use theme::ThemeSettings;
 use ui::{IntoElement, SharedString, px};
 use vim_mode_setting::HelixModeSetting;
 use vim_mode_setting::VimModeSetting;
+pub use vimrc::{NoremapKeystrokes, Vimrc, handle_init_file_changes};
 use workspace::{self, Pane, Workspace};
 
 use crate::{
@@ -269,6 +271,7 @@ actions!(
 /// Initializes the `vim` crate.
 pub fn init(cx: &mut App) {
     VimGlobals::register(cx);
+    vimrc::init(cx);
 
     cx.observe_new(Vim::register).detach();
 
@@ -944,6 +947,7 @@ impl Vim {
             visual::register(editor, cx);
             change_list::register(editor, cx);
             digraph::register(editor, cx);
+            vimrc::register(editor, cx);
 
             if editor.is_focused(window) {
                 cx.defer_in(window, |vim, window, cx| {
@@ -1365,6 +1369,10 @@ impl Vim {
             context.add("VimCount");
         }
 
+        if cx.global::<VimGlobals>().replaying_noremap > 0 {
+            context.add("VimNoremap");
+        }
+
         if let Some(active_operator) = active_operator {
             if active_operator.is_waiting(self.mode) {
                 if matches!(active_operator, Operator::Literal { .. }) {
@@ -0,0 +1,990 @@
+//! Support for a vimrc-style init file.
+//!
+//! The init file (and any file passed to `:source`) is read line by line. Mappings defined with
+//! `:map`, `:noremap` and their mode-specific variants are translated into key bindings that are
+//! layered over the user's keymap, and `:command` defines ex commands that expand into other ex
+//! commands. Anything else is reported as an error rather than silently ignored.
+
+use anyhow::{Result, anyhow, bail};
+use editor::Editor;
+use fs::Fs;
+use futures::{FutureExt as _, StreamExt as _, channel::mpsc, future::BoxFuture};
+use gpui::{
+    Action, App, AppContext as _, Context, Global, KeyBinding, KeyBindingContextPredicate,
+    Keystroke, NoAction,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use settings::{KeybindSource, Settings};
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+};
+use util::ResultExt as _;
+use vim_mode_setting::VimModeSetting;
+use workspace::{
+    Workspace,
+    notifications::{
+        NotificationId, NotifyResultExt as _, dismiss_app_notification, show_app_notification,
+        simple_message_notification::MessageNotification,
+    },
+};
+
+use crate::Vim;
+
+/// Files sourced from other files are only followed up to this depth, to avoid loops.
+const MAX_SOURCE_DEPTH: usize = 16;
+
+/// Replays keystrokes without triggering mappings from the vim init file, like `:noremap`.
+#[derive(Clone, Deserialize, JsonSchema, PartialEq, Action)]
+#[action(namespace = vim)]
+pub struct NoremapKeystrokes(pub String);
+
+/// Runs a line of the vim init file as an ex command.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct VimrcCommandLine {
+    pub(crate) line: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MapMode {
+    Normal,
+    Visual,
+    OperatorPending,
+    Insert,
+}
+
+impl MapMode {
+    fn for_prefix(prefix: Option<char>) -> Vec<Self> {
+        match prefix {
+            None => vec![Self::Normal, Self::Visual, Self::OperatorPending],
+            Some('n') => vec![Self::Normal],
+            Some('v' | 'x') => vec![Self::Visual],
+            Some('o') => vec![Self::OperatorPending],
+            Some('i') => vec![Self::Insert],
+            Some(_) => Vec::new(),
+        }
+    }
+
+    fn context(&self) -> &'static str {
+        match self {
+            Self::Normal => "vim_mode == normal",
+            Self::Visual => "vim_mode == visual",
+            Self::OperatorPending => "vim_mode == operator",
+            Self::Insert => "vim_mode == insert",
+        }
+    }
+}
+
+/// A single line of a vim init file.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum VimrcCommand {
+    SetLeader(String),
+    Map {
+        modes: Vec<MapMode>,
+        lhs: String,
+        rhs: String,
+        recursive: bool,
+    },
+    Unmap {
+        modes: Vec<MapMode>,
+        lhs: String,
+    },
+    Command {
+        name: String,
+        replacement: String,
+        overwrite: bool,
+    },
+    DelCommand(String),
+    Source(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum MapTarget {
+    Keystrokes(String),
+    Nop,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Mapping {
+    mode: MapMode,
+    lhs: String,
+    rhs: MapTarget,
+    recursive: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct UserCommand {
+    name: String,
+    replacement: String,
+}
+
+impl UserCommand {
+    /// Substitutes `<args>`, `<q-args>` and `<bang>` in the replacement.
+    fn expand(&self, bang: bool, args: &str) -> String {
+        self.replacement
+            .replace("<q-args>", &quote_string(args))
+            .replace("<args>", args)
+            .replace("<bang>", if bang { "!" } else { "" })
+    }
+}
+
+/// The mappings and user commands defined by the vim init file and at runtime.
+pub struct Vimrc {
+    leader: String,
+    mappings: Vec<Mapping>,
+    commands: Vec<UserCommand>,
+}
+
+impl Default for Vimrc {
+    fn default() -> Self {
+        Self {
+            leader: "\\".to_string(),
+            mappings: Vec::new(),
+            commands: Vec::new(),
+        }
+    }
+}
+
+impl Global for Vimrc {}
+
+// (prefix, suffix, mode prefix, recursive)
+const MAP_COMMANDS: &[(&str, &str, Option<char>, bool)] = &[
+    ("map", "", None, true),
+    ("no", "remap", None, false),
+    ("nm", "ap", Some('n'), true),
+    ("nn", "oremap", Some('n'), false),
+    ("vm", "ap", Some('v'), true),
+    ("vn", "oremap", Some('v'), false),
+    ("xm", "ap", Some('x'), true),
+    ("xn", "oremap", Some('x'), false),
+    ("om", "ap", Some('o'), true),
+    ("ono", "remap", Some('o'), false),
+    ("im", "ap", Some('i'), true),
+    ("ino", "remap", Some('i'), false),
+];
+
+// (prefix, suffix, mode prefix)
+const UNMAP_COMMANDS: &[(&str, &str, Option<char>)] = &[
+    ("unm", "ap", None),
+    ("nun", "map", Some('n')),
+    ("vu", "nmap", Some('v')),
+    ("xu", "nmap", Some('x')),
+    ("ou", "nmap", Some('o')),
+    ("iu", "nmap", Some('i')),
+];
+
+fn matches_command(word: &str, prefix: &str, suffix: &str) -> bool {
+    word.strip_prefix(prefix)
+        .is_some_and(|rest| suffix.starts_with(rest))
+}
+
+/// Splits `line` into the command name, whether it was followed by `!`, and its arguments.
+fn split_command(line: &str) -> (&str, bool, &str) {
+    let name_len = line
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(line.len());
+    let (name, rest) = line.split_at(name_len);
+    match rest.strip_prefix('!') {
+        Some(rest) => (name, true, rest.trim()),
+        None => (name, false, rest.trim()),
+    }
+}
+
+/// Quotes `text` as a vim double-quoted string, the way `<q-args>` does.
+fn quote_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for ch in text.chars() {
+        if ch == '"' || ch == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(ch);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Returns true if `word` names one of the ex commands that are handled by this module.
+fn is_vimrc_command(word: &str) -> bool {
+    MAP_COMMANDS
+        .iter()
+        .any(|(prefix, suffix, _, _)| matches_command(word, prefix, suffix))
+        || UNMAP_COMMANDS
+            .iter()
+            .any(|(prefix, suffix, _)| matches_command(word, prefix, suffix))
+        || matches_command(word, "com", "mand")
+        || matches_command(word, "delc", "ommand")
+        || matches_command(word, "so", "urce")
+        || word == "let"
+}
+
+/// Parses a line of a vim init file. Returns `None` for blank lines and comments.
+pub(crate) fn parse_line(line: &str) -> Result<Option<VimrcCommand>> {
+    let line = line.trim_start().trim_start_matches(':').trim_end();
+    if line.is_empty() || line.starts_with('"') {
+        return Ok(None);
+    }
+
+    let (name, bang, args) = split_command(line);
+
+    if let Some((_, _, mode, recursive)) = MAP_COMMANDS
+        .iter()
+        .find(|(prefix, suffix, _, _)| matches_command(name, prefix, suffix))
+    {
+        if bang {
+            bail!("`{name}!` is not supported, command-line mode mappings are not available");
+        }
+        let (lhs, rhs) = parse_map_arguments(args)?;
+        return Ok(Some(VimrcCommand::Map {
+            modes: MapMode::for_prefix(*mode),
+            lhs,
+            rhs,
+            recursive: *recursive,
+        }));
+    }
+
+    if let Some((_, _, mode)) = UNMAP_COMMANDS
+        .iter()
+        .find(|(prefix, suffix, _)| matches_command(name, prefix, suffix))
+    {
+        if bang {
+            bail!("`{name}!` is not supported, command-line mode mappings are not available");
+        }
+        let lhs = strip_map_modifiers(args)?;
+        if lhs.is_empty() {
+            bail!("`{name}` requires an argument");
+        }
+        return Ok(Some(VimrcCommand::Unmap {
+            modes: MapMode::for_prefix(*mode),
+            lhs: lhs.to_string(),
+        }));
+    }
+
+    if matches_command(name, "com", "mand") {
+        let mut args = args;
+        while let Some(rest) = args.strip_prefix('-') {
+            let (attribute, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let attribute_name = attribute.split('=').next().unwrap_or_default();
+            if !matches!(attribute_name, "nargs" | "bar" | "bang" | "complete") {
+                bail!("command attribute `-{attribute}` is not supported");
+            }
+            args = rest.trim_start();
+        }
+        let Some((name, replacement)) = args.split_once(char::is_whitespace) else {
+            if args.is_empty() {
+                bail!("listing user commands is not supported");
+            }
+            bail!("`:command {args}` requires a replacement");
+        };
+        return Ok(Some(VimrcCommand::Command {
+            name: name.to_string(),
+            replacement: replacement.trim().to_string(),
+            overwrite: bang,
+        }));
+    }
+
+    if matches_command(name, "delc", "ommand") {
+        if args.is_empty() {
+            bail!("`{name}` requires an argument");
+        }
+        return Ok(Some(VimrcCommand::DelCommand(args.to_string())));
+    }
+
+    if matches_command(name, "so", "urce") {
+        if args.is_empty() {
+            bail!("`{name}` requires a file name");
+        }
+        return Ok(Some(VimrcCommand::Source(args.to_string())));
+    }
+
+    if name == "let" {
+        let Some((variable, value)) = args.split_once('=') else {
+            bail!("invalid `let` expression: {args}");
+        };
+        let variable = variable.trim();
+        if variable != "mapleader" && variable != "g:mapleader" {
+            bail!("`let {variable}` is not supported, only `mapleader` can be set");
+        }
+        return Ok(Some(VimrcCommand::SetLeader(parse_string(value.trim())?)));
+    }
+
+    Err(anyhow!("unsupported command `{line}`"))
+}
+
+fn strip_map_modifiers(mut args: &str) -> Result<&str> {
+    loop {
+        let Some(end) = args.starts_with('<').then(|| args.find('>')).flatten() else {
+            return Ok(args);
+        };
+        let modifier = args[1..end].to_ascii_lowercase();
+        match modifier.as_str() {
+            "silent" | "nowait" | "unique" | "special" => {}
+            "buffer" | "expr" | "script" => bail!("`<{modifier}>` mappings are not supported"),
+            _ => return Ok(args),
+        }
+        args = args[end + 1..].trim_start();
+    }
+}
+
+fn parse_map_arguments(args: &str) -> Result<(String, String)> {
+    let args = strip_map_modifiers(args)?;
+    let Some((lhs, rhs)) = args.split_once(char::is_whitespace) else {
+        if args.is_empty() {
+            bail!("listing mappings is not supported");
+        }
+        bail!("mapping for `{args}` has no right-hand side");
+    };
+    Ok((lhs.to_string(), rhs.trim().to_string()))
+}
+
+/// Parses a vim string literal, translating `\<Key>` escapes into key notation.
+fn parse_string(value: &str) -> Result<String> {
+    if let Some(inner) = value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        return Ok(inner.replace("''", "'"));
+    }
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    else {
+        bail!("expected a string, found {value}");
+    };
+    let mut result = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            // `\<Space>` is written as the key notation `<Space>`.
+            Some('<') => result.push('<'),
+            Some('t') => result.push('\t'),
+            Some(c) => result.push(c),
+            None => bail!("unterminated escape in {value}"),
+        }
+    }
+    Ok(result)
+}
+
+/// Translates vim key notation such as `<C-w>j` or `<leader>f` into the space separated
+/// keystrokes used by zed's keymap.
+pub(crate) fn translate_keys(notation: &str, leader: &str) -> Result<String> {
+    let mut keys = Vec::new();
+    let mut rest = notation;
+    while let Some(c) = rest.chars().next() {
+        if c == '<'
+            && let Some(end) = rest.find('>')
+            && let Some(translated) = translate_special_key(&rest[1..end], leader)?
+        {
+            keys.push(translated);
+            rest = &rest[end + 1..];
+            continue;
+        }
+        keys.push(match c {
+            ' ' => "space".to_string(),
+            '\t' => "tab".to_string(),
+            c => c.to_string(),
+        });
+        rest = &rest[c.len_utf8()..];
+    }
+    for key in keys.iter().flat_map(|keys| keys.split(' ')) {
+        Keystroke::parse(key).map_err(|_| anyhow!("invalid key `{key}` in `{notation}`"))?;
+    }
+    Ok(keys.join(" "))
+}
+
+/// Translates the name of a `<...>` key. Returns `None` when the name is not a key, in which case
+/// vim treats the `<` literally.
+fn translate_special_key(name: &str, leader: &str) -> Result<Option<String>> {
+    let lowercase = name.to_ascii_lowercase();
+    let key = match lowercase.as_str() {
+        "cr" | "enter" | "return" => "enter",
+        "esc" => "escape",
+        "space" => "space",
+        "tab" => "tab",
+        "bs" | "backspace" => "backspace",
+        "del" => "delete",
+        "up" | "down" | "left" | "right" | "home" | "end" | "insert" | "pageup" | "pagedown" => {
+            lowercase.as_str()
+        }
+        "lt" => "<",
+        "bar" => "|",
+        "bslash" => "\\",
+        "leader" => return translate_keys(leader, "\\").map(Some),
+        "nop" => bail!("`<Nop>` can only be used as the whole right-hand side of a mapping"),
+        "plug" | "sid" | "snr" | "cmd" | "localleader" => bail!("`<{name}>` is not supported"),
+        _ => {
+            if let Some(number) = lowercase.strip_prefix('f')
+                && number
+                    .parse::<u8>()
+                    .is_ok_and(|number| (1..=24).contains(&number))
+            {
+                return Ok(Some(lowercase.clone()));
+            }
+            return translate_modified_key(name, leader);
+        }
+    };
+    Ok(Some(key.to_string()))
+}
+
+fn translate_modified_key(name: &str, leader: &str) -> Result<Option<String>> {
+    let Some((modifiers, key)) = name.rsplit_once('-') else {
+        return Ok(None);
+    };
+    let mut result = String::new();
+    let mut control = false;
+    for modifier in modifiers.split('-') {
+        match modifier.to_ascii_lowercase().as_str() {
+            "c" => {
+                control = true;
+                result.push_str("ctrl-")
+            }
+            "s" => result.push_str("shift-"),
+            "a" | "m" => result.push_str("alt-"),
+            "d" => result.push_str("cmd-"),
+            _ => return Ok(None),
+        }
+    }
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if control => result.extend(c.to_lowercase()),
+        (Some(c), None) => result.push(c),
+        _ => match translate_special_key(key, leader)? {
+            Some(key) if !key.contains(' ') => result.push_str(&key),
+            _ => return Ok(None),
+        },
+    }
+    Ok(Some(result))
+}
+
+impl Vimrc {
+    /// Returns the key bindings for the mappings defined in the vim init file, or at runtime with
+    /// `:map` and friends. These are intended to be bound after the user's keymap.
+    pub fn key_bindings(cx: &App) -> Vec<KeyBinding> {
+        if !VimModeSetting::get_global(cx).0 {
+            return Vec::new();
+        }
+        let Some(vimrc) = cx.try_global::<Self>() else {
+            return Vec::new();
+        };
+        vimrc
+            .mappings
+            .iter()
+            .filter_map(|mapping| mapping.key_binding(cx).log_err())
+            .collect()
+    }
+
+    fn apply(&mut self, command: VimrcCommand) -> Result<()> {
+        match command {
+            VimrcCommand::SetLeader(leader) => {
+                translate_keys(&leader, "\\")?;
+                self.leader = leader;
+            }
+            VimrcCommand::Map {
+                modes,
+                lhs,
+                rhs,
+                recursive,
+            } => {
+                let lhs = translate_keys(&lhs, &self.leader)?;
+                let rhs = if rhs.eq_ignore_ascii_case("<nop>") {
+                    MapTarget::Nop
+                } else {
+                    MapTarget::Keystrokes(translate_keys(&rhs, &self.leader)?)
+                };
+                for mode in modes {
+                    self.mappings
+                        .retain(|mapping| mapping.mode != mode || mapping.lhs != lhs);
+                    self.mappings.push(Mapping {
+                        mode,
+                        lhs: lhs.clone(),
+                        rhs: rhs.clone(),
+                        recursive,
+                    });
+                }
+            }
+            VimrcCommand::Unmap { modes, lhs } => {
+                let lhs = translate_keys(&lhs, &self.leader)?;
+                let previous_len = self.mappings.len();
+                self.mappings
+                    .retain(|mapping| !modes.contains(&mapping.mode) || mapping.lhs != lhs);
+                if self.mappings.len() == previous_len {
+                    bail!("E31: No such mapping");
+                }
+            }
+            VimrcCommand::Command {
+                name,
+                replacement,
+                overwrite,
+            } => {
+                if !name.starts_with(|c: char| c.is_ascii_uppercase())
+                    || !name.chars().all(|c| c.is_ascii_alphanumeric())
+                {
+                    bail!("E183: User defined commands must start with an uppercase letter");
+                }
+                if let Some(existing) = self.commands.iter_mut().find(|c| c.name == name) {
+                    if !overwrite {
+                        bail!("E174: Command already exists: add ! to replace it");
+                    }
+                    existing.replacement = replacement;
+                } else {
+                    self.commands.push(UserCommand { name, replacement });
+                }
+            }
+            VimrcCommand::DelCommand(name) => {
+                let previous_len = self.commands.len();
+                self.commands.retain(|command| command.name != name);
+                if self.commands.len() == previous_len {
+                    bail!("E184: No such user-defined command: {name}");
+                }
+            }
+            VimrcCommand::Source(_) => {
+                bail!("`:source` can't be nested here");
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies `commands` in order, returning the errors that occurred along the way.
+    fn apply_all(&mut self, commands: Vec<(String, VimrcCommand)>) -> Vec<String> {
+        commands
+            .into_iter()
+            .filter_map(|(location, command)| {
+                self.apply(command)
+                    .err()
+                    .map(|error| format!("{location}: {error}"))
+            })
+            .collect()
+    }
+
+    /// Expands a user-defined command, returning the ex command it stands for. Returns `None` if
+    /// `query` doesn't start with a user-defined command.
+    pub(crate) fn expand_user_command(query: &str, cx: &App) -> Option<String> {
+        let vimrc = cx.try_global::<Self>()?;
+        let mut query = query.to_string();
+        let mut expanded = false;
+        // User commands may expand into other user commands, but not indefinitely.
+        for _ in 0..MAX_SOURCE_DEPTH {
+            let (name, bang, args) = split_command(&query);
+            let Some(command) = vimrc.commands.iter().find(|command| command.name == name) else {
+                break;
+            };
+            query = command.expand(bang, args);
+            expanded = true;
+        }
+        expanded.then_some(query)
+    }
+}
+
+impl Mapping {
+    fn key_binding(&self, cx: &App) -> Result<KeyBinding> {
+        // Keystrokes replayed by `:noremap` mappings set `VimNoremap`, so that they don't trigger
+        // other mappings from the init file.
+        let context = format!("{} && !VimNoremap", self.mode.context());
+        let predicate = Rc::new(KeyBindingContextPredicate::parse(&context)?);
+        let action = match &self.rhs {
+            MapTarget::Nop => NoAction.boxed_clone(),
+            MapTarget::Keystrokes(keystrokes) if self.recursive => {
+                workspace::SendKeystrokes(keystrokes.clone()).boxed_clone()
+            }
+            MapTarget::Keystrokes(keystrokes) => {
+                NoremapKeystrokes(keystrokes.clone()).boxed_clone()
+            }
+        };
+        let binding = KeyBinding::load(
+            &self.lhs,
+            action,
+            Some(predicate),
+            false,
+            None,
+            cx.keyboard_mapper().as_ref(),
+        )
+        .map_err(|error| anyhow!("invalid keystroke `{}`", error.keystroke))?;
+        Ok(binding.with_meta(KeybindSource::User.meta()))
+    }
+}
+
+fn resolve_path(path: &str, directory: Option<&Path>) -> PathBuf {
+    let path = if let Some(rest) = path.strip_prefix("~/") {
+        util::paths::home_dir().join(rest)
+    } else {
+        PathBuf::from(path)
+    };
+    match directory {
+        Some(directory) if path.is_relative() => directory.join(path),
+        _ => path,
+    }
+}
+
+/// Parses `content`, read from `path`, following any `:source` commands. Returns the commands in
+/// the order they should be applied, each with a description of where it came from, along with
+/// any errors.
+fn load_commands(
+    path: PathBuf,
+    content: String,
+    fs: Arc<dyn Fs>,
+    depth: usize,
+) -> BoxFuture<'static, (Vec<(String, VimrcCommand)>, Vec<String>)> {
+    async move {
+        let mut commands = Vec::new();
+        let mut errors = Vec::new();
+        for (ix, line) in content.lines().enumerate() {
+            let location = format!("{}:{}", path.display(), ix + 1);
+            match parse_line(line) {
+                Ok(Some(VimrcCommand::Source(source))) => {
+                    if depth >= MAX_SOURCE_DEPTH {
+                        errors.push(format!("{location}: too many nested `:source` commands"));
+                        continue;
+                    }
+                    let source = resolve_path(&source, path.parent());
+                    match fs.load(&source).await {
+                        Ok(content) => {
+                            let (nested_commands, nested_errors) =
+                                load_commands(source, content, fs.clone(), depth + 1).await;
+                            commands.extend(nested_commands);
+                            errors.extend(nested_errors);
+                        }
+                        Err(error) => errors.push(format!(
+                            "{location}: failed to source {}: {error}",
+                            source.display()
+                        )),
+                    }
+                }
+                Ok(Some(command)) => commands.push((location, command)),
+                Ok(None) => {}
+                Err(error) => errors.push(format!("{location}: {error}")),
+            }
+        }
+        (commands, errors)
+    }
+    .boxed()
+}
+
+fn show_errors(errors: Vec<String>, cx: &mut App) {
+    struct VimrcErrorNotification;
+    let id = NotificationId::unique::<VimrcErrorNotification>();
+    if errors.is_empty() {
+        dismiss_app_notification(&id, cx);
+        return;
+    }
+    let message = format!("Errors in vim init file:\n{}", errors.join("\n"));
+    show_app_notification(id, cx, move |cx| {
+        cx.new(|cx| MessageNotification::new(message.clone(), cx))
+    });
+}
+
+/// Watches the vim init file, replacing all mappings and user commands whenever it changes.
+pub fn handle_init_file_changes(
+    mut init_file_rx: mpsc::UnboundedReceiver<String>,
+    fs: Arc<dyn Fs>,
+    cx: &mut App,
+) {
+    cx.spawn(async move |cx| {
+        while let Some(content) = init_file_rx.next().await {
+            let path = paths::vim_init_file().clone();
+            let (commands, mut errors) = load_commands(path, content, fs.clone(), 0).await;
+            cx.update(|cx| {
+                let mut vimrc = Vimrc::default();
+                errors.extend(vimrc.apply_all(commands));
+                cx.set_global(vimrc);
+                show_errors(errors, cx);
+            })
+            .ok();
+        }
+    })
+    .detach();
+}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.set_global(Vimrc::default());
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, action: &NoremapKeystrokes, window, cx| {
+            let keystrokes = action
+                .0
+                .split(' ')
+                .flat_map(|keystroke| Keystroke::parse(keystroke).log_err())
+                .collect();
+            Vim::update_globals(cx, |globals, _| globals.replaying_noremap += 1);
+            // The key context has to be recomputed before the replayed keystrokes are dispatched.
+            window.refresh();
+            let task = workspace.send_keystrokes_impl(keystrokes, window, cx);
+            cx.spawn_in(window, async move |_, cx| {
+                task.await;
+                cx.update(|window, cx| {
+                    Vim::update_globals(cx, |globals, _| {
+                        globals.replaying_noremap = globals.replaying_noremap.saturating_sub(1)
+                    });
+                    window.refresh();
+                })
+                .ok();
+            })
+            .detach();
+        });
+    })
+    .detach();
+}
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |vim, action: &VimrcCommandLine, window, cx| {
+        let Some(workspace) = vim.workspace(window) else {
+            return;
+        };
+        let command = match parse_line(&action.line) {
+            Ok(Some(command)) => command,
+            Ok(None) => return,
+            Err(error) => {
+                workspace.update(cx, |workspace, cx| {
+                    Err::<(), _>(error).notify_err(workspace, cx);
+                });
+                return;
+            }
+        };
+
+        let VimrcCommand::Source(source) = command else {
+            let result = cx.update_global(|vimrc: &mut Vimrc, _| vimrc.apply(command));
+            workspace.update(cx, |workspace, cx| {
+                result.notify_err(workspace, cx);
+            });
+            return;
+        };
+
+        let (fs, directory) = workspace.update(cx, |workspace, cx| {
+            let directory = workspace
+                .project()
+                .read(cx)
+                .visible_worktrees(cx)
+                .next()
+                .map(|worktree| worktree.read(cx).abs_path().to_path_buf());
+            (workspace.app_state().fs.clone(), directory)
+        });
+        let path = resolve_path(&source, directory.as_deref());
+        cx.spawn_in(window, async move |_, cx| {
+            let content = fs.load(&path).await;
+            let (commands, mut errors) = match content {
+                Ok(content) => load_commands(path, content, fs, 0).await,
+                Err(error) => (
+                    Vec::new(),
+                    vec![format!("failed to source {}: {error}", path.display())],
+                ),
+            };
+            cx.update(|_, cx| {
+                errors.extend(cx.update_global(|vimrc: &mut Vimrc, _| vimrc.apply_all(commands)));
+                if !errors.is_empty() {
+                    workspace.update(cx, |workspace, cx| {
+                        Err::<(), _>(anyhow!(errors.join("\n"))).notify_err(workspace, cx);
+                    });
+                }
+            })
+            .ok();
+        })
+        .detach();
+    });
+}
+
+/// Returns the action for an ex command that is handled by this module, if `query` is one.
+pub(crate) fn command_action(query: &str) -> Option<Box<dyn Action>> {
+    let (name, _, args) = split_command(query);
+    // A bare `:map` opens the default keymap instead.
+    if !is_vimrc_command(name) || (name == "map" && args.is_empty()) {
+        return None;
+    }
+    Some(
+        VimrcCommandLine {
+            line: query.to_string(),
+        }
+        .boxed_clone(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::TestAppContext;
+    use indoc::indoc;
+
+    use super::{MapMode, UserCommand, Vimrc, VimrcCommand, parse_line, translate_keys};
+    use crate::{command::command_interceptor, state::Mode, test::VimTestContext};
+
+    #[test]
+    fn test_parse_vimrc_lines() {
+        assert_eq!(parse_line("\" a comment").unwrap(), None);
+        assert_eq!(parse_line("   ").unwrap(), None);
+        assert_eq!(
+            parse_line("nnoremap <silent> <leader>w :w<CR>").unwrap(),
+            Some(VimrcCommand::Map {
+                modes: vec![MapMode::Normal],
+                lhs: "<leader>w".into(),
+                rhs: ":w<CR>".into(),
+                recursive: false,
+            })
+        );
+        assert_eq!(
+            parse_line("map Y y$").unwrap(),
+            Some(VimrcCommand::Map {
+                modes: vec![MapMode::Normal, MapMode::Visual, MapMode::OperatorPending],
+                lhs: "Y".into(),
+                rhs: "y$".into(),
+                recursive: true,
+            })
+        );
+        assert_eq!(
+            parse_line("xunmap Y").unwrap(),
+            Some(VimrcCommand::Unmap {
+                modes: vec![MapMode::Visual],
+                lhs: "Y".into(),
+            })
+        );
+        assert_eq!(
+            parse_line("command! -nargs=* W w <args>").unwrap(),
+            Some(VimrcCommand::Command {
+                name: "W".into(),
+                replacement: "w <args>".into(),
+                overwrite: true,
+            })
+        );
+        assert_eq!(
+            parse_line("let mapleader = \"\\<Space>\"").unwrap(),
+            Some(VimrcCommand::SetLeader("<Space>".into()))
+        );
+        assert_eq!(
+            parse_line("so ~/.vim/mappings.vim").unwrap(),
+            Some(VimrcCommand::Source("~/.vim/mappings.vim".into()))
+        );
+
+        assert!(parse_line("set number").is_err());
+        assert!(parse_line("nnoremap <buffer> x y").is_err());
+        assert!(parse_line("nmap x").is_err());
+        assert!(parse_line("let g:loaded_netrw = 1").is_err());
+    }
+
+    #[test]
+    fn test_translate_keys() {
+        assert_eq!(translate_keys("jk", "\\").unwrap(), "j k");
+        assert_eq!(translate_keys("<C-w>J", "\\").unwrap(), "ctrl-w J");
+        assert_eq!(translate_keys("<leader>f", "<Space>").unwrap(), "space f");
+        assert_eq!(translate_keys(":w<CR>", "\\").unwrap(), ": w enter");
+        assert_eq!(
+            translate_keys("<S-Tab><Esc>", "\\").unwrap(),
+            "shift-tab escape"
+        );
+        assert_eq!(translate_keys("a<b", "\\").unwrap(), "a < b");
+        assert!(translate_keys("<Plug>(foo)", "\\").is_err());
+    }
+
+    #[test]
+    fn test_user_commands() {
+        let mut vimrc = Vimrc::default();
+        vimrc
+            .apply(parse_line("command W w").unwrap().unwrap())
+            .unwrap();
+        assert!(
+            vimrc
+                .apply(parse_line("command W wall").unwrap().unwrap())
+                .is_err()
+        );
+        assert!(
+            vimrc
+                .apply(parse_line("command w wall").unwrap().unwrap())
+                .is_err()
+        );
+        vimrc
+            .apply(parse_line("command! W wall").unwrap().unwrap())
+            .unwrap();
+        vimrc
+            .apply(parse_line("delcommand W").unwrap().unwrap())
+            .unwrap();
+        assert!(vimrc.commands.is_empty());
+
+        let command = UserCommand {
+            name: "Say".into(),
+            replacement: "echo <q-args><bang> <args>".into(),
+        };
+        assert_eq!(command.expand(false, ""), r#"echo "" "#);
+        assert_eq!(
+            command.expand(true, r#"it's "quoted" \ é"#),
+            r#"echo "it's \"quoted\" \\ é"! it's "quoted" \ é"#
+        );
+    }
+
+    #[gpui::test]
+    async fn test_recursive_user_commands(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.update(|_, cx| {
+            cx.update_global(|vimrc: &mut Vimrc, _| {
+                for line in [
+                    "command! W W",
+                    "command A B",
+                    "command B A",
+                    "command Wa wa",
+                ] {
+                    vimrc.apply(parse_line(line).unwrap().unwrap()).unwrap();
+                }
+            });
+        });
+        let workspace = cx.workspace(|workspace, _, _| workspace.weak_handle());
+        let mut intercept = |input: &str| {
+            let task = cx.update(|_, cx| command_interceptor(input, workspace.clone(), cx));
+            cx.run_until_parked();
+            task
+        };
+
+        // Commands expanding into themselves are expanded once instead of recursing.
+        for input in ["W", "A", "B"] {
+            let result = intercept(input).await;
+            assert!(result.results.len() <= 1, "{input} should expand once");
+        }
+
+        let result = intercept("Wa").await;
+        assert_eq!(result.results.len(), 1);
+        assert_eq!(result.results[0].string, "Wa");
+    }
+
+    #[gpui::test]
+    async fn test_vimrc_mappings(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.update(|_, cx| {
+            cx.update_global(|vimrc: &mut Vimrc, _| {
+                for line in [
+                    "let mapleader = ','",
+                    "nnoremap <leader>d dd",
+                    "nmap Q ,d",
+                    "inoremap jk <Esc>",
+                ] {
+                    vimrc.apply(parse_line(line).unwrap().unwrap()).unwrap();
+                }
+            });
+            let bindings = Vimrc::key_bindings(cx);
+            cx.bind_keys(bindings);
+        });
+
+        cx.set_state(
+            indoc! {"
+                ˇone
+                two
+                three"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(", d");
+        cx.assert_state(
+            indoc! {"
+                ˇtwo
+                three"},
+            Mode::Normal,
+        );
+
+        // `Q` is a recursive mapping, so it runs the `,d` mapping.
+        cx.simulate_keystrokes("shift-q");
+        cx.assert_state("ˇthree", Mode::Normal);
+
+        cx.simulate_keystrokes("i j k");
+        cx.assert_state("ˇthree", Mode::Normal);
+    }
+}
@@ -1,11 +1,19 @@
-use std::process::ExitStatus;
+use std::{process::ExitStatus, sync::Arc};
 
-use anyhow::Result;
-use gpui::{AppContext, Context, Entity, Task};
+use anyhow::{Context as _, Result, anyhow, bail};
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{LocalBoxFuture, Shared},
+};
+use gpui::{AppContext, AsyncWindowContext, Context, Entity, Task, WeakEntity};
 use language::Buffer;
-use project::{TaskSourceKind, WorktreeId};
+use project::{TaskChain, TaskSourceKind, WorktreeId};
 use remote::ConnectionState;
-use task::{DebugScenario, ResolvedTask, SpawnInTerminal, TaskContext, TaskTemplate};
+use task::{
+    DebugScenario, DependsOrder, ResolvedTask, SpawnInTerminal, TaskContext, TaskId, TaskInput,
+    TaskTemplate,
+};
 use ui::Window;
 
 use crate::{Toast, Workspace, notifications::NotificationId};
@@ -33,6 +41,20 @@ impl Workspace {
             }
         }
 
+        if !task_to_resolve.depends_on.is_empty()
+            || !task_to_resolve.missing_inputs(task_cx).is_empty()
+        {
+            self.schedule_task_with_inputs(
+                task_source_kind,
+                task_to_resolve.clone(),
+                task_cx.clone(),
+                omit_history,
+                window,
+                cx,
+            );
+            return;
+        }
+
         if let Some(spawn_in_terminal) =
             task_to_resolve.resolve_task(&task_source_kind.to_id_base(), task_cx)
         {
@@ -53,24 +75,179 @@ impl Workspace {
         omit_history: bool,
         window: &mut Window,
         cx: &mut Context<Workspace>,
+    ) {
+        // Tasks are listed before the user provides their inputs, and the tasks they depend on
+        // may have inputs too, so such tasks are resolved again from their templates.
+        if !resolved_task.original_task().depends_on.is_empty()
+            || !resolved_task.missing_inputs().is_empty()
+        {
+            self.schedule_task_with_inputs(
+                task_source_kind,
+                resolved_task.original_task().clone(),
+                resolved_task.task_context().clone(),
+                omit_history,
+                window,
+                cx,
+            );
+            return;
+        }
+        self.run_resolved_task(task_source_kind, resolved_task, omit_history, window, cx);
+    }
+
+    /// Asks the user for the inputs that the task and the tasks it depends on use but have no
+    /// values for, then resolves the task with their values and runs it.
+    fn schedule_task_with_inputs(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        template: TaskTemplate,
+        task_context: TaskContext,
+        omit_history: bool,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let task_inventory = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned();
+        let inputs = match task_inventory {
+            Some(task_inventory) if !template.depends_on.is_empty() => {
+                task_inventory.read(cx).task_chain_inputs(
+                    &task_source_kind,
+                    template.clone(),
+                    task_context.clone(),
+                    cx,
+                )
+            }
+            _ => Task::ready(
+                template
+                    .missing_inputs(&task_context)
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+            ),
+        };
+        let id_base = task_source_kind.to_id_base();
+        let task = cx.spawn_in(window, async move |workspace, cx| {
+            let resolved_task = async {
+                let inputs = inputs.await;
+                let task_context = if inputs.is_empty() {
+                    task_context
+                } else {
+                    let Some(task_context) = workspace
+                        .update_in(cx, |workspace, window, cx| {
+                            workspace.request_task_inputs(
+                                &template.label,
+                                inputs,
+                                task_context,
+                                window,
+                                cx,
+                            )
+                        })?
+                        .await?
+                    else {
+                        return Ok(None);
+                    };
+                    task_context
+                };
+                template
+                    .resolve_task(&id_base, &task_context)
+                    .with_context(|| format!("resolving `{}` with its inputs", template.label))
+                    .map(Some)
+            }
+            .await;
+            match resolved_task {
+                Ok(Some(resolved_task)) => {
+                    workspace
+                        .update_in(cx, |workspace, window, cx| {
+                            workspace.run_resolved_task(
+                                task_source_kind,
+                                resolved_task,
+                                omit_history,
+                                window,
+                                cx,
+                            )
+                        })
+                        .ok();
+                }
+                Ok(None) => log::debug!("Task inputs got dismissed"),
+                Err(e) => {
+                    log::error!("Failed to resolve task inputs: {e:#}");
+                    _ = workspace.update(cx, |workspace, cx| {
+                        let id = NotificationId::unique::<TaskInput>();
+                        workspace.show_toast(
+                            Toast::new(id, format!("Failed to resolve task inputs: {e}")),
+                            cx,
+                        );
+                    });
+                }
+            }
+        });
+        self.scheduled_tasks.push(task);
+    }
+
+    /// Asks the user for the values of the inputs. Resolves to the task context with the values
+    /// added, or to `None` if the user dismisses the prompts.
+    fn request_task_inputs(
+        &mut self,
+        label: &str,
+        inputs: Vec<TaskInput>,
+        mut task_context: TaskContext,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<Option<TaskContext>>> {
+        let Some(task_input_provider) = self.task_input_provider.clone() else {
+            return Task::ready(Err(anyhow!("cannot ask for the inputs of `{label}`")));
+        };
+        let values =
+            task_input_provider.request_inputs(inputs.clone(), &task_context, self, window, cx);
+        cx.spawn(async move |_, _| {
+            let Some(values) = values.await? else {
+                return Ok(None);
+            };
+            for (input, value) in inputs.iter().zip(values) {
+                task_context
+                    .task_variables
+                    .insert(input.variable_name(), value);
+            }
+            Ok(Some(task_context))
+        })
+    }
+
+    fn run_resolved_task(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        omit_history: bool,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
     ) {
         let spawn_in_terminal = resolved_task.resolved.clone();
+        let has_dependencies = !resolved_task.original_task().depends_on.is_empty();
         if !omit_history {
             if let Some(debugger_provider) = self.debugger_provider.as_ref() {
                 debugger_provider.task_scheduled(cx);
             }
 
+            // Tasks with dependencies are recorded once, as a whole chain.
             self.project().update(cx, |project, cx| {
                 if let Some(task_inventory) =
                     project.task_store().read(cx).task_inventory().cloned()
                 {
                     task_inventory.update(cx, |inventory, _| {
-                        inventory.task_scheduled(task_source_kind, resolved_task);
+                        inventory.task_scheduled(task_source_kind.clone(), resolved_task.clone());
                     })
                 }
             });
         }
 
+        if has_dependencies {
+            self.schedule_task_chain(task_source_kind, resolved_task, window, cx);
+            return;
+        }
+
         if let Some(terminal_provider) = self.terminal_provider.as_ref() {
             let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
 
@@ -98,6 +275,43 @@ impl Workspace {
         }
     }
 
+    fn schedule_task_chain(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(task_inventory) = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        else {
+            return;
+        };
+        let label = resolved_task.resolved_label.clone();
+        let chain = task_inventory
+            .read(cx)
+            .resolve_task_chain(task_source_kind, resolved_task, cx);
+        let task = cx.spawn_in(window, async move |workspace, cx| {
+            let result = match chain.await {
+                Ok(chain) => run_task_chain(workspace.clone(), chain, cx).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                log::error!("Task `{label}` failed: {e:#}");
+                _ = workspace.update(cx, |workspace, cx| {
+                    let id = NotificationId::unique::<TaskChain>();
+                    workspace.show_toast(Toast::new(id, format!("Task `{label}` failed: {e}")), cx);
+                });
+            }
+        });
+        self.scheduled_tasks.push(task);
+    }
+
     pub fn start_debug_session(
         &mut self,
         scenario: DebugScenario,
@@ -132,3 +346,296 @@ impl Workspace {
         }
     }
 }
+
+/// The run of a task in a chain, shared with every task waiting for it.
+type ChainRun = Shared<LocalBoxFuture<'static, Result<(), Arc<anyhow::Error>>>>;
+
+/// Runs every task of the chain once its dependencies have finished, in their order, unless it
+/// only exists to run its dependencies. A task that several tasks depend on is run once, and
+/// all of them wait for that run. Stops at the first failure.
+async fn run_task_chain(
+    workspace: WeakEntity<Workspace>,
+    chain: TaskChain,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    // Runs are shared between the tasks depending on them and only start once first awaited,
+    // so that sequential dependencies don't start early.
+    let mut runs: HashMap<TaskId, ChainRun> = HashMap::default();
+    let mut root_run = None;
+    let task_count = chain.tasks.len();
+    for (ix, chained) in chain.tasks.into_iter().enumerate() {
+        let dependencies = chained
+            .dependencies
+            .iter()
+            .map(|id| {
+                runs.get(id)
+                    .cloned()
+                    .context("task dependency is not in the chain")
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let depends_order = chained.depends_order();
+        let workspace = workspace.clone();
+        let mut cx = cx.clone();
+        let id = chained.task.id.clone();
+        let mut spawn_in_terminal = chained.task.resolved;
+        spawn_in_terminal.is_dependency = ix + 1 < task_count;
+        let run = async move {
+            match depends_order {
+                DependsOrder::Sequential => {
+                    for dependency in dependencies {
+                        dependency.await?;
+                    }
+                }
+                DependsOrder::Parallel => {
+                    for result in futures::future::join_all(dependencies).await {
+                        result?;
+                    }
+                }
+            }
+            run_chained_task(workspace, spawn_in_terminal, &mut cx)
+                .await
+                .map_err(Arc::new)
+        }
+        .boxed_local()
+        .shared();
+        runs.insert(id, run.clone());
+        root_run = Some(run);
+    }
+
+    match root_run {
+        Some(run) => run.await.map_err(|e| anyhow!("{e:#}")),
+        None => Ok(()),
+    }
+}
+
+async fn run_chained_task(
+    workspace: WeakEntity<Workspace>,
+    spawn_in_terminal: SpawnInTerminal,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    if spawn_in_terminal.command.is_none() {
+        return Ok(());
+    }
+    let label = spawn_in_terminal.label.clone();
+    let status = workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.spawn_in_terminal(spawn_in_terminal, window, cx)
+        })?
+        .await;
+    match status {
+        Some(Ok(status)) if status.success() => Ok(()),
+        Some(Ok(status)) => bail!("`{label}` exited with {status}"),
+        Some(Err(e)) => Err(e),
+        None => bail!("`{label}` was cancelled"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use futures::channel::oneshot;
+    use gpui::{App, TestAppContext};
+    use project::{ChainedTask, FakeFs, Project};
+    use task::BackgroundPatterns;
+
+    use super::*;
+    use crate::{TaskInputProvider, TerminalProvider, tests::init_test};
+
+    /// Records the spawned tasks, each resolving once the test sends its status.
+    #[derive(Clone, Default)]
+    struct FakeTerminalProvider {
+        spawned: Rc<RefCell<Vec<(String, oneshot::Sender<ExitStatus>)>>>,
+        dependencies: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl FakeTerminalProvider {
+        fn labels(&self) -> Vec<String> {
+            let spawned = self.spawned.borrow();
+            spawned.iter().map(|(label, _)| label.clone()).collect()
+        }
+
+        /// Resolves the task like the terminal does once it finishes, or once it gets ready
+        /// if it runs in the background.
+        fn resolve(&self, label: &str) {
+            let mut spawned = self.spawned.borrow_mut();
+            let ix = spawned
+                .iter()
+                .position(|(spawned_label, _)| spawned_label == label)
+                .unwrap();
+            let (_, status_tx) = spawned.remove(ix);
+            status_tx.send(ExitStatus::default()).unwrap();
+        }
+    }
+
+    impl TerminalProvider for FakeTerminalProvider {
+        fn spawn(
+            &self,
+            task: SpawnInTerminal,
+            _: &mut Window,
+            cx: &mut App,
+        ) -> Task<Option<Result<ExitStatus>>> {
+            let (status_tx, status_rx) = oneshot::channel();
+            if task.is_dependency {
+                self.dependencies.borrow_mut().push(task.label.clone());
+            }
+            self.spawned.borrow_mut().push((task.label, status_tx));
+            cx.spawn(async move |_| Some(Ok(status_rx.await.ok()?)))
+        }
+    }
+
+    /// Provides `<id>-value` as the value of every input, recording the ids asked for.
+    #[derive(Clone, Default)]
+    struct FakeTaskInputProvider {
+        requested: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl TaskInputProvider for FakeTaskInputProvider {
+        fn request_inputs(
+            &self,
+            inputs: Vec<TaskInput>,
+            _: &TaskContext,
+            _: &mut Workspace,
+            _: &mut Window,
+            _: &mut Context<Workspace>,
+        ) -> Task<Result<Option<Vec<String>>>> {
+            let mut requested = self.requested.borrow_mut();
+            requested.extend(inputs.iter().map(|input| input.id.clone()));
+            Task::ready(Ok(Some(
+                inputs
+                    .iter()
+                    .map(|input| format!("{}-value", input.id))
+                    .collect(),
+            )))
+        }
+    }
+
+    #[gpui::test]
+    async fn test_task_inputs_are_requested_before_resolving(cx: &mut TestAppContext) {
+        init_test(cx);
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let terminal_provider = FakeTerminalProvider::default();
+        let task_input_provider = FakeTaskInputProvider::default();
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(terminal_provider.clone());
+            workspace.set_task_input_provider(task_input_provider.clone());
+        });
+
+        let template = TaskTemplate {
+            label: "deploy to $ZED_INPUT_target".to_string(),
+            command: "deploy".to_string(),
+            args: vec!["--target".to_string(), "$ZED_INPUT_target".to_string()],
+            inputs: vec![
+                TaskInput {
+                    id: "target".to_string(),
+                    ..TaskInput::default()
+                },
+                TaskInput {
+                    id: "unused".to_string(),
+                    ..TaskInput::default()
+                },
+            ],
+            ..TaskTemplate::default()
+        };
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.schedule_task(
+                TaskSourceKind::UserInput,
+                &template,
+                &TaskContext::default(),
+                true,
+                window,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        assert_eq!(*task_input_provider.requested.borrow(), ["target"]);
+        assert_eq!(terminal_provider.labels(), ["deploy to target-value"]);
+    }
+
+    #[gpui::test]
+    async fn test_task_chain_waits_for_ready_background_task(cx: &mut TestAppContext) {
+        init_test(cx);
+        let project = Project::test(FakeFs::new(cx.executor()), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let terminal_provider = FakeTerminalProvider::default();
+        workspace.update(cx, |workspace, _| {
+            workspace.set_terminal_provider(terminal_provider.clone())
+        });
+
+        let chained = |template: TaskTemplate, dependencies: &[&ChainedTask]| ChainedTask {
+            source_kind: TaskSourceKind::UserInput,
+            task: template
+                .resolve_task("test", &TaskContext::default())
+                .unwrap(),
+            dependencies: dependencies
+                .iter()
+                .map(|task| task.task.id.clone())
+                .collect(),
+        };
+        let build = chained(
+            TaskTemplate {
+                label: "build".to_string(),
+                command: "cargo build".to_string(),
+                ..TaskTemplate::default()
+            },
+            &[],
+        );
+        let server = chained(
+            TaskTemplate {
+                label: "server".to_string(),
+                command: "cargo run".to_string(),
+                background: Some(BackgroundPatterns {
+                    begins_pattern: "Compiling".to_string(),
+                    ends_pattern: "Listening".to_string(),
+                }),
+                ..TaskTemplate::default()
+            },
+            &[],
+        );
+        let client = chained(
+            TaskTemplate {
+                label: "client".to_string(),
+                command: "open http://localhost:8080".to_string(),
+                depends_on: vec!["build".to_string(), "server".to_string()],
+                ..TaskTemplate::default()
+            },
+            &[&build, &server],
+        );
+        let chain = TaskChain {
+            tasks: vec![build, server, client],
+        };
+
+        let run = workspace.update_in(cx, |_, window, cx| {
+            cx.spawn_in(window, async move |workspace, cx| {
+                run_task_chain(workspace, chain, cx).await
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(terminal_provider.labels(), ["build", "server"]);
+
+        terminal_provider.resolve("build");
+        cx.run_until_parked();
+        assert_eq!(
+            terminal_provider.labels(),
+            ["server"],
+            "the dependent task should wait for the background task to get ready"
+        );
+
+        // The terminal resolves a background task once it goes idle, while it keeps running.
+        terminal_provider.resolve("server");
+        cx.run_until_parked();
+        assert_eq!(terminal_provider.labels(), ["client"]);
+
+        terminal_provider.resolve("client");
+        cx.run_until_parked();
+        run.await.unwrap();
+        assert_eq!(
+            *terminal_provider.dependencies.borrow(),
+            ["build", "server"],
+            "only dependencies may reuse running background tasks"
+        );
+    }
+}
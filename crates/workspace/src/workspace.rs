@@ -108,7 +108,7 @@ use std::{
     },
     time::Duration,
 };
-use task::{DebugScenario, SpawnInTerminal, TaskContext};
+use task::{DebugScenario, SpawnInTerminal, TaskContext, TaskInput};
 use theme::{ActiveTheme, GlobalTheme, SystemAppearance, ThemeSettings};
 pub use toolbar::{Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 pub use ui;
@@ -149,6 +149,8 @@ static ZED_WINDOW_POSITION: LazyLock<Option<Point<Pixels>>> = LazyLock::new(|| {
 });
 
 pub trait TerminalProvider {
+    /// Spawns the task in a terminal and resolves with its exit status once it finishes.
+    /// Tasks running in the background resolve with a successful status once they get ready instead.
     fn spawn(
         &self,
         task: SpawnInTerminal,
@@ -157,6 +159,19 @@ pub trait TerminalProvider {
     ) -> Task<Option<Result<ExitStatus>>>;
 }
 
+pub trait TaskInputProvider {
+    /// Asks the user for the values of the task's inputs, one after another.
+    /// Resolves with the values in the order of the inputs, or `None` if the user dismisses any of the prompts.
+    fn request_inputs(
+        &self,
+        inputs: Vec<TaskInput>,
+        task_context: &TaskContext,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<Option<Vec<String>>>>;
+}
+
 pub trait DebuggerProvider {
     // `active_buffer` is used to resolve build task's name against language-specific tasks.
     fn start_session(
@@ -1168,6 +1183,7 @@ pub struct Workspace {
     on_prompt_for_open_path: Option<PromptForOpenPath>,
     terminal_provider: Option<Box<dyn TerminalProvider>>,
     debugger_provider: Option<Arc<dyn DebuggerProvider>>,
+    task_input_provider: Option<Arc<dyn TaskInputProvider>>,
     serializable_items_tx: UnboundedSender<Box<dyn SerializableItemHandle>>,
     _items_serializer: Task<Result<()>>,
     session_id: Option<String>,
@@ -1511,6 +1527,7 @@ impl Workspace {
             on_prompt_for_open_path: None,
             terminal_provider: None,
             debugger_provider: None,
+            task_input_provider: None,
             serializable_items_tx,
             _items_serializer,
             session_id: Some(session_id),
@@ -2113,6 +2130,10 @@ impl Workspace {
         self.debugger_provider.clone()
     }
 
+    pub fn set_task_input_provider(&mut self, provider: impl TaskInputProvider + 'static) {
+        self.task_input_provider = Some(Arc::new(provider));
+    }
+
     pub fn prompt_for_open_path(
         &mut self,
         path_prompt_options: PathPromptOptions,
@@ -6160,6 +6181,11 @@ impl Workspace {
             .update(cx, |toast_layer, cx| toast_layer.toggle_toast(cx, entity))
     }
 
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn active_status_toast<V: ToastView>(&self, cx: &App) -> Option<Entity<V>> {
+        self.toast_layer.read(cx).active_toast()
+    }
+
     pub fn toggle_centered_layout(
         &mut self,
         _: &ToggleCenteredLayout,
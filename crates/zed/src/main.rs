@@ -365,6 +365,11 @@ pub fn main() {
         fs.clone(),
         paths::keymap_file().clone(),
     );
+    let vim_init_file_rx = watch_config_file(
+        &app.background_executor(),
+        fs.clone(),
+        paths::vim_init_file().clone(),
+    );
 
     let (shell_env_loaded_tx, shell_env_loaded_rx) = oneshot::channel();
     if !stdout_is_a_pty() {
@@ -614,14 +619,17 @@ pub fn main() {
         file_finder::init(cx);
         tab_switcher::init(cx);
         outline::init(cx);
+        call_hierarchy::init(cx);
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        type_hierarchy_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
         search::init(cx);
         vim::init(cx);
+        vim::handle_init_file_changes(vim_init_file_rx, app_state.fs.clone(), cx);
         terminal_view::init(cx);
         journal::init(app_state.clone(), cx);
         language_selector::init(cx);
@@ -72,6 +72,7 @@ use std::{
 };
 use terminal_view::terminal_panel::{self, TerminalPanel};
 use theme::{ActiveTheme, GlobalTheme, SystemAppearance, ThemeRegistry, ThemeSettings};
+use type_hierarchy_panel::TypeHierarchyPanel;
 use ui::{PopoverMenuHandle, prelude::*};
 use util::markdown::MarkdownString;
 use util::rel_path::RelPath;
@@ -654,6 +655,8 @@ fn initialize_panels(
             workspace_handle.clone(),
             cx.clone(),
         );
+        let type_hierarchy_panel =
+            TypeHierarchyPanel::load(workspace_handle.clone(), cx.clone());
         let debug_panel = DebugPanel::load(workspace_handle.clone(), cx);
 
         async fn add_panel_when_ready(
@@ -678,6 +681,7 @@ fn initialize_panels(
             add_panel_when_ready(git_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(channels_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(notification_panel, workspace_handle.clone(), cx.clone()),
+            add_panel_when_ready(type_hierarchy_panel, workspace_handle.clone(), cx.clone()),
             add_panel_when_ready(debug_panel, workspace_handle.clone(), cx.clone()),
             initialize_agent_panel(workspace_handle, prompt_builder, cx.clone()).map(|r| r.log_err())
         );
@@ -1529,6 +1533,12 @@ pub fn handle_keymap_file_changes(
     let mut old_vim_enabled = VimModeSetting::get_global(cx).0;
     let mut old_helix_enabled = vim_mode_setting::HelixModeSetting::get_global(cx).0;
 
+    let vimrc_tx = base_keymap_tx.clone();
+    cx.observe_global::<vim::Vimrc>(move |_| {
+        vimrc_tx.unbounded_send(()).ok();
+    })
+    .detach();
+
     cx.observe_global::<SettingsStore>(move |cx| {
         let new_base_keymap = *BaseKeymap::get_global(cx);
         let new_vim_enabled = VimModeSetting::get_global(cx).0;
@@ -1728,6 +1738,7 @@ fn reload_keymaps(cx: &mut App, mut user_key_bindings: Vec<KeyBinding>) {
         key_binding.set_meta(KeybindSource::User.meta());
     }
     cx.bind_keys(user_key_bindings);
+    cx.bind_keys(vim::Vimrc::key_bindings(cx));
 
     let menus = app_menus(cx);
     cx.set_menus(menus);
@@ -4723,6 +4734,7 @@ mod tests {
                 "bedrock",
                 "branches",
                 "buffer_search",
+                "call_hierarchy",
                 "channel_modal",
                 "cli",
                 "client",
@@ -4782,6 +4794,7 @@ mod tests {
                 "theme_selector",
                 "toast",
                 "toolchain",
+                "type_hierarchy_panel",
                 "variable_list",
                 "vim",
                 "window",
@@ -4919,6 +4932,7 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            type_hierarchy_panel::init(cx);
             terminal_view::init(cx);
             copilot::copilot_chat::init(
                 app_state.fs.clone(),
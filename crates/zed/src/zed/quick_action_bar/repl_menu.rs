@@ -202,6 +202,7 @@ impl QuickActionBar {
                     )
                     .separator()
                     .action("View Sessions", Box::new(repl::Sessions))
+                    .action("View Variables", Box::new(repl::Variables))
                     // TODO: Add shut down all kernels action
                     // .action("Shut Down all Kernels", Box::new(gpui::NoAction))
                 })
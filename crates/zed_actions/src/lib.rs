@@ -224,6 +224,8 @@ pub mod git {
             Branch,
             /// Opens the git stash selector.
             ViewStash,
+            /// Opens the git tag selector.
+            Tag,
             /// Opens the git worktree selector.
             Worktree
         ]